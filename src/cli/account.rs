@@ -0,0 +1,125 @@
+//! `balances` and `order`: account queries and manual trading
+
+use super::setup::build_exchange;
+use super::{BalancesArgs, OrderArgs};
+use crate::config::AppConfig;
+use crate::error::Result;
+use crate::exchanges::{Exchange, Order, OrderSide, OrderType, split_pair};
+use crate::journal::{Journal, JournalEvent, new_correlation_id};
+use crate::logger::warn;
+use crate::risk::RiskManager;
+use crate::trading::{GuardedExchange, TradingGuard, TradingGuardConfig};
+
+/// Print available and held balances on every exchange
+///
+/// Without `--asset`, shows every asset of every configured market. An exchange that cannot be queried is reported and skipped.
+pub async fn balances(config: &AppConfig, args: &BalancesArgs) -> Result<()> {
+    let assets: Vec<&str> = if args.assets.is_empty() {
+        let mut assets = Vec::new();
+        for market in config.trading().markets() {
+            for id in market.venues(config.exchanges()) {
+                if let Some((base, quote)) = split_pair(market.symbol(id)) {
+                    for asset in [base, quote] {
+                        if !assets.contains(&asset) {
+                            assets.push(asset);
+                        }
+                    }
+                }
+            }
+        }
+        assets
+    } else {
+        args.assets.iter().map(String::as_str).collect()
+    };
+
+    println!("{:<10} {:<8} {:>20} {:>20}", "EXCHANGE", "ASSET", "AVAILABLE", "HOLD");
+    for id in config.exchanges().enabled() {
+        let exchange = build_exchange(config.exchanges(), id)?;
+        match exchange.get_balances(&assets).await {
+            Ok(balances) => {
+                for asset in &assets {
+                    match balances.get(*asset) {
+                        Some(balance) => println!(
+                            "{:<10} {:<8} {:>20} {:>20}",
+                            id.as_str(),
+                            asset,
+                            balance.available,
+                            balance.hold
+                        ),
+                        None => println!("{:<10} {:<8} {:>20}", id.as_str(), asset, "-"),
+                    }
+                }
+            }
+            Err(e) => {
+                warn!(exchange = %id, error = %e, "Balance query failed");
+                println!("{:<10} error: {}", id.as_str(), e);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Place one market order, subject to the configured risk limits
+///
+/// Without `--yes` the order is only printed. With `--kill-switch-file` the order
+/// is refused while the running bot's kill switch is engaged. Sent orders are
+/// journaled like the bot's own so startup reconciliation accounts for them.
+pub async fn order(config: &AppConfig, args: &OrderArgs) -> Result<()> {
+    let id = args.exchange;
+    // A configured market is ordered under the exchange's own symbol
+    let pair = match args.pair.as_deref() {
+        Some(pair) => config.trading().market(pair).map_or(pair, |m| m.symbol(id)),
+        None => config.trading().markets()[0].symbol(id),
+    };
+    let order = Order {
+        pair: pair.to_string(),
+        side: OrderSide::from(args.side),
+        order_type: OrderType::Market,
+        quantity: args.quantity,
+    };
+
+    if !args.yes {
+        println!(
+            "Would place {:?} {} {} on {} (pass --yes to send)",
+            order.side,
+            order.quantity,
+            order.pair,
+            id.as_str()
+        );
+        return Ok(());
+    }
+
+    let guard = TradingGuard::new(TradingGuardConfig {
+        kill_switch_file: args.kill_switch_file.clone(),
+        ..Default::default()
+    });
+    // Don't connect while halted
+    guard.check()?;
+    let mut exchange = GuardedExchange::new(build_exchange(config.exchanges(), id)?, guard)
+        .with_risk_manager(RiskManager::new(config.risk().clone()));
+    // The risk check values market orders at the live ask/bid
+    exchange.subscribe_ticker(pair).await?;
+
+    let journal = Journal::open(&args.journal)?;
+    let correlation_id = new_correlation_id();
+    journal.record(&correlation_id, JournalEvent::order_request(id.as_str(), &order));
+    let result = exchange.place_order(order.clone()).await;
+    journal.record_order_outcome(&correlation_id, id.as_str(), &order, &result);
+    journal.flush().await?;
+    exchange.disconnect().await.ok();
+
+    let result = result?;
+    println!(
+        "Order {} {:?}: filled {} at {} (fee {} {})",
+        result.order_id,
+        result.status,
+        result.filled_quantity,
+        result
+            .average_price
+            .map(|p| p.to_string())
+            .unwrap_or_else(|| "-".to_string()),
+        result.fee,
+        result.fee_asset
+    );
+    Ok(())
+}
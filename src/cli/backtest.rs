@@ -0,0 +1,64 @@
+//! `backtest`: replay recorded prices through the strategy
+
+use super::BacktestArgs;
+use crate::config::{AppConfig, FeesConfig};
+use crate::error::Result;
+use crate::strategy::{Backtest, BacktestReport, SpreadDetector, parse_ticks};
+
+/// Replay the CSV in `args.file` with each market's threshold, cooldown and order size
+///
+/// Every market is replayed separately on the ticks for its pair, matching an
+/// exchange's symbol override where one is set. Ticks for other pairs are ignored.
+pub fn run(config: &AppConfig, args: &BacktestArgs) -> Result<()> {
+    let ticks = parse_ticks(&std::fs::read_to_string(&args.file)?)?;
+    let fees = args
+        .fee_rate
+        .map(FeesConfig::flat)
+        .unwrap_or_else(|| config.fees().clone());
+
+    let mut report = BacktestReport::default();
+    for market in config.trading().markets() {
+        let market_ticks = ticks
+            .iter()
+            .filter(|tick| tick.price.pair == market.symbol(tick.exchange))
+            .map(|tick| {
+                let mut tick = tick.clone();
+                tick.price.pair = market.pair().to_string();
+                tick
+            });
+        let backtest = Backtest::new(
+            SpreadDetector::from_config(market),
+            market.order_size(),
+            fees.clone(),
+        );
+        let market_report = backtest.run(market_ticks);
+        report.ticks += market_report.ticks;
+        report.gross += market_report.gross;
+        report.fees += market_report.fees;
+        report.trades.extend(market_report.trades);
+    }
+    report.trades.sort_by_key(|trade| trade.at);
+
+    if args.verbose {
+        for trade in &report.trades {
+            println!(
+                "{} {} buy {} @ {} sell {} @ {} qty {} net {}",
+                trade.at.to_rfc3339(),
+                trade.opportunity.pair,
+                trade.opportunity.buy_exchange.as_str(),
+                trade.opportunity.buy_price,
+                trade.opportunity.sell_exchange.as_str(),
+                trade.opportunity.sell_price,
+                trade.quantity,
+                trade.net().round_dp(8)
+            );
+        }
+    }
+
+    println!("ticks:  {}", report.ticks);
+    println!("trades: {}", report.trades.len());
+    println!("gross:  {}", report.gross.round_dp(8));
+    println!("fees:   {}", report.fees.round_dp(8));
+    println!("net:    {}", report.net().round_dp(8));
+    Ok(())
+}
@@ -0,0 +1,48 @@
+//! `config check`: validate the configuration file
+
+use crate::config::ConfigLoader;
+use crate::error::Result;
+
+/// Load and validate every layer, printing a summary
+///
+/// Returns the validation error, which lists every problem with its field
+/// path, so the process exits non-zero on a bad config.
+pub fn run(loader: &ConfigLoader) -> Result<()> {
+    let source = match loader.profile_path() {
+        Some(profile) => format!("{} + {}", loader.path().display(), profile.display()),
+        None => loader.path().display().to_string(),
+    };
+    let config = match loader.load() {
+        Ok(config) => config,
+        Err(e) => {
+            println!("{}: INVALID", source);
+            return Err(e);
+        }
+    };
+
+    println!("{}: OK", source);
+    for market in config.trading().markets() {
+        let venues: Vec<String> = market
+            .venues(config.exchanges())
+            .into_iter()
+            .map(|id| match market.symbol(id) {
+                symbol if symbol == market.pair() => id.to_string(),
+                symbol => format!("{} as {}", id.as_str(), symbol),
+            })
+            .collect();
+        println!("  market:           {}", market.pair());
+        println!("    spread_threshold: {}", market.spread_threshold());
+        println!("    order_size:       {}", market.order_size());
+        println!("    cooldown_ms:      {}", market.cooldown_ms());
+        println!("    exchanges:        {}", venues.join(", "));
+    }
+    for id in config.exchanges().enabled() {
+        println!("  exchange:         {} (taker fee {})", id.as_str(), config.fees().taker(id));
+    }
+    println!(
+        "  logging:          {} {:?}",
+        config.logging().level(),
+        config.logging().format()
+    );
+    Ok(())
+}
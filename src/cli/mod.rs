@@ -0,0 +1,290 @@
+//! Command-Line Interface
+//!
+//! Subcommands of the `arb-bot` binary. `main.rs` parses arguments, sets up
+//! logging and hands over to `execute()`; each subcommand wires the library
+//! modules together.
+
+pub mod account;
+pub mod backtest;
+pub mod check;
+pub mod monitor;
+pub mod recover;
+pub mod run;
+pub mod setup;
+
+use crate::config::ConfigLoader;
+use crate::error::Result;
+use crate::exchanges::OrderSide;
+use crate::logger::{LogFormat, LoggerConfig};
+use crate::state::ExchangeId;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use rust_decimal::Decimal;
+use std::path::PathBuf;
+
+/// Cross-exchange arbitrage bot
+#[derive(Debug, Parser)]
+#[command(name = "arb-bot", version, about)]
+pub struct Cli {
+    /// Path to the TOML configuration file
+    #[arg(
+        short,
+        long,
+        global = true,
+        env = "ARB_BOT_CONFIG",
+        default_value = "config.toml"
+    )]
+    pub config: PathBuf,
+
+    /// Also merge `<config stem>.<profile>.toml` (e.g. config.prod.toml)
+    #[arg(short, long, global = true, env = "ARB_BOT_PROFILE")]
+    pub profile: Option<String>,
+
+    /// Log level or filter directive (e.g. "info", "arb_bot=debug"); overrides `[logging]`
+    #[arg(long, global = true)]
+    pub log_level: Option<String>,
+
+    /// Log output format; overrides `[logging]`
+    #[arg(long, global = true, value_enum)]
+    pub log_format: Option<LogFormatArg>,
+
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+impl Cli {
+    /// Loader for the selected config file and profile
+    pub fn loader(&self) -> ConfigLoader {
+        let loader = ConfigLoader::new(&self.config);
+        match &self.profile {
+            Some(profile) => loader.with_profile(profile),
+            None => loader,
+        }
+    }
+
+    /// Logging from the config's `[logging]` section, overridden by flags
+    ///
+    /// Falls back to defaults when the config cannot be loaded; the subcommand
+    /// then reports the config error itself.
+    pub fn logger_config(&self) -> LoggerConfig {
+        let mut config = self
+            .loader()
+            .load()
+            .map(|c| c.logging().clone())
+            .unwrap_or_default();
+        if let Some(level) = &self.log_level {
+            config = config.with_level(level);
+        }
+        if let Some(format) = self.log_format {
+            config = config.with_format(format.into());
+        }
+        config
+    }
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Run the full bot: reconcile, stream prices and trade opportunities
+    Run(RunArgs),
+    /// Stream prices and spreads without trading
+    Monitor(MonitorArgs),
+    /// Show balances on every exchange
+    Balances(BalancesArgs),
+    /// Place a single manual market order
+    Order(OrderArgs),
+    /// Replay recorded prices through the strategy
+    Backtest(BacktestArgs),
+    /// List order requests a crash left unresolved, or acknowledge one
+    Recover(RecoverArgs),
+    /// Configuration utilities
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ConfigCommand {
+    /// Validate the configuration file and exit
+    Check,
+    /// Print the effective configuration after all layers, secrets redacted
+    Show,
+}
+
+#[derive(Debug, Args)]
+pub struct RunArgs {
+    /// Detect and journal opportunities but never place orders
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Trade on live prices with simulated fills and balances
+    #[arg(long)]
+    pub paper: bool,
+
+    /// Starting paper balance on every exchange, e.g. `USDC=1000` (repeatable)
+    #[arg(long = "paper-balance", value_parser = asset_amount, requires = "paper")]
+    pub paper_balances: Vec<(String, Decimal)>,
+
+    /// Trade journal path (defaults to data/journal.jsonl, or
+    /// data/paper-journal.jsonl with --paper)
+    #[arg(long)]
+    pub journal: Option<PathBuf>,
+
+    /// Halt trading whenever this file exists
+    #[arg(long)]
+    pub kill_switch_file: Option<PathBuf>,
+}
+
+impl RunArgs {
+    /// The journal to use; paper fills never mix with live ones
+    pub fn journal_path(&self) -> PathBuf {
+        match &self.journal {
+            Some(path) => path.clone(),
+            None if self.paper => PathBuf::from("data/paper-journal.jsonl"),
+            None => PathBuf::from("data/journal.jsonl"),
+        }
+    }
+}
+
+#[derive(Debug, Args)]
+pub struct MonitorArgs {
+    /// Seconds between spread reports
+    #[arg(long, default_value_t = 5)]
+    pub interval: u64,
+}
+
+#[derive(Debug, Args)]
+pub struct BalancesArgs {
+    /// Assets to show (defaults to both sides of every configured market)
+    #[arg(long = "asset")]
+    pub assets: Vec<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct OrderArgs {
+    /// Exchange to trade on, as named in `[exchanges]`
+    #[arg(value_parser = exchange_id)]
+    pub exchange: ExchangeId,
+
+    /// Buy or sell
+    #[arg(value_enum)]
+    pub side: SideArg,
+
+    /// Order quantity
+    pub quantity: Decimal,
+
+    /// Trading pair (defaults to the first configured market)
+    #[arg(long)]
+    pub pair: Option<String>,
+
+    /// Actually send the order (otherwise only print it)
+    #[arg(long)]
+    pub yes: bool,
+
+    /// Trade journal path
+    #[arg(long, default_value = "data/journal.jsonl")]
+    pub journal: PathBuf,
+
+    /// Refuse to send while this file exists (pass the same file as `run`)
+    #[arg(long)]
+    pub kill_switch_file: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+pub struct RecoverArgs {
+    /// Correlation ID whose unresolved requests were checked on the exchange;
+    /// lists unresolved requests when omitted
+    #[arg(long)]
+    pub resolve: Option<String>,
+
+    /// Why the requests are resolved, kept in the journal
+    #[arg(long, default_value = "checked by operator")]
+    pub note: String,
+
+    /// Trade journal path
+    #[arg(long, default_value = "data/journal.jsonl")]
+    pub journal: PathBuf,
+}
+
+#[derive(Debug, Args)]
+pub struct BacktestArgs {
+    /// CSV of `timestamp,exchange,pair,bid,ask` rows
+    pub file: PathBuf,
+
+    /// Taker fee per leg on every exchange, as a fraction of notional
+    /// (defaults to the `[fees]` section)
+    #[arg(long)]
+    pub fee_rate: Option<Decimal>,
+
+    /// Print every simulated trade
+    #[arg(short, long)]
+    pub verbose: bool,
+}
+
+fn exchange_id(name: &str) -> std::result::Result<ExchangeId, String> {
+    ExchangeId::from_name(name).ok_or_else(|| format!("invalid exchange name: {}", name))
+}
+
+fn asset_amount(arg: &str) -> std::result::Result<(String, Decimal), String> {
+    let (asset, amount) = arg
+        .split_once('=')
+        .ok_or_else(|| format!("expected ASSET=AMOUNT, got {}", arg))?;
+    let amount = amount
+        .trim()
+        .parse::<Decimal>()
+        .map_err(|e| format!("invalid amount for {}: {}", asset, e))?;
+    Ok((asset.trim().to_uppercase(), amount))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SideArg {
+    Buy,
+    Sell,
+}
+
+impl From<SideArg> for OrderSide {
+    fn from(arg: SideArg) -> Self {
+        match arg {
+            SideArg::Buy => OrderSide::Buy,
+            SideArg::Sell => OrderSide::Sell,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum LogFormatArg {
+    Pretty,
+    Json,
+    Compact,
+}
+
+impl From<LogFormatArg> for LogFormat {
+    fn from(arg: LogFormatArg) -> Self {
+        match arg {
+            LogFormatArg::Pretty => LogFormat::Pretty,
+            LogFormatArg::Json => LogFormat::Json,
+            LogFormatArg::Compact => LogFormat::Compact,
+        }
+    }
+}
+
+/// Run the parsed command
+pub async fn execute(cli: Cli) -> Result<()> {
+    let loader = cli.loader();
+    match cli.command {
+        Command::Run(args) => run::run(&loader, &args).await,
+        Command::Monitor(args) => monitor::run(&loader.load()?, &args).await,
+        Command::Balances(args) => account::balances(&loader.load()?, &args).await,
+        Command::Order(args) => account::order(&loader.load()?, &args).await,
+        Command::Backtest(args) => backtest::run(&loader.load()?, &args),
+        Command::Recover(args) => recover::run(&args).await,
+        Command::Config {
+            command: ConfigCommand::Check,
+        } => check::run(&loader),
+        Command::Config {
+            command: ConfigCommand::Show,
+        } => {
+            print!("{}", loader.dump()?);
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,184 @@
+//! `monitor`: prices and spreads only, no trading
+
+use super::MonitorArgs;
+use super::setup::{
+    PriceFeed, build_exchange, shutdown_signal, subscribe, venue_pairs, watch_staleness,
+};
+use crate::clock::ClockSync;
+use crate::config::{AppConfig, MarketConfig};
+use crate::error::Result;
+use crate::exchanges::Exchange;
+use crate::logger::{info, warn};
+use crate::state::{ExchangeId, PriceState};
+use crate::strategy::SpreadDetector;
+use rust_decimal::Decimal;
+use std::time::Duration;
+
+/// Prices older than this are excluded from spreads
+const PRICE_MAX_AGE: Duration = Duration::from_secs(5);
+
+/// How often prices of exchanges without a stream are copied into the price state
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// One market's clients; a client follows a single pair
+struct MarketFeed<'a> {
+    market: &'a MarketConfig,
+    exchanges: Vec<(ExchangeId, Box<dyn Exchange>)>,
+    feed: PriceFeed,
+}
+
+/// Log prices (flagging stale ones), the mid spread and the best executable
+/// spread of every market, and each exchange's feed latency and clock offset,
+/// every `interval`
+pub async fn run(config: &AppConfig, args: &MonitorArgs) -> Result<()> {
+    let state = PriceState::new(PRICE_MAX_AGE);
+
+    let mut markets = Vec::new();
+    for market in config.trading().markets() {
+        let mut exchanges: Vec<(ExchangeId, Box<dyn Exchange>)> = Vec::new();
+        let mut feed = PriceFeed::default();
+        for id in market.venues(config.exchanges()) {
+            let mut exchange = build_exchange(config.exchanges(), id)?;
+            subscribe(id, exchange.as_mut(), &state, market.symbol(id)).await?;
+            feed.stream(id, exchange.as_ref(), &state);
+            exchanges.push((id, exchange));
+        }
+        markets.push(MarketFeed {
+            market,
+            exchanges,
+            feed,
+        });
+    }
+    let (staleness, staleness_task) = watch_staleness(&state, PRICE_MAX_AGE);
+
+    // Only used to evaluate spreads, so no threshold or cooldown
+    let detector = SpreadDetector::new(Decimal::MIN, Duration::ZERO);
+    let mut poll = tokio::time::interval(POLL_INTERVAL);
+    let mut report = tokio::time::interval(Duration::from_secs(args.interval.max(1)));
+    let shutdown = shutdown_signal();
+    tokio::pin!(shutdown);
+
+    info!(markets = markets.len(), "Monitoring (Ctrl+C to stop)");
+    loop {
+        tokio::select! {
+            _ = &mut shutdown => break,
+            _ = poll.tick() => {
+                for MarketFeed { market, exchanges, feed } in &mut markets {
+                    let feeds: Vec<(ExchangeId, &dyn Exchange)> =
+                        exchanges.iter().map(|(id, e)| (*id, e.as_ref())).collect();
+                    feed.poll(&state, &feeds, market).await;
+                }
+                continue;
+            }
+            _ = report.tick() => {}
+        }
+
+        for MarketFeed { market, exchanges, .. } in &markets {
+            let pair = market.pair();
+            for (id, exchange) in exchanges {
+                match state.get_price(*id, market.symbol(*id)) {
+                    Some(data) => info!(
+                        pair = %pair,
+                        exchange = %id,
+                        bid = %data.price.bid,
+                        ask = %data.price.ask,
+                        age_ms = data.age().as_millis() as u64,
+                        connection = %exchange.connection_state(),
+                        stale = staleness.is_flagged(*id, market.symbol(*id)),
+                        "Price"
+                    ),
+                    None => warn!(pair = %pair, exchange = %id, "No price yet"),
+                }
+            }
+
+            let ids: Vec<ExchangeId> = exchanges.iter().map(|(id, _)| *id).collect();
+            for (&a, &b) in venue_pairs(&ids) {
+                let (a, b) = ((a, market.symbol(a)), (b, market.symbol(b)));
+                let (Some(price_a), Some(price_b)) = (state.get_price(a.0, a.1), state.get_price(b.0, b.1))
+                else {
+                    warn!(pair = %pair, "Spread unavailable (missing price)");
+                    continue;
+                };
+                let mid = price_a.price.mid_price();
+                let spread_pct = state
+                    .get_spread_between(a, b)
+                    .filter(|_| !mid.is_zero())
+                    .map(|spread| spread / mid * Decimal::from(100));
+                let best = detector.evaluate((a.0, &price_a.price), (b.0, &price_b.price));
+                match (spread_pct, best) {
+                    (Some(spread_pct), Some(best)) => info!(
+                        pair = %pair,
+                        mid_spread_pct = %spread_pct.round_dp(4),
+                        buy = %best.buy_exchange.as_str(),
+                        sell = %best.sell_exchange.as_str(),
+                        executable_spread_pct = %(best.spread * Decimal::from(100)).round_dp(4),
+                        above_threshold = best.spread >= market.spread_threshold(),
+                        "Spread"
+                    ),
+                    (Some(spread_pct), None) => info!(
+                        pair = %pair,
+                        mid_spread_pct = %spread_pct.round_dp(4),
+                        "Spread (books overlap, nothing executable)"
+                    ),
+                    (None, _) => warn!(
+                        pair = %pair,
+                        "Spread unavailable (missing, stale or disconnected feed)"
+                    ),
+                }
+            }
+        }
+
+        let mut latencies: Vec<_> = state.latency_tracker().snapshots().into_iter().collect();
+        latencies.sort_unstable_by_key(|(id, _)| *id);
+        for (id, latency) in latencies {
+            info!(
+                exchange = %id,
+                p50_ms = latency.p50_ms,
+                p99_ms = latency.p99_ms,
+                max_ms = latency.max_ms,
+                samples = latency.count,
+                "Feed latency"
+            );
+        }
+
+        let mut clocks: Vec<(ExchangeId, ClockSync)> = Vec::new();
+        for MarketFeed { exchanges, .. } in &markets {
+            for (id, exchange) in exchanges {
+                if let Some(clock) = exchange.clock()
+                    && !clocks.iter().any(|(seen, _)| seen == id)
+                {
+                    clocks.push((*id, clock));
+                }
+            }
+        }
+        for (id, clock) in clocks {
+            match clock.offset() {
+                Some(offset) if clock.is_alarmed() => warn!(
+                    exchange = %id,
+                    offset_ms = offset.offset_ms,
+                    rtt_ms = offset.rtt_ms,
+                    limit_ms = clock.alarm_threshold().as_millis() as u64,
+                    "Clock offset exceeds drift limit"
+                ),
+                Some(offset) => info!(
+                    exchange = %id,
+                    offset_ms = offset.offset_ms,
+                    rtt_ms = offset.rtt_ms,
+                    "Clock offset"
+                ),
+                None => info!(exchange = %id, "Clock offset not measured yet"),
+            }
+        }
+    }
+
+    info!("Stopping monitor");
+    staleness_task.abort();
+    for MarketFeed { exchanges, .. } in &mut markets {
+        for (_, exchange) in exchanges {
+            if let Err(e) = exchange.disconnect().await {
+                warn!(exchange = %exchange.name(), error = %e, "Disconnect failed");
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,50 @@
+//! `recover`: acknowledge order requests a crash left without an outcome
+
+use super::RecoverArgs;
+use crate::error::{ArbitrageError, Result};
+use crate::journal::{Journal, JournalEvent, JournalQuery};
+use crate::trading::{Discrepancy, unresolved_requests};
+
+/// List unresolved order requests, or journal a resolution for one attempt
+///
+/// `run` keeps trading halted while the journal has a request with no outcome.
+/// Once the operator has checked the order on the exchange (and flattened or
+/// recorded any fill by hand), `--resolve <correlation id>` marks the attempt
+/// handled so the next startup reconciles cleanly.
+pub async fn run(args: &RecoverArgs) -> Result<()> {
+    let journal = Journal::open(&args.journal)?;
+    let unresolved = unresolved_requests(&journal.query(&JournalQuery::new())?);
+
+    let Some(correlation_id) = &args.resolve else {
+        if unresolved.is_empty() {
+            println!("No unresolved order requests");
+        }
+        for discrepancy in &unresolved {
+            println!("{}", discrepancy);
+        }
+        return Ok(());
+    };
+
+    let matching = unresolved
+        .iter()
+        .filter(|d| {
+            matches!(d, Discrepancy::UnresolvedRequest { correlation_id: id, .. } if id == correlation_id)
+        })
+        .count();
+    if matching == 0 {
+        return Err(ArbitrageError::ConfigError {
+            field: "resolve".to_string(),
+            reason: format!("no unresolved order request with correlation ID {}", correlation_id),
+        });
+    }
+
+    journal.record(
+        correlation_id,
+        JournalEvent::Resolution {
+            note: args.note.clone(),
+        },
+    );
+    journal.flush().await?;
+    println!("Resolved {} order request(s) of {}", matching, correlation_id);
+    Ok(())
+}
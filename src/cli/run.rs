@@ -0,0 +1,723 @@
+//! `run`: the full trading bot
+
+use super::RunArgs;
+use super::setup::{PriceFeed, shutdown_signal, subscribe, venue_pairs, watch_staleness};
+use crate::config::{
+    AppConfig, ConfigLoader, ConfigReloader, FeesConfig, LiveParams, MarketConfig,
+};
+use crate::error::{ArbitrageError, Result};
+use crate::exchanges::factory::{DefaultExchangeFactory, ExchangeFactory};
+use crate::exchanges::paper::PaperConfig;
+use crate::exchanges::{Exchange, Order, split_pair};
+use crate::inventory::{BalanceCache, InventoryLedger};
+use crate::journal::{Journal, JournalEvent, new_correlation_id};
+use crate::logger::{debug, error, info, log_arbitrage_opportunity, warn};
+use crate::pnl::PnlTracker;
+use crate::risk::RiskManager;
+use crate::state::{ExchangeId, PriceState};
+use crate::strategy::{Opportunity, SpreadDetector};
+use crate::trading::{GuardedExchange, Recovery, TradingGuard, TradingGuardConfig};
+use chrono::Utc;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tokio::sync::watch;
+
+/// Prices older than this are not traded on
+const PRICE_MAX_AGE: Duration = Duration::from_secs(5);
+
+/// How often exchange prices are checked for opportunities
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How often the PnL snapshot is logged
+const PNL_LOG_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How often config files are checked for changes
+const RELOAD_INTERVAL: Duration = Duration::from_secs(2);
+
+/// How often cached balances are refreshed from each exchange
+const BALANCE_REFRESH_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Cached balances older than this block trading
+const BALANCE_MAX_AGE: Duration = Duration::from_secs(60);
+
+/// Largest difference between refreshed and ledger balances put down to rounding
+const BALANCE_TOLERANCE: Decimal = Decimal::from_parts(1, 0, 0, false, 4);
+
+/// Shared by every market's task
+#[derive(Clone)]
+struct Trader {
+    guard: TradingGuard,
+    risk: RiskManager,
+    state: PriceState,
+    pnl: PnlTracker,
+    journal: Journal,
+    /// Per exchange, checked before both legs are sent
+    balances: HashMap<ExchangeId, BalanceCache>,
+    /// Expected holdings, following fills between refreshes
+    ledger: InventoryLedger,
+    orders: Arc<OrderCounts>,
+    fees: FeesConfig,
+    dry_run: bool,
+}
+
+/// Orders sent and settled, so a balance refresh can tell it raced a trade
+#[derive(Debug, Default)]
+struct OrderCounts {
+    sent: AtomicU64,
+    settled: AtomicU64,
+}
+
+impl OrderCounts {
+    /// Orders sent so far, if every one of them has settled
+    fn quiet(&self) -> Option<u64> {
+        let settled = self.settled.load(Ordering::SeqCst);
+        let sent = self.sent.load(Ordering::SeqCst);
+        (sent == settled).then_some(sent)
+    }
+}
+
+/// One market and its clients; a client follows a single pair
+struct Market {
+    config: MarketConfig,
+    exchanges: Vec<(ExchangeId, GuardedExchange<Box<dyn Exchange>>)>,
+}
+
+/// Reconcile, then stream prices and trade opportunities until shutdown
+///
+/// Every configured market trades concurrently in its own task, with its own
+/// exchange clients. Guard, risk limits, prices, PnL and journal are shared.
+///
+/// Trading stays halted if reconciliation fails or finds discrepancies; prices
+/// are still monitored and opportunities journaled so the operator can see what
+/// was missed while investigating. Requests a crash left unanswered are
+/// acknowledged with `arb-bot recover --resolve` before restarting.
+///
+/// Pairs whose price stops updating are logged by the staleness monitor.
+///
+/// Each exchange's balances are cached and refreshed in the background by a
+/// separate client; an opportunity is only traded if both legs are funded.
+/// Refreshed balances are checked against the ledger of expected holdings, and
+/// a discrepancy halts trading.
+///
+/// Trading and risk parameters are reloaded when the config file changes or on
+/// SIGHUP.
+///
+/// With `--paper` orders fill against live prices in a simulated account per
+/// exchange, starting from the `--paper-balance` amounts.
+pub async fn run(loader: &ConfigLoader, args: &RunArgs) -> Result<()> {
+    let config = loader.load()?;
+    let guard = TradingGuard::new(TradingGuardConfig {
+        balance_tolerance: BALANCE_TOLERANCE,
+        kill_switch_file: args.kill_switch_file.clone(),
+        ..Default::default()
+    });
+    let risk = RiskManager::new(config.risk().clone());
+    let state = PriceState::new(PRICE_MAX_AGE);
+    let pnl = PnlTracker::new(state.clone());
+    let journal = Journal::open(args.journal_path())?;
+    let factory = exchange_factory(&config, args);
+    if args.paper {
+        info!(balances = ?args.paper_balances, "Paper trading, no orders reach the exchanges");
+    }
+
+    let mut markets = Vec::new();
+    for market in config.trading().markets() {
+        let mut exchanges = Vec::new();
+        for id in market.venues(config.exchanges()) {
+            let exchange =
+                GuardedExchange::new(factory.create_enabled(config.exchanges(), id)?, guard.clone())
+                    .with_risk_manager(risk.clone());
+            exchanges.push((id, exchange));
+        }
+        markets.push(Market {
+            config: market.clone(),
+            exchanges,
+        });
+    }
+
+    // One client per exchange, checking every symbol traded there
+    let mut clients: Vec<(ExchangeId, &dyn Exchange)> = Vec::new();
+    let mut pairs: Vec<(ExchangeId, &str)> = Vec::new();
+    for market in &markets {
+        for (id, exchange) in &market.exchanges {
+            if !clients.iter().any(|(client, _)| client == id) {
+                clients.push((*id, exchange));
+            }
+            pairs.push((*id, market.config.symbol(*id)));
+        }
+    }
+    // Assets each exchange trades, for balance refreshes
+    let mut assets: Vec<(ExchangeId, Vec<String>)> = Vec::new();
+    for (id, symbol) in &pairs {
+        let Some((base, quote)) = split_pair(symbol) else {
+            continue;
+        };
+        let index = match assets.iter().position(|(venue, _)| venue == id) {
+            Some(index) => index,
+            None => {
+                assets.push((*id, Vec::new()));
+                assets.len() - 1
+            }
+        };
+        for asset in [base, quote] {
+            if !assets[index].1.iter().any(|a| a == asset) {
+                assets[index].1.push(asset.to_string());
+            }
+        }
+    }
+    let mut balances = HashMap::new();
+    let mut balance_clients = Vec::new();
+    for (id, assets) in assets {
+        balances.insert(id, BalanceCache::new(id, BALANCE_MAX_AGE));
+        let client =
+            GuardedExchange::new(factory.create_enabled(config.exchanges(), id)?, guard.clone());
+        balance_clients.push((id, client, assets));
+    }
+
+    let recovery = Recovery::new(journal.clone(), guard.clone())
+        .with_pnl_tracker(pnl.clone())
+        .with_risk_manager(risk.clone());
+    match recovery.run(&clients, &pairs).await {
+        Ok(report) if report.is_clean() => info!(
+            fills = report.fills_replayed,
+            pending = report.pending.len(),
+            "Reconciliation succeeded, trading enabled"
+        ),
+        Ok(report) => warn!(
+            discrepancies = report.discrepancies.len(),
+            "Reconciliation found discrepancies, trading halted (see `arb-bot recover`)"
+        ),
+        Err(e) => error!(error = %e, "Reconciliation failed, trading halted"),
+    }
+
+    for market in &mut markets {
+        for (id, exchange) in &mut market.exchanges {
+            subscribe(*id, exchange, &state, market.config.symbol(*id)).await?;
+        }
+    }
+    let (_, staleness_task) = watch_staleness(&state, PRICE_MAX_AGE);
+    let pnl_task = pnl.clone().spawn(PNL_LOG_INTERVAL);
+    let reloader = ConfigReloader::new(loader.clone(), config.clone());
+    let mut params = reloader.subscribe();
+    let reload_task = reloader.spawn(RELOAD_INTERVAL);
+
+    let trader = Trader {
+        guard,
+        risk: risk.clone(),
+        state,
+        pnl: pnl.clone(),
+        journal: journal.clone(),
+        balances,
+        ledger: InventoryLedger::new(),
+        orders: Arc::default(),
+        fees: config.fees().clone(),
+        dry_run: args.dry_run,
+    };
+    let balance_tasks: Vec<_> = balance_clients
+        .into_iter()
+        .map(|(id, client, assets)| {
+            let trader = trader.clone();
+            tokio::spawn(async move {
+                let assets: Vec<&str> = assets.iter().map(String::as_str).collect();
+                let mut ticker = tokio::time::interval(BALANCE_REFRESH_INTERVAL);
+                let mut seeded = false;
+                loop {
+                    ticker.tick().await;
+                    match refresh_balances(&trader, id, &client, &assets, seeded).await {
+                        Ok(now_seeded) => seeded = now_seeded,
+                        Err(e) => warn!(exchange = %id, error = %e, "Balance refresh failed"),
+                    }
+                }
+            })
+        })
+        .collect();
+    let (stop, stopped) = watch::channel(false);
+    let tasks: Vec<_> = markets
+        .into_iter()
+        .map(|market| {
+            info!(pair = %market.config.pair(), dry_run = args.dry_run, "Market running");
+            tokio::spawn(market.trade(trader.clone(), params.clone(), stopped.clone()))
+        })
+        .collect();
+
+    info!(markets = tasks.len(), dry_run = args.dry_run, "Bot running");
+    let shutdown = shutdown_signal();
+    tokio::pin!(shutdown);
+    loop {
+        tokio::select! {
+            _ = &mut shutdown => break,
+            Ok(()) = params.changed() => {
+                let live = params.borrow_and_update().clone();
+                risk.set_config(live.risk.clone());
+            }
+        }
+    }
+
+    info!("Shutting down");
+    stop.send_replace(true);
+    for task in tasks {
+        if let Err(e) = task.await {
+            error!(error = %e, "Market task failed");
+        }
+    }
+    pnl_task.abort();
+    reload_task.abort();
+    staleness_task.abort();
+    for task in balance_tasks {
+        task.abort();
+    }
+    journal.flush().await?;
+    info!(net_pnl = %pnl.snapshot().net(), "Stopped");
+    Ok(())
+}
+
+/// Builds every client; with `--paper` they fill in one simulated account per
+/// exchange, charged that exchange's `[fees]` rate so the ledger agrees
+fn exchange_factory(config: &AppConfig, args: &RunArgs) -> DefaultExchangeFactory {
+    let factory = DefaultExchangeFactory::new();
+    if !args.paper {
+        return factory;
+    }
+    let mut paper = PaperConfig::default();
+    for id in config.exchanges().enabled() {
+        paper = paper.with_exchange_fee(id, config.fees().taker(id));
+    }
+    for (asset, amount) in &args.paper_balances {
+        paper = paper.with_balance(asset.clone(), *amount);
+    }
+    factory.with_paper_trading(paper)
+}
+
+impl Market {
+    /// Poll prices and trade opportunities until `stopped` is set, then disconnect
+    async fn trade(
+        mut self,
+        trader: Trader,
+        mut params: watch::Receiver<Arc<LiveParams>>,
+        mut stopped: watch::Receiver<bool>,
+    ) {
+        let pair = self.config.pair().to_string();
+        let mut detector = SpreadDetector::from_config(&self.config);
+        let mut feed = PriceFeed::default();
+        for (id, exchange) in &self.exchanges {
+            feed.stream(*id, exchange, &trader.state);
+        }
+        let mut ticker = tokio::time::interval(POLL_INTERVAL);
+
+        loop {
+            tokio::select! {
+                _ = stopped.changed() => break,
+                _ = ticker.tick() => {}
+            }
+
+            if params.has_changed().unwrap_or(false) {
+                let live = params.borrow_and_update().clone();
+                if let Some(market) = live.trading.market(&pair) {
+                    detector.update(market);
+                    self.config = market.clone();
+                }
+            }
+
+            let feeds: Vec<(ExchangeId, &dyn Exchange)> = self
+                .exchanges
+                .iter()
+                .map(|(id, e)| (*id, e as &dyn Exchange))
+                .collect();
+            feed.poll(&trader.state, &feeds, &self.config).await;
+            drop(feeds);
+
+            let Some(mut opportunity) = self.detect(&mut detector, &trader.state) else {
+                continue;
+            };
+            // Journaled under the market's pair, whatever the venues call it
+            opportunity.pair = pair.clone();
+
+            log_arbitrage_opportunity(
+                opportunity.buy_exchange.as_str(),
+                opportunity.sell_exchange.as_str(),
+                &pair,
+                opportunity.spread * Decimal::from(100),
+            );
+            let correlation_id = new_correlation_id();
+            trader.journal.record(&correlation_id, opportunity.journal_event());
+
+            if trader.dry_run || trader.guard.check().is_err() {
+                continue;
+            }
+
+            let position = |id: ExchangeId| self.exchanges.iter().position(|(e, _)| *e == id);
+            let (Some(buy), Some(sell)) = (
+                position(opportunity.buy_exchange),
+                position(opportunity.sell_exchange),
+            ) else {
+                continue;
+            };
+            let Ok([(_, buy), (_, sell)]) = self.exchanges.get_disjoint_mut([buy, sell]) else {
+                continue;
+            };
+            let buy_leg = self.config.symbol(opportunity.buy_exchange);
+            let sell_leg = self.config.symbol(opportunity.sell_exchange);
+            if let Err(e) = check_funds(
+                &trader,
+                &opportunity,
+                buy_leg,
+                sell_leg,
+                self.config.order_size(),
+            ) {
+                warn!(correlation_id = %correlation_id, error = %e, "Opportunity skipped, legs not funded");
+                continue;
+            }
+            execute(
+                &trader,
+                &correlation_id,
+                &opportunity,
+                (buy_leg, buy),
+                (sell_leg, sell),
+                self.config.order_size(),
+            )
+            .await;
+        }
+
+        for (id, exchange) in &mut self.exchanges {
+            if let Err(e) = exchange.disconnect().await {
+                warn!(pair = %pair, exchange = %id, error = %e, "Disconnect failed");
+            }
+        }
+    }
+
+    /// The widest opportunity between any two venues, subject to the cooldown
+    ///
+    /// Only venues passing `PriceState`'s liveness, staleness and skew checks
+    /// are compared.
+    fn detect(&self, detector: &mut SpreadDetector, state: &PriceState) -> Option<Opportunity> {
+        let ids: Vec<ExchangeId> = self.exchanges.iter().map(|(id, _)| *id).collect();
+        let (_, (a, price_a), (b, price_b)) = venue_pairs(&ids)
+            .filter_map(|(&a, &b)| {
+                let (a, b) = ((a, self.config.symbol(a)), (b, self.config.symbol(b)));
+                state.get_spread_between(a, b)?;
+                let price_a = state.get_price(a.0, a.1)?.price;
+                let price_b = state.get_price(b.0, b.1)?.price;
+                let candidate = detector.evaluate((a.0, &price_a), (b.0, &price_b))?;
+                Some((candidate.spread, (a.0, price_a), (b.0, price_b)))
+            })
+            .max_by_key(|(spread, _, _)| *spread)?;
+        detector.detect((a, &price_a), (b, &price_b), Utc::now())
+    }
+}
+
+/// Fail unless cached balances cover both legs
+///
+/// The buy venue needs the quote for `quantity` at the opportunity's buy price
+/// plus its taker fee; the sell venue needs `quantity` of the base.
+fn check_funds(
+    trader: &Trader,
+    opportunity: &Opportunity,
+    buy_symbol: &str,
+    sell_symbol: &str,
+    quantity: Decimal,
+) -> Result<()> {
+    let cache = |id: ExchangeId| {
+        trader
+            .balances
+            .get(&id)
+            .ok_or_else(|| ArbitrageError::ExchangeError {
+                exchange: id,
+                message: "No balance cache".to_string(),
+                code: None,
+            })
+    };
+    let unsplittable = |symbol: &str| ArbitrageError::ParseError {
+        message: format!("Cannot split pair {}", symbol),
+        input: Some(symbol.to_string()),
+    };
+
+    let (_, quote) = split_pair(buy_symbol).ok_or_else(|| unsplittable(buy_symbol))?;
+    let fee_rate = trader.fees.taker(opportunity.buy_exchange);
+    let cost = quantity * opportunity.buy_price * (Decimal::ONE + fee_rate);
+    cache(opportunity.buy_exchange)?.check_available(quote, cost)?;
+
+    let (base, _) = split_pair(sell_symbol).ok_or_else(|| unsplittable(sell_symbol))?;
+    cache(opportunity.sell_exchange)?.check_available(base, quantity)
+}
+
+/// Refresh one exchange's cached balances and check them against the ledger
+///
+/// The ledger is seeded from the first refresh (`seeded` false) and then follows
+/// fills. Later refreshes compare each asset's total, available plus on hold,
+/// with `TradingGuard::check_balance`, then resync the ledger so rounding never
+/// accumulates. A refresh that overlapped an order is not compared, since its
+/// fills may show on one side only. Returns whether the ledger is seeded.
+async fn refresh_balances(
+    trader: &Trader,
+    id: ExchangeId,
+    client: &dyn Exchange,
+    assets: &[&str],
+    seeded: bool,
+) -> Result<bool> {
+    let cache = trader
+        .balances
+        .get(&id)
+        .ok_or_else(|| ArbitrageError::ExchangeError {
+            exchange: id,
+            message: "No balance cache".to_string(),
+            code: None,
+        })?;
+    let before = trader.orders.quiet();
+    cache.refresh(client, assets).await?;
+    let actual: Vec<(&str, Decimal)> = assets
+        .iter()
+        .map(|asset| (*asset, cache.get(asset).map(|b| b.available + b.hold).unwrap_or_default()))
+        .collect();
+    if before.is_none() || trader.orders.quiet() != before {
+        debug!(exchange = %id, "Balance check skipped, an order was in flight");
+        return Ok(seeded);
+    }
+
+    for (asset, actual) in actual {
+        if seeded {
+            let expected = trader.ledger.balance(id, asset);
+            trader.guard.check_balance(id, asset, expected, actual);
+        }
+        trader.ledger.set_balance(id, asset, actual);
+    }
+    Ok(true)
+}
+
+/// Send both legs at once and journal what happened
+///
+/// Each leg is a `(symbol, exchange)`, ordered in the exchange's own symbol.
+/// Realized PnL from the fills counts toward the risk manager's daily loss, and
+/// the fills adjust cached balances and the ledger until the next refresh.
+async fn execute(
+    trader: &Trader,
+    correlation_id: &str,
+    opportunity: &Opportunity,
+    (buy_symbol, buy): (&str, &mut dyn Exchange),
+    (sell_symbol, sell): (&str, &mut dyn Exchange),
+    quantity: Decimal,
+) {
+    let (journal, pnl, risk) = (&trader.journal, &trader.pnl, &trader.risk);
+    let buy_order = Order::market_buy(buy_symbol, quantity);
+    let sell_order = Order::market_sell(sell_symbol, quantity);
+    journal.record(correlation_id, JournalEvent::order_request(buy.name(), &buy_order));
+    journal.record(correlation_id, JournalEvent::order_request(sell.name(), &sell_order));
+
+    trader.orders.sent.fetch_add(2, Ordering::SeqCst);
+    let (buy_result, sell_result) = tokio::join!(
+        buy.place_order(buy_order.clone()),
+        sell.place_order(sell_order.clone())
+    );
+    journal.record_order_outcome(correlation_id, buy.name(), &buy_order, &buy_result);
+    journal.record_order_outcome(correlation_id, sell.name(), &sell_order, &sell_result);
+
+    for (id, order, result) in [
+        (opportunity.buy_exchange, &buy_order, &buy_result),
+        (opportunity.sell_exchange, &sell_order, &sell_result),
+    ] {
+        let Ok(result) = result else {
+            continue;
+        };
+        if let Some(cache) = trader.balances.get(&id) {
+            cache.apply_fill(&order.pair, &order.side, result);
+        }
+        trader.ledger.apply_fill(id, &order.pair, &order.side, result);
+        if let Some(realized) = pnl.record_fill(id, &order.pair, &order.side, result) {
+            risk.record_realized_pnl(realized);
+        }
+    }
+    trader.orders.settled.fetch_add(2, Ordering::SeqCst);
+
+    match (&buy_result, &sell_result) {
+        (Ok(_), Ok(_)) => info!(correlation_id = %correlation_id, "Arbitrage executed"),
+        (Err(e), Err(_)) => warn!(correlation_id = %correlation_id, error = %e, "Both legs failed"),
+        (Err(e), Ok(_)) | (Ok(_), Err(e)) => error!(
+            correlation_id = %correlation_id,
+            error = %e,
+            "One leg failed, position is unhedged"
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::RiskConfig;
+    use crate::exchanges::mock::MockExchange;
+    use crate::exchanges::{Balance, Price};
+    use crate::trading::TripReason;
+
+    fn price(mid: i64) -> Price {
+        let now = Utc::now();
+        Price {
+            pair: "SOL/USDC".to_string(),
+            bid: Decimal::from(mid),
+            ask: Decimal::from(mid),
+            last: Decimal::from(mid),
+            volume_24h: Decimal::ZERO,
+            timestamp: now,
+            received_at: now,
+            sequence: None,
+        }
+    }
+
+    fn opportunity(buy: ExchangeId, sell: ExchangeId) -> Opportunity {
+        Opportunity {
+            pair: "SOL/USDC".to_string(),
+            buy_exchange: buy,
+            sell_exchange: sell,
+            buy_price: Decimal::from(100),
+            sell_price: Decimal::from(100),
+            spread: Decimal::ZERO,
+        }
+    }
+
+    async fn venue(id: ExchangeId, trader: &Trader) -> (MockExchange, GuardedExchange<MockExchange>) {
+        let mut mock = MockExchange::new(id);
+        mock.connect().await.unwrap();
+        mock.set_price("SOL/USDC", price(100));
+        let guarded = GuardedExchange::new(mock.clone(), trader.guard.clone())
+            .with_risk_manager(trader.risk.clone());
+        (mock, guarded)
+    }
+
+    fn trader(risk: RiskConfig, journal: &std::path::Path) -> Trader {
+        let state = PriceState::new(PRICE_MAX_AGE);
+        Trader {
+            guard: TradingGuard::default(),
+            risk: RiskManager::new(risk),
+            state: state.clone(),
+            pnl: PnlTracker::new(state),
+            journal: Journal::open(journal).unwrap(),
+            balances: [ExchangeId::BINANCE, ExchangeId::COINBASE]
+                .into_iter()
+                .map(|id| (id, BalanceCache::new(id, BALANCE_MAX_AGE)))
+                .collect(),
+            ledger: InventoryLedger::new(),
+            orders: Arc::default(),
+            fees: FeesConfig::default().with_taker(ExchangeId::BINANCE, Decimal::new(1, 3)),
+            dry_run: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_both_legs_must_be_funded() {
+        let dir = tempfile::tempdir().unwrap();
+        let trader = trader(RiskConfig::default(), &dir.path().join("journal.jsonl"));
+        let opportunity = opportunity(ExchangeId::BINANCE, ExchangeId::COINBASE);
+        let funded = |available: i64| Balance {
+            available: Decimal::from(available),
+            hold: Decimal::ZERO,
+        };
+        let check = || check_funds(&trader, &opportunity, "SOL/USDC", "SOL/USDC", Decimal::from(2));
+
+        // Never refreshed: stale, so nothing trades
+        assert!(check().is_err());
+
+        // Buying 2 at 100 plus the 0.1% taker fee costs 200.2 USDC
+        trader.balances[&ExchangeId::BINANCE].set_balance("USDC", funded(200));
+        trader.balances[&ExchangeId::COINBASE].set_balance("SOL", funded(5));
+        assert!(matches!(check(), Err(ArbitrageError::InsufficientBalance { asset, .. }) if asset == "USDC"));
+
+        trader.balances[&ExchangeId::BINANCE].set_balance("USDC", funded(201));
+        assert!(check().is_ok());
+
+        trader.balances[&ExchangeId::COINBASE].set_balance("SOL", funded(1));
+        assert!(matches!(check(), Err(ArbitrageError::InsufficientBalance { asset, .. }) if asset == "SOL"));
+    }
+
+    #[tokio::test]
+    async fn test_refresh_checks_balances_against_ledger() {
+        let dir = tempfile::tempdir().unwrap();
+        let trader = trader(RiskConfig::default(), &dir.path().join("journal.jsonl"));
+        let (binance, mut binance_client) = venue(ExchangeId::BINANCE, &trader).await;
+        let (_, mut coinbase_client) = venue(ExchangeId::COINBASE, &trader).await;
+        binance.set_balance("SOL", Decimal::ZERO);
+        binance.set_balance("USDC", Decimal::from(1000));
+        let assets = ["SOL", "USDC"];
+
+        // First refresh seeds the ledger without comparing
+        assert!(refresh_balances(&trader, ExchangeId::BINANCE, &binance, &assets, false).await.unwrap());
+        assert_eq!(trader.ledger.balance(ExchangeId::BINANCE, "USDC"), Decimal::from(1000));
+
+        // Bought 1 SOL at 100 for a 1 USDC fee; the exchange agrees
+        execute(
+            &trader,
+            "c1",
+            &opportunity(ExchangeId::BINANCE, ExchangeId::COINBASE),
+            ("SOL/USDC", &mut binance_client),
+            ("SOL/USDC", &mut coinbase_client),
+            Decimal::ONE,
+        )
+        .await;
+        binance.set_balance("SOL", Decimal::ONE);
+        binance.set_balance("USDC", Decimal::from(899));
+        refresh_balances(&trader, ExchangeId::BINANCE, &binance, &assets, true).await.unwrap();
+        assert!(trader.guard.check().is_ok());
+
+        // A refresh racing an order is not compared
+        trader.orders.sent.fetch_add(1, Ordering::SeqCst);
+        binance.set_balance("USDC", Decimal::from(500));
+        refresh_balances(&trader, ExchangeId::BINANCE, &binance, &assets, true).await.unwrap();
+        assert!(trader.guard.check().is_ok());
+        trader.orders.settled.fetch_add(1, Ordering::SeqCst);
+
+        refresh_balances(&trader, ExchangeId::BINANCE, &binance, &assets, true).await.unwrap();
+        assert!(matches!(
+            trader.guard.trip_info().map(|t| t.reason),
+            Some(TripReason::BalanceDiscrepancy { asset, .. }) if asset == "USDC"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_losing_fills_trip_daily_loss_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        let risk = RiskConfig::default().with_max_daily_loss(Decimal::from(5));
+        let trader = trader(risk, &dir.path().join("journal.jsonl"));
+        let (binance, mut binance_client) = venue(ExchangeId::BINANCE, &trader).await;
+        let (coinbase, mut coinbase_client) = venue(ExchangeId::COINBASE, &trader).await;
+        let quantity = Decimal::ONE;
+
+        // Opens both positions; only the two 1 USDC fees are lost
+        execute(
+            &trader,
+            "c1",
+            &opportunity(ExchangeId::BINANCE, ExchangeId::COINBASE),
+            ("SOL/USDC", &mut binance_client),
+            ("SOL/USDC", &mut coinbase_client),
+            quantity,
+        )
+        .await;
+        assert_eq!(trader.risk.daily_realized_pnl(), Decimal::from(-2));
+
+        // Binance long closed 5 lower: -5, plus two more fees
+        binance.set_price("SOL/USDC", price(95));
+        execute(
+            &trader,
+            "c2",
+            &opportunity(ExchangeId::COINBASE, ExchangeId::BINANCE),
+            ("SOL/USDC", &mut coinbase_client),
+            ("SOL/USDC", &mut binance_client),
+            quantity,
+        )
+        .await;
+        assert_eq!(trader.risk.daily_realized_pnl(), Decimal::from(-9));
+
+        // Both legs are now refused before reaching either exchange
+        execute(
+            &trader,
+            "c3",
+            &opportunity(ExchangeId::BINANCE, ExchangeId::COINBASE),
+            ("SOL/USDC", &mut binance_client),
+            ("SOL/USDC", &mut coinbase_client),
+            quantity,
+        )
+        .await;
+        assert_eq!(binance.orders_placed(), 2);
+        assert_eq!(coinbase.orders_placed(), 2);
+        // Fills moved cached balances: bought 1 then sold 1 SOL on Binance
+        assert_eq!(trader.balances[&ExchangeId::BINANCE].available("SOL"), Decimal::ZERO);
+    }
+}
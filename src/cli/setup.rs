@@ -0,0 +1,198 @@
+//! Shared wiring for subcommands: exchange construction and price polling
+
+use crate::config::{ExchangesConfig, MarketConfig};
+use crate::error::Result;
+use crate::exchanges::Exchange;
+use crate::exchanges::factory::{DefaultExchangeFactory, ExchangeFactory};
+use crate::logger::warn;
+use crate::state::{ExchangeId, PriceState, StalenessMonitor};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::task::JoinHandle;
+
+/// Construct the client for an enabled exchange
+pub fn build_exchange(exchanges: &ExchangesConfig, id: ExchangeId) -> Result<Box<dyn Exchange>> {
+    DefaultExchangeFactory::new().create_enabled(exchanges, id)
+}
+
+/// Subscribe to `pair` and register the feed's connection state and sequence
+/// policy with `state`
+pub async fn subscribe(
+    id: ExchangeId,
+    exchange: &mut dyn Exchange,
+    state: &PriceState,
+    pair: &str,
+) -> Result<()> {
+    exchange.subscribe_ticker(pair).await?;
+    if let Some(rx) = exchange.watch_connection_state() {
+        state.watch_connection(id, rx);
+    }
+    if let Some(policy) = exchange.sequence_policy() {
+        state.sequence_tracker().set_exchange_policy(id, policy);
+    }
+    Ok(())
+}
+
+/// Every two-venue combination of `venues`, each once
+pub fn venue_pairs<T>(venues: &[T]) -> impl Iterator<Item = (&T, &T)> {
+    venues
+        .iter()
+        .enumerate()
+        .flat_map(move |(i, a)| venues[i + 1..].iter().map(move |b| (a, b)))
+}
+
+/// Copies exchanges' quotes into `PriceState`
+///
+/// Exchanges publishing `price_updates()` are streamed: every quote is ingested
+/// as it arrives, so the sequence tracker sees each one and a gap is a real gap.
+///
+/// Other exchanges keep only their latest price, so polling returns the same
+/// quote until a new one arrives. Only quotes not seen before are ingested -
+/// re-ingesting an old quote would restamp it and hide a stalled feed from
+/// staleness checks.
+#[derive(Debug, Default)]
+pub struct PriceFeed {
+    last_seen: HashMap<ExchangeId, DateTime<Utc>>,
+    /// Ingestion tasks of streamed exchanges, stopped when the feed is dropped
+    streams: HashMap<ExchangeId, JoinHandle<()>>,
+}
+
+impl PriceFeed {
+    /// Ingest every quote `exchange` publishes, returning whether it publishes any
+    ///
+    /// Streamed exchanges are skipped by `poll()`.
+    pub fn stream(&mut self, id: ExchangeId, exchange: &dyn Exchange, state: &PriceState) -> bool {
+        let Some(mut updates) = exchange.price_updates() else {
+            return false;
+        };
+        let state = state.clone();
+        let task = tokio::spawn(async move {
+            loop {
+                match updates.recv().await {
+                    Ok(price) => {
+                        state.ingest(id, price);
+                    }
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!(exchange = %id, skipped = skipped, "Price updates lagged");
+                    }
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        });
+        if let Some(previous) = self.streams.insert(id, task) {
+            previous.abort();
+        }
+        true
+    }
+
+    /// Ingest any new quote for `market` from each exchange not streamed
+    ///
+    /// Quotes are stored under each exchange's own symbol for the market.
+    pub async fn poll(
+        &mut self,
+        state: &PriceState,
+        exchanges: &[(ExchangeId, &dyn Exchange)],
+        market: &MarketConfig,
+    ) {
+        for (id, exchange) in exchanges {
+            if self.streams.contains_key(id) {
+                continue;
+            }
+            let Ok(price) = exchange.get_latest_price(market.symbol(*id)).await else {
+                continue;
+            };
+            if self.last_seen.get(id) == Some(&price.received_at) {
+                continue;
+            }
+            self.last_seen.insert(*id, price.received_at);
+            state.ingest(*id, price);
+        }
+    }
+}
+
+impl Drop for PriceFeed {
+    fn drop(&mut self) {
+        for task in self.streams.values() {
+            task.abort();
+        }
+    }
+}
+
+/// How often the staleness monitor scans the price state
+const STALENESS_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Start flagging pairs whose price is older than `threshold`
+///
+/// The returned monitor shares the background task's flags; abort the handle to
+/// stop it.
+pub fn watch_staleness(state: &PriceState, threshold: Duration) -> (StalenessMonitor, JoinHandle<()>) {
+    let monitor = StalenessMonitor::new(state.clone(), threshold);
+    let task = monitor.spawn(STALENESS_CHECK_INTERVAL);
+    (monitor, task)
+}
+
+/// Resolves on Ctrl+C or, on Unix, SIGTERM (sent by `docker stop`)
+pub async fn shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{SignalKind, signal};
+        match signal(SignalKind::terminate()) {
+            Ok(mut term) => {
+                tokio::select! {
+                    _ = tokio::signal::ctrl_c() => {}
+                    _ = term.recv() => {}
+                }
+                return;
+            }
+            Err(e) => warn!(error = %e, "Cannot listen for SIGTERM"),
+        }
+    }
+    let _ = tokio::signal::ctrl_c().await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exchanges::Price;
+    use crate::exchanges::mock::MockExchange;
+    use crate::state::SequenceStats;
+    use rust_decimal::Decimal;
+
+    fn quote(sequence: u64) -> Price {
+        let now = Utc::now();
+        Price {
+            pair: "SOL/USDC".to_string(),
+            bid: Decimal::from(100),
+            ask: Decimal::from(101),
+            last: Decimal::from(100),
+            volume_24h: Decimal::ZERO,
+            timestamp: now,
+            received_at: now,
+            sequence: Some(sequence),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_streamed_quotes_are_all_ingested() {
+        let state = PriceState::new(Duration::from_secs(5));
+        let mock = MockExchange::new("coinbase");
+        let mut feed = PriceFeed::default();
+        assert!(feed.stream(ExchangeId::COINBASE, &mock, &state));
+
+        // Faster than any poll: sampling would see only the last and report a gap
+        for sequence in 1..=3 {
+            mock.set_price("SOL/USDC", quote(sequence));
+        }
+        let tracker = state.sequence_tracker();
+        tokio::time::timeout(Duration::from_secs(1), async {
+            while tracker.last_sequence(ExchangeId::COINBASE, "SOL/USDC") != Some(3) {
+                tokio::task::yield_now().await;
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(tracker.stats(ExchangeId::COINBASE, "SOL/USDC"), SequenceStats::default());
+    }
+}
@@ -0,0 +1,16 @@
+//! Exchange Clock Synchronization
+//!
+//! Estimates the offset between the local clock and each exchange's server clock
+//! so signed requests (Coinbase JWT `nbf`/`exp`, Binance `timestamp`/`recvWindow`)
+//! keep working when the host clock drifts.
+//!
+//! Exchange clients with credentials own a `SyncedClock` and sign with it;
+//! `Exchange::clock()` exposes the offset for monitoring.
+
+pub mod source;
+pub mod sync;
+
+pub use source::{
+    BinanceTimeSource, BybitTimeSource, CoinbaseTimeSource, OkxTimeSource, ServerTimeSource,
+};
+pub use sync::{ClockOffset, ClockSync, ClockSyncConfig, SyncedClock};
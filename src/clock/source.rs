@@ -0,0 +1,253 @@
+//! Server time sources
+//!
+//! Public (unauthenticated) REST endpoints that report each exchange's clock.
+
+use crate::error::{ArbitrageError, Result};
+use crate::exchanges::ExchangeId;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+
+/// A source of an exchange's current server time
+#[async_trait]
+#[allow(clippy::result_large_err)]
+pub trait ServerTimeSource: Send + Sync {
+    /// Exchange name (for logs)
+    fn name(&self) -> &str;
+
+    /// Query the exchange's current server time
+    async fn server_time(&self) -> Result<DateTime<Utc>>;
+}
+
+/// Binance `GET /api/v3/time` -> `{"serverTime": 1499827319559}`
+pub struct BinanceTimeSource {
+    client: Client,
+    url: String,
+}
+
+impl BinanceTimeSource {
+    /// Create a time source for Binance.US production or the Binance testnet
+    pub fn new(testnet: bool) -> Self {
+        let base_url = if testnet {
+            crate::constants::api::BINANCE_TESTNET
+        } else {
+            crate::constants::api::BINANCE_US_PRODUCTION
+        };
+        Self::with_base_url(base_url)
+    }
+
+    /// Create a time source against a custom base URL (e.g. a local stand-in server)
+    pub fn with_base_url(base_url: &str) -> Self {
+        Self {
+            client: Client::new(),
+            url: format!("{}{}", base_url, crate::constants::api::BINANCE_TIME_PATH),
+        }
+    }
+}
+
+#[async_trait]
+impl ServerTimeSource for BinanceTimeSource {
+    fn name(&self) -> &str {
+        crate::constants::exchange::BINANCE
+    }
+
+    async fn server_time(&self) -> Result<DateTime<Utc>> {
+        let body = fetch_json(&self.client, &self.url, ExchangeId::BINANCE).await?;
+        body["serverTime"]
+            .as_i64()
+            .and_then(DateTime::from_timestamp_millis)
+            .ok_or_else(|| ArbitrageError::ParseError {
+                message: "Missing or invalid 'serverTime'".to_string(),
+                input: Some(body.to_string()),
+            })
+    }
+}
+
+/// Coinbase server time
+///
+/// - Production (Advanced Trade) `GET /api/v3/brokerage/time` ->
+///   `{"iso": "...", "epochSeconds": "...", "epochMillis": "..."}`
+/// - Sandbox (Exchange API) `GET /time` -> `{"iso": "...", "epoch": 1420674445.201}`
+pub struct CoinbaseTimeSource {
+    client: Client,
+    url: String,
+}
+
+impl CoinbaseTimeSource {
+    /// Create a time source for Coinbase production or sandbox
+    pub fn new(sandbox: bool) -> Self {
+        if sandbox {
+            Self::with_url(format!(
+                "{}{}",
+                crate::constants::api::COINBASE_SANDBOX,
+                crate::constants::api::COINBASE_EXCHANGE_TIME_PATH
+            ))
+        } else {
+            Self::with_base_url(crate::constants::api::COINBASE_PRODUCTION)
+        }
+    }
+
+    /// Create a time source against a custom base URL (e.g. a local stand-in server)
+    pub fn with_base_url(base_url: &str) -> Self {
+        Self::with_url(format!("{}{}", base_url, crate::constants::api::COINBASE_TIME_PATH))
+    }
+
+    fn with_url(url: String) -> Self {
+        Self {
+            client: Client::new(),
+            url,
+        }
+    }
+}
+
+#[async_trait]
+impl ServerTimeSource for CoinbaseTimeSource {
+    fn name(&self) -> &str {
+        crate::constants::exchange::COINBASE
+    }
+
+    async fn server_time(&self) -> Result<DateTime<Utc>> {
+        let body = fetch_json(&self.client, &self.url, ExchangeId::COINBASE).await?;
+        // epochMillis is returned as a string; the Exchange API has fractional `epoch` seconds
+        body["epochMillis"]
+            .as_str()
+            .and_then(|s| s.parse::<i64>().ok())
+            .or_else(|| body["epochMillis"].as_i64())
+            .or_else(|| body["epoch"].as_f64().map(|s| (s * 1000.0).round() as i64))
+            .and_then(DateTime::from_timestamp_millis)
+            .or_else(|| {
+                body["iso"]
+                    .as_str()
+                    .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+                    .map(|dt| dt.with_timezone(&Utc))
+            })
+            .ok_or_else(|| ArbitrageError::ParseError {
+                message: "Missing or invalid 'epochMillis'/'epoch'/'iso'".to_string(),
+                input: Some(body.to_string()),
+            })
+    }
+}
+
+/// OKX `GET /api/v5/public/time` -> `{"code": "0", "data": [{"ts": "1597026383085"}]}`
+///
+/// Demo trading shares the production host.
+pub struct OkxTimeSource {
+    client: Client,
+    url: String,
+}
+
+impl OkxTimeSource {
+    /// Create a time source for OKX
+    pub fn new() -> Self {
+        Self::with_base_url(crate::constants::api::OKX_PRODUCTION)
+    }
+
+    /// Create a time source against a custom base URL (e.g. a local stand-in server)
+    pub fn with_base_url(base_url: &str) -> Self {
+        Self {
+            client: Client::new(),
+            url: format!("{}{}", base_url, crate::constants::api::OKX_TIME_PATH),
+        }
+    }
+}
+
+impl Default for OkxTimeSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl ServerTimeSource for OkxTimeSource {
+    fn name(&self) -> &str {
+        crate::constants::exchange::OKX
+    }
+
+    async fn server_time(&self) -> Result<DateTime<Utc>> {
+        let body = fetch_json(&self.client, &self.url, ExchangeId::OKX).await?;
+        body["data"][0]["ts"]
+            .as_str()
+            .and_then(|s| s.parse::<i64>().ok())
+            .and_then(DateTime::from_timestamp_millis)
+            .ok_or_else(|| ArbitrageError::ParseError {
+                message: "Missing or invalid 'data[0].ts'".to_string(),
+                input: Some(body.to_string()),
+            })
+    }
+}
+
+/// Bybit `GET /v5/market/time` -> `{"retCode": 0, "result": {"timeSecond": "...", "timeNano": "..."}, "time": 1672025956592}`
+pub struct BybitTimeSource {
+    client: Client,
+    url: String,
+}
+
+impl BybitTimeSource {
+    /// Create a time source for Bybit production or testnet
+    pub fn new(testnet: bool) -> Self {
+        let base_url = if testnet {
+            crate::constants::api::BYBIT_TESTNET
+        } else {
+            crate::constants::api::BYBIT_PRODUCTION
+        };
+        Self::with_base_url(base_url)
+    }
+
+    /// Create a time source against a custom base URL (e.g. a local stand-in server)
+    pub fn with_base_url(base_url: &str) -> Self {
+        Self {
+            client: Client::new(),
+            url: format!("{}{}", base_url, crate::constants::api::BYBIT_TIME_PATH),
+        }
+    }
+}
+
+#[async_trait]
+impl ServerTimeSource for BybitTimeSource {
+    fn name(&self) -> &str {
+        crate::constants::exchange::BYBIT
+    }
+
+    async fn server_time(&self) -> Result<DateTime<Utc>> {
+        let body = fetch_json(&self.client, &self.url, ExchangeId::BYBIT).await?;
+        // timeNano is the most precise; `time` is when the response was built
+        body["result"]["timeNano"]
+            .as_str()
+            .and_then(|s| s.parse::<i64>().ok())
+            .map(|nanos| nanos / 1_000_000)
+            .or_else(|| body["time"].as_i64())
+            .and_then(DateTime::from_timestamp_millis)
+            .ok_or_else(|| ArbitrageError::ParseError {
+                message: "Missing or invalid 'result.timeNano'/'time'".to_string(),
+                input: Some(body.to_string()),
+            })
+    }
+}
+
+async fn fetch_json(client: &Client, url: &str, exchange: ExchangeId) -> Result<serde_json::Value> {
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| ArbitrageError::NetworkError {
+            message: format!("Server time request to {} failed: {}", url, e),
+            retry_after: None,
+        })?;
+
+    let status = response.status();
+    if !status.is_success() {
+        return Err(ArbitrageError::ExchangeError {
+            exchange,
+            message: format!("Server time request failed ({})", status),
+            code: Some(status.as_u16() as i32),
+        });
+    }
+
+    response
+        .json::<serde_json::Value>()
+        .await
+        .map_err(|e| ArbitrageError::ParseError {
+            message: format!("Invalid server time response: {}", e),
+            input: None,
+        })
+}
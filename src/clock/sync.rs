@@ -0,0 +1,351 @@
+//! Clock offset estimation
+//!
+//! NTP-style estimate: the server timestamp is assumed to be taken halfway through
+//! the round trip, so `offset = server_time - (local_send + rtt / 2)`.
+
+use super::source::ServerTimeSource;
+use crate::error::{ArbitrageError, Result};
+use crate::logger::{debug, error, info, warn};
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// A single clock offset measurement
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClockOffset {
+    /// Server clock minus local clock (positive = local clock is behind)
+    pub offset_ms: i64,
+    /// Round-trip time of the measurement request
+    pub rtt_ms: i64,
+    /// Local time the measurement was taken
+    pub measured_at: DateTime<Utc>,
+}
+
+impl ClockOffset {
+    /// Estimate the offset from a request sent at `sent_at`, answered with
+    /// `server_time`, and received at `received_at` (all but `server_time` local)
+    pub fn estimate(
+        sent_at: DateTime<Utc>,
+        server_time: DateTime<Utc>,
+        received_at: DateTime<Utc>,
+    ) -> Self {
+        let rtt_ms = (received_at - sent_at).num_milliseconds().max(0);
+        let midpoint = sent_at + chrono::Duration::milliseconds(rtt_ms / 2);
+        Self {
+            offset_ms: (server_time - midpoint).num_milliseconds(),
+            rtt_ms,
+            measured_at: received_at,
+        }
+    }
+}
+
+/// Clock sync settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClockSyncConfig {
+    /// How often to re-measure the offset
+    pub interval: Duration,
+    /// Requests per measurement round; the lowest-RTT sample wins
+    pub samples: usize,
+    /// Absolute offset above which an alarm is raised
+    pub alarm_threshold: Duration,
+}
+
+impl Default for ClockSyncConfig {
+    /// - Interval: 5 minutes
+    /// - Samples: 3
+    /// - Alarm threshold: 1 second
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(300),
+            samples: 3,
+            alarm_threshold: Duration::from_secs(1),
+        }
+    }
+}
+
+/// Shared, cheaply clonable view of one exchange's clock offset
+///
+/// # Business Logic
+///
+/// Signed requests are rejected when their timestamp falls outside the exchange's
+/// acceptance window (Coinbase JWT `nbf`/`exp`, Binance `recvWindow`). Signing code
+/// calls `now()` instead of `Utc::now()` to get the local time shifted onto the
+/// exchange clock. Until the first measurement `now()` is just the local clock.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use arb_bot::clock::{ClockSync, ClockSyncConfig, CoinbaseTimeSource};
+/// use std::sync::Arc;
+///
+/// # async fn run() {
+/// let clock = ClockSync::new("coinbase", ClockSyncConfig::default());
+/// let _handle = clock.clone().spawn(Arc::new(CoinbaseTimeSource::new(false)));
+///
+/// // Corrected time for request signing
+/// let now = clock.now();
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ClockSync {
+    exchange: String,
+    config: ClockSyncConfig,
+    offset: Arc<RwLock<Option<ClockOffset>>>,
+}
+
+impl ClockSync {
+    /// Create a clock sync for an exchange with no measurement yet
+    pub fn new(exchange: impl Into<String>, config: ClockSyncConfig) -> Self {
+        Self {
+            exchange: exchange.into(),
+            config,
+            offset: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    /// Exchange this clock tracks
+    pub fn exchange(&self) -> &str {
+        &self.exchange
+    }
+
+    /// Absolute offset above which `is_alarmed()` is true
+    pub fn alarm_threshold(&self) -> Duration {
+        self.config.alarm_threshold
+    }
+
+    /// Latest offset measurement, if any
+    pub fn offset(&self) -> Option<ClockOffset> {
+        *self.offset.read()
+    }
+
+    /// Latest offset in milliseconds (0 before the first measurement)
+    pub fn offset_ms(&self) -> i64 {
+        self.offset().map(|o| o.offset_ms).unwrap_or(0)
+    }
+
+    /// Local time corrected onto the exchange clock
+    pub fn now(&self) -> DateTime<Utc> {
+        Utc::now() + chrono::Duration::milliseconds(self.offset_ms())
+    }
+
+    /// Corrected time as milliseconds since epoch (Binance `timestamp` parameter)
+    pub fn timestamp_millis(&self) -> i64 {
+        self.now().timestamp_millis()
+    }
+
+    /// True if the latest offset exceeds the alarm threshold
+    pub fn is_alarmed(&self) -> bool {
+        self.offset()
+            .map(|o| o.offset_ms.unsigned_abs() > self.alarm_threshold().as_millis() as u64)
+            .unwrap_or(false)
+    }
+
+    /// Store a new measurement and raise the alarm if it exceeds the threshold
+    pub fn apply(&self, offset: ClockOffset) {
+        *self.offset.write() = Some(offset);
+
+        if self.is_alarmed() {
+            error!(
+                exchange = %self.exchange,
+                offset_ms = offset.offset_ms,
+                rtt_ms = offset.rtt_ms,
+                threshold_ms = self.config.alarm_threshold.as_millis() as u64,
+                "Clock offset exceeds threshold"
+            );
+        } else {
+            debug!(
+                exchange = %self.exchange,
+                offset_ms = offset.offset_ms,
+                rtt_ms = offset.rtt_ms,
+                "Clock offset updated"
+            );
+        }
+    }
+
+    /// Run one measurement round against a server time source
+    ///
+    /// Takes `config.samples` measurements and keeps the one with the lowest RTT,
+    /// since its midpoint assumption has the smallest possible error.
+    pub async fn sync_once(&self, source: &dyn ServerTimeSource) -> Result<ClockOffset> {
+        let mut best: Option<ClockOffset> = None;
+        let mut last_error = None;
+
+        for _ in 0..self.config.samples.max(1) {
+            let sent_at = Utc::now();
+            match source.server_time().await {
+                Ok(server_time) => {
+                    let sample = ClockOffset::estimate(sent_at, server_time, Utc::now());
+                    if best.is_none_or(|b| sample.rtt_ms < b.rtt_ms) {
+                        best = Some(sample);
+                    }
+                }
+                Err(e) => last_error = Some(e),
+            }
+        }
+
+        match best {
+            Some(offset) => {
+                self.apply(offset);
+                Ok(offset)
+            }
+            None => Err(last_error.unwrap_or_else(|| ArbitrageError::NetworkError {
+                message: format!("No clock samples collected from {}", source.name()),
+                retry_after: None,
+            })),
+        }
+    }
+
+    /// Spawn a background task that re-measures the offset every `config.interval`
+    ///
+    /// Failed rounds are logged and the previous offset is kept.
+    pub fn spawn(self, source: Arc<dyn ServerTimeSource>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.config.interval);
+            loop {
+                interval.tick().await;
+                match self.sync_once(source.as_ref()).await {
+                    Ok(offset) => info!(
+                        exchange = %self.exchange,
+                        offset_ms = offset.offset_ms,
+                        rtt_ms = offset.rtt_ms,
+                        "Clock sync completed"
+                    ),
+                    Err(e) => warn!(exchange = %self.exchange, error = %e, "Clock sync failed"),
+                }
+            }
+        })
+    }
+}
+
+/// A `ClockSync` kept current by a background task that stops when this is dropped
+///
+/// Exchange clients own one, so the sync task lives exactly as long as the
+/// client, and hand `clock()` to their request signer.
+#[derive(Debug)]
+pub struct SyncedClock {
+    clock: ClockSync,
+    task: Option<tokio::task::JoinHandle<()>>,
+}
+
+impl SyncedClock {
+    /// Start syncing `clock` against `source` on the current Tokio runtime
+    ///
+    /// Outside a runtime (e.g. a synchronous test) nothing is spawned and the
+    /// clock keeps reading the local time.
+    pub fn start(clock: ClockSync, source: Arc<dyn ServerTimeSource>) -> Self {
+        let task = tokio::runtime::Handle::try_current()
+            .ok()
+            .map(|_| clock.clone().spawn(source));
+        Self { clock, task }
+    }
+
+    /// The clock being synced
+    pub fn clock(&self) -> &ClockSync {
+        &self.clock
+    }
+}
+
+impl Drop for SyncedClock {
+    fn drop(&mut self) {
+        if let Some(task) = self.task.take() {
+            task.abort();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+
+    struct FixedOffsetSource {
+        offset: chrono::Duration,
+    }
+
+    #[async_trait]
+    impl ServerTimeSource for FixedOffsetSource {
+        fn name(&self) -> &str {
+            "fixed"
+        }
+
+        async fn server_time(&self) -> Result<DateTime<Utc>> {
+            Ok(Utc::now() + self.offset)
+        }
+    }
+
+    #[test]
+    fn test_estimate_uses_rtt_midpoint() {
+        let sent_at = DateTime::from_timestamp_millis(1_000_000).unwrap();
+        let received_at = DateTime::from_timestamp_millis(1_000_200).unwrap();
+        // Server clock is 500ms ahead: at local midpoint 1_000_100 it reads 1_000_600
+        let server_time = DateTime::from_timestamp_millis(1_000_600).unwrap();
+
+        let offset = ClockOffset::estimate(sent_at, server_time, received_at);
+        assert_eq!(offset.rtt_ms, 200);
+        assert_eq!(offset.offset_ms, 500);
+    }
+
+    #[test]
+    fn test_now_defaults_to_local_clock() {
+        let clock = ClockSync::new("test", ClockSyncConfig::default());
+        assert_eq!(clock.offset_ms(), 0);
+        assert!(!clock.is_alarmed());
+        assert!((clock.now() - Utc::now()).num_milliseconds().abs() < 100);
+    }
+
+    #[test]
+    fn test_alarm_threshold() {
+        let clock = ClockSync::new("test", ClockSyncConfig::default());
+        clock.apply(ClockOffset {
+            offset_ms: -1500,
+            rtt_ms: 20,
+            measured_at: Utc::now(),
+        });
+        assert!(clock.is_alarmed());
+
+        clock.apply(ClockOffset {
+            offset_ms: 300,
+            rtt_ms: 20,
+            measured_at: Utc::now(),
+        });
+        assert!(!clock.is_alarmed());
+    }
+
+    #[tokio::test]
+    async fn test_synced_clock_stops_when_dropped() {
+        let source = Arc::new(FixedOffsetSource {
+            offset: chrono::Duration::seconds(5),
+        });
+        let synced = SyncedClock::start(ClockSync::new("test", ClockSyncConfig::default()), source);
+        let clock = synced.clock().clone();
+
+        // The first round runs immediately
+        tokio::time::timeout(Duration::from_secs(1), async {
+            while clock.offset().is_none() {
+                tokio::task::yield_now().await;
+            }
+        })
+        .await
+        .unwrap();
+        assert!((clock.offset_ms() - 5_000).abs() < 100);
+
+        let task = synced.task.as_ref().unwrap().abort_handle();
+        drop(synced);
+        tokio::task::yield_now().await;
+        assert!(task.is_finished());
+    }
+
+    #[tokio::test]
+    async fn test_sync_once_corrects_now() {
+        let clock = ClockSync::new("test", ClockSyncConfig::default());
+        let source = FixedOffsetSource {
+            offset: chrono::Duration::seconds(10),
+        };
+
+        let offset = clock.sync_once(&source).await.unwrap();
+        assert!((offset.offset_ms - 10_000).abs() < 100);
+        assert!(((clock.now() - Utc::now()).num_milliseconds() - 10_000).abs() < 100);
+        assert!(clock.is_alarmed());
+    }
+}
@@ -0,0 +1,359 @@
+//! Application configuration
+//!
+//! One TOML file describes the whole bot. Each section has its own raw type and
+//! is validated by that section's `TryFrom`; `AppConfig` only assembles them.
+
+use crate::config::exchange::{ExchangesConfig, RawExchangesConfig};
+use crate::config::fees::{FeesConfig, RawFeesConfig};
+use crate::config::logging::RawLoggingConfig;
+use crate::config::loader::ConfigLoader;
+use crate::config::parse::{ConfigError, ValidationReport};
+use crate::config::risk::{RawRiskConfig, RiskConfig};
+use crate::config::trading::{RawTradingConfig, TradingConfig};
+use crate::error::Result;
+use crate::exchanges::split_pair;
+use crate::logger::LoggerConfig;
+use serde::{Deserialize, Deserializer};
+use std::path::Path;
+
+/// Raw application configuration for deserialization (loose validation)
+///
+/// Only `[trading]` is required; every other section has defaults.
+#[derive(Debug, Default, Deserialize)]
+pub struct RawAppConfig {
+    pub trading: Option<RawTradingConfig>,
+    #[serde(default)]
+    pub exchanges: RawExchangesConfig,
+    #[serde(default)]
+    pub logging: RawLoggingConfig,
+    #[serde(default)]
+    pub risk: RawRiskConfig,
+    #[serde(default)]
+    pub fees: RawFeesConfig,
+}
+
+/// Validated application configuration (guaranteed valid after parse)
+#[derive(Debug, Clone)]
+pub struct AppConfig {
+    trading: TradingConfig,
+    exchanges: ExchangesConfig,
+    logging: LoggerConfig,
+    risk: RiskConfig,
+    fees: FeesConfig,
+}
+
+/// Deserialize a raw config, collecting the paths of keys no section knows
+pub fn deserialize_raw<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<(RawAppConfig, Vec<String>), D::Error> {
+    let mut unknown = Vec::new();
+    let raw = serde_ignored::deserialize(deserializer, |path| unknown.push(key_path(&path)))?;
+    Ok((raw, unknown))
+}
+
+/// Dotted key path, without the markers serde_ignored adds for options and newtypes
+fn key_path(path: &serde_ignored::Path) -> String {
+    use serde_ignored::Path;
+    match path {
+        Path::Root => String::new(),
+        Path::Map { parent, key } => match key_path(parent) {
+            parent if parent.is_empty() => key.clone(),
+            parent => format!("{}.{}", parent, key),
+        },
+        Path::Seq { parent, index } => format!("{}[{}]", key_path(parent), index),
+        Path::Some { parent } | Path::NewtypeStruct { parent } | Path::NewtypeVariant { parent } => {
+            key_path(parent)
+        }
+    }
+}
+
+impl TryFrom<RawAppConfig> for AppConfig {
+    type Error = ConfigError;
+
+    fn try_from(raw: RawAppConfig) -> std::result::Result<Self, Self::Error> {
+        Self::validate(raw, Vec::new())
+    }
+}
+
+impl AppConfig {
+    /// Validate every section, reporting all problems at once
+    ///
+    /// `unknown_keys` (from `deserialize_raw`) are reported too - they are
+    /// usually misspelled settings that would otherwise be silently ignored.
+    pub fn validate(
+        raw: RawAppConfig,
+        unknown_keys: Vec<String>,
+    ) -> std::result::Result<Self, ConfigError> {
+        let mut report = ValidationReport::default();
+        for key in unknown_keys {
+            report.push(key.clone(), ConfigError::UnknownField { field: key });
+        }
+
+        let trading = report
+            .require("trading", raw.trading)
+            .and_then(|t| report.section("trading", TradingConfig::try_from(t)));
+        let exchanges = report.section("exchanges", ExchangesConfig::try_from(raw.exchanges));
+        let logging = report.section("logging", LoggerConfig::try_from(raw.logging));
+        let risk = report.section("risk", RiskConfig::try_from(raw.risk));
+        let fees = report.section("fees", FeesConfig::try_from(raw.fees));
+
+        // A position limit on an asset no market trades is almost always a typo
+        if let (Some(trading), Some(risk)) = (&trading, &risk) {
+            let traded: Vec<&str> = trading
+                .markets()
+                .iter()
+                .filter_map(|m| split_pair(m.pair()))
+                .flat_map(|(base, quote)| [base, quote])
+                .collect();
+            for asset in risk.position_assets() {
+                if !traded.contains(&asset) {
+                    let pairs: Vec<&str> = trading.markets().iter().map(|m| m.pair()).collect();
+                    report.push(
+                        format!("risk.max_position.{}", asset),
+                        ConfigError::UnknownAsset {
+                            asset: asset.to_string(),
+                            pair: pairs.join(", "),
+                        },
+                    );
+                }
+            }
+        }
+
+        // A market can only trade where the client is configured
+        if let (Some(trading), Some(exchanges)) = (&trading, &exchanges) {
+            for (i, market) in trading.markets().iter().enumerate() {
+                for id in market.exchanges().unwrap_or_default() {
+                    let reason = if exchanges.is_enabled(*id) {
+                        continue;
+                    } else if ExchangesConfig::VENUES.contains(id) {
+                        "exchange is disabled"
+                    } else {
+                        "exchange is not configured"
+                    };
+                    report.push(
+                        format!("trading.markets[{}].exchanges", i),
+                        ConfigError::InvalidExchange {
+                            exchange: id.to_string(),
+                            reason: reason.to_string(),
+                        },
+                    );
+                }
+            }
+        }
+
+        let config = match (trading, exchanges, logging, risk, fees) {
+            (Some(trading), Some(exchanges), Some(logging), Some(risk), Some(fees)) => {
+                Some(AppConfig {
+                    trading,
+                    exchanges,
+                    logging,
+                    risk,
+                    fees,
+                })
+            }
+            _ => None,
+        };
+        report.finish(config)
+    }
+
+    /// Read and validate a config file, with defaults and environment overrides
+    ///
+    /// See `ConfigLoader` for the layers and profiles.
+    pub fn load(path: &Path) -> Result<Self> {
+        ConfigLoader::new(path).load()
+    }
+
+    /// Parse and validate TOML config contents alone, without other layers
+    pub fn parse(contents: &str) -> Result<Self> {
+        let (raw, unknown) = deserialize_raw(toml::Deserializer::new(contents))?;
+        Ok(Self::validate(raw, unknown)?)
+    }
+
+    pub fn trading(&self) -> &TradingConfig {
+        &self.trading
+    }
+
+    pub fn exchanges(&self) -> &ExchangesConfig {
+        &self.exchanges
+    }
+
+    pub fn logging(&self) -> &LoggerConfig {
+        &self.logging
+    }
+
+    pub fn risk(&self) -> &RiskConfig {
+        &self.risk
+    }
+
+    pub fn fees(&self) -> &FeesConfig {
+        &self.fees
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logger::LogFormat;
+    use crate::state::ExchangeId;
+    use rust_decimal::Decimal;
+
+    const TRADING: &str = r#"
+[trading]
+pair = "SOL/USDC"
+spread_threshold = 0.002
+order_size = 10.0
+cooldown_ms = 5000
+"#;
+
+    #[test]
+    fn trading_only_uses_defaults() {
+        let cfg = AppConfig::parse(TRADING).unwrap();
+        assert_eq!(cfg.trading().pair(), "SOL/USDC");
+        assert_eq!(cfg.exchanges().enabled().len(), 2);
+        assert_eq!(cfg.logging().level(), "info");
+        assert!(cfg.risk().max_daily_loss().is_none());
+        assert_eq!(cfg.fees().taker(ExchangeId::BINANCE), Decimal::new(1, 3));
+    }
+
+    #[test]
+    fn all_sections_parse() {
+        let contents = format!(
+            "{}{}",
+            TRADING,
+            r#"
+[exchanges.binance]
+enabled = false
+
+[exchanges.coinbase]
+sandbox = true
+
+[logging]
+format = "json"
+
+[risk]
+max_daily_loss = 50.0
+
+[fees.exchange]
+coinbase = 0.006
+"#
+        );
+
+        let cfg = AppConfig::parse(&contents).unwrap();
+        assert_eq!(cfg.exchanges().enabled(), vec![ExchangeId::COINBASE]);
+        assert!(cfg.exchanges().coinbase().unwrap().sandbox);
+        assert_eq!(cfg.logging().format(), LogFormat::Json);
+        assert!(cfg.risk().max_daily_loss().is_some());
+        assert_eq!(cfg.fees().taker(ExchangeId::COINBASE), Decimal::new(6, 3));
+    }
+
+    #[test]
+    fn reject_missing_trading() {
+        let err = AppConfig::parse("[risk]\nmax_daily_loss = 50.0").unwrap_err();
+        assert!(format!("{}", err).contains("trading"));
+    }
+
+    #[test]
+    fn section_errors_propagate() {
+        let contents = format!("{}\n[logging]\nrotation = \"weekly\"\n", TRADING);
+        let err = AppConfig::parse(&contents).unwrap_err();
+        assert!(format!("{}", err).contains("rotation"));
+    }
+
+    #[test]
+    fn reports_problems_from_every_section() {
+        let contents = r#"
+[trading]
+pair = "SOL/USDC"
+spread_threshold = "2"
+order_size = 10.0
+cooldwn_ms = 5000
+
+[exchanges."bit stamp"]
+enabled = true
+
+[logging]
+format = "xml"
+
+[fees]
+taker = -0.1
+"#;
+
+        let err = AppConfig::parse(contents).unwrap_err();
+        let crate::error::ArbitrageError::ConfigParse(err) = err else {
+            panic!("expected a config error");
+        };
+        let ConfigError::Invalid(report) = *err else {
+            panic!("expected a validation report");
+        };
+        let mut paths: Vec<&str> = report.issues().iter().map(|i| i.path.as_str()).collect();
+        paths.sort();
+        assert_eq!(
+            paths,
+            [
+                "exchanges.bit stamp",
+                "fees.taker",
+                "logging.format",
+                "trading.cooldown_ms",
+                "trading.cooldwn_ms",
+                "trading.spread_threshold",
+            ]
+        );
+    }
+
+    #[test]
+    fn reject_position_limit_on_untraded_asset() {
+        let contents = format!("{}\n[risk.max_position]\nSOL = 100.0\nBTC = 1.0\n", TRADING);
+        let err = AppConfig::parse(&contents).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "risk.max_position.BTC: Unknown asset BTC: not part of pair SOL/USDC"
+        );
+    }
+
+    #[test]
+    fn market_exchanges_must_be_enabled() {
+        let contents = r#"
+[trading]
+spread_threshold = 0.002
+order_size = 1.0
+cooldown_ms = 5000
+
+[[trading.markets]]
+pair = "SOL/USDC"
+
+[[trading.markets]]
+pair = "BTC/USDT"
+exchanges = ["binance", "coinbase"]
+
+[exchanges.coinbase]
+enabled = false
+
+[risk.max_position]
+BTC = 1.0
+"#;
+        let err = AppConfig::parse(contents).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "trading.markets[1].exchanges: Invalid exchange config coinbase: exchange is disabled"
+        );
+    }
+
+    #[test]
+    fn market_exchanges_must_be_configured() {
+        let contents = r#"
+[trading]
+spread_threshold = 0.002
+order_size = 1.0
+cooldown_ms = 5000
+
+[[trading.markets]]
+pair = "SOL/USDC"
+exchanges = ["binance", "bitstamp"]
+"#;
+        let err = AppConfig::parse(contents).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "trading.markets[0].exchanges: Invalid exchange config bitstamp: exchange is not configured"
+        );
+    }
+}
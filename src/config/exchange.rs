@@ -1,27 +1,785 @@
 //! Exchange configuration types
 
+use crate::config::parse::{ConfigError, ValidationReport};
+use crate::state::ExchangeId;
 use serde::Deserialize;
+use std::collections::BTreeMap;
 
 /// Binance exchange configuration
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct BinanceConfig {
     /// Binance API key
     pub api_key: String,
     /// Binance API secret
     pub api_secret: String,
     /// Use testnet (true) or production (false)
-    // TODO Change to use environment variables
     pub testnet: bool,
 }
 
 /// Coinbase exchange configuration
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct CoinbaseConfig {
     /// Coinbase API key
     pub api_key: String,
     /// Coinbase API secret (for JWT signing)
     pub api_secret: String,
     /// Use sandbox (true) or production (false)
-    // TODO Change to use environment variables
     pub sandbox: bool,
 }
+
+/// Kraken exchange configuration
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct KrakenConfig {
+    /// Kraken API key
+    pub api_key: String,
+    /// Kraken private key, base64-encoded (for HMAC-SHA512 signing)
+    pub api_secret: String,
+}
+
+/// OKX exchange configuration
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct OkxConfig {
+    /// OKX API key
+    pub api_key: String,
+    /// OKX API secret (for HMAC-SHA256 signing)
+    pub api_secret: String,
+    /// Passphrase chosen when the API key was created
+    pub passphrase: String,
+    /// Use demo trading (true) or production (false)
+    pub demo: bool,
+}
+
+/// Bybit exchange configuration
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct BybitConfig {
+    /// Bybit API key
+    pub api_key: String,
+    /// Bybit API secret (for HMAC-SHA256 signing)
+    pub api_secret: String,
+    /// Use testnet (true) or production (false)
+    pub testnet: bool,
+}
+
+/// Raw `[exchanges.binance]` section (loose validation)
+///
+/// Empty credentials are filled from `BINANCE_API_KEY` / `BINANCE_API_SECRET`
+/// when the exchange is constructed, so secrets can stay out of the file.
+#[derive(Debug, Default, Deserialize)]
+#[cfg_attr(test, derive(serde::Serialize))]
+pub struct RawBinanceConfig {
+    pub enabled: Option<bool>,
+    pub api_key: Option<String>,
+    pub api_secret: Option<String>,
+    pub testnet: Option<bool>,
+}
+
+/// Raw `[exchanges.coinbase]` section (loose validation)
+///
+/// Empty credentials are filled from `COINBASE_API_KEY` / `COINBASE_API_SECRET`.
+#[derive(Debug, Default, Deserialize)]
+#[cfg_attr(test, derive(serde::Serialize))]
+pub struct RawCoinbaseConfig {
+    pub enabled: Option<bool>,
+    pub api_key: Option<String>,
+    pub api_secret: Option<String>,
+    pub sandbox: Option<bool>,
+}
+
+/// Raw `[exchanges.kraken]` section (loose validation)
+///
+/// Credentials come from the file only; there is no environment fallback.
+#[derive(Debug, Default, Deserialize)]
+#[cfg_attr(test, derive(serde::Serialize))]
+pub struct RawKrakenConfig {
+    pub enabled: Option<bool>,
+    pub api_key: Option<String>,
+    pub api_secret: Option<String>,
+}
+
+/// Raw `[exchanges.okx]` section (loose validation)
+///
+/// Credentials come from the file only; there is no environment fallback.
+#[derive(Debug, Default, Deserialize)]
+#[cfg_attr(test, derive(serde::Serialize))]
+pub struct RawOkxConfig {
+    pub enabled: Option<bool>,
+    pub api_key: Option<String>,
+    pub api_secret: Option<String>,
+    pub passphrase: Option<String>,
+    pub demo: Option<bool>,
+}
+
+/// Raw `[exchanges.bybit]` section (loose validation)
+///
+/// Credentials come from the file only; there is no environment fallback.
+#[derive(Debug, Default, Deserialize)]
+#[cfg_attr(test, derive(serde::Serialize))]
+pub struct RawBybitConfig {
+    pub enabled: Option<bool>,
+    pub api_key: Option<String>,
+    pub api_secret: Option<String>,
+    pub testnet: Option<bool>,
+}
+
+/// Raw `[exchanges]` table
+///
+/// An omitted Binance or Coinbase section means the exchange is enabled with
+/// defaults. Kraken, OKX and Bybit are opt-in: each is enabled only when its
+/// section is present.
+///
+/// ```toml
+/// [exchanges.binance]
+/// enabled = true
+/// testnet = false
+///
+/// [exchanges.coinbase]
+/// enabled = true
+/// sandbox = false
+///
+/// [exchanges.kraken]
+/// api_key = "..."
+/// api_secret = "..."
+///
+/// [exchanges.okx]
+/// api_key = "..."
+/// api_secret = "..."
+/// passphrase = "..."
+/// demo = true
+///
+/// [exchanges.bybit]
+/// api_key = "..."
+/// api_secret = "..."
+///
+/// # A second Binance account, traded as `binance:hedge`
+/// [exchanges."binance:hedge"]
+/// api_key = "..."
+/// api_secret = "..."
+/// ```
+///
+/// Further accounts take the settings of their venue's section, without an
+/// environment fallback; like Kraken they are enabled by their section.
+///
+/// Any other section belongs to an exchange registered with the factory at
+/// runtime. It is kept as written and handed to that exchange's constructor,
+/// which validates it; building an exchange nobody registered fails.
+///
+/// ```toml
+/// [exchanges.bitstamp]
+/// tier = 2
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[cfg_attr(test, derive(serde::Serialize))]
+pub struct RawExchangesConfig {
+    #[serde(default)]
+    pub binance: RawBinanceConfig,
+    #[serde(default)]
+    pub coinbase: RawCoinbaseConfig,
+    #[serde(default)]
+    pub kraken: Option<RawKrakenConfig>,
+    #[serde(default)]
+    pub okx: Option<RawOkxConfig>,
+    #[serde(default)]
+    pub bybit: Option<RawBybitConfig>,
+    /// Every other section: further accounts (`"binance:hedge"`) and registered exchanges
+    #[serde(flatten)]
+    pub others: BTreeMap<String, toml::Value>,
+}
+
+/// Validated settings for a further account on a built-in venue
+#[derive(Debug, Clone, PartialEq)]
+pub enum AccountConfig {
+    Binance(BinanceConfig),
+    Coinbase(CoinbaseConfig),
+    Kraken(KrakenConfig),
+    Okx(OkxConfig),
+    Bybit(BybitConfig),
+}
+
+/// Validated exchange settings; `None` means the exchange is disabled
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExchangesConfig {
+    binance: Option<BinanceConfig>,
+    coinbase: Option<CoinbaseConfig>,
+    kraken: Option<KrakenConfig>,
+    okx: Option<OkxConfig>,
+    bybit: Option<BybitConfig>,
+    accounts: BTreeMap<ExchangeId, AccountConfig>,
+    /// Sections of registered exchanges, as written
+    custom: BTreeMap<ExchangeId, toml::Value>,
+}
+
+/// Credentials must be given together or not at all, in the exchange's format
+fn credentials(
+    report: &mut ValidationReport,
+    exchange: &str,
+    api_key: Option<String>,
+    api_secret: Option<String>,
+    format: fn(&str, &str) -> Vec<(&'static str, &'static str)>,
+) -> (String, String) {
+    let api_key = api_key.unwrap_or_default();
+    let api_secret = api_secret.unwrap_or_default();
+    if api_key.is_empty() != api_secret.is_empty() {
+        report.push(
+            format!("{}.{}", exchange, if api_key.is_empty() { "api_key" } else { "api_secret" }),
+            ConfigError::InvalidExchange {
+                exchange: exchange.to_string(),
+                reason: "api_key and api_secret must be set together".to_string(),
+            },
+        );
+    } else {
+        for (field, reason) in format(&api_key, &api_secret) {
+            report.push(
+                format!("{}.{}", exchange, field),
+                ConfigError::InvalidExchange {
+                    exchange: exchange.to_string(),
+                    reason: reason.to_string(),
+                },
+            );
+        }
+    }
+    (api_key, api_secret)
+}
+
+/// Binance and Bybit keys and secrets are alphanumeric strings
+fn binance_key_format(api_key: &str, api_secret: &str) -> Vec<(&'static str, &'static str)> {
+    let alphanumeric = |s: &str| s.chars().all(|c| c.is_ascii_alphanumeric());
+    let mut problems = Vec::new();
+    if !alphanumeric(api_key) {
+        problems.push(("api_key", "must be alphanumeric"));
+    }
+    if !alphanumeric(api_secret) {
+        problems.push(("api_secret", "must be alphanumeric"));
+    }
+    problems
+}
+
+/// Coinbase CDP keys are a resource path plus a PEM-encoded EC private key
+fn coinbase_key_format(api_key: &str, api_secret: &str) -> Vec<(&'static str, &'static str)> {
+    let mut problems = Vec::new();
+    if !api_key.is_empty() && !api_key.starts_with("organizations/") {
+        problems.push((
+            "api_key",
+            "must look like organizations/{org_id}/apiKeys/{key_id}",
+        ));
+    }
+    if !api_secret.is_empty() && !api_secret.contains("BEGIN EC PRIVATE KEY") {
+        problems.push(("api_secret", "must be a PEM-encoded EC private key"));
+    }
+    problems
+}
+
+/// Kraken private keys are base64-encoded
+fn kraken_key_format(_api_key: &str, api_secret: &str) -> Vec<(&'static str, &'static str)> {
+    use base64::Engine;
+
+    let mut problems = Vec::new();
+    if base64::engine::general_purpose::STANDARD
+        .decode(api_secret.trim())
+        .is_err()
+    {
+        problems.push(("api_secret", "must be base64-encoded"));
+    }
+    problems
+}
+
+/// OKX secrets are hex strings
+fn okx_key_format(_api_key: &str, api_secret: &str) -> Vec<(&'static str, &'static str)> {
+    let mut problems = Vec::new();
+    if !api_secret.chars().all(|c| c.is_ascii_hexdigit()) {
+        problems.push(("api_secret", "must be hexadecimal"));
+    }
+    problems
+}
+
+fn binance(report: &mut ValidationReport, name: &str, raw: RawBinanceConfig) -> BinanceConfig {
+    let (api_key, api_secret) =
+        credentials(report, name, raw.api_key, raw.api_secret, binance_key_format);
+    BinanceConfig {
+        api_key,
+        api_secret,
+        testnet: raw.testnet.unwrap_or(false),
+    }
+}
+
+fn coinbase(report: &mut ValidationReport, name: &str, raw: RawCoinbaseConfig) -> CoinbaseConfig {
+    let (api_key, api_secret) =
+        credentials(report, name, raw.api_key, raw.api_secret, coinbase_key_format);
+    CoinbaseConfig {
+        api_key,
+        api_secret,
+        sandbox: raw.sandbox.unwrap_or(false),
+    }
+}
+
+fn kraken(report: &mut ValidationReport, name: &str, raw: RawKrakenConfig) -> KrakenConfig {
+    let (api_key, api_secret) =
+        credentials(report, name, raw.api_key, raw.api_secret, kraken_key_format);
+    KrakenConfig { api_key, api_secret }
+}
+
+fn okx(report: &mut ValidationReport, name: &str, raw: RawOkxConfig) -> OkxConfig {
+    let (api_key, api_secret) =
+        credentials(report, name, raw.api_key, raw.api_secret, okx_key_format);
+    // Every private request is signed with the passphrase as well
+    let passphrase = raw.passphrase.unwrap_or_default();
+    if api_key.is_empty() != passphrase.is_empty() {
+        report.push(
+            format!("{}.passphrase", name),
+            ConfigError::InvalidExchange {
+                exchange: name.to_string(),
+                reason: "api_key, api_secret and passphrase must be set together".to_string(),
+            },
+        );
+    }
+    OkxConfig {
+        api_key,
+        api_secret,
+        passphrase,
+        demo: raw.demo.unwrap_or(false),
+    }
+}
+
+fn bybit(report: &mut ValidationReport, name: &str, raw: RawBybitConfig) -> BybitConfig {
+    let (api_key, api_secret) =
+        credentials(report, name, raw.api_key, raw.api_secret, binance_key_format);
+    BybitConfig {
+        api_key,
+        api_secret,
+        testnet: raw.testnet.unwrap_or(false),
+    }
+}
+
+/// Deserialize a further account's section, reporting keys its venue doesn't know
+fn account_section<T: serde::de::DeserializeOwned>(
+    report: &mut ValidationReport,
+    name: &str,
+    section: toml::Value,
+) -> Option<T> {
+    let mut unknown = Vec::new();
+    let parsed = serde_ignored::deserialize(section, |path| unknown.push(path.to_string()));
+    for key in unknown {
+        let path = format!("{}.{}", name, key);
+        let field = format!("exchanges.{}", path);
+        report.push(path, ConfigError::UnknownField { field });
+    }
+    match parsed {
+        Ok(raw) => Some(raw),
+        Err(e) => {
+            report.push(
+                name,
+                ConfigError::InvalidExchange {
+                    exchange: name.to_string(),
+                    reason: e.to_string(),
+                },
+            );
+            None
+        }
+    }
+}
+
+/// A `[exchanges."venue:account"]` section, or `None` if disabled or invalid
+fn account(
+    report: &mut ValidationReport,
+    id: ExchangeId,
+    section: toml::Value,
+) -> Option<AccountConfig> {
+    let name = id.as_str();
+    if section.get("enabled").and_then(toml::Value::as_bool) == Some(false) {
+        return None;
+    }
+    let account = match id.venue() {
+        ExchangeId::BINANCE => {
+            let raw = account_section(report, name, section)?;
+            AccountConfig::Binance(binance(report, name, raw))
+        }
+        ExchangeId::COINBASE => {
+            let raw = account_section(report, name, section)?;
+            AccountConfig::Coinbase(coinbase(report, name, raw))
+        }
+        ExchangeId::KRAKEN => {
+            let raw = account_section(report, name, section)?;
+            AccountConfig::Kraken(kraken(report, name, raw))
+        }
+        ExchangeId::OKX => {
+            let raw = account_section(report, name, section)?;
+            AccountConfig::Okx(okx(report, name, raw))
+        }
+        ExchangeId::BYBIT => {
+            let raw = account_section(report, name, section)?;
+            AccountConfig::Bybit(bybit(report, name, raw))
+        }
+        _ => return None,
+    };
+    Some(account)
+}
+
+impl TryFrom<RawExchangesConfig> for ExchangesConfig {
+    type Error = ConfigError;
+
+    /// Validates every exchange, reporting all problems at once
+    fn try_from(raw: RawExchangesConfig) -> std::result::Result<Self, Self::Error> {
+        let mut report = ValidationReport::default();
+
+        let binance = raw
+            .binance
+            .enabled
+            .unwrap_or(true)
+            .then(|| binance(&mut report, "binance", raw.binance));
+        let coinbase = raw
+            .coinbase
+            .enabled
+            .unwrap_or(true)
+            .then(|| coinbase(&mut report, "coinbase", raw.coinbase));
+        let kraken = raw
+            .kraken
+            .filter(|kraken| kraken.enabled.unwrap_or(true))
+            .map(|raw| kraken(&mut report, "kraken", raw));
+        let okx = raw
+            .okx
+            .filter(|okx| okx.enabled.unwrap_or(true))
+            .map(|raw| okx(&mut report, "okx", raw));
+        let bybit = raw
+            .bybit
+            .filter(|bybit| bybit.enabled.unwrap_or(true))
+            .map(|raw| bybit(&mut report, "bybit", raw));
+
+        let mut accounts = BTreeMap::new();
+        let mut custom = BTreeMap::new();
+        for (name, section) in raw.others {
+            let Some(id) = ExchangeId::from_name(&name) else {
+                report.push(
+                    name.clone(),
+                    ConfigError::InvalidExchange {
+                        exchange: name,
+                        reason: "not a valid exchange name".to_string(),
+                    },
+                );
+                continue;
+            };
+            if Self::VENUES.contains(&id.venue()) {
+                if let Some(config) = account(&mut report, id, section) {
+                    accounts.insert(id, config);
+                }
+            } else if section.get("enabled").and_then(toml::Value::as_bool) != Some(false) {
+                custom.insert(id, section);
+            }
+        }
+
+        if binance.is_none()
+            && coinbase.is_none()
+            && kraken.is_none()
+            && okx.is_none()
+            && bybit.is_none()
+            && accounts.is_empty()
+            && custom.is_empty()
+        {
+            report.push(
+                "",
+                ConfigError::InvalidExchange {
+                    exchange: "exchanges".to_string(),
+                    reason: "at least one exchange must be enabled".to_string(),
+                },
+            );
+        }
+
+        report.finish(Some(ExchangesConfig {
+            binance,
+            coinbase,
+            kraken,
+            okx,
+            bybit,
+            accounts,
+            custom,
+        }))
+    }
+}
+
+impl ExchangesConfig {
+    /// Exchanges with a section in `[exchanges]`, in a fixed order
+    pub const VENUES: [ExchangeId; 5] = [
+        ExchangeId::BINANCE,
+        ExchangeId::COINBASE,
+        ExchangeId::KRAKEN,
+        ExchangeId::OKX,
+        ExchangeId::BYBIT,
+    ];
+
+    /// Binance settings, if enabled
+    pub fn binance(&self) -> Option<&BinanceConfig> {
+        self.binance.as_ref()
+    }
+
+    /// Coinbase settings, if enabled
+    pub fn coinbase(&self) -> Option<&CoinbaseConfig> {
+        self.coinbase.as_ref()
+    }
+
+    /// Kraken settings, if enabled
+    pub fn kraken(&self) -> Option<&KrakenConfig> {
+        self.kraken.as_ref()
+    }
+
+    /// OKX settings, if enabled
+    pub fn okx(&self) -> Option<&OkxConfig> {
+        self.okx.as_ref()
+    }
+
+    /// Bybit settings, if enabled
+    pub fn bybit(&self) -> Option<&BybitConfig> {
+        self.bybit.as_ref()
+    }
+
+    /// Settings of a further account such as `binance:hedge`, if enabled
+    pub fn account(&self, id: ExchangeId) -> Option<&AccountConfig> {
+        self.accounts.get(&id)
+    }
+
+    /// Section of an exchange registered at runtime, if enabled
+    pub fn custom(&self, id: ExchangeId) -> Option<&toml::Value> {
+        self.custom.get(&id)
+    }
+
+    /// Whether `id` is enabled
+    pub fn is_enabled(&self, id: ExchangeId) -> bool {
+        match id {
+            ExchangeId::BINANCE => self.binance.is_some(),
+            ExchangeId::COINBASE => self.coinbase.is_some(),
+            ExchangeId::KRAKEN => self.kraken.is_some(),
+            ExchangeId::OKX => self.okx.is_some(),
+            ExchangeId::BYBIT => self.bybit.is_some(),
+            _ => self.accounts.contains_key(&id) || self.custom.contains_key(&id),
+        }
+    }
+
+    /// Enabled exchanges, in a fixed order: venues, further accounts, then
+    /// registered exchanges, each by name
+    pub fn enabled(&self) -> Vec<ExchangeId> {
+        Self::VENUES
+            .into_iter()
+            .filter(|id| self.is_enabled(*id))
+            .chain(self.accounts.keys().copied())
+            .chain(self.custom.keys().copied())
+            .collect()
+    }
+}
+
+impl Default for ExchangesConfig {
+    /// Binance and Coinbase enabled, production endpoints, credentials from the environment
+    fn default() -> Self {
+        Self::try_from(RawExchangesConfig::default()).expect("default exchanges are valid")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn omitted_sections_enable_both_exchanges() {
+        let cfg = ExchangesConfig::try_from(RawExchangesConfig::default()).unwrap();
+        assert_eq!(cfg.enabled(), vec![ExchangeId::BINANCE, ExchangeId::COINBASE]);
+        assert!(!cfg.binance().unwrap().testnet);
+        assert!(cfg.coinbase().unwrap().api_key.is_empty());
+    }
+
+    #[test]
+    fn disabled_exchange_is_none() {
+        let raw = RawExchangesConfig {
+            binance: RawBinanceConfig {
+                enabled: Some(false),
+                ..Default::default()
+            },
+            coinbase: RawCoinbaseConfig {
+                sandbox: Some(true),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let cfg = ExchangesConfig::try_from(raw).unwrap();
+        assert!(cfg.binance().is_none());
+        assert!(cfg.coinbase().unwrap().sandbox);
+        assert_eq!(cfg.enabled(), vec![ExchangeId::COINBASE]);
+    }
+
+    #[test]
+    fn reject_key_without_secret() {
+        let raw = RawExchangesConfig {
+            binance: RawBinanceConfig {
+                api_key: Some("key".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let err = ExchangesConfig::try_from(raw).unwrap_err();
+        assert!(format!("{}", err).contains("binance.api_secret"));
+    }
+
+    #[test]
+    fn reject_malformed_credentials() {
+        let raw = RawExchangesConfig {
+            binance: RawBinanceConfig {
+                api_key: Some("abc 123".to_string()),
+                api_secret: Some("secret".to_string()),
+                ..Default::default()
+            },
+            coinbase: RawCoinbaseConfig {
+                api_key: Some("key-id".to_string()),
+                api_secret: Some("not a pem".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let Err(ConfigError::Invalid(report)) = ExchangesConfig::try_from(raw) else {
+            panic!("expected a validation report");
+        };
+        let paths: Vec<&str> = report.issues().iter().map(|i| i.path.as_str()).collect();
+        assert_eq!(paths, ["binance.api_key", "coinbase.api_key", "coinbase.api_secret"]);
+    }
+
+    #[test]
+    fn reject_all_disabled() {
+        let raw = RawExchangesConfig {
+            binance: RawBinanceConfig {
+                enabled: Some(false),
+                ..Default::default()
+            },
+            coinbase: RawCoinbaseConfig {
+                enabled: Some(false),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        assert!(ExchangesConfig::try_from(raw).is_err());
+    }
+
+    #[test]
+    fn kraken_is_opt_in() {
+        let cfg = ExchangesConfig::default();
+        assert!(cfg.kraken().is_none());
+
+        let raw = RawExchangesConfig {
+            binance: RawBinanceConfig {
+                enabled: Some(false),
+                ..Default::default()
+            },
+            coinbase: RawCoinbaseConfig {
+                enabled: Some(false),
+                ..Default::default()
+            },
+            kraken: Some(RawKrakenConfig::default()),
+            ..Default::default()
+        };
+        let cfg = ExchangesConfig::try_from(raw).unwrap();
+        assert_eq!(cfg.enabled(), vec![ExchangeId::KRAKEN]);
+    }
+
+    #[test]
+    fn reject_non_base64_kraken_secret() {
+        let raw = RawExchangesConfig {
+            kraken: Some(RawKrakenConfig {
+                api_key: Some("key".to_string()),
+                api_secret: Some("not base64!".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let err = ExchangesConfig::try_from(raw).unwrap_err();
+        assert!(format!("{}", err).contains("kraken.api_secret"));
+    }
+
+    #[test]
+    fn okx_requires_passphrase_with_credentials() {
+        let raw = RawExchangesConfig {
+            okx: Some(RawOkxConfig {
+                api_key: Some("key".to_string()),
+                api_secret: Some("22582BD0CFF14C41EDBF1AB98506286D".to_string()),
+                demo: Some(true),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let err = ExchangesConfig::try_from(raw).unwrap_err();
+        assert!(format!("{}", err).contains("okx.passphrase"));
+    }
+
+    #[test]
+    fn bybit_is_opt_in() {
+        assert!(ExchangesConfig::default().bybit().is_none());
+
+        let raw = RawExchangesConfig {
+            bybit: Some(RawBybitConfig {
+                testnet: Some(true),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let cfg = ExchangesConfig::try_from(raw).unwrap();
+        assert!(cfg.bybit().unwrap().testnet);
+        assert_eq!(
+            cfg.enabled(),
+            vec![ExchangeId::BINANCE, ExchangeId::COINBASE, ExchangeId::BYBIT]
+        );
+    }
+
+    #[test]
+    fn accounts_validate_like_their_venue() {
+        let raw: RawExchangesConfig = toml::from_str(
+            r#"
+            [exchanges."okx:hedge"]
+            api_key = "key"
+            api_secret = "22582BD0CFF14C41EDBF1AB98506286D"
+            dmeo = true
+
+            [exchanges."kraken:spare"]
+            enabled = false
+
+            [exchanges."bit stamp"]
+            tier = 2
+            "#,
+        )
+        .map(|mut raw: toml::Table| raw.remove("exchanges").unwrap().try_into().unwrap())
+        .unwrap();
+
+        let Err(ConfigError::Invalid(report)) = ExchangesConfig::try_from(raw) else {
+            panic!("expected a validation report");
+        };
+        let paths: Vec<&str> = report.issues().iter().map(|i| i.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            ["bit stamp", "okx:hedge.dmeo", "okx:hedge.passphrase"]
+        );
+    }
+
+    #[test]
+    fn other_sections_are_kept_for_registered_exchanges() {
+        let raw: toml::Table = toml::from_str(
+            r#"
+            [bitstamp]
+            tier = 2
+
+            [kucoin]
+            enabled = false
+            "#,
+        )
+        .unwrap();
+        let raw = RawExchangesConfig {
+            others: raw.into_iter().collect(),
+            ..Default::default()
+        };
+
+        let cfg = ExchangesConfig::try_from(raw).unwrap();
+        let bitstamp = ExchangeId::from_name("bitstamp").unwrap();
+        assert_eq!(cfg.custom(bitstamp).unwrap()["tier"].as_integer(), Some(2));
+        assert!(!cfg.is_enabled(ExchangeId::from_name("kucoin").unwrap()));
+        assert_eq!(
+            cfg.enabled(),
+            [ExchangeId::BINANCE, ExchangeId::COINBASE, bitstamp]
+        );
+    }
+}
@@ -0,0 +1,168 @@
+//! Fee configuration
+
+use crate::config::parse::{ConfigError, RawDecimal, ValidationReport};
+use crate::state::ExchangeId;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Raw fee configuration for deserialization (loose validation)
+///
+/// Rates are fractions of notional. `taker` applies to every exchange without
+/// an entry in `[fees.exchange]`.
+///
+/// ```toml
+/// [fees]
+/// taker = 0.001
+///
+/// [fees.exchange]
+/// coinbase = 0.006
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[cfg_attr(test, derive(serde::Serialize))]
+pub struct RawFeesConfig {
+    pub taker: Option<RawDecimal>,
+    #[serde(default)]
+    pub exchange: HashMap<String, RawDecimal>,
+}
+
+/// Validated taker fee rates (guaranteed valid after parse)
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeesConfig {
+    taker: Decimal,
+    exchange: HashMap<ExchangeId, Decimal>,
+}
+
+/// Taker rate used when `[fees]` gives none (Binance's base tier)
+const DEFAULT_TAKER: Decimal = Decimal::from_parts(1, 0, 0, false, 3);
+
+/// Validate a rate in [0, 1)
+fn fee_rate(field: &str, value: &RawDecimal) -> Result<Decimal, ConfigError> {
+    let value = value.to_decimal()?;
+    if value < Decimal::ZERO || value >= Decimal::ONE {
+        return Err(ConfigError::InvalidFeeRate {
+            field: field.to_string(),
+            value,
+            reason: "must be at least 0 and less than 1".to_string(),
+        });
+    }
+    Ok(value)
+}
+
+impl TryFrom<RawFeesConfig> for FeesConfig {
+    type Error = ConfigError;
+
+    /// Validates every rate, reporting all problems at once
+    fn try_from(raw: RawFeesConfig) -> std::result::Result<Self, Self::Error> {
+        let mut report = ValidationReport::default();
+
+        let taker = raw
+            .taker
+            .and_then(|v| report.check("taker", fee_rate("taker", &v)))
+            .unwrap_or(DEFAULT_TAKER);
+
+        let mut exchange = HashMap::new();
+        for (name, v) in raw.exchange {
+            let field = format!("exchange.{}", name);
+            let Some(id) = ExchangeId::from_name(&name) else {
+                report.push(
+                    &field,
+                    ConfigError::InvalidExchange {
+                        exchange: name,
+                        reason: "invalid exchange name".to_string(),
+                    },
+                );
+                continue;
+            };
+            if let Some(rate) = report.check(&field, fee_rate(&field, &v)) {
+                exchange.insert(id, rate);
+            }
+        }
+
+        report.finish(Some(FeesConfig { taker, exchange }))
+    }
+}
+
+impl FeesConfig {
+    /// The same taker rate on every exchange
+    pub fn flat(taker: Decimal) -> Self {
+        Self {
+            taker,
+            exchange: HashMap::new(),
+        }
+    }
+
+    /// Taker rate on `exchange`, as a fraction of notional
+    pub fn taker(&self, exchange: ExchangeId) -> Decimal {
+        self.exchange.get(&exchange).copied().unwrap_or(self.taker)
+    }
+
+    /// Override the taker rate on one exchange
+    pub fn with_taker(mut self, exchange: ExchangeId, rate: Decimal) -> Self {
+        self.exchange.insert(exchange, rate);
+        self
+    }
+}
+
+impl Default for FeesConfig {
+    fn default() -> Self {
+        Self::flat(DEFAULT_TAKER)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_section_uses_default_rate() {
+        let cfg = FeesConfig::try_from(RawFeesConfig::default()).unwrap();
+        assert_eq!(cfg.taker(ExchangeId::BINANCE), Decimal::new(1, 3));
+        assert_eq!(cfg.taker(ExchangeId::COINBASE), Decimal::new(1, 3));
+    }
+
+    #[test]
+    fn exchange_override_applies() {
+        let raw = RawFeesConfig {
+            taker: Some(0.002.into()),
+            exchange: HashMap::from([("Coinbase".to_string(), "0.006".into())]),
+        };
+
+        let cfg = FeesConfig::try_from(raw).unwrap();
+        assert_eq!(cfg.taker(ExchangeId::BINANCE), Decimal::new(2, 3));
+        assert_eq!(cfg.taker(ExchangeId::COINBASE), Decimal::new(6, 3));
+    }
+
+    #[test]
+    fn reject_rate_out_of_range() {
+        let raw = RawFeesConfig {
+            taker: Some(1.5.into()),
+            ..Default::default()
+        };
+
+        let err = FeesConfig::try_from(raw).unwrap_err();
+        assert!(format!("{}", err).contains("taker"));
+    }
+
+    #[test]
+    fn reject_malformed_exchange() {
+        let raw = RawFeesConfig {
+            taker: None,
+            exchange: HashMap::from([("kra ken".to_string(), 0.002.into())]),
+        };
+
+        let err = FeesConfig::try_from(raw).unwrap_err();
+        assert!(format!("{}", err).contains("kra ken"));
+    }
+
+    #[test]
+    fn any_exchange_can_have_a_rate() {
+        let raw = RawFeesConfig {
+            taker: None,
+            exchange: HashMap::from([("kraken".to_string(), 0.002.into())]),
+        };
+
+        let cfg = FeesConfig::try_from(raw).unwrap();
+        assert_eq!(cfg.taker(ExchangeId::from_name("kraken").unwrap()), Decimal::new(2, 3));
+    }
+}
@@ -0,0 +1,267 @@
+//! Layered configuration loading
+//!
+//! Sources, lowest precedence first:
+//!
+//! 1. Built-in defaults, plus the credential variables exchanges have always
+//!    read (`BINANCE_API_KEY`, `COINBASE_API_SECRET`, ...)
+//! 2. The config file (required)
+//! 3. The profile file `<stem>.<profile>.toml` next to it, when a profile is selected
+//! 4. `ARB_BOT__SECTION__KEY` environment variables, e.g. `ARB_BOT__TRADING__ORDER_SIZE=5`
+//!
+//! Secrets can be read from files instead, as Docker mounts them: a
+//! `<secret>_file` setting from any layer (`api_secret_file = "/run/secrets/cb"`,
+//! `ARB_BOT__EXCHANGES__COINBASE__API_SECRET_FILE`, `COINBASE_API_SECRET_FILE`)
+//! is replaced by the file's contents and wins over the plain value.
+//!
+//! Keys are case-insensitive; the `config` crate lowercases them. Variables
+//! are kept as strings, so a secret such as `00123` is never read as a number;
+//! numeric and boolean settings parse from strings when validated.
+
+use crate::config::app::{AppConfig, deserialize_raw};
+use crate::error::{ArbitrageError, Result};
+use config::{Config, ConfigBuilder, Environment, File, FileFormat, builder::DefaultState};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Prefix of override variables; sections and keys are separated by `__`
+pub const ENV_PREFIX: &str = "ARB_BOT";
+
+/// Shown in place of secret values
+pub const REDACTED: &str = "***";
+
+/// Setting names holding credentials
+const SECRET_KEYS: [&str; 3] = ["api_key", "api_secret", "passphrase"];
+
+/// Suffix of settings naming a file to read a secret from
+const FILE_SUFFIX: &str = "_file";
+
+/// Credential variables read before layered config existed, and the keys they set
+///
+/// Later entries win, so `COINBASE_API_KEY` beats its older `_ID` spelling.
+const LEGACY_CREDENTIALS: [(&str, &str); 5] = [
+    ("BINANCE_API_KEY", "exchanges.binance.api_key"),
+    ("BINANCE_API_SECRET", "exchanges.binance.api_secret"),
+    ("COINBASE_API_KEY_ID", "exchanges.coinbase.api_key"),
+    ("COINBASE_API_KEY", "exchanges.coinbase.api_key"),
+    ("COINBASE_API_SECRET", "exchanges.coinbase.api_secret"),
+];
+
+/// Lowest layer; mirrors the fallbacks in each section's `TryFrom`
+const DEFAULTS: &str = r#"
+[exchanges.binance]
+enabled = true
+testnet = false
+
+[exchanges.coinbase]
+enabled = true
+sandbox = false
+
+[logging]
+level = "info"
+format = "pretty"
+rotation = "never"
+"#;
+
+/// Whether the setting `key` holds a credential
+pub fn is_secret(key: &str) -> bool {
+    SECRET_KEYS.contains(&key)
+}
+
+/// Builds an `AppConfig` from defaults, files and environment
+///
+/// # Business Logic
+///
+/// Every layer is merged key by key before validation, so a profile or
+/// variable only needs the settings it changes. Validation runs once on the
+/// merged result.
+///
+/// ```rust,no_run
+/// use arb_bot::config::ConfigLoader;
+///
+/// // config.toml, then config.prod.toml, then ARB_BOT__* variables
+/// let config = ConfigLoader::new("config.toml").with_profile("prod").load()?;
+/// # Ok::<(), arb_bot::error::ArbitrageError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct ConfigLoader {
+    path: PathBuf,
+    profile: Option<String>,
+    env: Option<HashMap<String, String>>,
+}
+
+impl ConfigLoader {
+    /// Load `path` on top of the defaults
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            profile: None,
+            env: None,
+        }
+    }
+
+    /// Also merge `<stem>.<profile>.toml`, which must exist
+    pub fn with_profile(mut self, profile: impl Into<String>) -> Self {
+        self.profile = Some(profile.into());
+        self
+    }
+
+    /// Read variables from `vars` instead of the process environment
+    pub fn with_env(mut self, vars: HashMap<String, String>) -> Self {
+        self.env = Some(vars);
+        self
+    }
+
+    /// The base config file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The profile file, if a profile is selected
+    pub fn profile_path(&self) -> Option<PathBuf> {
+        let profile = self.profile.as_ref()?;
+        let stem = self
+            .path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "config".to_string());
+        Some(self.path.with_file_name(format!("{}.{}.toml", stem, profile)))
+    }
+
+    /// Merge every layer and validate the result
+    ///
+    /// Every problem is reported at once, including keys no section knows.
+    pub fn load(&self) -> Result<AppConfig> {
+        let (raw, unknown) = deserialize_raw(self.build()?).map_err(|e| self.error(e))?;
+        // `<secret>_file` settings were consumed while building
+        let unknown = unknown
+            .into_iter()
+            .filter(|path| {
+                let key = path.rsplit('.').next().unwrap_or(path);
+                !key.strip_suffix(FILE_SUFFIX).is_some_and(is_secret)
+            })
+            .collect();
+        Ok(AppConfig::validate(raw, unknown)?)
+    }
+
+    /// The merged settings as TOML, with secrets replaced by `REDACTED`
+    ///
+    /// Shows what `load` would validate, so it works on an invalid config too.
+    pub fn dump(&self) -> Result<String> {
+        let mut tree: Value = self
+            .build()?
+            .try_deserialize()
+            .map_err(|e| self.error(e))?;
+        redact(&mut tree);
+        toml::to_string_pretty(&tree).map_err(|e| ArbitrageError::ConfigError {
+            field: self.path.display().to_string(),
+            reason: format!("cannot render config: {}", e),
+        })
+    }
+
+    fn build(&self) -> Result<Config> {
+        let env = self
+            .env
+            .clone()
+            .unwrap_or_else(|| std::env::vars().collect());
+
+        let mut builder: ConfigBuilder<DefaultState> =
+            Config::builder().add_source(File::from_str(DEFAULTS, FileFormat::Toml));
+        for (var, key) in LEGACY_CREDENTIALS {
+            for (var, key) in [
+                (var.to_string(), key.to_string()),
+                (format!("{}_FILE", var), format!("{}{}", key, FILE_SUFFIX)),
+            ] {
+                if let Some(value) = env.get(&var) {
+                    builder = builder
+                        .set_default(key, value.as_str())
+                        .map_err(|e| self.error(e))?;
+                }
+            }
+        }
+
+        builder = builder.add_source(File::new(&self.path.to_string_lossy(), FileFormat::Toml));
+        if let Some(profile_path) = self.profile_path() {
+            builder =
+                builder.add_source(File::new(&profile_path.to_string_lossy(), FileFormat::Toml));
+        }
+        builder = builder.add_source(
+            Environment::with_prefix(ENV_PREFIX)
+                .prefix_separator("__")
+                .separator("__")
+                .source(Some(env.into_iter().collect())),
+        );
+
+        let merged = builder.build_cloned().map_err(|e| self.error(e))?;
+        let tree: Value = merged
+            .clone()
+            .try_deserialize()
+            .map_err(|e| self.error(e))?;
+        let mut secret_files = Vec::new();
+        collect_secret_files(&tree, "", &mut secret_files);
+        if secret_files.is_empty() {
+            return Ok(merged);
+        }
+
+        for (key, path) in secret_files {
+            let contents =
+                std::fs::read_to_string(&path).map_err(|e| ArbitrageError::ConfigError {
+                    field: format!("{}{}", key, FILE_SUFFIX),
+                    reason: format!("cannot read secret file {}: {}", path, e),
+                })?;
+            builder = builder
+                .set_override(key, contents.trim_end())
+                .map_err(|e| self.error(e))?;
+        }
+        builder.build().map_err(|e| self.error(e))
+    }
+
+    fn error(&self, e: config::ConfigError) -> ArbitrageError {
+        ArbitrageError::ConfigError {
+            field: self.path.display().to_string(),
+            reason: e.to_string(),
+        }
+    }
+}
+
+/// Find `<secret>_file` settings, returning the secret's dotted key and the file
+fn collect_secret_files(tree: &Value, prefix: &str, out: &mut Vec<(String, String)>) {
+    let Value::Object(map) = tree else {
+        return;
+    };
+    for (key, value) in map {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        match (key.strip_suffix(FILE_SUFFIX), value) {
+            (Some(secret), Value::String(file)) if is_secret(secret) => {
+                out.push((format!("{}{}", prefix_of(&path), secret), file.clone()));
+            }
+            _ => collect_secret_files(value, &path, out),
+        }
+    }
+}
+
+/// `a.b.c` -> `a.b.`
+fn prefix_of(path: &str) -> &str {
+    path.rfind('.').map(|i| &path[..=i]).unwrap_or("")
+}
+
+/// Replace non-empty secret values in place
+fn redact(tree: &mut Value) {
+    let Value::Object(map) = tree else {
+        return;
+    };
+    for (key, value) in map.iter_mut() {
+        if is_secret(key) {
+            let empty = matches!(value, Value::Null) || value.as_str() == Some("");
+            if !empty {
+                *value = Value::String(REDACTED.to_string());
+            }
+        } else {
+            redact(value);
+        }
+    }
+}
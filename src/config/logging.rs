@@ -0,0 +1,139 @@
+//! Logging configuration
+//!
+//! The `[logging]` section validates into the logger's own `LoggerConfig`.
+
+use crate::config::parse::{ConfigError, ValidationReport};
+use crate::logger::{LogFormat, LoggerConfig};
+use serde::Deserialize;
+use tracing_subscriber::EnvFilter;
+
+/// Rotation strategies understood by `LoggerConfig::init`
+const ROTATIONS: [&str; 3] = ["never", "hourly", "daily"];
+
+/// Raw logging configuration for deserialization (loose validation)
+///
+/// Every field is optional and falls back to `LoggerConfig::new()`.
+///
+/// ```toml
+/// [logging]
+/// level = "info"          # or a filter directive such as "arb_bot=debug"
+/// format = "pretty"       # json | pretty | compact
+/// directory = "logs"      # also write <directory>/app.log
+/// rotation = "daily"      # never | hourly | daily
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[cfg_attr(test, derive(serde::Serialize))]
+pub struct RawLoggingConfig {
+    pub level: Option<String>,
+    pub format: Option<String>,
+    pub directory: Option<String>,
+    pub rotation: Option<String>,
+}
+
+impl TryFrom<RawLoggingConfig> for LoggerConfig {
+    type Error = ConfigError;
+
+    /// Validates every field, reporting all problems at once
+    fn try_from(raw: RawLoggingConfig) -> std::result::Result<Self, Self::Error> {
+        let mut report = ValidationReport::default();
+        let mut config = LoggerConfig::new();
+        let invalid = |field: &str, value: &str, reason: String| ConfigError::InvalidLogging {
+            field: field.to_string(),
+            value: value.to_string(),
+            reason,
+        };
+
+        if let Some(level) = raw.level {
+            match EnvFilter::try_new(&level) {
+                Ok(_) => config = config.with_level(&level),
+                Err(e) => report.push("level", invalid("level", &level, e.to_string())),
+            }
+        }
+
+        if let Some(format) = raw.format {
+            match format.parse::<LogFormat>() {
+                Ok(parsed) => config = config.with_format(parsed),
+                Err(reason) => report.push("format", invalid("format", &format, reason)),
+            }
+        }
+
+        if let Some(directory) = raw.directory {
+            if directory.is_empty() {
+                report.push(
+                    "directory",
+                    invalid("directory", &directory, "must not be empty".to_string()),
+                );
+            } else {
+                config = config.with_file_path(&directory);
+            }
+        }
+
+        if let Some(rotation) = raw.rotation {
+            if ROTATIONS.contains(&rotation.as_str()) {
+                config = config.with_rotation(&rotation);
+            } else {
+                report.push(
+                    "rotation",
+                    invalid(
+                        "rotation",
+                        &rotation,
+                        "must be one of never, hourly, daily".to_string(),
+                    ),
+                );
+            }
+        }
+
+        report.finish(Some(config))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_section_uses_defaults() {
+        let cfg = LoggerConfig::try_from(RawLoggingConfig::default()).unwrap();
+        assert_eq!(cfg.level(), "info");
+        assert_eq!(cfg.format(), LogFormat::Pretty);
+        assert_eq!(cfg.file_path(), None);
+    }
+
+    #[test]
+    fn valid_config_parses() {
+        let raw = RawLoggingConfig {
+            level: Some("arb_bot=debug,warn".to_string()),
+            format: Some("JSON".to_string()),
+            directory: Some("logs".to_string()),
+            rotation: Some("hourly".to_string()),
+        };
+
+        let cfg = LoggerConfig::try_from(raw).unwrap();
+        assert_eq!(cfg.level(), "arb_bot=debug,warn");
+        assert_eq!(cfg.format(), LogFormat::Json);
+        assert_eq!(cfg.file_path(), Some("logs"));
+        assert_eq!(cfg.rotation(), "hourly");
+    }
+
+    #[test]
+    fn reject_unknown_format() {
+        let raw = RawLoggingConfig {
+            format: Some("xml".to_string()),
+            ..Default::default()
+        };
+
+        let err = LoggerConfig::try_from(raw).unwrap_err();
+        assert!(format!("{}", err).contains("format"));
+    }
+
+    #[test]
+    fn reject_unknown_rotation() {
+        let raw = RawLoggingConfig {
+            rotation: Some("weekly".to_string()),
+            ..Default::default()
+        };
+
+        let err = LoggerConfig::try_from(raw).unwrap_err();
+        assert!(format!("{}", err).contains("rotation"));
+    }
+}
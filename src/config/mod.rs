@@ -1,6 +1,20 @@
+pub mod app;
 pub mod exchange;
+pub mod fees;
+pub mod loader;
+pub mod reload;
+pub mod logging;
 pub mod trading;
+pub mod risk;
 pub mod parse;
 
-pub use exchange::{BinanceConfig, CoinbaseConfig};
-pub use trading::TradingConfig;
+pub use app::AppConfig;
+pub use exchange::{
+    AccountConfig, BinanceConfig, BybitConfig, CoinbaseConfig, ExchangesConfig, KrakenConfig,
+    OkxConfig,
+};
+pub use fees::FeesConfig;
+pub use loader::ConfigLoader;
+pub use reload::{ConfigReloader, LiveParams, ParamChange};
+pub use risk::RiskConfig;
+pub use trading::{MarketConfig, TradingConfig};
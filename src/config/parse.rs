@@ -1,22 +1,62 @@
 use crate::error::ArbitrageError;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::fmt;
+use std::str::FromStr;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
 pub enum ConfigError {
     #[error("Invalid spread threshold: {value} - {reason}")]
-    InvalidSpreadThreshold { value: f64, reason: String },
+    InvalidSpreadThreshold { value: Decimal, reason: String },
 
     #[error("Invalid order_size: {value} - {reason}")]
-    InvalidOrderSize { value: f64, reason: String },
+    InvalidOrderSize { value: Decimal, reason: String },
 
     #[error("Invalid cooldown: {value}ms - {reason}")]
     InvalidCooldown { value: u64, reason: String },
 
-    #[error("Invalid decimal conversion")]
-    InvalidDecimal,
+    #[error("Invalid risk limit {field}: {value} - {reason}")]
+    InvalidRiskLimit {
+        field: String,
+        value: Decimal,
+        reason: String,
+    },
+
+    #[error("Invalid fee rate {field}: {value} - {reason}")]
+    InvalidFeeRate {
+        field: String,
+        value: Decimal,
+        reason: String,
+    },
+
+    #[error("Invalid exchange config {exchange}: {reason}")]
+    InvalidExchange { exchange: String, reason: String },
+
+    #[error("Invalid logging {field}: {value} - {reason}")]
+    InvalidLogging {
+        field: String,
+        value: String,
+        reason: String,
+    },
+
+    #[error("Invalid pair {value:?} - {reason}")]
+    InvalidPair { value: String, reason: String },
+
+    #[error("Unknown asset {asset}: not part of pair {pair}")]
+    UnknownAsset { asset: String, pair: String },
+
+    #[error("Invalid decimal: {value:?}")]
+    InvalidDecimal { value: String },
 
     #[error("Missing required field: {field}")]
     MissingField { field: String },
+
+    #[error("Unknown field: {field}")]
+    UnknownField { field: String },
+
+    #[error("{0}")]
+    Invalid(ValidationReport),
 }
 
 impl From<ConfigError> for ArbitrageError {
@@ -24,3 +64,200 @@ impl From<ConfigError> for ArbitrageError {
         ArbitrageError::ConfigParse(Box::new(e))
     }
 }
+
+impl From<ValidationReport> for ConfigError {
+    fn from(report: ValidationReport) -> Self {
+        ConfigError::Invalid(report)
+    }
+}
+
+/// A decimal setting as written: a TOML number or a string such as `"0.002"`
+///
+/// Numbers are converted through their shortest decimal representation, so
+/// `0.002` becomes exactly `0.002` rather than the nearest binary fraction.
+/// Strings are parsed directly and keep every digit.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[cfg_attr(test, derive(serde::Serialize))]
+#[serde(untagged)]
+pub enum RawDecimal {
+    Number(f64),
+    Text(String),
+}
+
+impl RawDecimal {
+    pub fn to_decimal(&self) -> Result<Decimal, ConfigError> {
+        let text = match self {
+            RawDecimal::Number(v) if v.is_finite() => v.to_string(),
+            RawDecimal::Number(v) => {
+                return Err(ConfigError::InvalidDecimal {
+                    value: v.to_string(),
+                });
+            }
+            RawDecimal::Text(s) => s.trim().to_string(),
+        };
+        Decimal::from_str(&text)
+            .or_else(|_| Decimal::from_scientific(&text))
+            .map(|d| d.normalize())
+            .map_err(|_| ConfigError::InvalidDecimal { value: text })
+    }
+}
+
+impl From<f64> for RawDecimal {
+    fn from(v: f64) -> Self {
+        RawDecimal::Number(v)
+    }
+}
+
+impl From<&str> for RawDecimal {
+    fn from(s: &str) -> Self {
+        RawDecimal::Text(s.to_string())
+    }
+}
+
+/// One problem found while validating, located by its dotted field path
+#[derive(Debug)]
+pub struct ValidationIssue {
+    /// Field path such as `trading.spread_threshold`
+    pub path: String,
+    pub error: ConfigError,
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.error)
+        } else {
+            write!(f, "{}: {}", self.path, self.error)
+        }
+    }
+}
+
+/// Every problem found in a configuration
+///
+/// # Business Logic
+///
+/// Validation keeps going after a bad field so one run reports everything.
+/// Section validators record paths relative to their section; the caller
+/// prefixes them with `section`, so a report from `[trading]` reads
+/// `trading.pair`, `trading.order_size` and so on.
+#[derive(Debug, Default)]
+pub struct ValidationReport {
+    issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    pub fn issues(&self) -> &[ValidationIssue] {
+        &self.issues
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.issues.len()
+    }
+
+    /// Record a problem at `path`
+    pub fn push(&mut self, path: impl Into<String>, error: ConfigError) {
+        self.issues.push(ValidationIssue {
+            path: path.into(),
+            error,
+        });
+    }
+
+    /// Record a missing field, passing through a present value
+    pub fn require<T>(&mut self, path: &str, value: Option<T>) -> Option<T> {
+        if value.is_none() {
+            self.push(path, ConfigError::MissingField {
+                field: path.to_string(),
+            });
+        }
+        value
+    }
+
+    /// Record an error, passing through a valid value
+    pub fn check<T>(&mut self, path: &str, result: Result<T, ConfigError>) -> Option<T> {
+        result.map_err(|e| self.push(path, e)).ok()
+    }
+
+    /// Record a section's errors under `section`, passing through a valid section
+    pub fn section<T>(&mut self, section: &str, result: Result<T, ConfigError>) -> Option<T> {
+        match result {
+            Ok(value) => Some(value),
+            Err(ConfigError::Invalid(report)) => {
+                for issue in report.issues {
+                    let path = match issue.path.as_str() {
+                        "" => section.to_string(),
+                        path => format!("{}.{}", section, path),
+                    };
+                    self.push(path, issue.error);
+                }
+                None
+            }
+            Err(e) => {
+                self.push(section, e);
+                None
+            }
+        }
+    }
+
+    /// `Ok(value)` when nothing was recorded
+    pub fn finish<T>(self, value: Option<T>) -> Result<T, ConfigError> {
+        match value {
+            Some(value) if self.is_empty() => Ok(value),
+            _ => Err(ConfigError::Invalid(self)),
+        }
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.issues.as_slice() {
+            [] => write!(f, "Invalid configuration"),
+            [issue] => write!(f, "{}", issue),
+            issues => {
+                write!(f, "{} configuration problems:", issues.len())?;
+                for issue in issues {
+                    write!(f, "\n  - {}", issue)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decimals_parse_exactly() {
+        assert_eq!(RawDecimal::from(0.002).to_decimal().unwrap(), Decimal::new(2, 3));
+        assert_eq!(RawDecimal::from(10.0).to_decimal().unwrap(), Decimal::from(10));
+        assert_eq!(
+            RawDecimal::from("0.123456789012345678").to_decimal().unwrap(),
+            Decimal::from_str("0.123456789012345678").unwrap()
+        );
+        assert_eq!(RawDecimal::from("1e-3").to_decimal().unwrap(), Decimal::new(1, 3));
+        assert!(RawDecimal::from("ten").to_decimal().is_err());
+        assert!(RawDecimal::from(f64::NAN).to_decimal().is_err());
+    }
+
+    #[test]
+    fn report_prefixes_section_paths() {
+        let mut section = ValidationReport::default();
+        section.require::<String>("pair", None);
+        section.push("order_size", ConfigError::InvalidDecimal { value: "x".to_string() });
+
+        let mut report = ValidationReport::default();
+        assert!(report.section::<()>("trading", Err(section.into())).is_none());
+        report.section::<()>("exchanges", Err(ConfigError::UnknownField {
+            field: "kraken".to_string(),
+        }));
+
+        let paths: Vec<&str> = report.issues().iter().map(|i| i.path.as_str()).collect();
+        assert_eq!(paths, ["trading.pair", "trading.order_size", "exchanges"]);
+        assert!(report.to_string().starts_with("3 configuration problems:"));
+    }
+}
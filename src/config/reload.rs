@@ -0,0 +1,342 @@
+//! Hot reload of trading and risk parameters
+//!
+//! The `[trading]` and `[risk]` sections can change while the bot runs. Other
+//! sections, and which markets trade where, are read once at startup; changes
+//! to them are reported but need a restart.
+
+use crate::config::loader::ConfigLoader;
+use crate::config::{AppConfig, RiskConfig, TradingConfig};
+use crate::error::{ArbitrageError, Result};
+use crate::logger::{error, info, warn};
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+/// Parameters that take effect without a restart
+#[derive(Debug, Clone, PartialEq)]
+pub struct LiveParams {
+    pub trading: TradingConfig,
+    pub risk: RiskConfig,
+}
+
+impl LiveParams {
+    pub fn from_config(config: &AppConfig) -> Self {
+        Self {
+            trading: config.trading().clone(),
+            risk: config.risk().clone(),
+        }
+    }
+}
+
+/// One changed setting, with old and new values as displayed in logs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParamChange {
+    pub field: String,
+    pub old: String,
+    pub new: String,
+}
+
+impl fmt::Display for ParamChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} -> {}", self.field, self.old, self.new)
+    }
+}
+
+fn show<T: fmt::Display>(value: Option<T>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_else(|| "none".to_string())
+}
+
+/// Settings that differ between `old` and `new`, in a fixed order
+pub fn diff(old: &LiveParams, new: &LiveParams) -> Vec<ParamChange> {
+    let mut changes = Vec::new();
+    let mut compare = |field: &str, old: String, new: String| {
+        if old != new {
+            changes.push(ParamChange {
+                field: field.to_string(),
+                old,
+                new,
+            });
+        }
+    };
+
+    // A lone market keeps the flat `trading.*` names it is configured with
+    let single = old.trading.markets().len() == 1 && new.trading.markets().len() == 1;
+    for b in new.trading.markets() {
+        let Some(a) = old.trading.market(b.pair()).or(single.then(|| &old.trading.markets()[0]))
+        else {
+            continue;
+        };
+        let prefix = if single {
+            "trading".to_string()
+        } else {
+            format!("trading.markets[{}]", b.pair())
+        };
+        compare(&format!("{}.pair", prefix), a.pair().to_string(), b.pair().to_string());
+        compare(
+            &format!("{}.spread_threshold", prefix),
+            a.spread_threshold().to_string(),
+            b.spread_threshold().to_string(),
+        );
+        compare(
+            &format!("{}.order_size", prefix),
+            a.order_size().to_string(),
+            b.order_size().to_string(),
+        );
+        compare(
+            &format!("{}.cooldown_ms", prefix),
+            a.cooldown_ms().to_string(),
+            b.cooldown_ms().to_string(),
+        );
+    }
+
+    let (a, b) = (&old.risk, &new.risk);
+    compare(
+        "risk.max_order_notional",
+        show(a.max_order_notional()),
+        show(b.max_order_notional()),
+    );
+    compare(
+        "risk.max_orders_per_minute",
+        show(a.max_orders_per_minute()),
+        show(b.max_orders_per_minute()),
+    );
+    compare("risk.max_daily_loss", show(a.max_daily_loss()), show(b.max_daily_loss()));
+    let mut assets: Vec<&str> = a.position_assets().chain(b.position_assets()).collect();
+    assets.sort_unstable();
+    assets.dedup();
+    for asset in assets {
+        compare(
+            &format!("risk.max_position.{}", asset),
+            show(a.max_position(asset)),
+            show(b.max_position(asset)),
+        );
+    }
+
+    changes
+}
+
+/// Re-reads the config and publishes new `LiveParams`
+///
+/// # Business Logic
+///
+/// A reload runs the full layered load and validation. The new parameters are
+/// published only if the whole config is valid and the markets trade the same
+/// pairs on the same exchanges, so a half-edited file never reaches the
+/// trading loop; the current parameters stay in effect and the error is logged.
+///
+/// Consumers hold a `watch::Receiver` from `subscribe()`: each published value
+/// replaces the previous one as a unit, so trading and risk settings from
+/// different file versions are never mixed.
+///
+/// ```rust,no_run
+/// use arb_bot::config::{ConfigLoader, ConfigReloader};
+/// use std::time::Duration;
+///
+/// # async fn example() -> arb_bot::error::Result<()> {
+/// let loader = ConfigLoader::new("config.toml");
+/// let reloader = ConfigReloader::new(loader.clone(), loader.load()?);
+/// let mut params = reloader.subscribe();
+/// let _task = reloader.spawn(Duration::from_secs(2));
+///
+/// params.changed().await.ok();
+/// println!("threshold now {}", params.borrow().trading.spread_threshold());
+/// # Ok(())
+/// # }
+/// ```
+pub struct ConfigReloader {
+    loader: ConfigLoader,
+    /// The config the process started with; restart-only sections are compared against it
+    started: AppConfig,
+    tx: watch::Sender<Arc<LiveParams>>,
+    modified: Vec<Option<SystemTime>>,
+    restart_pending: Vec<&'static str>,
+}
+
+impl ConfigReloader {
+    /// Start from `current`, the config the bot was started with
+    pub fn new(loader: ConfigLoader, current: AppConfig) -> Self {
+        let tx = watch::Sender::new(Arc::new(LiveParams::from_config(&current)));
+        let mut reloader = Self {
+            loader,
+            started: current,
+            tx,
+            modified: Vec::new(),
+            restart_pending: Vec::new(),
+        };
+        reloader.modified = reloader.modified_times();
+        reloader
+    }
+
+    /// Receiver of the live parameters, starting with the current ones
+    pub fn subscribe(&self) -> watch::Receiver<Arc<LiveParams>> {
+        self.tx.subscribe()
+    }
+
+    /// The parameters currently published
+    pub fn params(&self) -> Arc<LiveParams> {
+        self.tx.borrow().clone()
+    }
+
+    /// Sections that differ from the running process as of the last reload
+    ///
+    /// Stays non-empty until the process restarts or the change is reverted.
+    pub fn restart_pending(&self) -> &[&'static str] {
+        &self.restart_pending
+    }
+
+    /// Load and validate the config, publishing any changed live parameters
+    ///
+    /// Returns the changes applied (empty if none). Changes to settings that
+    /// need a restart are logged and otherwise ignored; they are compared with
+    /// the startup config, so every reload warns until the process restarts.
+    pub fn reload(&mut self) -> Result<Vec<ParamChange>> {
+        self.modified = self.modified_times();
+        let config = self.loader.load()?;
+
+        let routes = |config: &AppConfig| -> Vec<String> {
+            config.trading().markets().iter().map(|m| m.route()).collect()
+        };
+        let (old_routes, new_routes) = (routes(&self.started), routes(&config));
+        if old_routes != new_routes {
+            return Err(ArbitrageError::ConfigError {
+                field: "trading.markets".to_string(),
+                reason: format!(
+                    "changing {} to {} requires a restart",
+                    old_routes.join(", "),
+                    new_routes.join(", ")
+                ),
+            });
+        }
+
+        let mut restart = Vec::new();
+        if config.exchanges() != self.started.exchanges() {
+            restart.push("exchanges");
+        }
+        if config.logging() != self.started.logging() {
+            restart.push("logging");
+        }
+        if config.fees() != self.started.fees() {
+            restart.push("fees");
+        }
+        if !restart.is_empty() {
+            warn!(sections = %restart.join(", "), "Config changes need a restart to apply");
+        }
+        self.restart_pending = restart;
+
+        let params = LiveParams::from_config(&config);
+        let changes = diff(&self.tx.borrow(), &params);
+        if !changes.is_empty() {
+            self.tx.send_replace(Arc::new(params));
+        }
+        Ok(changes)
+    }
+
+    /// Reload when a config file changes, or on SIGHUP
+    ///
+    /// Files are checked every `interval`. Environment variables are only
+    /// re-read on a reload, so send SIGHUP after changing them.
+    pub fn spawn(mut self, interval: Duration) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            #[cfg(unix)]
+            let mut hangup = match tokio::signal::unix::signal(
+                tokio::signal::unix::SignalKind::hangup(),
+            ) {
+                Ok(signal) => Some(signal),
+                Err(e) => {
+                    warn!(error = %e, "Cannot listen for SIGHUP, reloading on file changes only");
+                    None
+                }
+            };
+
+            loop {
+                #[cfg(unix)]
+                let forced = tokio::select! {
+                    _ = ticker.tick() => false,
+                    Some(()) = async {
+                        match hangup.as_mut() {
+                            Some(signal) => signal.recv().await,
+                            None => std::future::pending().await,
+                        }
+                    } => true,
+                };
+                #[cfg(not(unix))]
+                let forced = {
+                    ticker.tick().await;
+                    false
+                };
+
+                if forced || self.modified_times() != self.modified {
+                    self.apply();
+                }
+            }
+        })
+    }
+
+    /// Reload and log the outcome
+    fn apply(&mut self) {
+        match self.reload() {
+            Ok(changes) if changes.is_empty() => info!("Config reloaded, no live parameter changes"),
+            Ok(changes) => {
+                for change in &changes {
+                    info!(
+                        field = %change.field,
+                        old = %change.old,
+                        new = %change.new,
+                        "Config parameter changed"
+                    );
+                }
+                info!(changes = changes.len(), "Config reloaded");
+            }
+            Err(e) => error!(error = %e, "Config reload rejected, keeping current parameters"),
+        }
+    }
+
+    fn modified_times(&self) -> Vec<Option<SystemTime>> {
+        let paths: Vec<PathBuf> = std::iter::once(self.loader.path().to_path_buf())
+            .chain(self.loader.profile_path())
+            .collect();
+        paths
+            .iter()
+            .map(|p| std::fs::metadata(p).and_then(|m| m.modified()).ok())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+
+    fn params(threshold: &str, max_position: Option<i64>) -> LiveParams {
+        let mut contents = format!(
+            "[trading]\npair = \"SOL/USDC\"\nspread_threshold = \"{}\"\norder_size = 1.0\ncooldown_ms = 5000\n",
+            threshold
+        );
+        if let Some(limit) = max_position {
+            contents.push_str(&format!("\n[risk.max_position]\nSOL = {}\n", limit));
+        }
+        LiveParams::from_config(&AppConfig::parse(&contents).unwrap())
+    }
+
+    #[test]
+    fn diff_lists_changed_fields_only() {
+        assert!(diff(&params("0.002", None), &params("0.002", None)).is_empty());
+
+        let changes = diff(&params("0.002", None), &params("0.003", Some(50)));
+        assert_eq!(
+            changes.iter().map(|c| c.to_string()).collect::<Vec<_>>(),
+            [
+                "trading.spread_threshold: 0.002 -> 0.003",
+                "risk.max_position.SOL: none -> 50",
+            ]
+        );
+        assert_eq!(
+            params("0.003", Some(50)).risk.max_position("SOL"),
+            Some(Decimal::from(50))
+        );
+    }
+}
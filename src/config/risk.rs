@@ -0,0 +1,206 @@
+use crate::config::parse::{ConfigError, RawDecimal, ValidationReport};
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Wrapper for TOML deserialization with [risk] section
+#[derive(Debug, Deserialize)]
+pub struct RiskConfigToml {
+    pub risk: RawRiskConfig,
+}
+
+/// Raw risk configuration for deserialization (loose validation)
+///
+/// Every limit is optional; an omitted limit is not enforced.
+///
+/// ```toml
+/// [risk]
+/// max_order_notional = 1000.0
+/// max_orders_per_minute = 10
+/// max_daily_loss = 50.0
+///
+/// [risk.max_position]
+/// SOL = 100.0
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[cfg_attr(test, derive(serde::Serialize))]
+pub struct RawRiskConfig {
+    pub max_order_notional: Option<RawDecimal>,
+    pub max_orders_per_minute: Option<u32>,
+    pub max_daily_loss: Option<RawDecimal>,
+    #[serde(default)]
+    pub max_position: HashMap<String, RawDecimal>,
+}
+
+/// Validated risk limits (guaranteed valid after parse)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RiskConfig {
+    max_order_notional: Option<Decimal>,
+    max_orders_per_minute: Option<u32>,
+    max_daily_loss: Option<Decimal>,
+    max_position: HashMap<String, Decimal>,
+}
+
+/// Validate a positive limit and convert it to Decimal
+fn positive_decimal(field: &str, value: &RawDecimal) -> Result<Decimal, ConfigError> {
+    let value = value.to_decimal()?;
+    if value <= Decimal::ZERO {
+        return Err(ConfigError::InvalidRiskLimit {
+            field: field.to_string(),
+            value,
+            reason: "must be greater than 0".to_string(),
+        });
+    }
+    Ok(value)
+}
+
+impl TryFrom<RawRiskConfig> for RiskConfig {
+    type Error = ConfigError;
+
+    /// Validates every limit, reporting all problems at once
+    fn try_from(raw: RawRiskConfig) -> std::result::Result<Self, Self::Error> {
+        let mut report = ValidationReport::default();
+
+        let max_order_notional = raw.max_order_notional.and_then(|v| {
+            report.check("max_order_notional", positive_decimal("max_order_notional", &v))
+        });
+
+        if raw.max_orders_per_minute == Some(0) {
+            report.push(
+                "max_orders_per_minute",
+                ConfigError::InvalidRiskLimit {
+                    field: "max_orders_per_minute".to_string(),
+                    value: Decimal::ZERO,
+                    reason: "must be at least 1".to_string(),
+                },
+            );
+        }
+
+        let max_daily_loss = raw
+            .max_daily_loss
+            .and_then(|v| report.check("max_daily_loss", positive_decimal("max_daily_loss", &v)));
+
+        let mut max_position = HashMap::new();
+        for (asset, v) in raw.max_position {
+            let field = format!("max_position.{}", asset);
+            if let Some(limit) = report.check(&field, positive_decimal(&field, &v)) {
+                max_position.insert(asset.to_uppercase(), limit);
+            }
+        }
+
+        report.finish(Some(RiskConfig {
+            max_order_notional,
+            max_orders_per_minute: raw.max_orders_per_minute,
+            max_daily_loss,
+            max_position,
+        }))
+    }
+}
+
+impl RiskConfig {
+    /// Largest allowed `quantity * price` for a single order
+    pub fn max_order_notional(&self) -> Option<Decimal> {
+        self.max_order_notional
+    }
+
+    /// Orders allowed in any rolling 60-second window
+    pub fn max_orders_per_minute(&self) -> Option<u32> {
+        self.max_orders_per_minute
+    }
+
+    /// Realized loss (positive amount, quote currency) that stops trading for the day
+    pub fn max_daily_loss(&self) -> Option<Decimal> {
+        self.max_daily_loss
+    }
+
+    /// Largest absolute net position in `asset` on any single exchange
+    ///
+    /// Asset symbols are case-insensitive.
+    pub fn max_position(&self, asset: &str) -> Option<Decimal> {
+        self.max_position.get(&asset.to_uppercase()).copied()
+    }
+
+    /// Assets with a position limit
+    pub fn position_assets(&self) -> impl Iterator<Item = &str> {
+        self.max_position.keys().map(String::as_str)
+    }
+
+    pub fn with_max_order_notional(mut self, limit: Decimal) -> Self {
+        self.max_order_notional = Some(limit);
+        self
+    }
+
+    pub fn with_max_orders_per_minute(mut self, limit: u32) -> Self {
+        self.max_orders_per_minute = Some(limit);
+        self
+    }
+
+    pub fn with_max_daily_loss(mut self, limit: Decimal) -> Self {
+        self.max_daily_loss = Some(limit);
+        self
+    }
+
+    pub fn with_max_position(mut self, asset: impl Into<String>, limit: Decimal) -> Self {
+        self.max_position.insert(asset.into().to_uppercase(), limit);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_config_parses() {
+        let wrapper: RiskConfigToml = toml::from_str(
+            r#"
+            [risk]
+            max_order_notional = 1000.0
+            max_orders_per_minute = 10
+            max_daily_loss = 50.0
+
+            [risk.max_position]
+            SOL = 100.0
+            "#,
+        )
+        .unwrap();
+
+        let cfg = RiskConfig::try_from(wrapper.risk).unwrap();
+        assert_eq!(cfg.max_order_notional(), Some(Decimal::from(1000)));
+        assert_eq!(cfg.max_orders_per_minute(), Some(10));
+        assert_eq!(cfg.max_daily_loss(), Some(Decimal::from(50)));
+        assert_eq!(cfg.max_position("SOL"), Some(Decimal::from(100)));
+        assert_eq!(cfg.max_position("BTC"), None);
+    }
+
+    #[test]
+    fn empty_section_means_no_limits() {
+        let cfg = RiskConfig::try_from(RawRiskConfig::default()).unwrap();
+        assert!(cfg.max_order_notional().is_none());
+        assert!(cfg.max_orders_per_minute().is_none());
+        assert!(cfg.max_daily_loss().is_none());
+    }
+
+    #[test]
+    fn reject_non_positive_limits() {
+        let raw = RawRiskConfig {
+            max_daily_loss: Some((-5.0).into()),
+            ..Default::default()
+        };
+        let err = RiskConfig::try_from(raw).unwrap_err();
+        assert!(format!("{}", err).contains("max_daily_loss"));
+
+        let raw = RawRiskConfig {
+            max_position: HashMap::from([("SOL".to_string(), 0.0.into())]),
+            ..Default::default()
+        };
+        let err = RiskConfig::try_from(raw).unwrap_err();
+        assert!(format!("{}", err).contains("max_position.SOL"));
+
+        let raw = RawRiskConfig {
+            max_orders_per_minute: Some(0),
+            ..Default::default()
+        };
+        assert!(RiskConfig::try_from(raw).is_err());
+    }
+}
@@ -1,6 +1,10 @@
-use crate::config::parse::ConfigError;
+use crate::config::exchange::ExchangesConfig;
+use crate::config::parse::{ConfigError, RawDecimal, ValidationReport};
+use crate::exchanges::split_pair;
+use crate::state::ExchangeId;
 use rust_decimal::Decimal;
 use serde::Deserialize;
+use std::collections::HashMap;
 
 /// Wrapper for TOML deserialization with [trading] section
 #[derive(Debug, Deserialize)]
@@ -9,101 +13,379 @@ pub struct TradingConfigToml {
 }
 
 /// Raw trading configuration for deserialization (loose validation)
+///
+/// `spread_threshold` and `order_size` may be numbers or decimal strings.
+/// Either `pair` names the single market, or `markets` lists several; the
+/// other top-level settings are defaults for every market.
 #[derive(Debug, Deserialize)]
 #[cfg_attr(test, derive(serde::Serialize))]
 pub struct RawTradingConfig {
     pub pair: Option<String>,
-    pub spread_threshold: Option<f64>,
-    pub order_size: Option<f64>,
+    pub spread_threshold: Option<RawDecimal>,
+    pub order_size: Option<RawDecimal>,
     pub cooldown_ms: Option<u64>,
+    #[serde(default)]
+    pub markets: Vec<RawMarketConfig>,
 }
 
-/// Validated trading configuration (guaranteed valid after parse)
-#[derive(Debug, Clone)]
-pub struct TradingConfig {
+/// Raw `[[trading.markets]]` entry; unset settings fall back to `[trading]`
+///
+/// ```toml
+/// [[trading.markets]]
+/// pair = "BTC/USDT"
+/// spread_threshold = 0.001
+/// exchanges = ["binance", "coinbase"]
+/// symbols = { coinbase = "BTC/USD" }
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[cfg_attr(test, derive(serde::Serialize))]
+pub struct RawMarketConfig {
+    pub pair: Option<String>,
+    pub spread_threshold: Option<RawDecimal>,
+    pub order_size: Option<RawDecimal>,
+    pub cooldown_ms: Option<u64>,
+    pub exchanges: Option<Vec<String>>,
+    #[serde(default)]
+    pub symbols: HashMap<String, String>,
+}
+
+/// One traded market with its settings resolved
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarketConfig {
     pair: String,
     spread_threshold: Decimal,
     order_size: Decimal,
     cooldown_ms: u64,
+    exchanges: Option<Vec<ExchangeId>>,
+    symbols: HashMap<ExchangeId, String>,
+}
+
+/// Validated trading configuration (guaranteed valid after parse)
+///
+/// Holds at least one market; pairs are unique.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TradingConfig {
+    markets: Vec<MarketConfig>,
+}
+
+/// Validate a `BASE/QUOTE` pair of alphanumeric symbols
+pub fn validate_pair(pair: &str) -> Result<(), ConfigError> {
+    let symbol = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_alphanumeric());
+    match split_pair(pair) {
+        Some((base, quote)) if symbol(base) && symbol(quote) && base != quote => Ok(()),
+        _ => Err(ConfigError::InvalidPair {
+            value: pair.to_string(),
+            reason: "expected BASE/QUOTE, e.g. SOL/USDC".to_string(),
+        }),
+    }
+}
+
+/// Spread threshold: must be in [0.0, 1.0]
+fn spread_threshold(raw: RawDecimal) -> Result<Decimal, ConfigError> {
+    let value = raw.to_decimal()?;
+    if (Decimal::ZERO..=Decimal::ONE).contains(&value) {
+        Ok(value)
+    } else {
+        Err(ConfigError::InvalidSpreadThreshold {
+            value,
+            reason: "must be between 0.0 and 1.0".to_string(),
+        })
+    }
+}
+
+/// Order size: must be > 0
+fn order_size(raw: RawDecimal) -> Result<Decimal, ConfigError> {
+    let value = raw.to_decimal()?;
+    if value > Decimal::ZERO {
+        Ok(value)
+    } else {
+        Err(ConfigError::InvalidOrderSize {
+            value,
+            reason: "must be greater than 0".to_string(),
+        })
+    }
+}
+
+/// Cooldown: must be >= 1000ms
+fn cooldown_ms(value: u64) -> Result<u64, ConfigError> {
+    if value >= 1000 {
+        Ok(value)
+    } else {
+        Err(ConfigError::InvalidCooldown {
+            value,
+            reason: "must be at least 1000ms".to_string(),
+        })
+    }
+}
+
+fn exchange_id(name: &str) -> Result<ExchangeId, ConfigError> {
+    ExchangeId::from_name(name).ok_or_else(|| ConfigError::InvalidExchange {
+        exchange: name.to_string(),
+        reason: "invalid exchange name".to_string(),
+    })
+}
+
+/// A default from `[trading]`: `None` if unset, `Some(None)` if invalid
+type Fallback<T> = Option<Option<T>>;
+
+/// A market's own setting, else the default
+///
+/// A setting missing from both is reported at the market's path.
+fn resolve<R, T: Copy>(
+    report: &mut ValidationReport,
+    path: &str,
+    own: Option<R>,
+    fallback: Fallback<T>,
+    validate: fn(R) -> Result<T, ConfigError>,
+) -> Option<T> {
+    match (own, fallback) {
+        (Some(own), _) => report.check(path, validate(own)),
+        (None, Some(fallback)) => fallback,
+        (None, None) => report.require(path, None),
+    }
 }
 
 impl TryFrom<RawTradingConfig> for TradingConfig {
     type Error = ConfigError;
 
+    /// Validates every field, reporting all problems at once
+    ///
+    /// Problems in a market are reported under `markets[i]`; with the `pair`
+    /// shorthand they are reported at the top level.
     fn try_from(raw: RawTradingConfig) -> std::result::Result<Self, Self::Error> {
-        let pair = raw.pair.ok_or_else(|| ConfigError::MissingField {
-            field: "pair".to_string(),
-        })?;
-
-        let spread_threshold_raw =
-            raw.spread_threshold
-                .ok_or_else(|| ConfigError::MissingField {
-                    field: "spread_threshold".to_string(),
-                })?;
-
-        let order_size_raw = raw.order_size.ok_or_else(|| ConfigError::MissingField {
-            field: "order_size".to_string(),
-        })?;
-
-        let cooldown_ms = raw.cooldown_ms.ok_or_else(|| ConfigError::MissingField {
-            field: "cooldown_ms".to_string(),
-        })?;
-
-        // Validate spread_threshold: must be in [0.0, 1.0]
-        if !(0.0..=1.0).contains(&spread_threshold_raw) {
-            return Err(ConfigError::InvalidSpreadThreshold {
-                value: spread_threshold_raw,
-                reason: "must be between 0.0 and 1.0".to_string(),
-            });
+        let mut report = ValidationReport::default();
+
+        let shorthand = raw.markets.is_empty();
+        let markets = match (raw.pair, shorthand) {
+            (Some(pair), true) => vec![RawMarketConfig {
+                pair: Some(pair),
+                ..Default::default()
+            }],
+            (None, true) => {
+                report.require::<String>("pair", None);
+                Vec::new()
+            }
+            (pair, false) => {
+                if let Some(pair) = pair {
+                    report.push(
+                        "pair",
+                        ConfigError::InvalidPair {
+                            value: pair,
+                            reason: "set either pair or [[trading.markets]], not both".to_string(),
+                        },
+                    );
+                }
+                raw.markets
+            }
+        };
+        let path = |i: usize, field: &str| {
+            if shorthand {
+                field.to_string()
+            } else {
+                format!("markets[{}].{}", i, field)
+            }
+        };
+
+        let mut pairs: Vec<Option<String>> = Vec::new();
+        for (i, market) in markets.iter().enumerate() {
+            let field = path(i, "pair");
+            let pair = report
+                .require(&field, market.pair.clone())
+                .and_then(|pair| report.check(&field, validate_pair(&pair)).map(|_| pair))
+                .and_then(|pair| {
+                    if pairs.contains(&Some(pair.clone())) {
+                        report.push(
+                            &field,
+                            ConfigError::InvalidPair {
+                                value: pair,
+                                reason: "duplicate market".to_string(),
+                            },
+                        );
+                        return None;
+                    }
+                    Some(pair)
+                });
+            pairs.push(pair);
         }
 
-        // Validate order_size: must be > 0
-        if order_size_raw <= 0.0 {
-            return Err(ConfigError::InvalidOrderSize {
-                value: order_size_raw,
-                reason: "must be greater than 0".to_string(),
+        let spread_fallback = raw
+            .spread_threshold
+            .map(|v| report.check("spread_threshold", spread_threshold(v)));
+        let order_fallback = raw
+            .order_size
+            .map(|v| report.check("order_size", order_size(v)));
+        let cooldown_fallback = raw
+            .cooldown_ms
+            .map(|v| report.check("cooldown_ms", cooldown_ms(v)));
+
+        let mut resolved = Vec::new();
+        for (i, (market, pair)) in markets.into_iter().zip(pairs).enumerate() {
+            let spread_threshold = resolve(
+                &mut report,
+                &path(i, "spread_threshold"),
+                market.spread_threshold,
+                spread_fallback,
+                spread_threshold,
+            );
+            let order_size = resolve(
+                &mut report,
+                &path(i, "order_size"),
+                market.order_size,
+                order_fallback,
+                order_size,
+            );
+            let cooldown_ms = resolve(
+                &mut report,
+                &path(i, "cooldown_ms"),
+                market.cooldown_ms,
+                cooldown_fallback,
+                cooldown_ms,
+            );
+
+            let exchanges = market.exchanges.map(|names| {
+                let field = path(i, "exchanges");
+                let mut ids = Vec::new();
+                for name in names {
+                    if let Some(id) = report.check(&field, exchange_id(&name))
+                        && !ids.contains(&id)
+                    {
+                        ids.push(id);
+                    }
+                }
+                if ids.len() < 2 {
+                    report.push(
+                        &field,
+                        ConfigError::InvalidExchange {
+                            exchange: ids.iter().map(|id| id.as_str()).collect::<Vec<_>>().join(", "),
+                            reason: "a market needs at least two exchanges".to_string(),
+                        },
+                    );
+                }
+                ids
             });
+
+            let mut symbols = HashMap::new();
+            for (name, symbol) in market.symbols {
+                let field = path(i, &format!("symbols.{}", name));
+                let id = report.check(&field, exchange_id(&name));
+                let symbol = report.check(&field, validate_pair(&symbol)).map(|_| symbol);
+                if let (Some(id), Some(symbol)) = (id, symbol) {
+                    symbols.insert(id, symbol);
+                }
+            }
+
+            if let (Some(pair), Some(spread_threshold), Some(order_size), Some(cooldown_ms)) =
+                (pair, spread_threshold, order_size, cooldown_ms)
+            {
+                resolved.push(MarketConfig {
+                    pair,
+                    spread_threshold,
+                    order_size,
+                    cooldown_ms,
+                    exchanges,
+                    symbols,
+                });
+            }
         }
 
-        // Validate cooldown: must be >= 1000ms
-        if cooldown_ms < 1000 {
-            return Err(ConfigError::InvalidCooldown {
-                value: cooldown_ms,
-                reason: "must be at least 1000ms".to_string(),
-            });
+        report.finish(Some(TradingConfig { markets: resolved }))
+    }
+}
+
+impl MarketConfig {
+    /// The pair in `BASE/QUOTE` form; identifies the market
+    pub fn pair(&self) -> &str {
+        &self.pair
+    }
+
+    pub fn spread_threshold(&self) -> Decimal {
+        self.spread_threshold
+    }
+
+    pub fn order_size(&self) -> Decimal {
+        self.order_size
+    }
+
+    pub fn cooldown_ms(&self) -> u64 {
+        self.cooldown_ms
+    }
+
+    /// The exchanges listed for this market, or `None` for every enabled one
+    pub fn exchanges(&self) -> Option<&[ExchangeId]> {
+        self.exchanges.as_deref()
+    }
+
+    /// Exchanges this market trades on, given those enabled
+    pub fn venues(&self, exchanges: &ExchangesConfig) -> Vec<ExchangeId> {
+        match &self.exchanges {
+            Some(ids) => ids.clone(),
+            None => exchanges.enabled(),
         }
+    }
 
-        // Convert to validated types
-        Ok(TradingConfig {
-            pair,
-            spread_threshold: Decimal::from_f64_retain(spread_threshold_raw)
-                .ok_or(ConfigError::InvalidDecimal)?,
-            order_size: Decimal::from_f64_retain(order_size_raw)
-                .ok_or(ConfigError::InvalidDecimal)?,
-            cooldown_ms,
-        })
+    /// The pair as `exchange` lists it: its override, else its venue's, else `pair()`
+    pub fn symbol(&self, exchange: ExchangeId) -> &str {
+        self.symbols
+            .get(&exchange)
+            .or_else(|| self.symbols.get(&exchange.venue()))
+            .unwrap_or(&self.pair)
+    }
+
+    /// Pair, exchanges and symbol overrides, e.g. `BTC/USDT [binance, coinbase] {coinbase=BTC/USD}`
+    ///
+    /// Equal routes trade the same thing on the same venues.
+    pub fn route(&self) -> String {
+        let mut route = self.pair.clone();
+        if let Some(ids) = &self.exchanges {
+            let names: Vec<&str> = ids.iter().map(|id| id.as_str()).collect();
+            route.push_str(&format!(" [{}]", names.join(", ")));
+        }
+        let mut symbols: Vec<String> = self
+            .symbols
+            .iter()
+            .map(|(id, symbol)| format!("{}={}", id.as_str(), symbol))
+            .collect();
+        if !symbols.is_empty() {
+            symbols.sort();
+            route.push_str(&format!(" {{{}}}", symbols.join(", ")));
+        }
+        route
     }
 }
 
 impl TradingConfig {
+    /// Every configured market, in file order
+    pub fn markets(&self) -> &[MarketConfig] {
+        &self.markets
+    }
+
+    /// The market trading `pair`
+    pub fn market(&self, pair: &str) -> Option<&MarketConfig> {
+        self.markets.iter().find(|m| m.pair == pair)
+    }
+
+    /// The first market's pair; the only one with the `pair` shorthand
     pub fn pair(&self) -> &str {
-        &self.pair
+        self.markets[0].pair()
     }
 
     pub fn trading_pair(&self) -> &str {
-        &self.pair
+        self.pair()
     }
 
+    /// The first market's threshold
     pub fn spread_threshold(&self) -> Decimal {
-        self.spread_threshold
+        self.markets[0].spread_threshold()
     }
 
+    /// The first market's order size
     pub fn order_size(&self) -> Decimal {
-        self.order_size
+        self.markets[0].order_size()
     }
 
+    /// The first market's cooldown
     pub fn cooldown_ms(&self) -> u64 {
-        self.cooldown_ms
+        self.markets[0].cooldown_ms()
     }
 }
 
@@ -115,26 +397,26 @@ mod tests {
     fn valid_config_parses() {
         let raw = RawTradingConfig {
             pair: Some("SOL/USDC".to_string()),
-            spread_threshold: Some(0.002),
-            order_size: Some(10.0),
+            spread_threshold: Some(0.002.into()),
+            order_size: Some(10.0.into()),
             cooldown_ms: Some(5000),
+            markets: Vec::new(),
         };
 
         let cfg = TradingConfig::try_from(raw).unwrap();
         assert_eq!(cfg.pair(), "SOL/USDC");
-        assert_eq!(
-            cfg.spread_threshold(),
-            Decimal::from_f64_retain(0.002).unwrap()
-        );
+        assert_eq!(cfg.spread_threshold(), Decimal::new(2, 3));
+        assert_eq!(cfg.order_size(), Decimal::from(10));
     }
 
     #[test]
     fn reject_invalid_spread_threshold() {
         let raw = RawTradingConfig {
             pair: Some("SOL/USDC".to_string()),
-            spread_threshold: Some(1.5),
-            order_size: Some(10.0),
+            spread_threshold: Some(1.5.into()),
+            order_size: Some(10.0.into()),
             cooldown_ms: Some(5000),
+            markets: Vec::new(),
         };
 
         let err = TradingConfig::try_from(raw).unwrap_err();
@@ -145,9 +427,10 @@ mod tests {
     fn reject_invalid_order_size() {
         let raw = RawTradingConfig {
             pair: Some("SOL/USDC".to_string()),
-            spread_threshold: Some(0.002),
-            order_size: Some(0.0),
+            spread_threshold: Some(0.002.into()),
+            order_size: Some(0.0.into()),
             cooldown_ms: Some(5000),
+            markets: Vec::new(),
         };
 
         let err = TradingConfig::try_from(raw).unwrap_err();
@@ -158,12 +441,128 @@ mod tests {
     fn reject_missing_pair() {
         let raw = RawTradingConfig {
             pair: None,
-            spread_threshold: Some(0.002),
-            order_size: Some(10.0),
+            spread_threshold: Some(0.002.into()),
+            order_size: Some(10.0.into()),
             cooldown_ms: Some(5000),
+            markets: Vec::new(),
         };
 
         let err = TradingConfig::try_from(raw).unwrap_err();
         assert!(format!("{}", err).to_lowercase().contains("pair"));
     }
+
+    #[test]
+    fn decimal_strings_parse_exactly() {
+        let raw = RawTradingConfig {
+            pair: Some("SOL/USDC".to_string()),
+            spread_threshold: Some("0.0015".into()),
+            order_size: Some("2.125".into()),
+            cooldown_ms: Some(5000),
+            markets: Vec::new(),
+        };
+
+        let cfg = TradingConfig::try_from(raw).unwrap();
+        assert_eq!(cfg.spread_threshold(), Decimal::new(15, 4));
+        assert_eq!(cfg.order_size(), Decimal::new(2125, 3));
+    }
+
+    #[test]
+    fn reports_every_problem() {
+        let raw = RawTradingConfig {
+            pair: Some("SOLUSDC".to_string()),
+            spread_threshold: Some(1.5.into()),
+            order_size: Some("lots".into()),
+            cooldown_ms: None,
+            markets: Vec::new(),
+        };
+
+        let Err(ConfigError::Invalid(report)) = TradingConfig::try_from(raw) else {
+            panic!("expected a validation report");
+        };
+        let paths: Vec<&str> = report.issues().iter().map(|i| i.path.as_str()).collect();
+        assert_eq!(paths, ["pair", "spread_threshold", "order_size", "cooldown_ms"]);
+    }
+
+    fn market(pair: &str) -> RawMarketConfig {
+        RawMarketConfig {
+            pair: Some(pair.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn markets_fall_back_to_defaults() {
+        let raw = RawTradingConfig {
+            pair: None,
+            spread_threshold: Some(0.002.into()),
+            order_size: Some(10.0.into()),
+            cooldown_ms: Some(5000),
+            markets: vec![
+                market("SOL/USDC"),
+                RawMarketConfig {
+                    spread_threshold: Some("0.001".into()),
+                    order_size: Some(0.5.into()),
+                    exchanges: Some(vec!["Binance".to_string(), "coinbase".to_string()]),
+                    symbols: HashMap::from([("coinbase".to_string(), "BTC/USD".to_string())]),
+                    ..market("BTC/USDT")
+                },
+            ],
+        };
+
+        let cfg = TradingConfig::try_from(raw).unwrap();
+        let pairs: Vec<&str> = cfg.markets().iter().map(|m| m.pair()).collect();
+        assert_eq!(pairs, ["SOL/USDC", "BTC/USDT"]);
+        assert_eq!(cfg.pair(), "SOL/USDC");
+
+        let sol = cfg.market("SOL/USDC").unwrap();
+        assert_eq!(sol.spread_threshold(), Decimal::new(2, 3));
+        assert!(sol.exchanges().is_none());
+        assert_eq!(sol.symbol(ExchangeId::COINBASE), "SOL/USDC");
+
+        let btc = cfg.market("BTC/USDT").unwrap();
+        assert_eq!(btc.spread_threshold(), Decimal::new(1, 3));
+        assert_eq!(btc.order_size(), Decimal::new(5, 1));
+        assert_eq!(btc.cooldown_ms(), 5000);
+        assert_eq!(btc.exchanges(), Some(&[ExchangeId::BINANCE, ExchangeId::COINBASE][..]));
+        assert_eq!(btc.symbol(ExchangeId::BINANCE), "BTC/USDT");
+        assert_eq!(btc.symbol(ExchangeId::COINBASE), "BTC/USD");
+        assert_eq!(btc.route(), "BTC/USDT [binance, coinbase] {coinbase=BTC/USD}");
+    }
+
+    #[test]
+    fn reports_market_problems_by_index() {
+        let raw = RawTradingConfig {
+            pair: Some("SOL/USDC".to_string()),
+            spread_threshold: Some(0.002.into()),
+            order_size: None,
+            cooldown_ms: Some(5000),
+            markets: vec![
+                RawMarketConfig {
+                    order_size: Some(1.0.into()),
+                    ..market("SOL/USDC")
+                },
+                RawMarketConfig {
+                    exchanges: Some(vec!["binance".to_string(), "kra ken".to_string()]),
+                    symbols: HashMap::from([("coinbase".to_string(), "BTCUSD".to_string())]),
+                    ..market("SOL/USDC")
+                },
+            ],
+        };
+
+        let Err(ConfigError::Invalid(report)) = TradingConfig::try_from(raw) else {
+            panic!("expected a validation report");
+        };
+        let paths: Vec<&str> = report.issues().iter().map(|i| i.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "pair",
+                "markets[1].pair",
+                "markets[1].order_size",
+                "markets[1].exchanges",
+                "markets[1].exchanges",
+                "markets[1].symbols.coinbase",
+            ]
+        );
+    }
 }
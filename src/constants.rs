@@ -10,6 +10,15 @@ pub mod exchange {
 
     /// Coinbase exchange identifier
     pub const COINBASE: &str = "coinbase";
+
+    /// Kraken exchange identifier
+    pub const KRAKEN: &str = "kraken";
+
+    /// OKX exchange identifier
+    pub const OKX: &str = "okx";
+
+    /// Bybit exchange identifier
+    pub const BYBIT: &str = "bybit";
 }
 
 /// WebSocket endpoints
@@ -22,10 +31,40 @@ pub mod websocket {
 
     /// Coinbase Exchange WebSocket endpoint (public, no auth required)
     pub const COINBASE_EXCHANGE: &str = "wss://ws-feed.exchange.coinbase.com";
+
+    /// Kraken WebSocket v2 public endpoint
+    pub const KRAKEN_V2: &str = "wss://ws.kraken.com/v2";
+
+    /// OKX v5 public WebSocket endpoint (market data)
+    pub const OKX_PUBLIC: &str = "wss://ws.okx.com:8443/ws/v5/public";
+
+    /// OKX v5 private WebSocket endpoint (login required)
+    pub const OKX_PRIVATE: &str = "wss://ws.okx.com:8443/ws/v5/private";
+
+    /// OKX demo trading public WebSocket endpoint
+    pub const OKX_DEMO_PUBLIC: &str = "wss://wspap.okx.com:8443/ws/v5/public";
+
+    /// OKX demo trading private WebSocket endpoint
+    pub const OKX_DEMO_PRIVATE: &str = "wss://wspap.okx.com:8443/ws/v5/private";
+
+    /// Bybit v5 public spot WebSocket endpoint
+    pub const BYBIT_SPOT: &str = "wss://stream.bybit.com/v5/public/spot";
+
+    /// Bybit testnet v5 public spot WebSocket endpoint
+    pub const BYBIT_SPOT_TESTNET: &str = "wss://stream-testnet.bybit.com/v5/public/spot";
 }
 
 /// REST API endpoints
 pub mod api {
+    /// Binance testnet API base URL
+    pub const BINANCE_TESTNET: &str = "https://testnet.binance.vision";
+
+    /// Binance.US production API base URL
+    pub const BINANCE_US_PRODUCTION: &str = "https://api.binance.us";
+
+    /// Binance server time endpoint path
+    pub const BINANCE_TIME_PATH: &str = "/api/v3/time";
+
     /// Coinbase sandbox API base URL
     pub const COINBASE_SANDBOX: &str = "https://api-public.sandbox.exchange.coinbase.com";
 
@@ -37,6 +76,66 @@ pub mod api {
 
     /// Coinbase orders endpoint path
     pub const COINBASE_ORDERS_PATH: &str = "/api/v3/brokerage/orders";
+
+    /// Coinbase server time endpoint path
+    pub const COINBASE_TIME_PATH: &str = "/api/v3/brokerage/time";
+
+    /// Coinbase Exchange (sandbox) server time endpoint path
+    pub const COINBASE_EXCHANGE_TIME_PATH: &str = "/time";
+
+    /// Kraken REST API base URL
+    pub const KRAKEN_PRODUCTION: &str = "https://api.kraken.com";
+
+    /// Kraken balances with holds (private)
+    pub const KRAKEN_BALANCE_PATH: &str = "/0/private/BalanceEx";
+
+    /// Kraken order placement (private)
+    pub const KRAKEN_ADD_ORDER_PATH: &str = "/0/private/AddOrder";
+
+    /// Kraken order lookup by txid (private)
+    pub const KRAKEN_QUERY_ORDERS_PATH: &str = "/0/private/QueryOrders";
+
+    /// Kraken open orders (private)
+    pub const KRAKEN_OPEN_ORDERS_PATH: &str = "/0/private/OpenOrders";
+
+    /// OKX REST API base URL (production and demo trading)
+    pub const OKX_PRODUCTION: &str = "https://www.okx.com";
+
+    /// OKX trading account balances
+    pub const OKX_BALANCE_PATH: &str = "/api/v5/account/balance";
+
+    /// OKX order placement and lookup
+    pub const OKX_ORDER_PATH: &str = "/api/v5/trade/order";
+
+    /// OKX open orders
+    pub const OKX_PENDING_ORDERS_PATH: &str = "/api/v5/trade/orders-pending";
+
+    /// OKX server time endpoint path
+    pub const OKX_TIME_PATH: &str = "/api/v5/public/time";
+
+    /// Path signed by WebSocket logins
+    pub const OKX_LOGIN_PATH: &str = "/users/self/verify";
+
+    /// Bybit REST API base URL
+    pub const BYBIT_PRODUCTION: &str = "https://api.bybit.com";
+
+    /// Bybit testnet REST API base URL
+    pub const BYBIT_TESTNET: &str = "https://api-testnet.bybit.com";
+
+    /// Bybit unified account balances
+    pub const BYBIT_BALANCE_PATH: &str = "/v5/account/wallet-balance";
+
+    /// Bybit order placement
+    pub const BYBIT_CREATE_ORDER_PATH: &str = "/v5/order/create";
+
+    /// Bybit open and recently closed orders
+    pub const BYBIT_REALTIME_ORDERS_PATH: &str = "/v5/order/realtime";
+
+    /// Bybit order history
+    pub const BYBIT_ORDER_HISTORY_PATH: &str = "/v5/order/history";
+
+    /// Bybit server time endpoint path
+    pub const BYBIT_TIME_PATH: &str = "/v5/market/time";
 }
 
 /// Currency symbols
@@ -101,6 +200,9 @@ pub mod order {
     /// Pending order status
     pub const PENDING: &str = "PENDING";
 
+    /// Open (resting) order status
+    pub const OPEN: &str = "OPEN";
+
     /// Partially filled order status
     pub const PARTIALLY_FILLED: &str = "PARTIALLY_FILLED";
 
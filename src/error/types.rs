@@ -1,3 +1,4 @@
+use crate::exchanges::ExchangeId;
 use thiserror::Error;
 
 pub type Result<T> = std::result::Result<T, ArbitrageError>;
@@ -6,7 +7,7 @@ pub type Result<T> = std::result::Result<T, ArbitrageError>;
 pub enum ArbitrageError {
     #[error("Exchange error on {exchange}: {message}{}", code.map(|c| format!(" (code {c})")).unwrap_or_default())]
     ExchangeError {
-        exchange: String,
+        exchange: ExchangeId,
         message: String,
         code: Option<i32>,
     },
@@ -33,16 +34,22 @@ pub enum ArbitrageError {
     ConfigError { field: String, reason: String },
 
     #[error("Rate limit exceeded on {exchange}, retry after {retry_after}ms")]
-    RateLimitExceeded { exchange: String, retry_after: u64 },
+    RateLimitExceeded { exchange: ExchangeId, retry_after: u64 },
 
     #[error("Authentication error on {exchange}: {reason}")]
-    AuthenticationError { exchange: String, reason: String },
+    AuthenticationError { exchange: ExchangeId, reason: String },
+
+    #[error("Trading halted: {reason}")]
+    TradingHalted { reason: String },
+
+    #[error("Risk limit {limit} exceeded: {message}")]
+    RiskLimitExceeded { limit: String, message: String },
 
     #[error(
         "Insufficient balance on {exchange} for {asset}: required {required}, available {available}"
     )]
     InsufficientBalance {
-        exchange: String,
+        exchange: ExchangeId,
         asset: String,
         required: String,
         available: String,
@@ -55,7 +62,7 @@ pub enum ArbitrageError {
     Json(#[from] serde_json::Error),
 
     #[error("WebSocket library error: {0}")]
-    WebSocketLib(#[from] tokio_tungstenite::tungstenite::Error),
+    WebSocketLib(Box<tokio_tungstenite::tungstenite::Error>),
 
     #[error("TOML parse error: {0}")]
     Toml(#[from] toml::de::Error),
@@ -64,6 +71,27 @@ pub enum ArbitrageError {
     ConfigParse(Box<crate::config::parse::ConfigError>),
 }
 
+impl ArbitrageError {
+    /// Server-requested wait before retrying, if the error carries one
+    pub fn retry_after(&self) -> Option<std::time::Duration> {
+        match self {
+            ArbitrageError::NetworkError { retry_after, .. } => {
+                retry_after.map(std::time::Duration::from_millis)
+            }
+            ArbitrageError::RateLimitExceeded { retry_after, .. } => {
+                Some(std::time::Duration::from_millis(*retry_after))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl From<tokio_tungstenite::tungstenite::Error> for ArbitrageError {
+    fn from(e: tokio_tungstenite::tungstenite::Error) -> Self {
+        ArbitrageError::WebSocketLib(Box::new(e))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -71,7 +99,7 @@ mod tests {
     #[test]
     fn display_exchange_with_code() {
         let e = ArbitrageError::ExchangeError {
-            exchange: "X".into(),
+            exchange: ExchangeId::BINANCE,
             message: "m".into(),
             code: Some(1),
         };
@@ -79,6 +107,21 @@ mod tests {
         assert!(s.contains("code 1"));
     }
 
+    #[test]
+    fn retry_after_hint() {
+        let e = ArbitrageError::RateLimitExceeded {
+            exchange: ExchangeId::BINANCE,
+            retry_after: 1500,
+        };
+        assert_eq!(e.retry_after(), Some(std::time::Duration::from_millis(1500)));
+
+        let e = ArbitrageError::NetworkError {
+            message: "m".into(),
+            retry_after: None,
+        };
+        assert_eq!(e.retry_after(), None);
+    }
+
     #[test]
     fn from_io() {
         let e: ArbitrageError = std::io::Error::other("x").into();
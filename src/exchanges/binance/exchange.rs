@@ -4,16 +4,20 @@
 
 use crate::config::BinanceConfig;
 use crate::error::{ArbitrageError, Result};
-use crate::exchanges::{Exchange, Price};
+use crate::exchanges::{Exchange, ExchangeId, PRICE_UPDATES_CAPACITY, Price};
 use crate::logger::{error, warn};
-use crate::websocket::{ReconnectionStrategy, WebSocketManager};
+use crate::websocket::{CircuitBreaker, ConnectionState, ReconnectionStrategy, WebSocketManager};
 use parking_lot::RwLock;
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::broadcast;
+use tokio::sync::{broadcast, watch};
 
 use super::parser::BinanceParser;
 
+/// Reconnect if no data arrives for this long.
+/// Binance pushes 24hrTicker updates every second, so 30s of silence means a dead feed.
+const FEED_INACTIVITY_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
 /// Binance exchange implementation using WebSocket for price feeds
 ///
 /// # Business Logic
@@ -24,7 +28,7 @@ use super::parser::BinanceParser;
 /// **WebSocket-only**: This implementation focuses on price feeds only.
 /// REST API for trading will be added later.
 pub struct BinanceExchange {
-    name: String,
+    id: ExchangeId,
     #[allow(dead_code)] // Kept for future use (testnet flag, API credentials)
     config: BinanceConfig,
     /// WebSocket manager (moved into spawned task on connect)
@@ -33,6 +37,10 @@ pub struct BinanceExchange {
     price_rx: Option<broadcast::Receiver<Price>>,
     /// In-memory store of latest prices by trading pair
     latest_prices: Arc<RwLock<HashMap<String, Price>>>,
+    /// Re-broadcasts every parsed quote to `price_updates()` receivers
+    prices_tx: broadcast::Sender<Price>,
+    /// Connection state, shared with each manager so receivers survive resubscribes
+    state_tx: watch::Sender<ConnectionState>,
     /// Base WebSocket URL (without subscription)
     base_url: String,
 }
@@ -53,21 +61,29 @@ impl BinanceExchange {
         };
 
         Ok(Self {
-            name: crate::constants::exchange::BINANCE.to_string(),
+            id: ExchangeId::BINANCE,
             config,
             ws_manager_handle: None,
             price_rx: None,
             latest_prices: Arc::new(RwLock::new(HashMap::new())),
+            prices_tx: broadcast::channel(PRICE_UPDATES_CAPACITY).0,
+            state_tx: watch::Sender::new(ConnectionState::Disconnected),
             base_url,
         })
     }
 
+    /// Report as `id` instead, e.g. `binance:hedge` for a second account
+    pub fn with_id(mut self, id: ExchangeId) -> Self {
+        self.id = id;
+        self
+    }
+
     /// Connect to WebSocket with a specific ticker subscription
     ///
     /// Binance supports subscribing via URL parameter:
     /// Production: `wss://stream.binance.com:9443/ws/<symbol>@ticker` OR `wss://stream.binance.com:9443/stream?streams=<symbol>@ticker`
     /// Testnet: `wss://testnet.binance.vision/ws/<symbol>@ticker`
-    #[tracing::instrument(name = "connect_with_subscription", skip(self), fields(exchange = %self.name, pair = %pair))]
+    #[tracing::instrument(name = "connect_with_subscription", skip(self), fields(exchange = %self.id, pair = %pair))]
     async fn connect_with_subscription(&mut self, pair: &str) -> Result<()> {
         let symbol = BinanceParser::pair_to_symbol(pair);
 
@@ -76,10 +92,16 @@ impl BinanceExchange {
         let url = format!("{}/{}@ticker", self.base_url, symbol);
 
         let parser = BinanceParser::new();
-        let reconnect_strategy = ReconnectionStrategy::exponential_backoff();
+        // Jitter keeps feeds that dropped together from reconnecting in lockstep
+        let reconnect_strategy = ReconnectionStrategy::exponential_backoff()
+            .with_jitter()
+            .with_circuit_breaker(CircuitBreaker::default());
 
         // Create WebSocket manager with subscription URL
-        let (mut manager, price_rx) = WebSocketManager::new(url, parser, reconnect_strategy);
+        let (manager, price_rx) = WebSocketManager::new(self.id, url, parser, reconnect_strategy);
+        let mut manager = manager
+            .with_inactivity_timeout(FEED_INACTIVITY_TIMEOUT)
+            .with_state_sender(self.state_tx.clone());
 
         // Store receiver
         self.price_rx = Some(price_rx);
@@ -96,12 +118,15 @@ impl BinanceExchange {
         // Spawn background task to update latest prices from WebSocket stream
         if let Some(mut rx) = self.price_rx.take() {
             let prices = self.latest_prices.clone();
+            let prices_tx = self.prices_tx.clone();
             tokio::spawn(async move {
                 loop {
                     match rx.recv().await {
                         Ok(price) => {
                             // Silently cache price updates (no verbose logging)
-                            prices.write().insert(price.pair.clone(), price);
+                            prices.write().insert(price.pair.clone(), price.clone());
+                            // Nobody listening is fine; pollers read the cache
+                            let _ = prices_tx.send(price);
                         }
                         Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped)) => {
                             warn!(skipped = skipped, "Lagged messages");
@@ -129,7 +154,7 @@ impl Exchange for BinanceExchange {
         Ok(())
     }
 
-    #[tracing::instrument(name = "subscribe_ticker", skip(self), fields(exchange = %self.name, pair = %pair))]
+    #[tracing::instrument(name = "subscribe_ticker", skip(self), fields(exchange = %self.id, pair = %pair))]
     async fn subscribe_ticker(&mut self, pair: &str) -> Result<()> {
         // Disconnect existing connection if any
         self.disconnect().await.ok();
@@ -159,14 +184,14 @@ impl Exchange for BinanceExchange {
         Ok(())
     }
 
-    #[tracing::instrument(name = "get_latest_price", skip(self), fields(exchange = %self.name, pair = %pair))]
+    #[tracing::instrument(name = "get_latest_price", skip(self), fields(exchange = %self.id, pair = %pair))]
     async fn get_latest_price(&self, pair: &str) -> Result<Price> {
         let prices = self.latest_prices.read();
         prices
             .get(pair)
             .cloned()
             .ok_or_else(|| ArbitrageError::ExchangeError {
-                exchange: self.name.clone(),
+                exchange: self.id,
                 message: format!("No price data available for {}", pair),
                 code: None,
             })
@@ -178,7 +203,7 @@ impl Exchange for BinanceExchange {
     ) -> Result<crate::exchanges::OrderResult> {
         // REST API not implemented yet - WebSocket only
         Err(ArbitrageError::ExchangeError {
-            exchange: self.name.clone(),
+            exchange: self.id,
             message: "Trading not implemented yet - WebSocket price feed only".to_string(),
             code: None,
         })
@@ -187,19 +212,45 @@ impl Exchange for BinanceExchange {
     async fn get_balance(&self, _asset: &str) -> Result<rust_decimal::Decimal> {
         // REST API not implemented yet - WebSocket only
         Err(ArbitrageError::ExchangeError {
-            exchange: self.name.clone(),
+            exchange: self.id,
             message: "Balance queries not implemented yet - WebSocket price feed only".to_string(),
             code: None,
         })
     }
 
-    fn name(&self) -> &str {
-        &self.name
+    async fn get_open_orders(&self, _pair: &str) -> Result<Vec<crate::exchanges::OrderResult>> {
+        // Trading is not implemented, so no orders can be working
+        Ok(Vec::new())
+    }
+
+    fn id(&self) -> ExchangeId {
+        self.id
     }
 
     fn is_connected(&self) -> bool {
-        // Check if we have recent price data (indicates connection is working)
-        !self.latest_prices.read().is_empty()
+        self.connection_state().is_live()
+    }
+
+    fn connection_state(&self) -> ConnectionState {
+        // An aborted or panicked manager task can't publish its own exit
+        let running = self
+            .ws_manager_handle
+            .as_ref()
+            .is_some_and(|handle| !handle.is_finished());
+        let state = self.state_tx.borrow().clone();
+        if running || matches!(state, ConnectionState::Failed { .. }) {
+            state
+        } else {
+            ConnectionState::Disconnected
+        }
+    }
+
+    fn watch_connection_state(&self) -> Option<watch::Receiver<ConnectionState>> {
+        Some(self.state_tx.subscribe())
+    }
+
+    fn price_updates(&self) -> Option<broadcast::Receiver<Price>> {
+        Some(self.prices_tx.subscribe())
     }
 
     async fn disconnect(&mut self) -> Result<()> {
@@ -207,6 +258,7 @@ impl Exchange for BinanceExchange {
         if let Some(handle) = self.ws_manager_handle.take() {
             handle.abort();
         }
+        self.state_tx.send_replace(ConnectionState::Disconnected);
 
         // Clear price data
         self.latest_prices.write().clear();
@@ -3,9 +3,11 @@
 //! Implements the Exchange trait for Binance, providing WebSocket price feeds
 //! and REST API for trading operations.
 
+pub mod auth;
 pub mod exchange;
 pub mod parser;
 pub mod types;
 
+pub use auth::BinanceAuth;
 pub use exchange::BinanceExchange;
 pub use parser::BinanceParser;
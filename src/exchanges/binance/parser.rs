@@ -2,8 +2,9 @@
 
 use crate::error::{ArbitrageError, Result};
 use crate::exchanges::Price;
+use crate::exchanges::symbols::SymbolFormat;
 use crate::websocket::MessageParser;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 
 /// Parser for Binance WebSocket ticker messages
@@ -21,8 +22,12 @@ impl BinanceParser {
     /// Convert Binance symbol format to trading pair
     ///
     /// Example: "SOLUSDC" -> "SOL/USDC"
-    /// Note: This is a heuristic - Binance symbols vary in length
+    /// Note: Symbols with an unknown quote asset fall back to a heuristic
     pub fn symbol_to_pair(symbol: &str) -> String {
+        if let Some(pair) = SymbolFormat::Joined.to_pair(symbol) {
+            return pair;
+        }
+
         // Binance symbols are typically 6-12 chars (e.g., BTCUSDT, SOLUSDC)
         // For simplicity, assume format: BASEQUOTE where BASE is first 3-4 chars
         // This is a heuristic - real implementation might need a symbol mapping
@@ -45,7 +50,13 @@ impl BinanceParser {
     ///
     /// Example: "SOL/USDC" -> "SOLUSDC" (Binance uses UPPERCASE)
     pub fn pair_to_symbol(pair: &str) -> String {
-        pair.replace("/", "").to_uppercase()
+        SymbolFormat::Joined.to_venue(pair)
+    }
+}
+
+impl Default for BinanceParser {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -53,6 +64,8 @@ impl MessageParser for BinanceParser {
     type Output = Price;
 
     fn parse(&self, message: &str) -> Result<Self::Output> {
+        let received_at = Utc::now();
+
         // Debug: print first 200 chars of message (only for errors)
         let preview = if message.len() > 200 {
             format!("{}...", &message[..200])
@@ -75,6 +88,7 @@ impl MessageParser for BinanceParser {
         // Binance ticker format:
         // {
         //   "e": "24hrTicker",
+        //   "E": 1761825600000,  // Event time (ms)
         //   "s": "SOLUSDC",
         //   "c": "143.50",  // Close price (last)
         //   "b": "143.48",  // Best bid
@@ -154,13 +168,22 @@ impl MessageParser for BinanceParser {
                 input: Some(message.to_string()),
             })?;
 
+        // Event time in milliseconds since epoch
+        let timestamp = value["E"]
+            .as_i64()
+            .and_then(DateTime::from_timestamp_millis)
+            .unwrap_or(received_at);
+
         Ok(Price {
             pair,
             bid,
             ask,
             last,
             volume_24h: volume,
-            timestamp: Utc::now(),
+            timestamp,
+            // The 24hrTicker stream has no per-message update id
+            sequence: None,
+            received_at,
         })
     }
 }
@@ -173,6 +196,7 @@ mod tests {
     fn test_symbol_to_pair() {
         assert_eq!(BinanceParser::symbol_to_pair("SOLUSDC"), "SOL/USDC");
         assert_eq!(BinanceParser::symbol_to_pair("BTCUSDT"), "BTC/USDT");
+        assert_eq!(BinanceParser::symbol_to_pair("BTCFDUSD"), "BTC/FDUSD");
     }
 
     #[test]
@@ -187,6 +211,7 @@ mod tests {
 
         let ticker_json = r#"{
             "e": "24hrTicker",
+            "E": 1761825600000,
             "s": "SOLUSDC",
             "c": "143.50",
             "b": "143.48",
@@ -204,6 +229,8 @@ mod tests {
             price.volume_24h,
             Decimal::from_str_exact("1234567.89").unwrap()
         );
+        assert_eq!(price.timestamp.timestamp_millis(), 1761825600000);
+        assert!(price.received_at >= price.timestamp);
     }
 
     #[test]
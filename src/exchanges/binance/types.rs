@@ -1,6 +1,6 @@
 //! Binance-specific response types
 
-use crate::exchanges::{OrderResult, OrderStatus};
+use crate::exchanges::{Balance, OrderResult, OrderStatus};
 use chrono::Utc;
 use rust_decimal::Decimal;
 use serde::Deserialize;
@@ -67,6 +67,15 @@ pub struct BinanceBalance {
     pub locked: Decimal,
 }
 
+impl From<&BinanceBalance> for Balance {
+    fn from(balance: &BinanceBalance) -> Self {
+        Balance {
+            available: balance.free,
+            hold: balance.locked,
+        }
+    }
+}
+
 fn decimal_from_str<'de, D>(deserializer: D) -> Result<Decimal, D::Error>
 where
     D: serde::Deserializer<'de>,
@@ -0,0 +1,586 @@
+//! Bybit Exchange Implementation
+//!
+//! Connects to the Bybit v5 public spot stream for prices and trades through
+//! the signed v5 REST API.
+
+use crate::clock::{BybitTimeSource, ClockSync, ClockSyncConfig, SyncedClock};
+use crate::config::BybitConfig;
+use crate::error::{ArbitrageError, Result};
+use crate::exchanges::{
+    Balance, Exchange, ExchangeId, Order, OrderResult, OrderSide, OrderStatus, OrderType,
+    PRICE_UPDATES_CAPACITY, Price, split_pair,
+};
+use crate::logger::{debug, error, warn};
+use crate::websocket::{CircuitBreaker, ConnectionState, ReconnectionStrategy, WebSocketManager};
+use chrono::Utc;
+use parking_lot::RwLock;
+use reqwest::{Client, Method};
+use rust_decimal::Decimal;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::{broadcast, watch};
+
+use super::auth::BybitAuth;
+use super::parser::BybitParser;
+use super::types::{
+    BybitList, BybitOrder, BybitOrderAck, BybitOrderRequest, BybitResponse, BybitWallet,
+};
+
+/// Reconnect if no data arrives for this long
+const FEED_INACTIVITY_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Bybit drops connections without a `ping` op; it recommends one every 20 seconds
+const HEARTBEAT_INTERVAL: std::time::Duration = std::time::Duration::from_secs(20);
+
+/// Heartbeat request; answered with `{"success":true,"ret_msg":"pong","op":"ping"}`
+const PING: &str = r#"{"op":"ping"}"#;
+
+/// Error codes meaning the key, signature, permissions or timestamp is wrong
+const AUTH_ERRORS: [i64; 6] = [10002, 10003, 10004, 10005, 10007, 33004];
+
+/// "Too many visits" and IP rate limit
+const RATE_LIMIT_ERRORS: [i64; 2] = [10006, 10018];
+
+/// Spot "insufficient balance" and unified account "available balance not enough"
+const INSUFFICIENT_BALANCE_ERRORS: [i64; 2] = [170131, 110007];
+
+/// Bybit rate limits are per 1-second window
+const RATE_LIMIT_BACKOFF_MS: u64 = 1000;
+
+/// Bybit spot exchange implementation
+///
+/// # Business Logic
+///
+/// `subscribe_ticker()` subscribes to the `tickers` and level-1 `orderbook`
+/// topics for a pair and keeps the connection alive with Bybit's JSON `ping`.
+/// Prices are stored in-memory and can be queried via `get_latest_price()`.
+///
+/// With API credentials, balances and spot orders go through the signed v5
+/// REST API on the unified trading account.
+pub struct BybitExchange {
+    id: ExchangeId,
+    /// WebSocket manager (moved into spawned task on connect)
+    ws_manager_handle: Option<tokio::task::JoinHandle<()>>,
+    /// In-memory store of latest prices by trading pair
+    latest_prices: Arc<RwLock<HashMap<String, Price>>>,
+    /// Re-broadcasts every parsed quote to `price_updates()` receivers
+    prices_tx: broadcast::Sender<Price>,
+    /// Connection state, shared with each manager so receivers survive resubscribes
+    state_tx: watch::Sender<ConnectionState>,
+    /// Public spot WebSocket URL
+    ws_url: String,
+    /// REST API base URL
+    rest_url: String,
+    client: Client,
+    /// Request signer (optional, only if API credentials provided)
+    auth: Option<BybitAuth>,
+    /// Bybit clock for `X-BAPI-TIMESTAMP`, synced against `rest_url` while credentials are configured
+    clock: Option<SyncedClock>,
+}
+
+impl BybitExchange {
+    /// Create a new Bybit exchange instance
+    pub fn new(config: BybitConfig) -> Result<Self> {
+        let (ws_url, rest_url) = if config.testnet {
+            (
+                crate::constants::websocket::BYBIT_SPOT_TESTNET,
+                crate::constants::api::BYBIT_TESTNET,
+            )
+        } else {
+            (
+                crate::constants::websocket::BYBIT_SPOT,
+                crate::constants::api::BYBIT_PRODUCTION,
+            )
+        };
+        let auth = if !config.api_key.is_empty() && !config.api_secret.is_empty() {
+            Some(BybitAuth::new(config.api_key, config.api_secret))
+        } else {
+            None
+        };
+
+        Ok(Self {
+            id: ExchangeId::BYBIT,
+            ws_manager_handle: None,
+            latest_prices: Arc::new(RwLock::new(HashMap::new())),
+            prices_tx: broadcast::channel(PRICE_UPDATES_CAPACITY).0,
+            state_tx: watch::Sender::new(ConnectionState::Disconnected),
+            ws_url: ws_url.to_string(),
+            rest_url: rest_url.to_string(),
+            client: Client::new(),
+            auth,
+            clock: None,
+        }
+        .with_synced_clock())
+    }
+
+    /// Report as `id` instead, e.g. `bybit:hedge` for a second account
+    pub fn with_id(mut self, id: ExchangeId) -> Self {
+        self.id = id;
+        self
+    }
+
+    /// Point the price feed at a different WebSocket URL (e.g. a local test server)
+    pub fn with_ws_url(mut self, ws_url: impl Into<String>) -> Self {
+        self.ws_url = ws_url.into();
+        self
+    }
+
+    /// Point trading at a different REST base URL (e.g. a local test server)
+    ///
+    /// The clock is re-synced against the new URL.
+    pub fn with_rest_url(mut self, rest_url: impl Into<String>) -> Self {
+        self.rest_url = rest_url.into();
+        self.with_synced_clock()
+    }
+
+    /// Sync a clock against `rest_url` and sign with it (replacing any previous one)
+    fn with_synced_clock(mut self) -> Self {
+        let Some(auth) = self.auth.take() else {
+            return self;
+        };
+        let clock = SyncedClock::start(
+            ClockSync::new(crate::constants::exchange::BYBIT, ClockSyncConfig::default()),
+            Arc::new(BybitTimeSource::with_base_url(&self.rest_url)),
+        );
+        self.auth = Some(auth.with_clock(clock.clock().clone()));
+        self.clock = Some(clock);
+        self
+    }
+
+    /// Build the subscription message for a trading pair
+    ///
+    /// Format: {"op":"subscribe","args":["tickers.SOLUSDC","orderbook.1.SOLUSDC"]}
+    pub fn subscribe_message(pair: &str) -> String {
+        let symbol = BybitParser::pair_to_symbol(pair);
+        serde_json::json!({
+            "op": "subscribe",
+            "args": [format!("tickers.{}", symbol), format!("orderbook.1.{}", symbol)]
+        })
+        .to_string()
+    }
+
+    /// Connect to WebSocket with ticker and book subscriptions for `pair`
+    #[tracing::instrument(name = "connect_with_subscription", skip(self), fields(exchange = %self.id, pair = %pair))]
+    async fn connect_with_subscription(&mut self, pair: &str) -> Result<()> {
+        let subscribe_text = Self::subscribe_message(pair);
+        debug!(subscription = %subscribe_text, "Registering subscription message");
+
+        // Jitter keeps feeds that dropped together from reconnecting in lockstep
+        let reconnect_strategy = ReconnectionStrategy::exponential_backoff()
+            .with_jitter()
+            .with_circuit_breaker(CircuitBreaker::default());
+
+        let (manager, mut price_rx) =
+            WebSocketManager::new(self.id, self.ws_url.clone(), BybitParser::new(), reconnect_strategy);
+        let mut manager = manager
+            .with_on_connect_messages(vec![subscribe_text])
+            .with_heartbeat(PING, HEARTBEAT_INTERVAL)
+            .with_inactivity_timeout(FEED_INACTIVITY_TIMEOUT)
+            .with_state_sender(self.state_tx.clone());
+
+        // Spawn background task to run WebSocket manager
+        self.ws_manager_handle = Some(tokio::spawn(async move {
+            if let Err(e) = manager.run().await {
+                error!(error = %e, "Bybit WebSocket manager error");
+            }
+        }));
+
+        // Spawn background task to update latest prices from WebSocket stream
+        let prices = self.latest_prices.clone();
+        let prices_tx = self.prices_tx.clone();
+        tokio::spawn(async move {
+            loop {
+                match price_rx.recv().await {
+                    Ok(price) => {
+                        prices.write().insert(price.pair.clone(), price.clone());
+                        // Nobody listening is fine; pollers read the cache
+                        let _ = prices_tx.send(price);
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!(skipped = skipped, "Lagged messages");
+                        continue;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => {
+                        error!("Broadcast channel closed");
+                        break;
+                    }
+                }
+            }
+        });
+
+        Ok(())
+    }
+
+    /// Look up an order, falling back to history once it has left the realtime list
+    async fn find_order(&self, order_id: &str) -> Result<OrderResult> {
+        let query = format!("category=spot&orderId={}", order_id);
+        for path in [
+            crate::constants::api::BYBIT_REALTIME_ORDERS_PATH,
+            crate::constants::api::BYBIT_ORDER_HISTORY_PATH,
+        ] {
+            let orders: BybitList<BybitOrder> =
+                self.request(Method::GET, path, &query, "order", None).await?;
+            if let Some(order) = orders.list.into_iter().next() {
+                return Ok(order.into());
+            }
+        }
+        Err(ArbitrageError::ExchangeError {
+            exchange: self.id,
+            message: format!("Order not found: {}", order_id),
+            code: None,
+        })
+    }
+
+    /// Signed request, unwrapping Bybit's `{retCode, retMsg, result}` envelope
+    ///
+    /// `payload` is the query string for GET and the JSON body for POST; either
+    /// way it is what gets signed. `asset` names the asset short of balance if
+    /// the request fails for that reason.
+    async fn request<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        payload: &str,
+        what: &str,
+        asset: Option<&str>,
+    ) -> Result<T> {
+        let auth = self.auth.as_ref().ok_or_else(|| ArbitrageError::ExchangeError {
+            exchange: self.id,
+            message: "REST API not available - API credentials required".to_string(),
+            code: None,
+        })?;
+
+        let mut request = if method == Method::GET {
+            self.client
+                .get(format!("{}{}?{}", self.rest_url, path, payload))
+        } else {
+            self.client
+                .request(method, format!("{}{}", self.rest_url, path))
+                .header("Content-Type", "application/json")
+                .body(payload.to_string())
+        };
+        for (name, value) in auth.headers(payload) {
+            request = request.header(name, value);
+        }
+
+        let response = request.send().await.map_err(|e| ArbitrageError::ExchangeError {
+            exchange: self.id,
+            message: format!("HTTP request failed: {}", e),
+            code: None,
+        })?;
+
+        let status = response.status();
+        let response_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unable to read response".to_string());
+
+        // Failures still carry a `result`, usually `{}`, so decode it only on success
+        let envelope: BybitResponse<serde_json::Value> = match serde_json::from_str(&response_text) {
+            Ok(envelope) => envelope,
+            Err(_) if !status.is_success() => {
+                return Err(ArbitrageError::ExchangeError {
+                    exchange: self.id,
+                    message: format!("API error ({}): {}", status, response_text),
+                    code: Some(status.as_u16() as i32),
+                });
+            }
+            Err(e) => {
+                return Err(ArbitrageError::ExchangeError {
+                    exchange: self.id,
+                    message: format!("Failed to parse {} response: {}", what, e),
+                    code: None,
+                });
+            }
+        };
+
+        if envelope.ret_code != 0 {
+            return Err(self.classify(envelope.ret_code, &envelope.ret_msg, asset));
+        }
+        serde_json::from_value(envelope.result.unwrap_or_default()).map_err(|e| {
+            ArbitrageError::ExchangeError {
+                exchange: self.id,
+                message: format!("Failed to parse {} response: {}", what, e),
+                code: None,
+            }
+        })
+    }
+
+    /// Map a Bybit `retCode` to our error type
+    fn classify(&self, code: i64, msg: &str, asset: Option<&str>) -> ArbitrageError {
+        if AUTH_ERRORS.contains(&code) {
+            ArbitrageError::AuthenticationError {
+                exchange: self.id,
+                reason: format!("{} ({})", msg, code),
+            }
+        } else if RATE_LIMIT_ERRORS.contains(&code) {
+            ArbitrageError::RateLimitExceeded {
+                exchange: self.id,
+                retry_after: RATE_LIMIT_BACKOFF_MS,
+            }
+        } else if INSUFFICIENT_BALANCE_ERRORS.contains(&code)
+            && let Some(asset) = asset
+        {
+            ArbitrageError::InsufficientBalance {
+                exchange: self.id,
+                asset: asset.to_string(),
+                required: "unknown".to_string(),
+                available: "unknown".to_string(),
+            }
+        } else {
+            ArbitrageError::ExchangeError {
+                exchange: self.id,
+                message: format!("{} ({})", msg, code),
+                code: i32::try_from(code).ok(),
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Exchange for BybitExchange {
+    async fn connect(&mut self) -> Result<()> {
+        // Actual connection happens on subscribe_ticker(), which knows the pair
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "subscribe_ticker", skip(self), fields(exchange = %self.id, pair = %pair))]
+    async fn subscribe_ticker(&mut self, pair: &str) -> Result<()> {
+        // Disconnect existing connection if any
+        self.disconnect().await.ok();
+
+        self.connect_with_subscription(pair).await?;
+
+        // Wait for first price to arrive (max 10 seconds)
+        for _ in 0..100 {
+            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+            if self.latest_prices.read().contains_key(pair) {
+                return Ok(());
+            }
+        }
+
+        // Connection might still be establishing; caller can check get_latest_price()
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "get_latest_price", skip(self), fields(exchange = %self.id, pair = %pair))]
+    async fn get_latest_price(&self, pair: &str) -> Result<Price> {
+        self.latest_prices
+            .read()
+            .get(pair)
+            .cloned()
+            .ok_or_else(|| ArbitrageError::ExchangeError {
+                exchange: self.id,
+                message: format!("No price data available for {}", pair),
+                code: None,
+            })
+    }
+
+    /// Place a spot market or limit order, sized in the base asset
+    ///
+    /// The placement response only carries the order id, so the order is looked
+    /// up once for its fills; if that lookup fails it is reported `Pending`.
+    #[tracing::instrument(name = "place_order", skip(self, order), fields(
+        exchange = %self.id,
+        pair = %order.pair,
+        side = ?order.side,
+        order_type = ?order.order_type,
+        quantity = %order.quantity
+    ))]
+    async fn place_order(&mut self, order: Order) -> Result<OrderResult> {
+        let (base, quote) = split_pair(&order.pair).ok_or_else(|| ArbitrageError::ExchangeError {
+            exchange: self.id,
+            message: format!("Invalid pair: {}", order.pair),
+            code: None,
+        })?;
+        let (side, spent) = match order.side {
+            OrderSide::Buy => ("Buy", quote),
+            OrderSide::Sell => ("Sell", base),
+        };
+        let (order_type, price) = match order.order_type {
+            OrderType::Market => ("Market", None),
+            OrderType::Limit { price } => ("Limit", Some(price.normalize().to_string())),
+        };
+        let request = BybitOrderRequest {
+            category: "spot".to_string(),
+            symbol: BybitParser::pair_to_symbol(&order.pair),
+            side: side.to_string(),
+            order_type: order_type.to_string(),
+            qty: order.quantity.normalize().to_string(),
+            price,
+            market_unit: (order.order_type == OrderType::Market).then(|| "baseCoin".to_string()),
+        };
+        let body = serde_json::to_string(&request).map_err(|e| ArbitrageError::ParseError {
+            message: format!("Failed to serialize order: {}", e),
+            input: None,
+        })?;
+
+        let ack: BybitOrderAck = self
+            .request(
+                Method::POST,
+                crate::constants::api::BYBIT_CREATE_ORDER_PATH,
+                &body,
+                "order",
+                Some(spent),
+            )
+            .await?;
+
+        match self.find_order(&ack.order_id).await {
+            Ok(result) => Ok(result),
+            Err(e) => {
+                warn!(order_id = %ack.order_id, error = %e, "Order placed but lookup failed");
+                Ok(OrderResult {
+                    order_id: ack.order_id,
+                    status: OrderStatus::Pending,
+                    filled_quantity: Decimal::ZERO,
+                    average_price: None,
+                    fee: Decimal::ZERO,
+                    fee_asset: if order.side == OrderSide::Buy { base } else { quote }.to_string(),
+                    timestamp: Utc::now(),
+                })
+            }
+        }
+    }
+
+    #[tracing::instrument(name = "get_balance", skip(self), fields(exchange = %self.id, asset = %asset))]
+    async fn get_balance(&self, asset: &str) -> Result<Decimal> {
+        let balances = self.get_balances(&[asset]).await?;
+        Ok(balances.get(asset).map(|b| b.available).unwrap_or_default())
+    }
+
+    /// Balances of the unified trading account; assets without one are omitted
+    async fn get_balances(&self, assets: &[&str]) -> Result<HashMap<String, Balance>> {
+        let query = format!("accountType=UNIFIED&coin={}", assets.join(","));
+        let wallets: BybitList<BybitWallet> = self
+            .request(
+                Method::GET,
+                crate::constants::api::BYBIT_BALANCE_PATH,
+                &query,
+                "balance",
+                None,
+            )
+            .await?;
+
+        Ok(wallets
+            .list
+            .iter()
+            .flat_map(|wallet| &wallet.coin)
+            .filter(|balance| assets.contains(&balance.coin.as_str()))
+            .map(|balance| (balance.coin.clone(), Balance::from(balance)))
+            .collect())
+    }
+
+    async fn get_order(&self, _pair: &str, order_id: &str) -> Result<OrderResult> {
+        self.find_order(order_id).await
+    }
+
+    async fn get_open_orders(&self, pair: &str) -> Result<Vec<OrderResult>> {
+        // openOnly=0 selects active orders
+        let query = format!(
+            "category=spot&symbol={}&openOnly=0",
+            BybitParser::pair_to_symbol(pair)
+        );
+        let orders: BybitList<BybitOrder> = self
+            .request(
+                Method::GET,
+                crate::constants::api::BYBIT_REALTIME_ORDERS_PATH,
+                &query,
+                "orders",
+                None,
+            )
+            .await?;
+        Ok(orders.list.into_iter().map(OrderResult::from).collect())
+    }
+
+    fn id(&self) -> ExchangeId {
+        self.id
+    }
+
+    fn is_connected(&self) -> bool {
+        self.connection_state().is_live()
+    }
+
+    fn connection_state(&self) -> ConnectionState {
+        // An aborted or panicked manager task can't publish its own exit
+        let running = self
+            .ws_manager_handle
+            .as_ref()
+            .is_some_and(|handle| !handle.is_finished());
+        let state = self.state_tx.borrow().clone();
+        if running || matches!(state, ConnectionState::Failed { .. }) {
+            state
+        } else {
+            ConnectionState::Disconnected
+        }
+    }
+
+    fn watch_connection_state(&self) -> Option<watch::Receiver<ConnectionState>> {
+        Some(self.state_tx.subscribe())
+    }
+
+    fn price_updates(&self) -> Option<broadcast::Receiver<Price>> {
+        Some(self.prices_tx.subscribe())
+    }
+
+    fn clock(&self) -> Option<ClockSync> {
+        self.clock.as_ref().map(|c| c.clock().clone())
+    }
+
+    async fn disconnect(&mut self) -> Result<()> {
+        if let Some(handle) = self.ws_manager_handle.take() {
+            handle.abort();
+        }
+        self.state_tx.send_replace(ConnectionState::Disconnected);
+        self.latest_prices.write().clear();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exchange() -> BybitExchange {
+        BybitExchange::new(BybitConfig {
+            api_key: String::new(),
+            api_secret: String::new(),
+            testnet: false,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_subscribe_message() {
+        assert_eq!(
+            BybitExchange::subscribe_message("sol/usdc"),
+            r#"{"args":["tickers.SOLUSDC","orderbook.1.SOLUSDC"],"op":"subscribe"}"#
+        );
+    }
+
+    #[test]
+    fn test_classify_errors() {
+        let exchange = exchange();
+        assert!(matches!(
+            exchange.classify(10004, "error sign!", None),
+            ArbitrageError::AuthenticationError { .. }
+        ));
+        assert_eq!(
+            exchange.classify(10006, "Too many visits!", None).retry_after(),
+            Some(std::time::Duration::from_millis(RATE_LIMIT_BACKOFF_MS))
+        );
+        assert!(matches!(
+            exchange.classify(170131, "Insufficient balance.", Some("USDC")),
+            ArbitrageError::InsufficientBalance { .. }
+        ));
+        assert!(matches!(
+            exchange.classify(170130, "Data sent for parameter is out of range.", None),
+            ArbitrageError::ExchangeError { code: Some(170130), .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_rest_requires_credentials() {
+        assert!(exchange().get_balance("USDC").await.is_err());
+    }
+}
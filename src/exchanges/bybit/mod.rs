@@ -0,0 +1,13 @@
+//! Bybit Exchange Integration
+//!
+//! Implements the Exchange trait for Bybit spot, providing v5 WebSocket price
+//! feeds and signed v5 REST API for trading operations.
+
+pub mod auth;
+pub mod exchange;
+pub mod parser;
+pub mod types;
+
+pub use auth::BybitAuth;
+pub use exchange::BybitExchange;
+pub use parser::BybitParser;
@@ -0,0 +1,210 @@
+//! Bybit v5 public spot WebSocket message parser
+//!
+//! Based on: https://bybit-exchange.github.io/docs/v5/websocket/public/ticker
+
+use crate::error::{ArbitrageError, Result};
+use crate::exchanges::symbols::SymbolFormat;
+use crate::exchanges::{ExchangeId, Price};
+use crate::websocket::MessageParser;
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use rust_decimal::Decimal;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Latest ticker and top of book for one symbol
+#[derive(Debug, Default)]
+struct Quote {
+    bid: Option<Decimal>,
+    ask: Option<Decimal>,
+    last: Option<Decimal>,
+    volume: Decimal,
+}
+
+/// Parser for Bybit `tickers` and `orderbook.1` messages
+///
+/// # Business Logic
+///
+/// Spot `tickers` carry the last trade and 24h volume but no bid or ask, so
+/// the top of book comes from the level-1 `orderbook` topic. Both update the
+/// same per-symbol quote; a `Price` is emitted once the quote has a last
+/// trade and both sides of the book. Clones share the quotes.
+#[derive(Debug, Clone, Default)]
+pub struct BybitParser {
+    quotes: Arc<Mutex<HashMap<String, Quote>>>,
+}
+
+impl BybitParser {
+    /// Create a new Bybit parser
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Convert trading pair to Bybit symbol format
+    ///
+    /// Example: "SOL/USDC" -> "SOLUSDC"
+    pub fn pair_to_symbol(pair: &str) -> String {
+        SymbolFormat::Joined.to_venue(pair)
+    }
+
+    /// Convert Bybit symbol format to trading pair
+    ///
+    /// Example: "SOLUSDC" -> "SOL/USDC"; unknown quote assets are kept as-is
+    pub fn symbol_to_pair(symbol: &str) -> String {
+        SymbolFormat::Joined
+            .to_pair(symbol)
+            .unwrap_or_else(|| symbol.to_string())
+    }
+}
+
+impl MessageParser for BybitParser {
+    type Output = Price;
+
+    fn parse(&self, message: &str) -> Result<Self::Output> {
+        let received_at = Utc::now();
+        let value: Value = serde_json::from_str(message).map_err(|e| ArbitrageError::ParseError {
+            message: format!("Invalid JSON: {}", e),
+            input: Some(message.to_string()),
+        })?;
+
+        // Rejected requests: {"success":false,"ret_msg":"error:handler not found","op":"subscribe"}
+        if value["success"].as_bool() == Some(false) {
+            return Err(ArbitrageError::ExchangeError {
+                exchange: ExchangeId::BYBIT,
+                message: format!(
+                    "Bybit WebSocket {} failed: {}",
+                    value["op"].as_str().unwrap_or("request"),
+                    value["ret_msg"].as_str().unwrap_or("Unknown error")
+                ),
+                code: None,
+            });
+        }
+
+        // {"topic":"tickers.SOLUSDC","ts":1673853746003,"type":"snapshot","data":{...}}
+        let topic = value["topic"]
+            .as_str()
+            .ok_or_else(|| ArbitrageError::ParseError {
+                message: "Missing topic".to_string(),
+                input: Some(message.to_string()),
+            })?;
+        let data = &value["data"];
+
+        let (symbol, is_ticker) = if let Some(symbol) = topic.strip_prefix("tickers.") {
+            (symbol, true)
+        } else if let Some(symbol) = topic
+            .strip_prefix("orderbook.")
+            .and_then(|rest| rest.split_once('.'))
+            .map(|(_, symbol)| symbol)
+        {
+            (symbol, false)
+        } else {
+            return Err(ArbitrageError::ParseError {
+                message: format!("Not a tickers or orderbook message, got topic: {}", topic),
+                input: Some(message.to_string()),
+            });
+        };
+        let pair = Self::symbol_to_pair(symbol);
+
+        let mut quotes = self.quotes.lock();
+        let quote = quotes.entry(pair.clone()).or_default();
+        if is_ticker {
+            quote.last = Some(decimal(&data["lastPrice"], "lastPrice", message)?);
+            quote.volume = decimal(&data["volume24h"], "volume24h", message)?;
+        } else {
+            // Level 1 pushes are snapshots: [[price, size]] per side, empty if the side is empty
+            if let Some(bid) = data["b"][0].get(0) {
+                quote.bid = Some(decimal(bid, "b", message)?);
+            }
+            if let Some(ask) = data["a"][0].get(0) {
+                quote.ask = Some(decimal(ask, "a", message)?);
+            }
+        }
+
+        let (Some(bid), Some(ask), Some(last)) = (quote.bid, quote.ask, quote.last) else {
+            return Err(ArbitrageError::ParseError {
+                message: format!("Waiting for both ticker and book for {}", pair),
+                input: None,
+            });
+        };
+
+        Ok(Price {
+            pair,
+            bid,
+            ask,
+            last,
+            volume_24h: quote.volume,
+            timestamp: value["ts"]
+                .as_i64()
+                .and_then(DateTime::from_timestamp_millis)
+                .unwrap_or(received_at),
+            // Level-1 update ids restart on every snapshot, so they can't be gap-checked
+            sequence: None,
+            received_at,
+        })
+    }
+
+    /// Pong replies and subscription acks: `{"success":true,"ret_msg":"pong","op":"ping"}`
+    fn is_control(&self, message: &str) -> bool {
+        message.contains("\"op\":") && message.contains("\"success\":true")
+    }
+}
+
+fn decimal(value: &Value, field: &str, message: &str) -> Result<Decimal> {
+    value
+        .as_str()
+        .and_then(|s| Decimal::from_str_exact(s).ok())
+        .ok_or_else(|| ArbitrageError::ParseError {
+            message: format!("Invalid {}", field),
+            input: Some(message.to_string()),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TICKER: &str = r#"{"topic":"tickers.SOLUSDC","ts":1700000000123,"type":"snapshot","cs":2588407389,"data":{"symbol":"SOLUSDC","lastPrice":"150.12","highPrice24h":"152","lowPrice24h":"147","prevPrice24h":"148","volume24h":"10000.5","turnover24h":"1500000","price24hPcnt":"0.0143","usdIndexPrice":"150.1"}}"#;
+    const BOOK: &str = r#"{"topic":"orderbook.1.SOLUSDC","type":"snapshot","ts":1700000000500,"data":{"s":"SOLUSDC","b":[["150.1","4"]],"a":[["150.14","3"]],"u":18521288,"seq":7961638724},"cts":1700000000498}"#;
+
+    #[test]
+    fn test_ticker_and_book_combine() {
+        let parser = BybitParser::new();
+
+        // No bid or ask yet
+        assert!(parser.parse(TICKER).is_err());
+
+        let price = parser.parse(BOOK).unwrap();
+        assert_eq!(price.pair, "SOL/USDC");
+        assert_eq!(price.bid, Decimal::new(1501, 1));
+        assert_eq!(price.ask, Decimal::new(15014, 2));
+        assert_eq!(price.last, Decimal::new(15012, 2));
+        assert_eq!(price.volume_24h, Decimal::new(100005, 1));
+        assert_eq!(price.timestamp.timestamp_millis(), 1_700_000_000_500);
+    }
+
+    #[test]
+    fn test_control_and_error_messages() {
+        let parser = BybitParser::new();
+        assert!(parser.is_control(
+            r#"{"success":true,"ret_msg":"pong","conn_id":"0970e817-426e-429a-a679-ff7f55e0b16a","op":"ping"}"#
+        ));
+        assert!(parser.is_control(
+            r#"{"success":true,"ret_msg":"subscribe","conn_id":"2324d924-aa4d-45b0-a858-7b8be29ab52b","req_id":"10001","op":"subscribe"}"#
+        ));
+        assert!(!parser.is_control(TICKER));
+
+        let rejected = r#"{"success":false,"ret_msg":"error:handler not found","conn_id":"2324d924","op":"subscribe"}"#;
+        assert!(!parser.is_control(rejected));
+        assert!(matches!(
+            parser.parse(rejected),
+            Err(ArbitrageError::ExchangeError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_symbol_conversion() {
+        assert_eq!(BybitParser::pair_to_symbol("sol/usdc"), "SOLUSDC");
+        assert_eq!(BybitParser::symbol_to_pair("BTCUSDT"), "BTC/USDT");
+    }
+}
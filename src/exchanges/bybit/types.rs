@@ -0,0 +1,174 @@
+//! Bybit-specific REST response types
+
+use crate::exchanges::{Balance, OrderResult, OrderStatus};
+use crate::exchanges::symbols::SymbolFormat;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Envelope of every v5 response: `ret_code` is 0 on success
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BybitResponse<T> {
+    pub ret_code: i64,
+    #[serde(default)]
+    pub ret_msg: String,
+    pub result: Option<T>,
+}
+
+/// `result` of list endpoints
+#[derive(Debug, Deserialize)]
+pub struct BybitList<T> {
+    #[serde(default = "Vec::new")]
+    pub list: Vec<T>,
+}
+
+/// One account in the `wallet-balance` result
+#[derive(Debug, Deserialize)]
+pub struct BybitWallet {
+    #[serde(default)]
+    pub coin: Vec<BybitBalance>,
+}
+
+/// One coin in a unified trading account
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BybitBalance {
+    pub coin: String,
+    #[serde(deserialize_with = "decimal_or_zero")]
+    pub wallet_balance: Decimal,
+    /// Reserved by open spot orders
+    #[serde(default, deserialize_with = "decimal_or_zero")]
+    pub locked: Decimal,
+}
+
+impl From<&BybitBalance> for Balance {
+    fn from(balance: &BybitBalance) -> Self {
+        Balance {
+            available: balance.wallet_balance - balance.locked,
+            hold: balance.locked,
+        }
+    }
+}
+
+/// `order/create` request body
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BybitOrderRequest {
+    /// Always `spot`
+    pub category: String,
+    pub symbol: String,
+    /// `Buy` or `Sell`
+    pub side: String,
+    /// `Market` or `Limit`
+    pub order_type: String,
+    pub qty: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price: Option<String>,
+    /// Size market buys in the base coin (default is quote)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub market_unit: Option<String>,
+}
+
+/// `result` of `order/create`
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BybitOrderAck {
+    pub order_id: String,
+}
+
+/// An order as returned by `order/realtime` and `order/history`
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BybitOrder {
+    pub order_id: String,
+    /// Symbol, e.g. `SOLUSDC`
+    pub symbol: String,
+    /// `Buy` or `Sell`
+    pub side: String,
+    /// `New`, `PartiallyFilled`, `Filled`, `Cancelled`, `PartiallyFilledCanceled`, `Rejected`, ...
+    pub order_status: String,
+    #[serde(deserialize_with = "decimal_or_zero")]
+    pub cum_exec_qty: Decimal,
+    /// Empty or zero until the first fill
+    #[serde(default, deserialize_with = "decimal_or_zero")]
+    pub avg_price: Decimal,
+    #[serde(default, deserialize_with = "decimal_or_zero")]
+    pub cum_exec_fee: Decimal,
+    /// Last update, Unix milliseconds
+    #[serde(default)]
+    pub updated_time: String,
+}
+
+impl From<BybitOrder> for OrderResult {
+    fn from(order: BybitOrder) -> Self {
+        let status = match order.order_status.as_str() {
+            "Filled" => OrderStatus::Filled,
+            "PartiallyFilled" => OrderStatus::PartiallyFilled,
+            "New" | "Created" | "Untriggered" => OrderStatus::Pending,
+            "Cancelled" | "PartiallyFilledCanceled" | "Deactivated" => OrderStatus::Cancelled,
+            _ => OrderStatus::Failed,
+        };
+        let timestamp = order
+            .updated_time
+            .parse::<i64>()
+            .ok()
+            .and_then(DateTime::from_timestamp_millis)
+            .unwrap_or_else(Utc::now);
+
+        // Spot fees come out of what was received: base for buys, quote for sells
+        let pair = SymbolFormat::Joined.to_pair(&order.symbol);
+        let fee_asset = pair
+            .as_deref()
+            .and_then(|pair| pair.split_once('/'))
+            .map(|(base, quote)| if order.side == "Buy" { base } else { quote })
+            .unwrap_or_default()
+            .to_string();
+
+        OrderResult {
+            order_id: order.order_id,
+            status,
+            filled_quantity: order.cum_exec_qty,
+            average_price: (!order.avg_price.is_zero()).then_some(order.avg_price),
+            fee: order.cum_exec_fee,
+            fee_asset,
+            timestamp,
+        }
+    }
+}
+
+/// Bybit sends `""` for amounts that don't apply (e.g. `avgPrice` before a fill)
+fn decimal_or_zero<'de, D>(deserializer: D) -> Result<Decimal, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    if s.is_empty() {
+        return Ok(Decimal::ZERO);
+    }
+    Decimal::from_str_exact(&s)
+        .or_else(|_| Decimal::from_scientific(&s))
+        .map_err(serde::de::Error::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_order_conversion() {
+        let order: BybitOrder = serde_json::from_str(
+            r#"{"orderId":"1321003749386327552","orderLinkId":"","symbol":"SOLUSDC","side":"Sell",
+                "orderType":"Market","orderStatus":"Filled","qty":"2","cumExecQty":"2",
+                "avgPrice":"150.5","cumExecFee":"0.301","createdTime":"1700000000000","updatedTime":"1700000000250"}"#,
+        )
+        .unwrap();
+
+        let result = OrderResult::from(order);
+        assert_eq!(result.status, OrderStatus::Filled);
+        assert_eq!(result.average_price, Some(Decimal::new(1505, 1)));
+        assert_eq!(result.fee, Decimal::new(301, 3));
+        assert_eq!(result.fee_asset, "USDC");
+        assert_eq!(result.timestamp.timestamp_millis(), 1_700_000_000_250);
+    }
+}
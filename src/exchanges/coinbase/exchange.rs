@@ -2,21 +2,25 @@
 //!
 //! Connects to Coinbase Advanced Trade WebSocket to receive real-time price updates.
 
+use crate::clock::{ClockSync, ClockSyncConfig, CoinbaseTimeSource, SyncedClock};
 use crate::config::CoinbaseConfig;
 use crate::error::{ArbitrageError, Result};
-use crate::exchanges::{Exchange, Price};
-use crate::logger::{debug, error, info, warn};
-use crate::websocket::MessageParser;
-use futures_util::{SinkExt, StreamExt};
+use crate::exchanges::{Balance, Exchange, ExchangeId, OrderResult, PRICE_UPDATES_CAPACITY, Price};
+use crate::logger::{debug, error, warn};
+use crate::state::SequencePolicy;
+use crate::websocket::{CircuitBreaker, ConnectionState, ReconnectionStrategy, WebSocketManager};
 use parking_lot::RwLock;
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::broadcast;
-use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tokio::sync::{broadcast, watch};
 
 use super::parser::CoinbaseParser;
 use super::rest::CoinbaseRestClient;
 
+/// Reconnect if no data arrives for this long.
+/// Coinbase only pushes ticker updates on trades, so allow longer quiet periods.
+const FEED_INACTIVITY_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(60);
+
 /// Coinbase exchange implementation using WebSocket for price feeds
 ///
 /// # Business Logic
@@ -26,7 +30,7 @@ use super::rest::CoinbaseRestClient;
 ///
 /// REST API client is available for order placement and balance queries.
 pub struct CoinbaseExchange {
-    name: String,
+    id: ExchangeId,
     #[allow(dead_code)] // Kept for future use (sandbox flag)
     config: CoinbaseConfig,
     /// WebSocket manager (moved into spawned task on connect)
@@ -35,10 +39,16 @@ pub struct CoinbaseExchange {
     price_rx: Option<broadcast::Receiver<Price>>,
     /// In-memory store of latest prices by trading pair
     latest_prices: Arc<RwLock<HashMap<String, Price>>>,
+    /// Re-broadcasts every parsed quote to `price_updates()` receivers
+    prices_tx: broadcast::Sender<Price>,
+    /// Connection state, shared with each manager so receivers survive resubscribes
+    state_tx: watch::Sender<ConnectionState>,
     /// Base WebSocket URL
     base_url: String,
     /// REST API client for trading operations (optional, only if API credentials provided)
     rest_client: Option<CoinbaseRestClient>,
+    /// Coinbase clock for JWT `nbf`/`exp`, synced while credentials are configured
+    clock: Option<SyncedClock>,
 }
 
 impl CoinbaseExchange {
@@ -51,165 +61,127 @@ impl CoinbaseExchange {
         let base_url = crate::constants::websocket::COINBASE_EXCHANGE.to_string();
 
         // Initialize REST client if API credentials are provided
-        // First try config, then fall back to environment variables
-        let (api_key, api_secret) = if !config.api_key.is_empty() && !config.api_secret.is_empty() {
-            (config.api_key.clone(), config.api_secret.clone())
-        } else {
-            // Try to load from environment variables
-            let _ = dotenvy::dotenv();
-            let env_key = std::env::var("COINBASE_API_KEY")
-                .or_else(|_| std::env::var("COINBASE_API_KEY_ID"))
-                .unwrap_or_default();
-            let env_secret = std::env::var("COINBASE_API_SECRET").unwrap_or_default();
-            (env_key, env_secret)
-        };
-
-        let rest_client = if !api_key.is_empty() && !api_secret.is_empty() {
-            Some(CoinbaseRestClient::new(
-                api_key,
-                api_secret,
+        let (rest_client, clock) = if !config.api_key.is_empty() && !config.api_secret.is_empty() {
+            let rest_client = CoinbaseRestClient::new(
+                config.api_key.clone(),
+                config.api_secret.clone(),
                 config.sandbox,
-            )?)
+            )?;
+            let clock = SyncedClock::start(
+                ClockSync::new(crate::constants::exchange::COINBASE, ClockSyncConfig::default()),
+                Arc::new(CoinbaseTimeSource::new(config.sandbox)),
+            );
+            (Some(rest_client.with_clock(clock.clock().clone())), Some(clock))
         } else {
-            None
+            (None, None)
         };
 
         Ok(Self {
-            name: crate::constants::exchange::COINBASE.to_string(),
+            id: ExchangeId::COINBASE,
             config,
             ws_manager_handle: None,
             price_rx: None,
             latest_prices: Arc::new(RwLock::new(HashMap::new())),
+            prices_tx: broadcast::channel(PRICE_UPDATES_CAPACITY).0,
+            state_tx: watch::Sender::new(ConnectionState::Disconnected),
             base_url,
             rest_client,
+            clock,
         })
     }
 
+    /// Report as `id` instead, e.g. `coinbase:hedge` for a second account
+    pub fn with_id(mut self, id: ExchangeId) -> Self {
+        self.id = id;
+        self
+    }
+
     /// Connect to WebSocket with a specific ticker subscription
     ///
     /// Coinbase requires sending a subscription message after connection:
     /// {"type":"subscribe","product_ids":["SOL-USDC"],"channels":["ticker"]}
-    #[tracing::instrument(name = "connect_with_subscription", skip(self), fields(exchange = %self.name, pair = %pair))]
+    ///
+    /// The message is registered as an on-connect message on the `WebSocketManager`,
+    /// so it is re-sent after every reconnect.
+    #[tracing::instrument(name = "connect_with_subscription", skip(self), fields(exchange = %self.id, pair = %pair))]
     async fn connect_with_subscription(&mut self, pair: &str) -> Result<()> {
-        let product_id = CoinbaseParser::pair_to_product_id(pair);
+        let subscribe_text = Self::subscribe_message(pair)?;
+        debug!(subscription = %subscribe_text, "Registering subscription message");
 
-        // Connect to base WebSocket URL
-        let url = self.base_url.clone();
-        info!(url = %url, "Connecting to Coinbase WebSocket");
+        let parser = CoinbaseParser::new();
+        // Jitter keeps feeds that dropped together from reconnecting in lockstep
+        let reconnect_strategy = ReconnectionStrategy::exponential_backoff()
+            .with_jitter()
+            .with_circuit_breaker(CircuitBreaker::default());
+
+        // Create WebSocket manager that subscribes on every (re)connect
+        let (manager, price_rx) =
+            WebSocketManager::new(self.id, self.base_url.clone(), parser, reconnect_strategy);
+        let mut manager = manager
+            .with_on_connect_messages(vec![subscribe_text])
+            .with_inactivity_timeout(FEED_INACTIVITY_TIMEOUT)
+            .with_state_sender(self.state_tx.clone());
+
+        // Store receiver
+        self.price_rx = Some(price_rx);
 
-        let (ws_stream, response) = connect_async(&url).await.map_err(|e| {
-            error!(url = %url, error = %e, "Connection failed");
-            ArbitrageError::NetworkError {
-                message: format!("Failed to connect to {}: {}", url, e),
-                retry_after: None,
+        // Spawn background task to run WebSocket manager
+        let handle = tokio::spawn(async move {
+            if let Err(e) = manager.run().await {
+                error!(error = %e, "Coinbase WebSocket manager error");
             }
-        })?;
-
-        info!(status = %response.status(), "Connected to Coinbase WebSocket");
-
-        // Split into read and write halves
-        let (mut write, mut read) = ws_stream.split();
-
-        // Send subscription message
-        // Classic Coinbase Exchange WebSocket format (public, no auth required)
-        // See: https://docs.cdp.coinbase.com/exchange/docs/websocket-feed
-        // Format: {"type": "subscribe", "product_ids": ["BTC-USD"], "channels": ["ticker"]}
-        let subscribe_msg = serde_json::json!({
-            "type": "subscribe",
-            "product_ids": [product_id],
-            "channels": ["ticker"]
         });
 
-        let subscribe_text =
-            serde_json::to_string(&subscribe_msg).map_err(|e| ArbitrageError::ParseError {
-                message: format!("Failed to serialize subscription message: {}", e),
-                input: None,
-            })?;
-
-        debug!(subscription = %subscribe_text, "Sending subscription message");
-        write
-            .send(Message::Text(subscribe_text))
-            .await
-            .map_err(|e| ArbitrageError::NetworkError {
-                message: format!("Failed to send subscription message: {}", e),
-                retry_after: None,
-            })?;
-
-        // Create parser
-        let parser = CoinbaseParser::new();
-
-        // Create broadcast channel for price updates
-        let (message_tx, price_rx) = broadcast::channel(100);
-        self.price_rx = Some(price_rx);
+        self.ws_manager_handle = Some(handle);
 
-        // Spawn background task to handle WebSocket messages
-        let prices = self.latest_prices.clone();
-        let handle = tokio::spawn(async move {
-            let mut ping_interval = tokio::time::interval(std::time::Duration::from_secs(30));
-
-            loop {
-                tokio::select! {
-                    // Handle incoming messages
-                    message_result = read.next() => {
-                        match message_result {
-                            Some(Ok(Message::Text(text))) => {
-                                // Parse message using the parser
-                                match parser.parse(&text) {
-                                    Ok(parsed) => {
-                                        // Broadcast to subscribers
-                                        let _ = message_tx.send(parsed.clone());
-                                        // Store in cache (silently - no verbose logging)
-                                        prices.write().insert(parsed.pair.clone(), parsed);
-                                    }
-                                    Err(e) => {
-                                        // Silently ignore subscription confirmations and other non-ticker messages
-                                        let error_str = e.to_string();
-                                        if !error_str.contains("Subscription confirmation") {
-                                            // Only log actual errors
-                                            warn!(error = %e, "Parse error");
-                                        }
-                                    }
-                                }
-                            }
-                            Some(Ok(Message::Ping(data))) => {
-                                // Respond to server ping with pong
-                                if let Err(e) = write.send(Message::Pong(data)).await {
-                                    error!(error = %e, "Failed to send pong");
-                                    break;
-                                }
-                            }
-                            Some(Ok(Message::Close(_))) => {
-                                info!("Server closed connection");
-                                break;
-                            }
-                            Some(Err(e)) => {
-                                error!(error = %e, "WebSocket error");
-                                break;
-                            }
-                            None => {
-                                info!("Stream ended");
-                                break;
-                            }
-                            _ => {
-                                // Other message types - ignore
-                            }
+        // Spawn background task to update latest prices from WebSocket stream
+        if let Some(mut rx) = self.price_rx.take() {
+            let prices = self.latest_prices.clone();
+            let prices_tx = self.prices_tx.clone();
+            tokio::spawn(async move {
+                loop {
+                    match rx.recv().await {
+                        Ok(price) => {
+                            // Silently cache price updates (no verbose logging)
+                            prices.write().insert(price.pair.clone(), price.clone());
+                            // Nobody listening is fine; pollers read the cache
+                            let _ = prices_tx.send(price);
                         }
-                    }
-                    // Send periodic ping to keep connection alive
-                    _ = ping_interval.tick() => {
-                        if let Err(e) = write.send(Message::Ping(vec![])).await {
-                            error!(error = %e, "Failed to send ping");
+                        Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped)) => {
+                            warn!(skipped = skipped, "Lagged messages");
+                            continue;
+                        }
+                        Err(tokio::sync::broadcast::error::RecvError::Closed) => {
+                            error!("Broadcast channel closed");
                             break;
                         }
                     }
                 }
-            }
-        });
-
-        self.ws_manager_handle = Some(handle);
+            });
+        }
 
         Ok(())
     }
+
+    /// Build the ticker subscription message for a trading pair
+    ///
+    /// Classic Coinbase Exchange WebSocket format (public, no auth required)
+    /// See: https://docs.cdp.coinbase.com/exchange/docs/websocket-feed
+    /// Format: {"type": "subscribe", "product_ids": ["BTC-USD"], "channels": ["ticker"]}
+    pub fn subscribe_message(pair: &str) -> Result<String> {
+        let product_id = CoinbaseParser::pair_to_product_id(pair);
+
+        let subscribe_msg = serde_json::json!({
+            "type": "subscribe",
+            "product_ids": [product_id],
+            "channels": ["ticker"]
+        });
+
+        serde_json::to_string(&subscribe_msg).map_err(|e| ArbitrageError::ParseError {
+            message: format!("Failed to serialize subscription message: {}", e),
+            input: None,
+        })
+    }
 }
 
 #[async_trait::async_trait]
@@ -221,7 +193,7 @@ impl Exchange for CoinbaseExchange {
         Ok(())
     }
 
-    #[tracing::instrument(name = "subscribe_ticker", skip(self), fields(exchange = %self.name, pair = %pair))]
+    #[tracing::instrument(name = "subscribe_ticker", skip(self), fields(exchange = %self.id, pair = %pair))]
     async fn subscribe_ticker(&mut self, pair: &str) -> Result<()> {
         // Disconnect existing connection if any
         self.disconnect().await.ok();
@@ -250,21 +222,21 @@ impl Exchange for CoinbaseExchange {
         Ok(())
     }
 
-    #[tracing::instrument(name = "get_latest_price", skip(self), fields(exchange = %self.name, pair = %pair))]
+    #[tracing::instrument(name = "get_latest_price", skip(self), fields(exchange = %self.id, pair = %pair))]
     async fn get_latest_price(&self, pair: &str) -> Result<Price> {
         let prices = self.latest_prices.read();
         prices
             .get(pair)
             .cloned()
             .ok_or_else(|| ArbitrageError::ExchangeError {
-                exchange: self.name.clone(),
+                exchange: self.id,
                 message: format!("No price data available for {}", pair),
                 code: None,
             })
     }
 
     #[tracing::instrument(name = "place_order", skip(self, order), fields(
-        exchange = %self.name,
+        exchange = %self.id,
         pair = %order.pair,
         side = ?order.side,
         order_type = ?order.order_type,
@@ -277,32 +249,98 @@ impl Exchange for CoinbaseExchange {
         match &self.rest_client {
             Some(client) => client.place_market_order(order).await,
             None => Err(ArbitrageError::ExchangeError {
-                exchange: self.name.clone(),
+                exchange: self.id,
                 message: "REST API not available - API credentials required".to_string(),
                 code: None,
             }),
         }
     }
 
-    #[tracing::instrument(name = "get_balance", skip(self), fields(exchange = %self.name, asset = %asset))]
+    #[tracing::instrument(name = "get_balance", skip(self), fields(exchange = %self.id, asset = %asset))]
     async fn get_balance(&self, asset: &str) -> Result<rust_decimal::Decimal> {
         match &self.rest_client {
             Some(client) => client.get_balance(asset).await,
             None => Err(ArbitrageError::ExchangeError {
-                exchange: self.name.clone(),
+                exchange: self.id,
+                message: "REST API not available - API credentials required".to_string(),
+                code: None,
+            }),
+        }
+    }
+
+    async fn get_balances(&self, assets: &[&str]) -> Result<HashMap<String, Balance>> {
+        match &self.rest_client {
+            Some(client) => client.get_balances(assets).await,
+            None => Err(ArbitrageError::ExchangeError {
+                exchange: self.id,
+                message: "REST API not available - API credentials required".to_string(),
+                code: None,
+            }),
+        }
+    }
+
+    async fn get_order(&self, _pair: &str, order_id: &str) -> Result<OrderResult> {
+        match &self.rest_client {
+            Some(client) => client.get_order(order_id).await,
+            None => Err(ArbitrageError::ExchangeError {
+                exchange: self.id,
+                message: "REST API not available - API credentials required".to_string(),
+                code: None,
+            }),
+        }
+    }
+
+    async fn get_open_orders(&self, pair: &str) -> Result<Vec<OrderResult>> {
+        match &self.rest_client {
+            Some(client) => client.get_open_orders(pair).await,
+            None => Err(ArbitrageError::ExchangeError {
+                exchange: self.id,
                 message: "REST API not available - API credentials required".to_string(),
                 code: None,
             }),
         }
     }
 
-    fn name(&self) -> &str {
-        &self.name
+    fn id(&self) -> ExchangeId {
+        self.id
     }
 
     fn is_connected(&self) -> bool {
-        // Check if we have recent price data (indicates connection is working)
-        !self.latest_prices.read().is_empty()
+        self.connection_state().is_live()
+    }
+
+    fn connection_state(&self) -> ConnectionState {
+        // An aborted or panicked manager task can't publish its own exit
+        let running = self
+            .ws_manager_handle
+            .as_ref()
+            .is_some_and(|handle| !handle.is_finished());
+        let state = self.state_tx.borrow().clone();
+        if running || matches!(state, ConnectionState::Failed { .. }) {
+            state
+        } else {
+            ConnectionState::Disconnected
+        }
+    }
+
+    fn watch_connection_state(&self) -> Option<watch::Receiver<ConnectionState>> {
+        Some(self.state_tx.subscribe())
+    }
+
+    fn price_updates(&self) -> Option<broadcast::Receiver<Price>> {
+        Some(self.prices_tx.subscribe())
+    }
+
+    fn sequence_policy(&self) -> Option<SequencePolicy> {
+        // Only the ticker channel is subscribed, but the sequence counts them all
+        Some(SequencePolicy {
+            contiguous: false,
+            ..Default::default()
+        })
+    }
+
+    fn clock(&self) -> Option<ClockSync> {
+        self.clock.as_ref().map(|c| c.clock().clone())
     }
 
     async fn disconnect(&mut self) -> Result<()> {
@@ -310,6 +348,7 @@ impl Exchange for CoinbaseExchange {
         if let Some(handle) = self.ws_manager_handle.take() {
             handle.abort();
         }
+        self.state_tx.send_replace(ConnectionState::Disconnected);
 
         // Clear price data
         self.latest_prices.write().clear();
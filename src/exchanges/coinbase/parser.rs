@@ -3,7 +3,8 @@
 //! Converts Coinbase Advanced Trade WebSocket ticker messages into our common `Price` type.
 
 use crate::error::{ArbitrageError, Result};
-use crate::exchanges::Price;
+use crate::exchanges::symbols::SymbolFormat;
+use crate::exchanges::{ExchangeId, Price};
 use crate::websocket::MessageParser;
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
@@ -24,14 +25,22 @@ impl CoinbaseParser {
     ///
     /// Example: "SOL-USDC" -> "SOL/USDC"
     pub fn product_id_to_pair(product_id: &str) -> String {
-        product_id.replace("-", "/")
+        SymbolFormat::Dashed
+            .to_pair(product_id)
+            .unwrap_or_else(|| product_id.to_string())
     }
 
     /// Convert trading pair to Coinbase product_id format
     ///
     /// Example: "SOL/USDC" -> "SOL-USDC"
     pub fn pair_to_product_id(pair: &str) -> String {
-        pair.replace("/", "-")
+        SymbolFormat::Dashed.to_venue(pair)
+    }
+}
+
+impl Default for CoinbaseParser {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -39,6 +48,8 @@ impl MessageParser for CoinbaseParser {
     type Output = Price;
 
     fn parse(&self, message: &str) -> Result<Self::Output> {
+        let received_at = Utc::now();
+
         let value: serde_json::Value = serde_json::from_str(message).map_err(|e| {
             ArbitrageError::ParseError {
                 message: format!("Invalid JSON: {}", e),
@@ -50,7 +61,7 @@ impl MessageParser for CoinbaseParser {
         if value["type"].as_str() == Some("error") {
             let error_msg = value["message"].as_str().unwrap_or("Unknown error");
             return Err(ArbitrageError::ExchangeError {
-                exchange: crate::constants::exchange::COINBASE.to_string(),
+                exchange: ExchangeId::COINBASE,
                 message: format!("Coinbase WebSocket error: {}", error_msg),
                 code: None,
             });
@@ -190,7 +201,12 @@ impl MessageParser for CoinbaseParser {
             .or_else(|| value["timestamp"].as_str())
             .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
             .map(|dt| dt.with_timezone(&Utc))
-            .unwrap_or_else(Utc::now);
+            .unwrap_or(received_at);
+
+        // Classic Exchange ticker carries the product-level sequence number.
+        // It is shared with the other channels of the product, so gaps are expected
+        // when subscribed to ticker only.
+        let sequence = ticker["sequence"].as_u64();
 
         Ok(Price {
             pair,
@@ -199,8 +215,22 @@ impl MessageParser for CoinbaseParser {
             last,
             volume_24h: volume,
             timestamp,
+            sequence,
+            received_at,
         })
     }
+
+    /// Subscription acks and heartbeats, in both the Exchange and Advanced Trade formats
+    fn is_control(&self, message: &str) -> bool {
+        [
+            "\"type\":\"subscriptions\"",
+            "\"type\":\"heartbeat\"",
+            "\"channel\":\"subscriptions\"",
+            "\"channel\":\"heartbeats\"",
+        ]
+        .iter()
+        .any(|marker| message.contains(marker))
+    }
 }
 
 #[cfg(test)]
@@ -243,6 +273,26 @@ mod tests {
             price.volume_24h,
             Decimal::from_str_exact("1234567.89").unwrap()
         );
+        assert_eq!(price.sequence, None);
+        assert_eq!(price.timestamp.to_rfc3339(), "2025-10-30T12:00:00+00:00");
+    }
+
+    #[test]
+    fn test_parse_ticker_sequence() {
+        let parser = CoinbaseParser::new();
+
+        let ticker_json = r#"{
+            "type": "ticker",
+            "sequence": 71560891234,
+            "product_id": "SOL-USDC",
+            "price": "143.50",
+            "best_bid": "143.48",
+            "best_ask": "143.52",
+            "time": "2025-10-30T12:00:00.000000Z"
+        }"#;
+
+        let price = parser.parse(ticker_json).unwrap();
+        assert_eq!(price.sequence, Some(71560891234));
     }
 
     #[test]
@@ -258,6 +308,24 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_subscription_ack_is_control() {
+        let parser = CoinbaseParser::new();
+
+        assert!(parser.is_control(
+            r#"{"type":"subscriptions","channels":[{"name":"ticker","product_ids":["SOL-USDC"]}]}"#
+        ));
+        assert!(parser.is_control(
+            r#"{"type":"heartbeat","last_trade_id":20,"product_id":"SOL-USDC","sequence":90,"time":"2025-10-30T12:00:00.000000Z"}"#
+        ));
+        assert!(parser.is_control(
+            r#"{"channel":"subscriptions","client_id":"","timestamp":"2025-10-30T12:00:00Z","sequence_num":1,"events":[{"subscriptions":{"ticker":["SOL-USDC"]}}]}"#
+        ));
+        assert!(!parser.is_control(
+            r#"{"type":"ticker","product_id":"SOL-USDC","price":"143.50","best_bid":"143.48","best_ask":"143.52"}"#
+        ));
+    }
+
     #[test]
     fn test_parse_missing_fields() {
         let parser = CoinbaseParser::new();
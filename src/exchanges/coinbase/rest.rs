@@ -6,10 +6,14 @@
 
 use crate::error::{ArbitrageError, Result};
 use crate::exchanges::coinbase::auth::CoinbaseAuth;
-use crate::exchanges::coinbase::types::{CoinbaseAccountsResponse, MarketIocConfig};
-use crate::exchanges::{Order, OrderResult, OrderSide, OrderType};
+use crate::exchanges::coinbase::types::{
+    CoinbaseAccountsResponse, CoinbaseOrderList, CoinbaseOrderLookup, MarketIocConfig,
+};
+use crate::exchanges::{Balance, ExchangeId, Order, OrderResult, OrderSide, OrderType};
 use reqwest::Client;
+use serde::de::DeserializeOwned;
 use rust_decimal::Decimal;
+use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tokio::time::sleep;
@@ -130,6 +134,18 @@ impl CoinbaseRestClient {
         })
     }
 
+    /// Use an exchange-corrected clock when signing requests
+    pub fn with_clock(mut self, clock: crate::clock::ClockSync) -> Self {
+        self.auth = self.auth.with_clock(clock);
+        self
+    }
+
+    /// Point the client at a different REST base URL (e.g. a local test server)
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
     /// Get account balance for a specific currency
     ///
     /// # Arguments
@@ -139,10 +155,80 @@ impl CoinbaseRestClient {
     /// Available balance as Decimal, or ExchangeError if account not found
     #[tracing::instrument(name = "get_balance", skip(self), fields(asset = %asset))]
     pub async fn get_balance(&self, asset: &str) -> Result<Decimal> {
+        let accounts_response = self.fetch_accounts().await?;
+
+        // Find account matching the requested asset
+        let account = accounts_response
+            .accounts
+            .iter()
+            .find(|acc| acc.currency == asset)
+            .ok_or_else(|| ArbitrageError::ExchangeError {
+                exchange: ExchangeId::COINBASE,
+                message: format!("Account not found for currency: {}", asset),
+                code: None,
+            })?;
+
+        account.available_balance_decimal()
+    }
+
+    /// Get available and on-hold balances for several currencies in one request
+    ///
+    /// Currencies without an account are omitted from the result.
+    #[tracing::instrument(name = "get_balances", skip(self))]
+    pub async fn get_balances(&self, assets: &[&str]) -> Result<HashMap<String, Balance>> {
+        let accounts_response = self.fetch_accounts().await?;
+
+        accounts_response
+            .accounts
+            .iter()
+            .filter(|acc| assets.contains(&acc.currency.as_str()))
+            .map(|acc| Ok((acc.currency.clone(), acc.balance()?)))
+            .collect()
+    }
+
+    /// Look up an order by ID
+    #[tracing::instrument(name = "get_order", skip(self), fields(order_id = %order_id))]
+    pub async fn get_order(&self, order_id: &str) -> Result<OrderResult> {
+        let path = format!(
+            "{}/historical/{}",
+            crate::constants::api::COINBASE_ORDERS_PATH,
+            order_id
+        );
+        let lookup: CoinbaseOrderLookup = self.signed_get(&path, "", "order").await?;
+        lookup.order.try_into()
+    }
+
+    /// List open orders for a pair (e.g. "SOL/USDC")
+    #[tracing::instrument(name = "get_open_orders", skip(self), fields(pair = %pair))]
+    pub async fn get_open_orders(&self, pair: &str) -> Result<Vec<OrderResult>> {
+        let path = format!(
+            "{}/historical/batch",
+            crate::constants::api::COINBASE_ORDERS_PATH
+        );
+        let query = format!(
+            "order_status={}&product_ids={}",
+            crate::constants::order::OPEN,
+            pair.replace("/", "-")
+        );
+        let list: CoinbaseOrderList = self.signed_get(&path, &query, "orders").await?;
+        list.orders.into_iter().map(OrderResult::try_from).collect()
+    }
+
+    /// Fetch all brokerage accounts
+    async fn fetch_accounts(&self) -> Result<CoinbaseAccountsResponse> {
+        self.signed_get(crate::constants::api::COINBASE_ACCOUNTS_PATH, "", "accounts")
+            .await
+    }
+
+    /// Authenticated GET of `path` (the JWT covers the path without the query string)
+    async fn signed_get<T: DeserializeOwned>(&self, path: &str, query: &str, what: &str) -> Result<T> {
         self.rate_limiter.wait_if_needed().await?;
 
-        let path = crate::constants::api::COINBASE_ACCOUNTS_PATH;
-        let url = format!("{}{}", self.base_url, path);
+        let url = if query.is_empty() {
+            format!("{}{}", self.base_url, path)
+        } else {
+            format!("{}{}?{}", self.base_url, path, query)
+        };
 
         let jwt = self.auth.generate_jwt(
             crate::constants::http::GET,
@@ -158,7 +244,7 @@ impl CoinbaseRestClient {
             .send()
             .await
             .map_err(|e| ArbitrageError::ExchangeError {
-                exchange: crate::constants::exchange::COINBASE.to_string(),
+                exchange: ExchangeId::COINBASE,
                 message: format!("HTTP request failed: {}", e),
                 code: None,
             })?;
@@ -172,36 +258,22 @@ impl CoinbaseRestClient {
         if !status.is_success() {
             if status == 401 || status == 403 {
                 return Err(ArbitrageError::AuthenticationError {
-                    exchange: crate::constants::exchange::COINBASE.to_string(),
+                    exchange: ExchangeId::COINBASE,
                     reason: format!("Authentication failed: {}", response_text),
                 });
             }
             return Err(ArbitrageError::ExchangeError {
-                exchange: crate::constants::exchange::COINBASE.to_string(),
+                exchange: ExchangeId::COINBASE,
                 message: format!("API error ({}): {}", status, response_text),
                 code: Some(status.as_u16() as i32),
             });
         }
 
-        let accounts_response: CoinbaseAccountsResponse = serde_json::from_str(&response_text)
-            .map_err(|e| ArbitrageError::ExchangeError {
-                exchange: crate::constants::exchange::COINBASE.to_string(),
-                message: format!("Failed to parse accounts response: {}", e),
-                code: None,
-            })?;
-
-        // Find account matching the requested asset
-        let account = accounts_response
-            .accounts
-            .iter()
-            .find(|acc| acc.currency == asset)
-            .ok_or_else(|| ArbitrageError::ExchangeError {
-                exchange: crate::constants::exchange::COINBASE.to_string(),
-                message: format!("Account not found for currency: {}", asset),
-                code: None,
-            })?;
-
-        account.available_balance_decimal()
+        serde_json::from_str(&response_text).map_err(|e| ArbitrageError::ExchangeError {
+            exchange: ExchangeId::COINBASE,
+            message: format!("Failed to parse {} response: {}", what, e),
+            code: None,
+        })
     }
 
     /// Place a market order (IOC - Immediate or Cancel)
@@ -220,7 +292,7 @@ impl CoinbaseRestClient {
         // Validate order type
         if !matches!(order.order_type, OrderType::Market) {
             return Err(ArbitrageError::ExchangeError {
-                exchange: crate::constants::exchange::COINBASE.to_string(),
+                exchange: ExchangeId::COINBASE,
                 message: "Only market orders are supported".to_string(),
                 code: None,
             });
@@ -323,7 +395,7 @@ impl CoinbaseRestClient {
             .send()
             .await
             .map_err(|e| ArbitrageError::ExchangeError {
-                exchange: crate::constants::exchange::COINBASE.to_string(),
+                exchange: ExchangeId::COINBASE,
                 message: format!("HTTP request failed: {}", e),
                 code: None,
             })?;
@@ -337,12 +409,12 @@ impl CoinbaseRestClient {
         if !status.is_success() {
             if status == 401 || status == 403 {
                 return Err(ArbitrageError::AuthenticationError {
-                    exchange: crate::constants::exchange::COINBASE.to_string(),
+                    exchange: ExchangeId::COINBASE,
                     reason: format!("Authentication failed: {}", response_text),
                 });
             }
             return Err(ArbitrageError::ExchangeError {
-                exchange: crate::constants::exchange::COINBASE.to_string(),
+                exchange: ExchangeId::COINBASE,
                 message: format!("Order placement failed ({}): {}", status, response_text),
                 code: Some(status.as_u16() as i32),
             });
@@ -350,7 +422,7 @@ impl CoinbaseRestClient {
 
         let wrapper: crate::exchanges::coinbase::types::CoinbaseOrderResponseWrapper =
             serde_json::from_str(&response_text).map_err(|e| ArbitrageError::ExchangeError {
-                exchange: crate::constants::exchange::COINBASE.to_string(),
+                exchange: ExchangeId::COINBASE,
                 message: format!(
                     "Failed to parse order response: {}. Response was: {}",
                     e, response_text
@@ -364,7 +436,7 @@ impl CoinbaseRestClient {
                 .map(|e| format!("{}: {}", e.error, e.message))
                 .unwrap_or_else(|| "Unknown error".to_string());
             return Err(ArbitrageError::ExchangeError {
-                exchange: crate::constants::exchange::COINBASE.to_string(),
+                exchange: ExchangeId::COINBASE,
                 message: format!("Order placement failed: {}", error_msg),
                 code: None,
             });
@@ -374,7 +446,7 @@ impl CoinbaseRestClient {
             wrapper
                 .success_response
                 .ok_or_else(|| ArbitrageError::ExchangeError {
-                    exchange: crate::constants::exchange::COINBASE.to_string(),
+                    exchange: ExchangeId::COINBASE,
                     message: "Order response missing success_response".to_string(),
                     code: None,
                 })?;
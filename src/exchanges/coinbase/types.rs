@@ -2,7 +2,7 @@
 //!
 //! Types for Coinbase Advanced Trade API request/response structures.
 
-use crate::exchanges::{OrderResult, OrderStatus};
+use crate::exchanges::{Balance, ExchangeId, OrderResult, OrderStatus};
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
@@ -67,7 +67,7 @@ pub struct CoinbaseOrderResponse {
     #[serde(rename = "filled_size")]
     #[serde(default)]
     pub filled_size: Option<String>,
-    #[serde(default)]
+    #[serde(default, alias = "total_fees")]
     pub fees: Option<String>,
     #[serde(rename = "number_of_fills")]
     #[serde(default)]
@@ -81,24 +81,28 @@ impl TryFrom<CoinbaseOrderResponse> for OrderResult {
     type Error = crate::error::ArbitrageError;
 
     fn try_from(response: CoinbaseOrderResponse) -> Result<Self, Self::Error> {
+        let filled_quantity = response
+            .filled_size
+            .as_ref()
+            .and_then(|s| Decimal::from_str(s).ok())
+            .unwrap_or(Decimal::ZERO);
+
         let status = match response
             .status
             .as_deref()
             .unwrap_or(crate::constants::order::FILLED)
         {
             s if s == crate::constants::order::FILLED => OrderStatus::Filled,
+            s if s == crate::constants::order::OPEN && !filled_quantity.is_zero() => {
+                OrderStatus::PartiallyFilled
+            }
+            s if s == crate::constants::order::OPEN => OrderStatus::Pending,
             s if s == crate::constants::order::PENDING => OrderStatus::Pending,
             s if s == crate::constants::order::PARTIALLY_FILLED => OrderStatus::PartiallyFilled,
             s if s == crate::constants::order::CANCELLED => OrderStatus::Cancelled,
             _ => OrderStatus::Failed,
         };
 
-        let filled_quantity = response
-            .filled_size
-            .as_ref()
-            .and_then(|s| Decimal::from_str(s).ok())
-            .unwrap_or(Decimal::ZERO);
-
         let average_price = response
             .average_filled_price
             .as_ref()
@@ -136,6 +140,19 @@ impl TryFrom<CoinbaseOrderResponse> for OrderResult {
     }
 }
 
+/// Response from the historical order lookup endpoint
+#[derive(Debug, Deserialize)]
+pub struct CoinbaseOrderLookup {
+    pub order: CoinbaseOrderResponse,
+}
+
+/// Response from the historical order list endpoint
+#[derive(Debug, Deserialize)]
+pub struct CoinbaseOrderList {
+    #[serde(default)]
+    pub orders: Vec<CoinbaseOrderResponse>,
+}
+
 /// Coinbase account response
 #[derive(Debug, Deserialize)]
 pub struct CoinbaseAccountsResponse {
@@ -164,10 +181,28 @@ impl CoinbaseAccount {
     pub fn available_balance_decimal(&self) -> Result<Decimal, crate::error::ArbitrageError> {
         Decimal::from_str(&self.available_balance.value).map_err(|e| {
             crate::error::ArbitrageError::ExchangeError {
-                exchange: crate::constants::exchange::COINBASE.to_string(),
+                exchange: ExchangeId::COINBASE,
                 message: format!("Failed to parse balance: {}", e),
                 code: None,
             }
         })
     }
+
+    /// Get available and on-hold balance (missing hold counts as zero)
+    pub fn balance(&self) -> Result<Balance, crate::error::ArbitrageError> {
+        let hold = match &self.hold {
+            Some(hold) => Decimal::from_str(&hold.value).map_err(|e| {
+                crate::error::ArbitrageError::ExchangeError {
+                    exchange: ExchangeId::COINBASE,
+                    message: format!("Failed to parse hold: {}", e),
+                    code: None,
+                }
+            })?,
+            None => Decimal::ZERO,
+        };
+        Ok(Balance {
+            available: self.available_balance_decimal()?,
+            hold,
+        })
+    }
 }
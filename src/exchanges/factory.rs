@@ -1,35 +1,325 @@
+//! Building exchanges by name
+//!
+//! `DefaultExchangeFactory` knows the built-in exchanges; downstream crates
+//! add their own venues with `register()`.
+
 use super::Exchange;
+use super::binance::BinanceExchange;
+use super::bybit::BybitExchange;
+use super::coinbase::CoinbaseExchange;
+use super::kraken::KrakenExchange;
+use super::mock::MockExchange;
+use super::okx::OkxExchange;
+use super::paper::{PaperAccount, PaperConfig, PaperExchange};
+use crate::config::{
+    AccountConfig, BinanceConfig, BybitConfig, CoinbaseConfig, ExchangesConfig, KrakenConfig,
+    OkxConfig,
+};
 use crate::error::{ArbitrageError, Result};
+use crate::state::ExchangeId;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Settings handed to an exchange constructor
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExchangeConfig {
+    Binance(BinanceConfig),
+    Coinbase(CoinbaseConfig),
+    Kraken(KrakenConfig),
+    Okx(OkxConfig),
+    Bybit(BybitConfig),
+    /// Settings of an exchange registered at runtime, in whatever shape it reads
+    Custom(toml::Value),
+    /// No settings, e.g. for the mock exchange
+    None,
+}
+
+impl ExchangeConfig {
+    /// The settings for `id`, or `None` if it is disabled
+    pub fn from_exchanges(exchanges: &ExchangesConfig, id: ExchangeId) -> Option<Self> {
+        match id {
+            ExchangeId::BINANCE => exchanges.binance().cloned().map(Self::Binance),
+            ExchangeId::COINBASE => exchanges.coinbase().cloned().map(Self::Coinbase),
+            ExchangeId::KRAKEN => exchanges.kraken().cloned().map(Self::Kraken),
+            ExchangeId::OKX => exchanges.okx().cloned().map(Self::Okx),
+            ExchangeId::BYBIT => exchanges.bybit().cloned().map(Self::Bybit),
+            _ => exchanges
+                .account(id)
+                .cloned()
+                .map(Self::from)
+                .or_else(|| exchanges.custom(id).cloned().map(Self::Custom)),
+        }
+    }
+}
+
+impl From<AccountConfig> for ExchangeConfig {
+    fn from(config: AccountConfig) -> Self {
+        match config {
+            AccountConfig::Binance(config) => Self::Binance(config),
+            AccountConfig::Coinbase(config) => Self::Coinbase(config),
+            AccountConfig::Kraken(config) => Self::Kraken(config),
+            AccountConfig::Okx(config) => Self::Okx(config),
+            AccountConfig::Bybit(config) => Self::Bybit(config),
+        }
+    }
+}
+
+/// Builds one exchange client, reporting as the given id, from its settings
+pub type ExchangeConstructor =
+    Arc<dyn Fn(ExchangeId, &ExchangeConfig) -> Result<Box<dyn Exchange>> + Send + Sync>;
 
 #[allow(clippy::result_large_err)]
 pub trait ExchangeFactory {
-    fn create_exchange(&self, name: &str, _config: Option<&()>) -> Result<Box<dyn Exchange>>;
+    /// Build the exchange registered as `name` (case-insensitive)
+    ///
+    /// `venue:account` builds the venue's exchange, reporting as that account.
+    fn create_exchange(&self, name: &str, config: &ExchangeConfig) -> Result<Box<dyn Exchange>>;
+
+    /// Build a configured exchange, failing if it is disabled
+    fn create_enabled(
+        &self,
+        exchanges: &ExchangesConfig,
+        id: ExchangeId,
+    ) -> Result<Box<dyn Exchange>> {
+        let config = ExchangeConfig::from_exchanges(exchanges, id).ok_or_else(|| {
+            ArbitrageError::ConfigError {
+                field: format!("exchanges.{}", id.as_str()),
+                reason: "exchange is disabled".to_string(),
+            }
+        })?;
+        self.create_exchange(id.as_str(), &config)
+    }
+}
+
+/// Constructors for `binance`, `coinbase`, `kraken`, `okx`, `bybit` and `mock`, plus any registered
+///
+/// # Business Logic
+///
+/// Each name maps to a constructor taking the id to report and `ExchangeConfig`.
+/// Accounts (`binance:hedge`) use their venue's constructor. The built-in
+/// exchanges reject settings meant for another exchange rather than guessing.
+/// Registering a name that already exists replaces its constructor, so a
+/// downstream crate can also swap a built-in for its own client.
+///
+/// With `with_paper_trading()` every exchange built is wrapped in a
+/// `PaperExchange`: live prices from the real venue, simulated fills. Exchanges
+/// built for the same id (and by clones of the factory) share one account.
+///
+/// ```rust
+/// use arb_bot::exchanges::factory::{DefaultExchangeFactory, ExchangeConfig, ExchangeFactory};
+/// use arb_bot::exchanges::mock::MockExchange;
+///
+/// let mut factory = DefaultExchangeFactory::new();
+/// factory.register("venue", |id, _: &ExchangeConfig| Ok(Box::new(MockExchange::new(id))));
+///
+/// let exchange = factory.create_exchange("venue", &ExchangeConfig::None)?;
+/// assert_eq!(exchange.name(), "venue");
+/// let second = factory.create_exchange("venue:second", &ExchangeConfig::None)?;
+/// assert_eq!(second.name(), "venue:second");
+/// # Ok::<(), arb_bot::error::ArbitrageError>(())
+/// ```
+#[derive(Clone)]
+pub struct DefaultExchangeFactory {
+    constructors: HashMap<String, ExchangeConstructor>,
+    paper: Option<PaperConfig>,
+    paper_accounts: Arc<Mutex<HashMap<ExchangeId, PaperAccount>>>,
+}
+
+impl Default for DefaultExchangeFactory {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
-pub struct DefaultExchangeFactory;
+impl DefaultExchangeFactory {
+    /// Factory for the built-in exchanges
+    pub fn new() -> Self {
+        let mut factory = Self {
+            constructors: HashMap::new(),
+            paper: None,
+            paper_accounts: Arc::default(),
+        };
+        factory.register(crate::constants::exchange::BINANCE, |id, config| match config {
+            ExchangeConfig::Binance(config) => {
+                Ok(Box::new(BinanceExchange::new(config.clone())?.with_id(id)) as Box<dyn Exchange>)
+            }
+            other => Err(mismatched(crate::constants::exchange::BINANCE, other)),
+        });
+        factory.register(crate::constants::exchange::COINBASE, |id, config| match config {
+            ExchangeConfig::Coinbase(config) => {
+                Ok(Box::new(CoinbaseExchange::new(config.clone())?.with_id(id)) as Box<dyn Exchange>)
+            }
+            other => Err(mismatched(crate::constants::exchange::COINBASE, other)),
+        });
+        factory.register(crate::constants::exchange::KRAKEN, |id, config| match config {
+            ExchangeConfig::Kraken(config) => {
+                Ok(Box::new(KrakenExchange::new(config.clone())?.with_id(id)) as Box<dyn Exchange>)
+            }
+            other => Err(mismatched(crate::constants::exchange::KRAKEN, other)),
+        });
+        factory.register(crate::constants::exchange::OKX, |id, config| match config {
+            ExchangeConfig::Okx(config) => {
+                Ok(Box::new(OkxExchange::new(config.clone())?.with_id(id)) as Box<dyn Exchange>)
+            }
+            other => Err(mismatched(crate::constants::exchange::OKX, other)),
+        });
+        factory.register(crate::constants::exchange::BYBIT, |id, config| match config {
+            ExchangeConfig::Bybit(config) => {
+                Ok(Box::new(BybitExchange::new(config.clone())?.with_id(id)) as Box<dyn Exchange>)
+            }
+            other => Err(mismatched(crate::constants::exchange::BYBIT, other)),
+        });
+        factory.register("mock", |id, _| Ok(Box::new(MockExchange::new(id))));
+        factory
+    }
+
+    /// Paper trade every exchange built, starting from `config`
+    pub fn with_paper_trading(mut self, config: PaperConfig) -> Self {
+        self.paper = Some(config);
+        self
+    }
+
+    /// Add or replace the constructor for `name` (case-insensitive)
+    pub fn register<F>(&mut self, name: &str, constructor: F) -> &mut Self
+    where
+        F: Fn(ExchangeId, &ExchangeConfig) -> Result<Box<dyn Exchange>> + Send + Sync + 'static,
+    {
+        self.constructors
+            .insert(name.to_ascii_lowercase(), Arc::new(constructor));
+        self
+    }
+
+    /// Registered names, sorted
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.constructors.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+}
 
 impl ExchangeFactory for DefaultExchangeFactory {
-    fn create_exchange(&self, name: &str, _config: Option<&()>) -> Result<Box<dyn Exchange>> {
-        match name {
-            // Production exchanges will be added here in future tasks
-            // "binance" => Ok(Box::new(BinanceExchange::new(config)?)),
-            // "coinbase" => Ok(Box::new(CoinbaseExchange::new(config)?)),
-            _ => Err(ArbitrageError::ConfigError {
+    fn create_exchange(&self, name: &str, config: &ExchangeConfig) -> Result<Box<dyn Exchange>> {
+        let id = ExchangeId::from_name(name).ok_or_else(|| ArbitrageError::ConfigError {
+            field: "exchange".to_string(),
+            reason: format!("Invalid exchange name: {:?}", name),
+        })?;
+        let constructor = self
+            .constructors
+            .get(id.venue().as_str())
+            .ok_or_else(|| ArbitrageError::ConfigError {
                 field: "exchange".to_string(),
-                reason: format!("Unknown exchange: {}", name),
-            }),
-        }
+                reason: format!("Unknown exchange: {} (known: {})", name, self.names().join(", ")),
+            })?;
+        let exchange = constructor(id, config)?;
+        Ok(match &self.paper {
+            Some(paper) => {
+                let account = self
+                    .paper_accounts
+                    .lock()
+                    .entry(id)
+                    .or_insert_with(|| PaperAccount::new(id, paper))
+                    .clone();
+                Box::new(PaperExchange::with_account(exchange, account))
+            }
+            None => exchange,
+        })
+    }
+}
+
+fn mismatched(name: &str, config: &ExchangeConfig) -> ArbitrageError {
+    ArbitrageError::ConfigError {
+        field: format!("exchanges.{}", name),
+        reason: format!("expected {} settings, got {:?}", name, kind(config)),
+    }
+}
+
+/// Variant name only, so credentials never reach an error message
+fn kind(config: &ExchangeConfig) -> &'static str {
+    match config {
+        ExchangeConfig::Binance(_) => "binance",
+        ExchangeConfig::Coinbase(_) => "coinbase",
+        ExchangeConfig::Kraken(_) => "kraken",
+        ExchangeConfig::Okx(_) => "okx",
+        ExchangeConfig::Bybit(_) => "bybit",
+        ExchangeConfig::Custom(_) => "custom",
+        ExchangeConfig::None => "none",
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rust_decimal::Decimal;
+
+    fn binance() -> ExchangeConfig {
+        ExchangeConfig::Binance(BinanceConfig {
+            api_key: String::new(),
+            api_secret: String::new(),
+            testnet: true,
+        })
+    }
 
     #[test]
     fn test_factory_rejects_unknown() {
-        let factory = DefaultExchangeFactory;
-        let result = factory.create_exchange("binance", None);
-        assert!(result.is_err());
+        let factory = DefaultExchangeFactory::new();
+        let Err(err) = factory.create_exchange("bitstamp", &ExchangeConfig::None) else {
+            panic!("expected an unknown exchange error");
+        };
+        assert!(err.to_string().contains("Unknown exchange: bitstamp"));
+        assert!(factory.create_exchange("binance", &ExchangeConfig::None).is_err());
+        let Err(err) = factory.create_exchange("mock:", &ExchangeConfig::None) else {
+            panic!("expected an invalid name error");
+        };
+        assert!(err.to_string().contains("Invalid exchange name"));
+    }
+
+    #[test]
+    fn builds_builtin_exchanges() {
+        let factory = DefaultExchangeFactory::new();
+        assert_eq!(factory.names(), ["binance", "bybit", "coinbase", "kraken", "mock", "okx"]);
+        assert_eq!(factory.create_exchange("Binance", &binance()).unwrap().name(), "binance");
+        assert_eq!(factory.create_exchange("mock", &ExchangeConfig::None).unwrap().name(), "mock");
+
+        let exchanges = ExchangesConfig::default();
+        let coinbase = factory.create_enabled(&exchanges, ExchangeId::COINBASE).unwrap();
+        assert_eq!(coinbase.name(), "coinbase");
+    }
+
+    #[tokio::test]
+    async fn registered_constructors_receive_settings() {
+        let mock = MockExchange::new("bitstamp");
+        let mut factory = DefaultExchangeFactory::new()
+            .with_paper_trading(PaperConfig::default().with_balance("USD", Decimal::from(10)));
+        let shared = mock.clone();
+        factory.register("bitstamp", move |_, config| match config {
+            ExchangeConfig::Custom(settings) if settings.get("tier").is_some() => {
+                Ok(Box::new(shared.clone()))
+            }
+            _ => Err(ArbitrageError::ConfigError {
+                field: "exchanges.bitstamp".to_string(),
+                reason: "missing tier".to_string(),
+            }),
+        });
+
+        let settings: toml::Value = toml::from_str("tier = 2").unwrap();
+        let exchange = factory
+            .create_exchange("bitstamp", &ExchangeConfig::Custom(settings))
+            .unwrap();
+        assert_eq!(exchange.name(), "bitstamp");
+        // Paper trading: balances are simulated, not the venue's
+        assert_eq!(exchange.get_balance("USD").await.unwrap(), Decimal::from(10));
+        assert!(factory.create_exchange("bitstamp", &ExchangeConfig::None).is_err());
+
+        // `[exchanges.bitstamp]` reaches the constructor as written
+        let config = crate::config::AppConfig::parse(
+            "[trading]\npair = \"SOL/USDC\"\nspread_threshold = 0.002\norder_size = 1.0\n\
+             cooldown_ms = 5000\n\n[exchanges.bitstamp]\ntier = 2\n",
+        )
+        .unwrap();
+        let bitstamp = ExchangeId::from_name("bitstamp").unwrap();
+        assert!(factory.create_enabled(config.exchanges(), bitstamp).is_ok());
+        let unregistered = DefaultExchangeFactory::new().create_enabled(config.exchanges(), bitstamp);
+        assert!(unregistered.is_err());
     }
 }
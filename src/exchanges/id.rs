@@ -0,0 +1,164 @@
+//! Interned exchange identifiers
+
+use parking_lot::Mutex;
+use std::borrow::Borrow;
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::LazyLock;
+
+/// Every name seen so far; each is leaked once and shared by all ids
+static NAMES: LazyLock<Mutex<HashSet<&'static str>>> = LazyLock::new(Default::default);
+
+/// Identifies an exchange, or one account on an exchange
+///
+/// # Business Logic
+///
+/// One id names a venue everywhere: `Exchange::id()`, price state keys, config
+/// sections, error messages and logs. Names are lowercase and interned for the
+/// life of the process, so an id is a `Copy` handle that compares and hashes
+/// like its string, and adding a venue needs no changes here.
+///
+/// Several accounts on one venue are told apart by a suffix after `:`
+/// (`binance:hedge`), each with its own `[exchanges."binance:hedge"]` section;
+/// `venue()` strips it to find the exchange to talk to.
+///
+/// ```rust
+/// use arb_bot::exchanges::ExchangeId;
+///
+/// let hedge = ExchangeId::from_name("Binance:Hedge").unwrap();
+/// assert_eq!(hedge.as_str(), "binance:hedge");
+/// assert_eq!(hedge.venue(), ExchangeId::BINANCE);
+/// assert_eq!(ExchangeId::from_name("KRAKEN"), Some(ExchangeId::KRAKEN));
+/// assert_eq!(ExchangeId::from_name("not a venue"), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ExchangeId(&'static str);
+
+impl ExchangeId {
+    pub const BINANCE: ExchangeId = ExchangeId(crate::constants::exchange::BINANCE);
+    pub const COINBASE: ExchangeId = ExchangeId(crate::constants::exchange::COINBASE);
+    pub const KRAKEN: ExchangeId = ExchangeId(crate::constants::exchange::KRAKEN);
+    pub const OKX: ExchangeId = ExchangeId(crate::constants::exchange::OKX);
+    pub const BYBIT: ExchangeId = ExchangeId(crate::constants::exchange::BYBIT);
+
+    /// Id for `name`, lowercased; `from_name` validates it first
+    fn new(name: &str) -> Self {
+        let name = name.trim().to_ascii_lowercase();
+        let mut names = NAMES.lock();
+        match names.get(name.as_str()) {
+            Some(interned) => ExchangeId(interned),
+            None => {
+                let interned: &'static str = Box::leak(name.into_boxed_str());
+                names.insert(interned);
+                ExchangeId(interned)
+            }
+        }
+    }
+
+    /// Parse an exchange name (case-insensitive)
+    ///
+    /// Accepts letters, digits, `_` and `-`, optionally followed by `:account`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let valid = |part: &str| {
+            !part.is_empty()
+                && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        };
+        let name = name.trim();
+        let valid = match name.split_once(':') {
+            Some((venue, account)) => valid(venue) && valid(account),
+            None => valid(name),
+        };
+        valid.then(|| Self::new(name))
+    }
+
+    /// The lowercase name, as used in config and logs
+    pub fn as_str(&self) -> &'static str {
+        self.0
+    }
+
+    /// The exchange without any account suffix
+    pub fn venue(&self) -> ExchangeId {
+        match self.0.split_once(':') {
+            Some((venue, _)) => Self::new(venue),
+            None => *self,
+        }
+    }
+
+    /// The account suffix, if this id names one account on a venue
+    pub fn account(&self) -> Option<&'static str> {
+        self.0.split_once(':').map(|(_, account)| account)
+    }
+}
+
+impl fmt::Display for ExchangeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+impl AsRef<str> for ExchangeId {
+    fn as_ref(&self) -> &str {
+        self.0
+    }
+}
+
+/// Lets maps keyed by id be queried with a plain name
+impl Borrow<str> for ExchangeId {
+    fn borrow(&self) -> &str {
+        self.0
+    }
+}
+
+impl PartialEq<str> for ExchangeId {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for ExchangeId {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn names_are_lowercased_and_interned() {
+        let a = ExchangeId::new("Kraken");
+        let b = ExchangeId::new("kraken");
+        assert_eq!(a, b);
+        assert!(std::ptr::eq(a.as_str(), b.as_str()));
+        assert_eq!(a.to_string(), "kraken");
+        assert_eq!(ExchangeId::new("binance"), ExchangeId::BINANCE);
+    }
+
+    #[test]
+    fn from_name_validates() {
+        assert_eq!(ExchangeId::from_name("Coinbase"), Some(ExchangeId::COINBASE));
+        assert_eq!(ExchangeId::from_name("okx-eu:arb_1").map(|id| id.as_str()), Some("okx-eu:arb_1"));
+        for bad in ["", "bin ance", "binance:", ":main", "a:b:c"] {
+            assert_eq!(ExchangeId::from_name(bad), None, "{bad:?}");
+        }
+    }
+
+    #[test]
+    fn accounts_share_a_venue() {
+        let main = ExchangeId::BINANCE;
+        let hedge = ExchangeId::new("binance:hedge");
+        assert_ne!(main, hedge);
+        assert_eq!(hedge.venue(), main);
+        assert_eq!(hedge.account(), Some("hedge"));
+        assert_eq!(main.account(), None);
+    }
+
+    #[test]
+    fn map_lookup_by_name() {
+        let map = HashMap::from([(ExchangeId::COINBASE, 1)]);
+        assert_eq!(map.get("coinbase"), Some(&1));
+        assert_eq!(ExchangeId::COINBASE, "coinbase");
+    }
+}
@@ -0,0 +1,325 @@
+//! Kraken Exchange WebSocket Implementation
+//!
+//! Connects to Kraken WebSocket v2 to receive real-time ticker and book updates.
+
+use crate::config::KrakenConfig;
+use crate::error::{ArbitrageError, Result};
+use crate::exchanges::{Balance, Exchange, ExchangeId, OrderResult, PRICE_UPDATES_CAPACITY, Price};
+use crate::logger::{debug, error, warn};
+use crate::websocket::{CircuitBreaker, ConnectionState, ReconnectionStrategy, WebSocketManager};
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::{broadcast, watch};
+
+use super::parser::{BOOK_DEPTH, KrakenParser};
+use super::rest::KrakenRestClient;
+use super::symbols::KrakenSymbols;
+
+/// Reconnect if no data arrives for this long.
+/// Kraken sends a heartbeat every second, so a quiet feed is a dead feed.
+const FEED_INACTIVITY_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Kraken exchange implementation using WebSocket v2 for price feeds
+///
+/// # Business Logic
+///
+/// Subscribes to the `ticker` and `book` channels for a pair; the parser
+/// merges them so each `Price` carries the current top of book plus the last
+/// trade. Prices are stored in-memory and can be queried via `get_latest_price()`.
+///
+/// REST API client is available for order placement and balance queries once
+/// API credentials are configured.
+pub struct KrakenExchange {
+    id: ExchangeId,
+    symbols: KrakenSymbols,
+    /// WebSocket manager (moved into spawned task on connect)
+    ws_manager_handle: Option<tokio::task::JoinHandle<()>>,
+    /// In-memory store of latest prices by trading pair
+    latest_prices: Arc<RwLock<HashMap<String, Price>>>,
+    /// Re-broadcasts every parsed quote to `price_updates()` receivers
+    prices_tx: broadcast::Sender<Price>,
+    /// Connection state, shared with each manager so receivers survive resubscribes
+    state_tx: watch::Sender<ConnectionState>,
+    /// Base WebSocket URL
+    base_url: String,
+    /// REST API client for trading operations (optional, only if API credentials provided)
+    rest_client: Option<KrakenRestClient>,
+}
+
+impl KrakenExchange {
+    /// Create a new Kraken exchange instance
+    ///
+    /// Fails if credentials are given but the secret is not valid base64.
+    pub fn new(config: KrakenConfig) -> Result<Self> {
+        let rest_client = if !config.api_key.is_empty() && !config.api_secret.is_empty() {
+            Some(KrakenRestClient::new(config.api_key, config.api_secret)?)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            id: ExchangeId::KRAKEN,
+            symbols: KrakenSymbols::default(),
+            ws_manager_handle: None,
+            latest_prices: Arc::new(RwLock::new(HashMap::new())),
+            prices_tx: broadcast::channel(PRICE_UPDATES_CAPACITY).0,
+            state_tx: watch::Sender::new(ConnectionState::Disconnected),
+            base_url: crate::constants::websocket::KRAKEN_V2.to_string(),
+            rest_client,
+        })
+    }
+
+    /// Report as `id` instead, e.g. `kraken:hedge` for a second account
+    pub fn with_id(mut self, id: ExchangeId) -> Self {
+        self.id = id;
+        self
+    }
+
+    /// Translate asset and pair names with `symbols` for both feeds and REST
+    pub fn with_symbols(mut self, symbols: KrakenSymbols) -> Self {
+        self.rest_client = self
+            .rest_client
+            .map(|client| client.with_symbols(symbols.clone()));
+        self.symbols = symbols;
+        self
+    }
+
+    /// Connect to WebSocket with ticker and book subscriptions for `pair`
+    ///
+    /// Both messages are registered as on-connect messages on the
+    /// `WebSocketManager`, so they are re-sent after every reconnect. The
+    /// parser is fresh per connection, so a reconnect starts from a new book
+    /// snapshot.
+    #[tracing::instrument(name = "connect_with_subscription", skip(self), fields(exchange = %self.id, pair = %pair))]
+    async fn connect_with_subscription(&mut self, pair: &str) -> Result<()> {
+        let messages = self.subscribe_messages(pair)?;
+        debug!(subscriptions = ?messages, "Registering subscription messages");
+
+        let parser = KrakenParser::new().with_symbols(self.symbols.clone());
+        // Jitter keeps feeds that dropped together from reconnecting in lockstep
+        let reconnect_strategy = ReconnectionStrategy::exponential_backoff()
+            .with_jitter()
+            .with_circuit_breaker(CircuitBreaker::default());
+
+        let (manager, mut price_rx) =
+            WebSocketManager::new(self.id, self.base_url.clone(), parser, reconnect_strategy);
+        let mut manager = manager
+            .with_on_connect_messages(messages)
+            .with_inactivity_timeout(FEED_INACTIVITY_TIMEOUT)
+            .with_state_sender(self.state_tx.clone());
+
+        // Spawn background task to run WebSocket manager
+        self.ws_manager_handle = Some(tokio::spawn(async move {
+            if let Err(e) = manager.run().await {
+                error!(error = %e, "Kraken WebSocket manager error");
+            }
+        }));
+
+        // Spawn background task to update latest prices from WebSocket stream
+        let prices = self.latest_prices.clone();
+        let prices_tx = self.prices_tx.clone();
+        tokio::spawn(async move {
+            loop {
+                match price_rx.recv().await {
+                    Ok(price) => {
+                        prices.write().insert(price.pair.clone(), price.clone());
+                        // Nobody listening is fine; pollers read the cache
+                        let _ = prices_tx.send(price);
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!(skipped = skipped, "Lagged messages");
+                        continue;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => {
+                        error!("Broadcast channel closed");
+                        break;
+                    }
+                }
+            }
+        });
+
+        Ok(())
+    }
+
+    /// Build the ticker and book subscription messages for a trading pair
+    ///
+    /// Format: {"method":"subscribe","params":{"channel":"book","symbol":["BTC/USD"],"depth":10}}
+    pub fn subscribe_messages(&self, pair: &str) -> Result<Vec<String>> {
+        let symbol = self.symbols.ws_symbol(pair);
+        [
+            serde_json::json!({
+                "method": "subscribe",
+                "params": { "channel": "ticker", "symbol": [symbol] }
+            }),
+            serde_json::json!({
+                "method": "subscribe",
+                "params": { "channel": "book", "symbol": [symbol], "depth": BOOK_DEPTH }
+            }),
+        ]
+        .iter()
+        .map(|message| {
+            serde_json::to_string(message).map_err(|e| ArbitrageError::ParseError {
+                message: format!("Failed to serialize subscription message: {}", e),
+                input: None,
+            })
+        })
+        .collect()
+    }
+
+    fn rest_client(&self) -> Result<&KrakenRestClient> {
+        self.rest_client
+            .as_ref()
+            .ok_or_else(|| ArbitrageError::ExchangeError {
+                exchange: self.id,
+                message: "REST API not available - API credentials required".to_string(),
+                code: None,
+            })
+    }
+}
+
+#[async_trait::async_trait]
+impl Exchange for KrakenExchange {
+    async fn connect(&mut self) -> Result<()> {
+        // Connection happens in subscribe_ticker(), which knows the pair
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "subscribe_ticker", skip(self), fields(exchange = %self.id, pair = %pair))]
+    async fn subscribe_ticker(&mut self, pair: &str) -> Result<()> {
+        // Disconnect existing connection if any
+        self.disconnect().await.ok();
+
+        self.connect_with_subscription(pair).await?;
+
+        // Wait for first price to arrive (max 10 seconds)
+        for _ in 0..100 {
+            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+            if self.latest_prices.read().contains_key(pair) {
+                return Ok(());
+            }
+        }
+
+        // Connection might still be establishing; caller can check get_latest_price()
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "get_latest_price", skip(self), fields(exchange = %self.id, pair = %pair))]
+    async fn get_latest_price(&self, pair: &str) -> Result<Price> {
+        self.latest_prices
+            .read()
+            .get(pair)
+            .cloned()
+            .ok_or_else(|| ArbitrageError::ExchangeError {
+                exchange: self.id,
+                message: format!("No price data available for {}", pair),
+                code: None,
+            })
+    }
+
+    #[tracing::instrument(name = "place_order", skip(self, order), fields(
+        exchange = %self.id,
+        pair = %order.pair,
+        side = ?order.side,
+        order_type = ?order.order_type,
+        quantity = %order.quantity
+    ))]
+    async fn place_order(
+        &mut self,
+        order: crate::exchanges::Order,
+    ) -> Result<crate::exchanges::OrderResult> {
+        self.rest_client()?.place_order(order).await
+    }
+
+    #[tracing::instrument(name = "get_balance", skip(self), fields(exchange = %self.id, asset = %asset))]
+    async fn get_balance(&self, asset: &str) -> Result<rust_decimal::Decimal> {
+        self.rest_client()?.get_balance(asset).await
+    }
+
+    async fn get_balances(&self, assets: &[&str]) -> Result<HashMap<String, Balance>> {
+        self.rest_client()?.get_balances(assets).await
+    }
+
+    async fn get_order(&self, pair: &str, order_id: &str) -> Result<OrderResult> {
+        self.rest_client()?.get_order(pair, order_id).await
+    }
+
+    async fn get_open_orders(&self, pair: &str) -> Result<Vec<OrderResult>> {
+        self.rest_client()?.get_open_orders(pair).await
+    }
+
+    fn id(&self) -> ExchangeId {
+        self.id
+    }
+
+    fn is_connected(&self) -> bool {
+        self.connection_state().is_live()
+    }
+
+    fn connection_state(&self) -> ConnectionState {
+        // An aborted or panicked manager task can't publish its own exit
+        let running = self
+            .ws_manager_handle
+            .as_ref()
+            .is_some_and(|handle| !handle.is_finished());
+        let state = self.state_tx.borrow().clone();
+        if running || matches!(state, ConnectionState::Failed { .. }) {
+            state
+        } else {
+            ConnectionState::Disconnected
+        }
+    }
+
+    fn watch_connection_state(&self) -> Option<watch::Receiver<ConnectionState>> {
+        Some(self.state_tx.subscribe())
+    }
+
+    fn price_updates(&self) -> Option<broadcast::Receiver<Price>> {
+        Some(self.prices_tx.subscribe())
+    }
+
+    async fn disconnect(&mut self) -> Result<()> {
+        if let Some(handle) = self.ws_manager_handle.take() {
+            handle.abort();
+        }
+        self.state_tx.send_replace(ConnectionState::Disconnected);
+        self.latest_prices.write().clear();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> KrakenConfig {
+        KrakenConfig {
+            api_key: String::new(),
+            api_secret: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_subscribe_messages() {
+        let exchange = KrakenExchange::new(config()).unwrap();
+        let messages = exchange.subscribe_messages("XBT/USD").unwrap();
+        assert_eq!(
+            messages,
+            [
+                r#"{"method":"subscribe","params":{"channel":"ticker","symbol":["BTC/USD"]}}"#,
+                r#"{"method":"subscribe","params":{"channel":"book","depth":10,"symbol":["BTC/USD"]}}"#,
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rest_requires_credentials() {
+        let exchange = KrakenExchange::new(config()).unwrap();
+        assert!(exchange.get_balance("BTC").await.is_err());
+        assert!(KrakenExchange::new(KrakenConfig {
+            api_key: "key".to_string(),
+            api_secret: "not base64!".to_string(),
+        })
+        .is_err());
+    }
+}
@@ -0,0 +1,17 @@
+//! Kraken Exchange Integration
+//!
+//! Implements the Exchange trait for Kraken, providing WebSocket v2 price feeds
+//! and REST API for trading operations.
+
+pub mod auth;
+pub mod exchange;
+pub mod parser;
+pub mod rest;
+pub mod symbols;
+pub mod types;
+
+pub use auth::KrakenAuth;
+pub use exchange::KrakenExchange;
+pub use parser::KrakenParser;
+pub use rest::KrakenRestClient;
+pub use symbols::KrakenSymbols;
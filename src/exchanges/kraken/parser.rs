@@ -0,0 +1,316 @@
+//! Kraken WebSocket v2 message parser
+//!
+//! Converts `ticker` and `book` channel messages into our common `Price` type.
+//!
+//! Based on: https://docs.kraken.com/api/docs/websocket-v2/ticker and
+//! https://docs.kraken.com/api/docs/websocket-v2/book
+
+use super::symbols::KrakenSymbols;
+use crate::error::{ArbitrageError, Result};
+use crate::exchanges::{ExchangeId, Price};
+use crate::websocket::MessageParser;
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use rust_decimal::Decimal;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+/// Book depth to subscribe to; levels beyond it are dropped after each update
+pub const BOOK_DEPTH: usize = 10;
+
+/// What we know about one symbol: the local book plus the latest ticker
+#[derive(Debug, Default)]
+struct Quote {
+    bids: BTreeMap<Decimal, Decimal>,
+    asks: BTreeMap<Decimal, Decimal>,
+    ticker_bid: Decimal,
+    ticker_ask: Decimal,
+    last: Option<Decimal>,
+    volume: Decimal,
+}
+
+impl Quote {
+    /// Best bid and ask, from the book once it has levels, else from the ticker
+    fn touch(&self) -> (Decimal, Decimal) {
+        let bid = self.bids.last_key_value().map(|(p, _)| *p).unwrap_or(self.ticker_bid);
+        let ask = self.asks.first_key_value().map(|(p, _)| *p).unwrap_or(self.ticker_ask);
+        (bid, ask)
+    }
+
+    /// Drop levels beyond the subscribed depth, as Kraken requires after every update
+    fn truncate(&mut self, depth: usize) {
+        while self.bids.len() > depth {
+            self.bids.pop_first();
+        }
+        while self.asks.len() > depth {
+            self.asks.pop_last();
+        }
+    }
+}
+
+/// Parser for Kraken WebSocket v2 `ticker` and `book` messages
+///
+/// # Business Logic
+///
+/// The ticker channel carries last price and volume but only updates on
+/// trades; the book channel moves with every quote change. The parser keeps a
+/// local book per symbol (snapshot, then level updates where a zero quantity
+/// deletes) and emits a `Price` for each message with the book's best bid and
+/// ask plus the latest ticker's last price and volume.
+///
+/// Book messages before the first ticker for a symbol produce no price, since
+/// there is no last trade yet. Clones share the books, so the copy handed to
+/// the `WebSocketManager` and any held elsewhere agree.
+#[derive(Debug, Clone, Default)]
+pub struct KrakenParser {
+    symbols: KrakenSymbols,
+    quotes: Arc<Mutex<HashMap<String, Quote>>>,
+}
+
+impl KrakenParser {
+    /// Create a new Kraken parser
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Translate symbols with `symbols` instead of the built-in table
+    pub fn with_symbols(mut self, symbols: KrakenSymbols) -> Self {
+        self.symbols = symbols;
+        self
+    }
+
+    fn ticker(&self, data: &Value, message: &str) -> Result<(String, Option<DateTime<Utc>>)> {
+        let symbol = self.symbol(data, message)?;
+        let mut quotes = self.quotes.lock();
+        let quote = quotes.entry(symbol.clone()).or_default();
+        quote.ticker_bid = decimal(&data["bid"], "bid", message)?;
+        quote.ticker_ask = decimal(&data["ask"], "ask", message)?;
+        quote.last = Some(decimal(&data["last"], "last", message)?);
+        if !data["volume"].is_null() {
+            quote.volume = decimal(&data["volume"], "volume", message)?;
+        }
+        Ok((symbol, timestamp(data)))
+    }
+
+    fn book(
+        &self,
+        data: &Value,
+        snapshot: bool,
+        message: &str,
+    ) -> Result<(String, Option<DateTime<Utc>>)> {
+        let symbol = self.symbol(data, message)?;
+        let mut quotes = self.quotes.lock();
+        let quote = quotes.entry(symbol.clone()).or_default();
+        if snapshot {
+            quote.bids.clear();
+            quote.asks.clear();
+        }
+        apply_levels(&mut quote.bids, &data["bids"], message)?;
+        apply_levels(&mut quote.asks, &data["asks"], message)?;
+        quote.truncate(BOOK_DEPTH);
+        Ok((symbol, timestamp(data)))
+    }
+
+    fn symbol(&self, data: &Value, message: &str) -> Result<String> {
+        data["symbol"]
+            .as_str()
+            .map(|s| self.symbols.ws_symbol(s))
+            .ok_or_else(|| ArbitrageError::ParseError {
+                message: "Missing symbol".to_string(),
+                input: Some(message.to_string()),
+            })
+    }
+}
+
+impl MessageParser for KrakenParser {
+    type Output = Price;
+
+    fn parse(&self, message: &str) -> Result<Self::Output> {
+        let received_at = Utc::now();
+
+        let value: Value = serde_json::from_str(message).map_err(|e| ArbitrageError::ParseError {
+            message: format!("Invalid JSON: {}", e),
+            input: Some(message.to_string()),
+        })?;
+
+        // Rejected subscriptions: {"method":"subscribe","success":false,"error":"..."}
+        if value["success"].as_bool() == Some(false) {
+            return Err(ArbitrageError::ExchangeError {
+                exchange: ExchangeId::KRAKEN,
+                message: format!(
+                    "Kraken WebSocket error: {}",
+                    value["error"].as_str().unwrap_or("Unknown error")
+                ),
+                code: None,
+            });
+        }
+
+        // {"channel":"ticker"|"book","type":"snapshot"|"update","data":[{...}]}
+        let data = &value["data"][0];
+        let snapshot = value["type"].as_str() == Some("snapshot");
+        let (pair, timestamp) = match value["channel"].as_str() {
+            Some("ticker") => self.ticker(data, message)?,
+            Some("book") => self.book(data, snapshot, message)?,
+            other => {
+                return Err(ArbitrageError::ParseError {
+                    message: format!("Not a ticker or book message, got channel: {}", other.unwrap_or("none")),
+                    input: Some(message.to_string()),
+                });
+            }
+        };
+
+        let quotes = self.quotes.lock();
+        let quote = &quotes[&pair];
+        let Some(last) = quote.last else {
+            return Err(ArbitrageError::ParseError {
+                message: format!("Book update for {} before its first ticker", pair),
+                input: None,
+            });
+        };
+        let (bid, ask) = quote.touch();
+
+        Ok(Price {
+            pair,
+            bid,
+            ask,
+            last,
+            volume_24h: quote.volume,
+            timestamp: timestamp.unwrap_or(received_at),
+            // v2 has no sequence numbers; the book carries a checksum instead
+            sequence: None,
+            received_at,
+        })
+    }
+
+    fn is_control(&self, message: &str) -> bool {
+        ["\"channel\":\"heartbeat\"", "\"channel\":\"status\"", "\"method\":\"pong\""]
+            .iter()
+            .any(|marker| message.contains(marker))
+            || (message.contains("\"method\":\"subscribe\"") && message.contains("\"success\":true"))
+    }
+}
+
+/// Apply `[{"price":..,"qty":..}]` levels; a zero quantity removes the level
+fn apply_levels(side: &mut BTreeMap<Decimal, Decimal>, levels: &Value, message: &str) -> Result<()> {
+    for level in levels.as_array().into_iter().flatten() {
+        let price = decimal(&level["price"], "price", message)?;
+        let qty = decimal(&level["qty"], "qty", message)?;
+        if qty.is_zero() {
+            side.remove(&price);
+        } else {
+            side.insert(price, qty);
+        }
+    }
+    Ok(())
+}
+
+/// Kraken v2 sends prices as JSON numbers; read them via their shortest decimal form
+fn decimal(value: &Value, field: &str, message: &str) -> Result<Decimal> {
+    let invalid = |reason: String| ArbitrageError::ParseError {
+        message: format!("Invalid {}: {}", field, reason),
+        input: Some(message.to_string()),
+    };
+    let text = match value {
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => s.clone(),
+        Value::Null => return Err(invalid("missing".to_string())),
+        other => return Err(invalid(format!("expected a number, got {}", other))),
+    };
+    Decimal::from_str_exact(&text)
+        .or_else(|_| Decimal::from_scientific(&text))
+        .map_err(|e| invalid(e.to_string()))
+}
+
+fn timestamp(data: &Value) -> Option<DateTime<Utc>> {
+    data["timestamp"]
+        .as_str()
+        .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TICKER: &str = r#"{"channel":"ticker","type":"snapshot","data":[{"symbol":"BTC/USD","bid":63000.1,"bid_qty":0.5,"ask":63000.5,"ask_qty":1.2,"last":63000.2,"volume":1234.56,"vwap":62000.0,"low":61000.0,"high":64000.0,"change":100.0,"change_pct":0.16}]}"#;
+
+    #[test]
+    fn test_parse_ticker() {
+        let price = KrakenParser::new().parse(TICKER).unwrap();
+        assert_eq!(price.pair, "BTC/USD");
+        assert_eq!(price.bid, Decimal::from_str_exact("63000.1").unwrap());
+        assert_eq!(price.ask, Decimal::from_str_exact("63000.5").unwrap());
+        assert_eq!(price.last, Decimal::from_str_exact("63000.2").unwrap());
+        assert_eq!(price.volume_24h, Decimal::from_str_exact("1234.56").unwrap());
+        assert_eq!(price.sequence, None);
+    }
+
+    #[test]
+    fn test_book_moves_touch_between_tickers() {
+        let parser = KrakenParser::new();
+        parser.parse(TICKER).unwrap();
+
+        let snapshot = r#"{"channel":"book","type":"snapshot","data":[{"symbol":"BTC/USD",
+            "bids":[{"price":63000.2,"qty":1.0},{"price":63000.0,"qty":2.0}],
+            "asks":[{"price":63000.4,"qty":0.3},{"price":63001.0,"qty":4.0}],
+            "checksum":123,"timestamp":"2026-01-01T00:00:00.000000Z"}]}"#;
+        let price = parser.parse(snapshot).unwrap();
+        assert_eq!(price.bid, Decimal::from_str_exact("63000.2").unwrap());
+        assert_eq!(price.ask, Decimal::from_str_exact("63000.4").unwrap());
+        assert_eq!(price.last, Decimal::from_str_exact("63000.2").unwrap());
+        assert_eq!(price.timestamp.to_rfc3339(), "2026-01-01T00:00:00+00:00");
+
+        // The best ask is taken out; the next level becomes the touch
+        let update = r#"{"channel":"book","type":"update","data":[{"symbol":"BTC/USD",
+            "bids":[],"asks":[{"price":63000.4,"qty":0}],"checksum":456,
+            "timestamp":"2026-01-01T00:00:01.000000Z"}]}"#;
+        let price = parser.parse(update).unwrap();
+        assert_eq!(price.ask, Decimal::from(63001));
+    }
+
+    #[test]
+    fn test_book_before_ticker_is_not_a_price() {
+        let book = r#"{"channel":"book","type":"snapshot","data":[{"symbol":"SOL/USD",
+            "bids":[{"price":150.1,"qty":1}],"asks":[{"price":150.2,"qty":1}],"checksum":1}]}"#;
+        assert!(KrakenParser::new().parse(book).is_err());
+    }
+
+    #[test]
+    fn test_book_keeps_subscribed_depth() {
+        let mut quote = Quote::default();
+        for i in 0..15 {
+            quote.bids.insert(Decimal::from(100 - i), Decimal::ONE);
+            quote.asks.insert(Decimal::from(101 + i), Decimal::ONE);
+        }
+        quote.truncate(BOOK_DEPTH);
+        assert_eq!(quote.bids.len(), BOOK_DEPTH);
+        assert_eq!(quote.touch(), (Decimal::from(100), Decimal::from(101)));
+        assert_eq!(quote.asks.last_key_value().map(|(p, _)| *p), Some(Decimal::from(110)));
+    }
+
+    #[test]
+    fn test_xbt_symbols_are_normalized() {
+        let price = KrakenParser::new()
+            .parse(&TICKER.replace("BTC/USD", "XBT/USD"))
+            .unwrap();
+        assert_eq!(price.pair, "BTC/USD");
+    }
+
+    #[test]
+    fn test_control_and_error_messages() {
+        let parser = KrakenParser::new();
+        assert!(parser.is_control(r#"{"channel":"heartbeat"}"#));
+        assert!(parser.is_control(
+            r#"{"method":"subscribe","result":{"channel":"ticker","symbol":"BTC/USD"},"success":true,"time_in":"x","time_out":"y"}"#
+        ));
+        assert!(!parser.is_control(TICKER));
+
+        let rejected = r#"{"error":"Currency pair not supported FOO/BAR","method":"subscribe","success":false}"#;
+        assert!(!parser.is_control(rejected));
+        assert!(matches!(
+            parser.parse(rejected),
+            Err(ArbitrageError::ExchangeError { .. })
+        ));
+    }
+}
@@ -0,0 +1,291 @@
+//! Kraken REST API Client
+//!
+//! Private endpoints for balances and orders, signed with `KrakenAuth`.
+//!
+//! Based on: https://docs.kraken.com/api/docs/rest-api/add-order
+
+use super::auth::KrakenAuth;
+use super::symbols::KrakenSymbols;
+use super::types::{KrakenAddOrder, KrakenBalance, KrakenOpenOrders, KrakenOrder, KrakenResponse};
+use crate::error::{ArbitrageError, Result};
+use crate::exchanges::{Balance, ExchangeId, Order, OrderResult, OrderSide, OrderStatus, OrderType, split_pair};
+use chrono::Utc;
+use reqwest::Client;
+use rust_decimal::Decimal;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+
+/// Error prefixes that mean the key, signature or permissions are wrong
+const AUTH_ERRORS: [&str; 4] = [
+    "EAPI:Invalid key",
+    "EAPI:Invalid signature",
+    "EAPI:Invalid nonce",
+    "EGeneral:Permission denied",
+];
+
+/// Kraken rate-limit errors; the counter decays by about one call per 3 seconds
+const RATE_LIMIT_ERRORS: [&str; 2] = ["EAPI:Rate limit exceeded", "EOrder:Rate limit exceeded"];
+
+/// Suggested wait after a rate-limit error
+const RATE_LIMIT_BACKOFF_MS: u64 = 3000;
+
+/// Kraken REST API client
+pub struct KrakenRestClient {
+    client: Client,
+    auth: KrakenAuth,
+    symbols: KrakenSymbols,
+    base_url: String,
+}
+
+impl KrakenRestClient {
+    /// Create a new Kraken REST API client
+    ///
+    /// # Arguments
+    /// * `api_key` - API key
+    /// * `api_secret` - Base64-encoded private key
+    ///
+    /// # Returns
+    /// Result containing KrakenRestClient or AuthenticationError if the secret is malformed
+    pub fn new(api_key: String, api_secret: String) -> Result<Self> {
+        Ok(Self {
+            client: Client::new(),
+            auth: KrakenAuth::new(api_key, api_secret)?,
+            symbols: KrakenSymbols::default(),
+            base_url: crate::constants::api::KRAKEN_PRODUCTION.to_string(),
+        })
+    }
+
+    /// Translate asset and pair names with `symbols`
+    pub fn with_symbols(mut self, symbols: KrakenSymbols) -> Self {
+        self.symbols = symbols;
+        self
+    }
+
+    /// Point the client at a different REST base URL (e.g. a local test server)
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    /// Get available and on-hold balances for several assets in one request
+    ///
+    /// Assets are named by common ticker (`BTC`, not `XXBT`); assets without a
+    /// balance are omitted from the result.
+    #[tracing::instrument(name = "get_balances", skip(self))]
+    pub async fn get_balances(&self, assets: &[&str]) -> Result<HashMap<String, Balance>> {
+        let balances: HashMap<String, KrakenBalance> = self
+            .private(crate::constants::api::KRAKEN_BALANCE_PATH, &[], "balance")
+            .await?;
+
+        Ok(balances
+            .iter()
+            .map(|(code, balance)| (self.symbols.asset_from_kraken(code), balance))
+            .filter(|(asset, _)| assets.contains(asset))
+            .map(|(asset, balance)| (asset.to_string(), Balance::from(balance)))
+            .collect())
+    }
+
+    /// Get available balance for one asset (zero if the account holds none)
+    #[tracing::instrument(name = "get_balance", skip(self), fields(asset = %asset))]
+    pub async fn get_balance(&self, asset: &str) -> Result<Decimal> {
+        let balances = self.get_balances(&[asset]).await?;
+        Ok(balances.get(asset).map(|b| b.available).unwrap_or_default())
+    }
+
+    /// Place a market or limit order
+    ///
+    /// Kraken only returns the order id, so a filled market order is looked up
+    /// once for its fills; if that lookup fails the order is reported `Pending`.
+    #[tracing::instrument(name = "place_order", skip(self, order), fields(
+        pair = %order.pair,
+        side = ?order.side,
+        quantity = %order.quantity
+    ))]
+    pub async fn place_order(&self, order: Order) -> Result<OrderResult> {
+        let quote = quote_asset(&order.pair)?;
+        let mut params = vec![
+            ("pair", self.symbols.rest_pair(&order.pair)),
+            (
+                "type",
+                match order.side {
+                    OrderSide::Buy => "buy",
+                    OrderSide::Sell => "sell",
+                }
+                .to_string(),
+            ),
+            ("volume", order.quantity.normalize().to_string()),
+        ];
+        match order.order_type {
+            OrderType::Market => params.push(("ordertype", "market".to_string())),
+            OrderType::Limit { price } => {
+                params.push(("ordertype", "limit".to_string()));
+                params.push(("price", price.normalize().to_string()));
+            }
+        }
+
+        let added: KrakenAddOrder = self
+            .private(crate::constants::api::KRAKEN_ADD_ORDER_PATH, &params, "order")
+            .await?;
+        let txid = added.txid.into_iter().next().ok_or_else(|| ArbitrageError::ExchangeError {
+            exchange: ExchangeId::KRAKEN,
+            message: "Order response missing txid".to_string(),
+            code: None,
+        })?;
+
+        match self.get_order(&order.pair, &txid).await {
+            Ok(result) => Ok(result),
+            Err(e) => {
+                tracing::warn!(order_id = %txid, error = %e, "Order placed but lookup failed");
+                Ok(OrderResult {
+                    order_id: txid,
+                    status: OrderStatus::Pending,
+                    filled_quantity: Decimal::ZERO,
+                    average_price: None,
+                    fee: Decimal::ZERO,
+                    fee_asset: quote.to_string(),
+                    timestamp: Utc::now(),
+                })
+            }
+        }
+    }
+
+    /// Look up an order by txid
+    #[tracing::instrument(name = "get_order", skip(self), fields(order_id = %order_id))]
+    pub async fn get_order(&self, pair: &str, order_id: &str) -> Result<OrderResult> {
+        let quote = quote_asset(pair)?;
+        let mut orders: HashMap<String, KrakenOrder> = self
+            .private(
+                crate::constants::api::KRAKEN_QUERY_ORDERS_PATH,
+                &[("txid", order_id.to_string()), ("trades", "false".to_string())],
+                "order",
+            )
+            .await?;
+        let order = orders.remove(order_id).ok_or_else(|| ArbitrageError::ExchangeError {
+            exchange: ExchangeId::KRAKEN,
+            message: format!("Order not found: {}", order_id),
+            code: None,
+        })?;
+        Ok(order.into_result(order_id.to_string(), quote))
+    }
+
+    /// List open orders for a pair (e.g. "BTC/USD")
+    #[tracing::instrument(name = "get_open_orders", skip(self), fields(pair = %pair))]
+    pub async fn get_open_orders(&self, pair: &str) -> Result<Vec<OrderResult>> {
+        let quote = quote_asset(pair)?;
+        let rest_pair = self.symbols.rest_pair(pair);
+        let open: KrakenOpenOrders = self
+            .private(crate::constants::api::KRAKEN_OPEN_ORDERS_PATH, &[], "orders")
+            .await?;
+        Ok(open
+            .open
+            .into_iter()
+            .filter(|(_, order)| order.descr.pair == rest_pair)
+            .map(|(txid, order)| order.into_result(txid, quote))
+            .collect())
+    }
+
+    /// Signed POST to a private endpoint, unwrapping Kraken's `{error, result}` envelope
+    async fn private<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        params: &[(&str, String)],
+        what: &str,
+    ) -> Result<T> {
+        let (body, signature) = self.auth.signed_body(path, params);
+
+        let response = self
+            .client
+            .post(format!("{}{}", self.base_url, path))
+            .header("API-Key", self.auth.api_key())
+            .header("API-Sign", signature)
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| ArbitrageError::ExchangeError {
+                exchange: ExchangeId::KRAKEN,
+                message: format!("HTTP request failed: {}", e),
+                code: None,
+            })?;
+
+        let status = response.status();
+        let response_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unable to read response".to_string());
+
+        if !status.is_success() {
+            return Err(ArbitrageError::ExchangeError {
+                exchange: ExchangeId::KRAKEN,
+                message: format!("API error ({}): {}", status, response_text),
+                code: Some(status.as_u16() as i32),
+            });
+        }
+
+        let envelope: KrakenResponse<T> =
+            serde_json::from_str(&response_text).map_err(|e| ArbitrageError::ExchangeError {
+                exchange: ExchangeId::KRAKEN,
+                message: format!("Failed to parse {} response: {}", what, e),
+                code: None,
+            })?;
+
+        // Kraken reports failures with HTTP 200 and a non-empty error list
+        if let Some(error) = envelope.error.first() {
+            return Err(classify(error));
+        }
+        envelope.result.ok_or_else(|| ArbitrageError::ExchangeError {
+            exchange: ExchangeId::KRAKEN,
+            message: format!("{} response missing result", what),
+            code: None,
+        })
+    }
+}
+
+/// Map a Kraken error string (`EAPI:Invalid key`) to our error type
+fn classify(error: &str) -> ArbitrageError {
+    if AUTH_ERRORS.iter().any(|prefix| error.starts_with(prefix)) {
+        ArbitrageError::AuthenticationError {
+            exchange: ExchangeId::KRAKEN,
+            reason: error.to_string(),
+        }
+    } else if RATE_LIMIT_ERRORS.iter().any(|prefix| error.starts_with(prefix)) {
+        ArbitrageError::RateLimitExceeded {
+            exchange: ExchangeId::KRAKEN,
+            retry_after: RATE_LIMIT_BACKOFF_MS,
+        }
+    } else {
+        ArbitrageError::ExchangeError {
+            exchange: ExchangeId::KRAKEN,
+            message: error.to_string(),
+            code: None,
+        }
+    }
+}
+
+fn quote_asset(pair: &str) -> Result<&str> {
+    split_pair(pair)
+        .map(|(_, quote)| quote)
+        .ok_or_else(|| ArbitrageError::ExchangeError {
+            exchange: ExchangeId::KRAKEN,
+            message: format!("Invalid pair: {}", pair),
+            code: None,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_errors() {
+        assert!(matches!(
+            classify("EAPI:Invalid key"),
+            ArbitrageError::AuthenticationError { .. }
+        ));
+        assert_eq!(
+            classify("EAPI:Rate limit exceeded").retry_after(),
+            Some(std::time::Duration::from_millis(RATE_LIMIT_BACKOFF_MS))
+        );
+        assert!(classify("EOrder:Insufficient funds").to_string().contains("Insufficient funds"));
+    }
+}
@@ -0,0 +1,151 @@
+//! Kraken asset and pair naming
+//!
+//! Kraken predates today's tickers: bitcoin is `XBT`, dogecoin `XDG`, and the
+//! oldest assets carry an `X` (crypto) or `Z` (fiat) prefix in REST responses
+//! (`XXBT`, `ZUSD`). WebSocket v2 uses the common names (`BTC/USD`).
+//!
+//! Based on: https://docs.kraken.com/api/docs/rest-api/get-asset-info
+
+/// Built-in assets whose Kraken names differ from the common ticker:
+/// `(common, Kraken altname, legacy REST code)`
+const ASSETS: [(&str, &str, &str); 17] = [
+    ("BTC", "XBT", "XXBT"),
+    ("DOGE", "XDG", "XXDG"),
+    ("ETH", "ETH", "XETH"),
+    ("ETC", "ETC", "XETC"),
+    ("LTC", "LTC", "XLTC"),
+    ("MLN", "MLN", "XMLN"),
+    ("REP", "REP", "XREP"),
+    ("XLM", "XLM", "XXLM"),
+    ("XMR", "XMR", "XXMR"),
+    ("XRP", "XRP", "XXRP"),
+    ("ZEC", "ZEC", "XZEC"),
+    ("AUD", "AUD", "ZAUD"),
+    ("CAD", "CAD", "ZCAD"),
+    ("EUR", "EUR", "ZEUR"),
+    ("GBP", "GBP", "ZGBP"),
+    ("JPY", "JPY", "ZJPY"),
+    ("USD", "USD", "ZUSD"),
+];
+
+/// Maps between our asset and pair names and Kraken's
+///
+/// # Business Logic
+///
+/// Config, prices and balances use common tickers (`BTC/USD`, `BTC`). This
+/// table translates at the edges:
+///
+/// - REST requests take altname pairs (`XBTUSD`)
+/// - REST responses name assets by legacy code (`XXBT`) or altname (`SOL`)
+/// - WebSocket v2 symbols are common names, but `XBT/USD` is accepted too
+///
+/// Assets not in the table (most listed since 2018) have the same name
+/// everywhere. `with_asset()` adds or overrides an entry.
+///
+/// ```rust
+/// use arb_bot::exchanges::kraken::KrakenSymbols;
+///
+/// let symbols = KrakenSymbols::default();
+/// assert_eq!(symbols.rest_pair("BTC/USD"), "XBTUSD");
+/// assert_eq!(symbols.ws_symbol("XBT/USDC"), "BTC/USDC");
+/// assert_eq!(symbols.asset_from_kraken("XXBT"), "BTC");
+/// assert_eq!(symbols.asset_from_kraken("SOL"), "SOL");
+/// ```
+#[derive(Debug, Clone)]
+pub struct KrakenSymbols {
+    /// `(common, altname, legacy)` entries; later entries win
+    assets: Vec<(String, String, String)>,
+}
+
+impl Default for KrakenSymbols {
+    fn default() -> Self {
+        Self {
+            assets: ASSETS
+                .iter()
+                .map(|(common, alt, legacy)| (common.to_string(), alt.to_string(), legacy.to_string()))
+                .collect(),
+        }
+    }
+}
+
+impl KrakenSymbols {
+    /// Add or override an asset: `common` is called `altname` on Kraken
+    pub fn with_asset(mut self, common: &str, altname: &str) -> Self {
+        self.assets.push((common.to_string(), altname.to_string(), altname.to_string()));
+        self
+    }
+
+    /// Kraken altname for a common asset (`BTC` -> `XBT`)
+    pub fn asset_to_kraken<'a>(&'a self, asset: &'a str) -> &'a str {
+        self.assets
+            .iter()
+            .rev()
+            .find(|(common, _, _)| common.eq_ignore_ascii_case(asset))
+            .map(|(_, alt, _)| alt.as_str())
+            .unwrap_or(asset)
+    }
+
+    /// Common name for a Kraken asset code, legacy or altname (`XXBT`/`XBT` -> `BTC`)
+    pub fn asset_from_kraken<'a>(&'a self, code: &'a str) -> &'a str {
+        self.assets
+            .iter()
+            .rev()
+            .find(|(_, alt, legacy)| alt.eq_ignore_ascii_case(code) || legacy.eq_ignore_ascii_case(code))
+            .map(|(common, _, _)| common.as_str())
+            .unwrap_or(code)
+    }
+
+    /// REST pair name: altnames joined without a separator (`BTC/USD` -> `XBTUSD`)
+    pub fn rest_pair(&self, pair: &str) -> String {
+        match pair.split_once('/') {
+            Some((base, quote)) => {
+                format!("{}{}", self.asset_to_kraken(base), self.asset_to_kraken(quote))
+            }
+            None => pair.to_string(),
+        }
+    }
+
+    /// WebSocket v2 symbol, which is also our pair name (`XBT/USD` -> `BTC/USD`)
+    pub fn ws_symbol(&self, pair: &str) -> String {
+        match pair.split_once('/') {
+            Some((base, quote)) => format!(
+                "{}/{}",
+                self.asset_from_kraken(base),
+                self.asset_from_kraken(quote)
+            ),
+            None => pair.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_legacy_and_altnames() {
+        let symbols = KrakenSymbols::default();
+        assert_eq!(symbols.asset_from_kraken("ZUSD"), "USD");
+        assert_eq!(symbols.asset_from_kraken("XBT"), "BTC");
+        assert_eq!(symbols.asset_from_kraken("XXDG"), "DOGE");
+        assert_eq!(symbols.asset_from_kraken("USDC"), "USDC");
+        assert_eq!(symbols.asset_to_kraken("DOGE"), "XDG");
+        assert_eq!(symbols.asset_to_kraken("SOL"), "SOL");
+    }
+
+    #[test]
+    fn maps_pairs() {
+        let symbols = KrakenSymbols::default();
+        assert_eq!(symbols.rest_pair("SOL/USDC"), "SOLUSDC");
+        assert_eq!(symbols.rest_pair("DOGE/USD"), "XDGUSD");
+        assert_eq!(symbols.ws_symbol("BTC/USD"), "BTC/USD");
+        assert_eq!(symbols.ws_symbol("XDG/EUR"), "DOGE/EUR");
+    }
+
+    #[test]
+    fn overrides_win() {
+        let symbols = KrakenSymbols::default().with_asset("BTC", "TBTC");
+        assert_eq!(symbols.rest_pair("BTC/USD"), "TBTCUSD");
+        assert_eq!(symbols.asset_from_kraken("TBTC"), "BTC");
+    }
+}
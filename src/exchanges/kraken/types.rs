@@ -0,0 +1,154 @@
+//! Kraken-specific REST response types
+
+use crate::exchanges::{Balance, OrderResult, OrderStatus};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Envelope of every REST response: errors such as `EAPI:Invalid key`, or a result
+#[derive(Debug, Deserialize)]
+pub struct KrakenResponse<T> {
+    #[serde(default)]
+    pub error: Vec<String>,
+    pub result: Option<T>,
+}
+
+/// One asset in the `BalanceEx` result, keyed by Kraken asset code
+#[derive(Debug, Deserialize)]
+pub struct KrakenBalance {
+    #[serde(deserialize_with = "decimal_from_str")]
+    pub balance: Decimal,
+    /// Reserved by open orders
+    #[serde(default, deserialize_with = "decimal_from_str")]
+    pub hold_trade: Decimal,
+}
+
+impl From<&KrakenBalance> for Balance {
+    fn from(balance: &KrakenBalance) -> Self {
+        Balance {
+            available: balance.balance - balance.hold_trade,
+            hold: balance.hold_trade,
+        }
+    }
+}
+
+/// `AddOrder` result
+#[derive(Debug, Deserialize)]
+pub struct KrakenAddOrder {
+    pub txid: Vec<String>,
+}
+
+/// `OpenOrders` result
+#[derive(Debug, Deserialize)]
+pub struct KrakenOpenOrders {
+    #[serde(default)]
+    pub open: HashMap<String, KrakenOrder>,
+}
+
+/// An order as returned by `QueryOrders` and `OpenOrders`
+#[derive(Debug, Deserialize)]
+pub struct KrakenOrder {
+    /// `pending`, `open`, `closed`, `canceled` or `expired`
+    pub status: String,
+    /// Altname pair, e.g. `XBTUSD`
+    pub descr: KrakenOrderDescription,
+    #[serde(deserialize_with = "decimal_from_str")]
+    pub vol_exec: Decimal,
+    /// Quote-currency value of the executed volume
+    #[serde(deserialize_with = "decimal_from_str")]
+    pub cost: Decimal,
+    /// Charged in the quote currency unless the order asked otherwise
+    #[serde(deserialize_with = "decimal_from_str")]
+    pub fee: Decimal,
+    /// Unix seconds with fractional part
+    pub opentm: f64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct KrakenOrderDescription {
+    pub pair: String,
+}
+
+impl KrakenOrder {
+    /// Convert to our `OrderResult`; `quote` is the pair's quote asset, in which fees are paid
+    pub fn into_result(self, order_id: String, quote: &str) -> OrderResult {
+        let filled = self.vol_exec > Decimal::ZERO;
+        let status = match self.status.as_str() {
+            "closed" => OrderStatus::Filled,
+            "open" if filled => OrderStatus::PartiallyFilled,
+            "pending" | "open" => OrderStatus::Pending,
+            "canceled" | "expired" => OrderStatus::Cancelled,
+            _ => OrderStatus::Failed,
+        };
+
+        OrderResult {
+            order_id,
+            status,
+            filled_quantity: self.vol_exec,
+            average_price: filled.then(|| self.cost / self.vol_exec),
+            fee: self.fee,
+            fee_asset: quote.to_string(),
+            timestamp: DateTime::from_timestamp_millis((self.opentm * 1000.0) as i64)
+                .unwrap_or_else(Utc::now),
+        }
+    }
+}
+
+fn decimal_from_str<'de, D>(deserializer: D) -> Result<Decimal, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    Decimal::from_str_exact(&s).map_err(serde::de::Error::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_order_conversion() {
+        let order: KrakenOrder = serde_json::from_str(
+            r#"{"status":"closed","descr":{"pair":"XBTUSD","type":"buy","ordertype":"market"},
+                "vol":"0.5","vol_exec":"0.5","cost":"30000.0","fee":"78.0","opentm":1700000000.25}"#,
+        )
+        .unwrap();
+
+        let result = order.into_result("OQCLML-BW3P3-BUCMWZ".to_string(), "USD");
+        assert_eq!(result.status, OrderStatus::Filled);
+        assert_eq!(result.average_price, Some(Decimal::from(60000)));
+        assert_eq!(result.fee, Decimal::from(78));
+        assert_eq!(result.fee_asset, "USD");
+        assert_eq!(result.timestamp.timestamp_millis(), 1_700_000_000_250);
+    }
+
+    #[test]
+    fn test_status_mapping() {
+        let order = |status: &str, vol_exec: &str| KrakenOrder {
+            status: status.to_string(),
+            descr: KrakenOrderDescription {
+                pair: "SOLUSD".to_string(),
+            },
+            vol_exec: Decimal::from_str_exact(vol_exec).unwrap(),
+            cost: Decimal::ZERO,
+            fee: Decimal::ZERO,
+            opentm: 0.0,
+        };
+        let status = |status, vol_exec| order(status, vol_exec).into_result("o".to_string(), "USD").status;
+
+        assert_eq!(status("open", "0"), OrderStatus::Pending);
+        assert_eq!(status("open", "1"), OrderStatus::PartiallyFilled);
+        assert_eq!(status("expired", "1"), OrderStatus::Cancelled);
+        assert_eq!(status("rejected", "0"), OrderStatus::Failed);
+    }
+
+    #[test]
+    fn test_balance_subtracts_hold() {
+        let balance: KrakenBalance =
+            serde_json::from_str(r#"{"balance":"10.5","hold_trade":"0.5"}"#).unwrap();
+        let balance = Balance::from(&balance);
+        assert_eq!(balance.available, Decimal::from(10));
+        assert_eq!(balance.total(), Decimal::new(105, 1));
+    }
+}
@@ -0,0 +1,211 @@
+//! In-memory exchange for tests and offline wiring
+//!
+//! Prices, balances and order outcomes are whatever the caller sets; nothing
+//! goes over the network.
+
+use crate::error::{ArbitrageError, Result};
+use crate::exchanges::{
+    Exchange, ExchangeId, Order, OrderResult, OrderStatus, PRICE_UPDATES_CAPACITY, Price,
+};
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+
+/// Simulates exchange behavior for integration tests without real API calls.
+///
+/// Clones share state, so a test can keep a handle to a mock it handed out.
+#[derive(Clone)]
+pub struct MockExchange {
+    id: ExchangeId,
+    connected: Arc<RwLock<bool>>,
+    prices: Arc<RwLock<HashMap<String, Price>>>,
+    prices_tx: broadcast::Sender<Price>,
+    balances: Arc<RwLock<HashMap<String, Decimal>>>,
+    subscriptions: Arc<RwLock<Vec<String>>>,
+    fail_orders: Arc<RwLock<bool>>,
+    orders_placed: Arc<RwLock<usize>>,
+    orders: Arc<RwLock<HashMap<String, OrderResult>>>,
+    open_orders: Arc<RwLock<HashMap<String, Vec<OrderResult>>>>,
+}
+
+impl MockExchange {
+    /// Mock reporting as `name`; panics if it isn't a valid exchange name
+    pub fn new(name: impl AsRef<str>) -> Self {
+        let name = name.as_ref();
+        Self {
+            id: ExchangeId::from_name(name)
+                .unwrap_or_else(|| panic!("invalid exchange name: {:?}", name)),
+            connected: Arc::new(RwLock::new(false)),
+            prices: Arc::new(RwLock::new(HashMap::new())),
+            prices_tx: broadcast::channel(PRICE_UPDATES_CAPACITY).0,
+            balances: Arc::new(RwLock::new(HashMap::new())),
+            subscriptions: Arc::new(RwLock::new(Vec::new())),
+            fail_orders: Arc::new(RwLock::new(false)),
+            orders_placed: Arc::new(RwLock::new(0)),
+            orders: Arc::new(RwLock::new(HashMap::new())),
+            open_orders: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Set the latest price and publish it to `price_updates()` receivers
+    pub fn set_price(&self, pair: &str, price: Price) {
+        self.prices.write().insert(pair.to_string(), price.clone());
+        let _ = self.prices_tx.send(price);
+    }
+
+    pub fn set_balance(&self, asset: &str, amount: Decimal) {
+        self.balances.write().insert(asset.to_string(), amount);
+    }
+
+    /// Make subsequent orders fail with an exchange error
+    pub fn set_fail_orders(&self, fail: bool) {
+        *self.fail_orders.write() = fail;
+    }
+
+    /// Number of orders that reached the exchange
+    pub fn orders_placed(&self) -> usize {
+        *self.orders_placed.read()
+    }
+
+    /// Set what `get_order()` returns for `result.order_id`
+    pub fn set_order(&self, result: OrderResult) {
+        self.orders.write().insert(result.order_id.clone(), result);
+    }
+
+    /// Add an order that `get_open_orders()` reports as working
+    pub fn add_open_order(&self, pair: &str, result: OrderResult) {
+        self.open_orders
+            .write()
+            .entry(pair.to_string())
+            .or_default()
+            .push(result);
+    }
+}
+
+#[async_trait]
+#[allow(clippy::result_large_err)]
+impl Exchange for MockExchange {
+    async fn connect(&mut self) -> Result<()> {
+        *self.connected.write() = true;
+        Ok(())
+    }
+
+    async fn subscribe_ticker(&mut self, pair: &str) -> Result<()> {
+        if !*self.connected.read() {
+            return Err(ArbitrageError::NetworkError {
+                message: "Not connected".to_string(),
+                retry_after: None,
+            });
+        }
+
+        self.subscriptions.write().push(pair.to_string());
+        Ok(())
+    }
+
+    async fn get_latest_price(&self, pair: &str) -> Result<Price> {
+        if !*self.connected.read() {
+            return Err(ArbitrageError::NetworkError {
+                message: "Not connected".to_string(),
+                retry_after: None,
+            });
+        }
+
+        self.prices
+            .read()
+            .get(pair)
+            .cloned()
+            .ok_or_else(|| ArbitrageError::ParseError {
+                message: format!("Price not found for pair: {}", pair),
+                input: None,
+            })
+    }
+
+    async fn place_order(&mut self, order: Order) -> Result<OrderResult> {
+        if !*self.connected.read() {
+            return Err(ArbitrageError::NetworkError {
+                message: "Not connected".to_string(),
+                retry_after: None,
+            });
+        }
+
+        *self.orders_placed.write() += 1;
+        if *self.fail_orders.read() {
+            return Err(ArbitrageError::ExchangeError {
+                exchange: self.id,
+                message: "Order rejected".to_string(),
+                code: None,
+            });
+        }
+
+        // Sequential so several orders in one test get distinct IDs
+        let order_id = format!("mock_{}", *self.orders_placed.read());
+
+        let result = OrderResult {
+            order_id,
+            status: OrderStatus::Filled,
+            filled_quantity: order.quantity,
+            average_price: self.prices.read().get(&order.pair).map(|p| p.mid_price()),
+            fee: Decimal::from(1),
+            fee_asset: "USDC".to_string(),
+            timestamp: chrono::Utc::now(),
+        };
+        self.orders
+            .write()
+            .insert(result.order_id.clone(), result.clone());
+        Ok(result)
+    }
+
+    async fn get_order(&self, _pair: &str, order_id: &str) -> Result<OrderResult> {
+        self.orders
+            .read()
+            .get(order_id)
+            .cloned()
+            .ok_or_else(|| ArbitrageError::ExchangeError {
+                exchange: self.id,
+                message: format!("Unknown order: {}", order_id),
+                code: None,
+            })
+    }
+
+    async fn get_open_orders(&self, pair: &str) -> Result<Vec<OrderResult>> {
+        Ok(self.open_orders.read().get(pair).cloned().unwrap_or_default())
+    }
+
+    async fn get_balance(&self, asset: &str) -> Result<Decimal> {
+        if !*self.connected.read() {
+            return Err(ArbitrageError::NetworkError {
+                message: "Not connected".to_string(),
+                retry_after: None,
+            });
+        }
+
+        self.balances.read().get(asset).copied().ok_or_else(|| {
+            ArbitrageError::InsufficientBalance {
+                exchange: self.id,
+                asset: asset.to_string(),
+                required: "0".to_string(),
+                available: "0".to_string(),
+            }
+        })
+    }
+
+    fn id(&self) -> ExchangeId {
+        self.id
+    }
+
+    fn is_connected(&self) -> bool {
+        *self.connected.read()
+    }
+
+    fn price_updates(&self) -> Option<broadcast::Receiver<Price>> {
+        Some(self.prices_tx.subscribe())
+    }
+
+    async fn disconnect(&mut self) -> Result<()> {
+        *self.connected.write() = false;
+        Ok(())
+    }
+}
@@ -1,11 +1,30 @@
 pub mod binance;
+pub mod bybit;
 pub mod coinbase;
+pub mod factory;
+pub mod id;
+pub mod kraken;
+pub mod okx;
+pub mod mock;
+pub mod paper;
+pub mod symbols;
 pub mod types;
 
-pub use types::{Order, OrderResult, OrderSide, OrderStatus, OrderType, Price};
+pub use id::ExchangeId;
+pub use types::{
+    Balance, Order, OrderResult, OrderSide, OrderStatus, OrderType, Price, split_pair,
+};
 
+use crate::clock::ClockSync;
 use crate::error::Result;
+use crate::state::SequencePolicy;
+use crate::websocket::ConnectionState;
 use async_trait::async_trait;
+use std::collections::HashMap;
+use tokio::sync::{broadcast, watch};
+
+/// Quotes buffered for each `price_updates()` receiver before it lags
+pub const PRICE_UPDATES_CAPACITY: usize = 100;
 
 /// Trait abstraction for cryptocurrency exchange interactions.
 ///
@@ -45,12 +64,159 @@ pub trait Exchange: Send + Sync {
     /// Get account balance for an asset
     async fn get_balance(&self, asset: &str) -> Result<rust_decimal::Decimal>;
 
+    /// Get available and on-hold balances for several assets
+    ///
+    /// Defaults to one `get_balance()` call per asset with no hold. Exchanges that
+    /// return all accounts in one request should override this.
+    async fn get_balances(&self, assets: &[&str]) -> Result<HashMap<String, Balance>> {
+        let mut balances = HashMap::new();
+        for asset in assets {
+            let available = self.get_balance(asset).await?;
+            balances.insert(
+                asset.to_string(),
+                Balance {
+                    available,
+                    hold: rust_decimal::Decimal::ZERO,
+                },
+            );
+        }
+        Ok(balances)
+    }
+
+    /// Look up an order's current status and fills
+    async fn get_order(&self, _pair: &str, order_id: &str) -> Result<OrderResult> {
+        Err(crate::error::ArbitrageError::ExchangeError {
+            exchange: self.id(),
+            message: format!("Order lookup not supported (order {})", order_id),
+            code: None,
+        })
+    }
+
+    /// Orders still working on the exchange for a pair
+    async fn get_open_orders(&self, pair: &str) -> Result<Vec<OrderResult>> {
+        Err(crate::error::ArbitrageError::ExchangeError {
+            exchange: self.id(),
+            message: format!("Open order queries not supported ({})", pair),
+            code: None,
+        })
+    }
+
+    /// Identifier of this exchange (or account), shared with price state, config and errors
+    fn id(&self) -> ExchangeId;
+
     /// Get exchange name
-    fn name(&self) -> &str;
+    fn name(&self) -> &str {
+        self.id().as_str()
+    }
 
     /// Check if connected
     fn is_connected(&self) -> bool;
 
+    /// Current connection lifecycle state
+    ///
+    /// Defaults to `Live`/`Disconnected` from `is_connected()` for exchanges
+    /// without a WebSocket manager.
+    fn connection_state(&self) -> ConnectionState {
+        if self.is_connected() {
+            ConnectionState::Live
+        } else {
+            ConnectionState::Disconnected
+        }
+    }
+
+    /// Subscribe to connection state transitions, if the exchange publishes them
+    fn watch_connection_state(&self) -> Option<watch::Receiver<ConnectionState>> {
+        None
+    }
+
+    /// Exchange clock used to timestamp signed requests, if the exchange syncs one
+    fn clock(&self) -> Option<ClockSync> {
+        None
+    }
+
+    /// Every quote the ticker feed receives, in arrival order, if the exchange streams them
+    ///
+    /// Unlike `get_latest_price()` nothing is skipped, so sequence numbers can be
+    /// gap-checked. Receivers stay valid across resubscribes and reconnects.
+    fn price_updates(&self) -> Option<broadcast::Receiver<Price>> {
+        None
+    }
+
+    /// How the feed's sequence numbers are checked, if not the tracker's default
+    fn sequence_policy(&self) -> Option<SequencePolicy> {
+        None
+    }
+
     /// Disconnect from exchange
     async fn disconnect(&mut self) -> Result<()>;
 }
+
+/// Lets boxed exchanges be used wherever `E: Exchange` is expected
+/// (e.g. `GuardedExchange<Box<dyn Exchange>>` for an exchange chosen at runtime)
+#[async_trait]
+#[allow(clippy::result_large_err)]
+impl<E: Exchange + ?Sized> Exchange for Box<E> {
+    async fn connect(&mut self) -> Result<()> {
+        (**self).connect().await
+    }
+
+    async fn subscribe_ticker(&mut self, pair: &str) -> Result<()> {
+        (**self).subscribe_ticker(pair).await
+    }
+
+    async fn get_latest_price(&self, pair: &str) -> Result<Price> {
+        (**self).get_latest_price(pair).await
+    }
+
+    async fn place_order(&mut self, order: Order) -> Result<OrderResult> {
+        (**self).place_order(order).await
+    }
+
+    async fn get_balance(&self, asset: &str) -> Result<rust_decimal::Decimal> {
+        (**self).get_balance(asset).await
+    }
+
+    async fn get_balances(&self, assets: &[&str]) -> Result<HashMap<String, Balance>> {
+        (**self).get_balances(assets).await
+    }
+
+    async fn get_order(&self, pair: &str, order_id: &str) -> Result<OrderResult> {
+        (**self).get_order(pair, order_id).await
+    }
+
+    async fn get_open_orders(&self, pair: &str) -> Result<Vec<OrderResult>> {
+        (**self).get_open_orders(pair).await
+    }
+
+    fn id(&self) -> ExchangeId {
+        (**self).id()
+    }
+
+    fn is_connected(&self) -> bool {
+        (**self).is_connected()
+    }
+
+    fn connection_state(&self) -> ConnectionState {
+        (**self).connection_state()
+    }
+
+    fn watch_connection_state(&self) -> Option<watch::Receiver<ConnectionState>> {
+        (**self).watch_connection_state()
+    }
+
+    fn clock(&self) -> Option<ClockSync> {
+        (**self).clock()
+    }
+
+    fn price_updates(&self) -> Option<broadcast::Receiver<Price>> {
+        (**self).price_updates()
+    }
+
+    fn sequence_policy(&self) -> Option<SequencePolicy> {
+        (**self).sequence_policy()
+    }
+
+    async fn disconnect(&mut self) -> Result<()> {
+        (**self).disconnect().await
+    }
+}
@@ -0,0 +1,418 @@
+//! OKX Exchange WebSocket Implementation
+//!
+//! Public `tickers`/`books5` channels for prices, and the login-authenticated
+//! private `orders` channel for order updates.
+
+use crate::clock::{ClockSync, ClockSyncConfig, OkxTimeSource, SyncedClock};
+use crate::config::OkxConfig;
+use crate::error::{ArbitrageError, Result};
+use crate::exchanges::symbols::SymbolFormat;
+use crate::exchanges::{Balance, Exchange, ExchangeId, OrderResult, PRICE_UPDATES_CAPACITY, Price};
+use crate::logger::{debug, error, warn};
+use crate::websocket::{CircuitBreaker, ConnectionState, ReconnectionStrategy, WebSocketManager};
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::{broadcast, watch};
+
+use super::auth::OkxAuth;
+use super::parser::{OkxOrderParser, OkxParser};
+use super::rest::OkxRestClient;
+
+/// Reconnect if no data arrives for this long
+const FEED_INACTIVITY_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// OKX closes connections that are silent for 30 seconds, so send `"ping"` well within that
+const HEARTBEAT_INTERVAL: std::time::Duration = std::time::Duration::from_secs(20);
+
+/// OKX exchange implementation using WebSocket for price feeds and order updates
+///
+/// # Business Logic
+///
+/// `subscribe_ticker()` opens the public stream with the `tickers` and
+/// `books5` channels for a pair; prices are stored in-memory and can be
+/// queried via `get_latest_price()`.
+///
+/// With API credentials, `connect()` also opens the private stream: it logs in
+/// (re-signed on every reconnect), subscribes to spot `orders`, and keeps the
+/// latest state of each order. `get_order()` answers finished orders from
+/// there and asks the REST API otherwise; `order_updates()` streams them.
+pub struct OkxExchange {
+    id: ExchangeId,
+    config: OkxConfig,
+    /// Public stream task (moved into spawned task on subscribe)
+    ws_manager_handle: Option<tokio::task::JoinHandle<()>>,
+    /// Private stream task, only with API credentials
+    orders_handle: Option<tokio::task::JoinHandle<()>>,
+    /// In-memory store of latest prices by trading pair
+    latest_prices: Arc<RwLock<HashMap<String, Price>>>,
+    /// Re-broadcasts every parsed quote to `price_updates()` receivers
+    prices_tx: broadcast::Sender<Price>,
+    /// Latest state of each order seen on the private stream, by order id
+    orders: Arc<RwLock<HashMap<String, OrderResult>>>,
+    /// Re-broadcasts order updates to `order_updates()` receivers
+    orders_tx: broadcast::Sender<OrderResult>,
+    /// Connection state of the public stream
+    state_tx: watch::Sender<ConnectionState>,
+    /// REST API client for trading operations (optional, only if API credentials provided)
+    rest_client: Option<OkxRestClient>,
+    /// OKX clock for REST and login timestamps, synced while credentials are configured
+    clock: Option<SyncedClock>,
+}
+
+impl OkxExchange {
+    /// Create a new OKX exchange instance
+    pub fn new(config: OkxConfig) -> Result<Self> {
+        let (rest_client, clock) = if !config.api_key.is_empty() && !config.api_secret.is_empty() {
+            let clock = SyncedClock::start(
+                ClockSync::new(crate::constants::exchange::OKX, ClockSyncConfig::default()),
+                Arc::new(OkxTimeSource::new()),
+            );
+            let rest_client = OkxRestClient::new(
+                config.api_key.clone(),
+                config.api_secret.clone(),
+                config.passphrase.clone(),
+                config.demo,
+            )
+            .with_clock(clock.clock().clone());
+            (Some(rest_client), Some(clock))
+        } else {
+            (None, None)
+        };
+
+        Ok(Self {
+            id: ExchangeId::OKX,
+            config,
+            ws_manager_handle: None,
+            orders_handle: None,
+            latest_prices: Arc::new(RwLock::new(HashMap::new())),
+            prices_tx: broadcast::channel(PRICE_UPDATES_CAPACITY).0,
+            orders: Arc::new(RwLock::new(HashMap::new())),
+            orders_tx: broadcast::channel(100).0,
+            state_tx: watch::Sender::new(ConnectionState::Disconnected),
+            rest_client,
+            clock,
+        })
+    }
+
+    /// Report as `id` instead, e.g. `okx:hedge` for a second account
+    pub fn with_id(mut self, id: ExchangeId) -> Self {
+        self.id = id;
+        self
+    }
+
+    /// Order updates from the private stream (empty until `connect()` with credentials)
+    pub fn order_updates(&self) -> broadcast::Receiver<OrderResult> {
+        self.orders_tx.subscribe()
+    }
+
+    fn public_url(&self) -> &'static str {
+        if self.config.demo {
+            crate::constants::websocket::OKX_DEMO_PUBLIC
+        } else {
+            crate::constants::websocket::OKX_PUBLIC
+        }
+    }
+
+    fn private_url(&self) -> &'static str {
+        if self.config.demo {
+            crate::constants::websocket::OKX_DEMO_PRIVATE
+        } else {
+            crate::constants::websocket::OKX_PRIVATE
+        }
+    }
+
+    fn reconnect_strategy() -> ReconnectionStrategy {
+        // Jitter keeps feeds that dropped together from reconnecting in lockstep
+        ReconnectionStrategy::exponential_backoff()
+            .with_jitter()
+            .with_circuit_breaker(CircuitBreaker::default())
+    }
+
+    /// Build the public subscription message for a trading pair
+    ///
+    /// Format: {"op":"subscribe","args":[{"channel":"tickers","instId":"SOL-USDC"},{"channel":"books5","instId":"SOL-USDC"}]}
+    pub fn subscribe_message(pair: &str) -> String {
+        let inst_id = SymbolFormat::Dashed.to_venue(pair);
+        serde_json::json!({
+            "op": "subscribe",
+            "args": [
+                { "channel": "tickers", "instId": inst_id },
+                { "channel": "books5", "instId": inst_id },
+            ]
+        })
+        .to_string()
+    }
+
+    /// Connect the public stream with ticker and book subscriptions for `pair`
+    #[tracing::instrument(name = "connect_with_subscription", skip(self), fields(exchange = %self.id, pair = %pair))]
+    async fn connect_with_subscription(&mut self, pair: &str) -> Result<()> {
+        let subscribe_text = Self::subscribe_message(pair);
+        debug!(subscription = %subscribe_text, "Registering subscription message");
+
+        let (manager, mut price_rx) = WebSocketManager::new(
+            self.id,
+            self.public_url().to_string(),
+            OkxParser::new(),
+            Self::reconnect_strategy(),
+        );
+        let mut manager = manager
+            .with_on_connect_messages(vec![subscribe_text])
+            .with_heartbeat("ping", HEARTBEAT_INTERVAL)
+            .with_inactivity_timeout(FEED_INACTIVITY_TIMEOUT)
+            .with_state_sender(self.state_tx.clone());
+
+        self.ws_manager_handle = Some(tokio::spawn(async move {
+            if let Err(e) = manager.run().await {
+                error!(error = %e, "OKX WebSocket manager error");
+            }
+        }));
+
+        let prices = self.latest_prices.clone();
+        let prices_tx = self.prices_tx.clone();
+        tokio::spawn(async move {
+            loop {
+                match price_rx.recv().await {
+                    Ok(price) => {
+                        prices.write().insert(price.pair.clone(), price.clone());
+                        // Nobody listening is fine; pollers read the cache
+                        let _ = prices_tx.send(price);
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!(skipped = skipped, "Lagged messages");
+                        continue;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => {
+                        error!("Broadcast channel closed");
+                        break;
+                    }
+                }
+            }
+        });
+
+        Ok(())
+    }
+
+    /// Open the private stream: login, then subscribe to spot order updates
+    fn connect_orders(&mut self) {
+        let mut auth = OkxAuth::new(
+            self.config.api_key.clone(),
+            self.config.api_secret.clone(),
+            self.config.passphrase.clone(),
+        );
+        if let Some(clock) = &self.clock {
+            auth = auth.with_clock(clock.clock().clone());
+        }
+        let auth = Arc::new(auth);
+        let subscribe = serde_json::json!({
+            "op": "subscribe",
+            "args": [{ "channel": "orders", "instType": "SPOT" }]
+        })
+        .to_string();
+
+        let (manager, mut updates_rx) = WebSocketManager::new(
+            self.id,
+            self.private_url().to_string(),
+            OkxOrderParser::new(),
+            Self::reconnect_strategy(),
+        );
+        // No inactivity timeout: a quiet account is not a dead connection
+        let mut manager = manager
+            .with_login(move || auth.login_message())
+            .with_on_connect_messages(vec![subscribe])
+            .with_heartbeat("ping", HEARTBEAT_INTERVAL);
+
+        let handle = tokio::spawn(async move {
+            if let Err(e) = manager.run().await {
+                error!(error = %e, "OKX private WebSocket manager error");
+            }
+        });
+        self.orders_handle = Some(handle);
+
+        let orders = self.orders.clone();
+        let orders_tx = self.orders_tx.clone();
+        tokio::spawn(async move {
+            loop {
+                match updates_rx.recv().await {
+                    Ok(updates) => {
+                        for update in updates {
+                            orders.write().insert(update.order_id.clone(), update.clone());
+                            let _ = orders_tx.send(update);
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!(skipped = skipped, "Lagged order updates");
+                        continue;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+    }
+
+    fn rest_client(&self) -> Result<&OkxRestClient> {
+        self.rest_client
+            .as_ref()
+            .ok_or_else(|| ArbitrageError::ExchangeError {
+                exchange: self.id,
+                message: "REST API not available - API credentials required".to_string(),
+                code: None,
+            })
+    }
+}
+
+#[async_trait::async_trait]
+impl Exchange for OkxExchange {
+    /// Open the private order stream if API credentials are configured
+    ///
+    /// The public stream is opened by `subscribe_ticker()`, which knows the pair.
+    async fn connect(&mut self) -> Result<()> {
+        if self.rest_client.is_some() && self.orders_handle.is_none() {
+            self.connect_orders();
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "subscribe_ticker", skip(self), fields(exchange = %self.id, pair = %pair))]
+    async fn subscribe_ticker(&mut self, pair: &str) -> Result<()> {
+        // Replace the public stream only; the private one is per account, not per pair
+        if let Some(handle) = self.ws_manager_handle.take() {
+            handle.abort();
+        }
+        self.latest_prices.write().clear();
+
+        self.connect_with_subscription(pair).await?;
+
+        // Wait for first price to arrive (max 10 seconds)
+        for _ in 0..100 {
+            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+            if self.latest_prices.read().contains_key(pair) {
+                return Ok(());
+            }
+        }
+
+        // Connection might still be establishing; caller can check get_latest_price()
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "get_latest_price", skip(self), fields(exchange = %self.id, pair = %pair))]
+    async fn get_latest_price(&self, pair: &str) -> Result<Price> {
+        self.latest_prices
+            .read()
+            .get(pair)
+            .cloned()
+            .ok_or_else(|| ArbitrageError::ExchangeError {
+                exchange: self.id,
+                message: format!("No price data available for {}", pair),
+                code: None,
+            })
+    }
+
+    #[tracing::instrument(name = "place_order", skip(self, order), fields(
+        exchange = %self.id,
+        pair = %order.pair,
+        side = ?order.side,
+        order_type = ?order.order_type,
+        quantity = %order.quantity
+    ))]
+    async fn place_order(
+        &mut self,
+        order: crate::exchanges::Order,
+    ) -> Result<crate::exchanges::OrderResult> {
+        self.rest_client()?.place_order(order).await
+    }
+
+    #[tracing::instrument(name = "get_balance", skip(self), fields(exchange = %self.id, asset = %asset))]
+    async fn get_balance(&self, asset: &str) -> Result<rust_decimal::Decimal> {
+        self.rest_client()?.get_balance(asset).await
+    }
+
+    async fn get_balances(&self, assets: &[&str]) -> Result<HashMap<String, Balance>> {
+        self.rest_client()?.get_balances(assets).await
+    }
+
+    /// Finished orders come from the private stream; anything else from REST
+    async fn get_order(&self, pair: &str, order_id: &str) -> Result<OrderResult> {
+        if let Some(order) = self.orders.read().get(order_id).filter(|o| o.is_complete()) {
+            return Ok(order.clone());
+        }
+        self.rest_client()?.get_order(pair, order_id).await
+    }
+
+    async fn get_open_orders(&self, pair: &str) -> Result<Vec<OrderResult>> {
+        self.rest_client()?.get_open_orders(pair).await
+    }
+
+    fn id(&self) -> ExchangeId {
+        self.id
+    }
+
+    fn is_connected(&self) -> bool {
+        self.connection_state().is_live()
+    }
+
+    fn connection_state(&self) -> ConnectionState {
+        // An aborted or panicked manager task can't publish its own exit
+        let running = self
+            .ws_manager_handle
+            .as_ref()
+            .is_some_and(|handle| !handle.is_finished());
+        let state = self.state_tx.borrow().clone();
+        if running || matches!(state, ConnectionState::Failed { .. }) {
+            state
+        } else {
+            ConnectionState::Disconnected
+        }
+    }
+
+    fn watch_connection_state(&self) -> Option<watch::Receiver<ConnectionState>> {
+        Some(self.state_tx.subscribe())
+    }
+
+    fn price_updates(&self) -> Option<broadcast::Receiver<Price>> {
+        Some(self.prices_tx.subscribe())
+    }
+
+    fn clock(&self) -> Option<ClockSync> {
+        self.clock.as_ref().map(|c| c.clock().clone())
+    }
+
+    async fn disconnect(&mut self) -> Result<()> {
+        for handle in [self.ws_manager_handle.take(), self.orders_handle.take()]
+            .into_iter()
+            .flatten()
+        {
+            handle.abort();
+        }
+        self.state_tx.send_replace(ConnectionState::Disconnected);
+        self.latest_prices.write().clear();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subscribe_message() {
+        assert_eq!(
+            OkxExchange::subscribe_message("sol/usdc"),
+            r#"{"args":[{"channel":"tickers","instId":"SOL-USDC"},{"channel":"books5","instId":"SOL-USDC"}],"op":"subscribe"}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rest_requires_credentials() {
+        let mut exchange = OkxExchange::new(OkxConfig {
+            api_key: String::new(),
+            api_secret: String::new(),
+            passphrase: String::new(),
+            demo: false,
+        })
+        .unwrap();
+        exchange.connect().await.unwrap();
+        assert!(exchange.orders_handle.is_none());
+        assert!(exchange.get_balance("USDC").await.is_err());
+    }
+}
@@ -0,0 +1,16 @@
+//! OKX Exchange Integration
+//!
+//! Implements the Exchange trait for OKX, providing public WebSocket price
+//! feeds, a login-authenticated private order stream, and REST API for
+//! trading operations.
+
+pub mod auth;
+pub mod exchange;
+pub mod parser;
+pub mod rest;
+pub mod types;
+
+pub use auth::OkxAuth;
+pub use exchange::OkxExchange;
+pub use parser::{OkxOrderParser, OkxParser};
+pub use rest::OkxRestClient;
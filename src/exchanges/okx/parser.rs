@@ -0,0 +1,307 @@
+//! OKX WebSocket message parsers
+//!
+//! `OkxParser` turns public `tickers` and `books5` pushes into our common
+//! `Price` type; `OkxOrderParser` turns private `orders` pushes into
+//! `OrderResult`s.
+//!
+//! Based on: https://www.okx.com/docs-v5/en/#order-book-trading-market-data-ws-tickers-channel
+
+use super::types::OkxOrder;
+use crate::error::{ArbitrageError, Result};
+use crate::exchanges::symbols::SymbolFormat;
+use crate::exchanges::{ExchangeId, OrderResult, Price};
+use crate::websocket::MessageParser;
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use rust_decimal::Decimal;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Heartbeat reply to our `"ping"`
+const PONG: &str = "pong";
+
+/// Latest ticker and top of book for one instrument
+#[derive(Debug, Default)]
+struct Quote {
+    bid: Decimal,
+    ask: Decimal,
+    last: Option<Decimal>,
+    volume: Decimal,
+}
+
+/// Parser for OKX public `tickers` and `books5` messages
+///
+/// # Business Logic
+///
+/// `tickers` carries the last trade and 24h volume; `books5` pushes a full
+/// five-level snapshot whenever the top of the book changes. Both update the
+/// same per-instrument quote, and each message emits a `Price` with the
+/// freshest bid and ask.
+///
+/// Book messages before the first ticker for an instrument produce no price,
+/// since there is no last trade yet. Clones share the quotes.
+#[derive(Debug, Clone, Default)]
+pub struct OkxParser {
+    quotes: Arc<Mutex<HashMap<String, Quote>>>,
+}
+
+impl OkxParser {
+    /// Create a new OKX parser
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl MessageParser for OkxParser {
+    type Output = Price;
+
+    fn parse(&self, message: &str) -> Result<Self::Output> {
+        let received_at = Utc::now();
+        let value = json(message)?;
+        check_event(&value)?;
+
+        // {"arg":{"channel":"tickers","instId":"SOL-USDC"},"data":[{...}]}
+        let data = &value["data"][0];
+        let inst_id = value["arg"]["instId"]
+            .as_str()
+            .ok_or_else(|| ArbitrageError::ParseError {
+                message: "Missing instId".to_string(),
+                input: Some(message.to_string()),
+            })?;
+        let pair = SymbolFormat::Dashed
+            .to_pair(inst_id)
+            .unwrap_or_else(|| inst_id.to_string());
+
+        let mut quotes = self.quotes.lock();
+        let quote = quotes.entry(pair.clone()).or_default();
+        match value["arg"]["channel"].as_str() {
+            Some("tickers") => {
+                quote.bid = decimal(&data["bidPx"], "bidPx", message)?;
+                quote.ask = decimal(&data["askPx"], "askPx", message)?;
+                quote.last = Some(decimal(&data["last"], "last", message)?);
+                quote.volume = decimal(&data["vol24h"], "vol24h", message)?;
+            }
+            Some("books5") => {
+                // Levels are [price, size, deprecated, order count], best first
+                if let Some(bid) = data["bids"][0].get(0) {
+                    quote.bid = decimal(bid, "bids", message)?;
+                }
+                if let Some(ask) = data["asks"][0].get(0) {
+                    quote.ask = decimal(ask, "asks", message)?;
+                }
+            }
+            other => {
+                return Err(ArbitrageError::ParseError {
+                    message: format!(
+                        "Not a tickers or books5 message, got channel: {}",
+                        other.unwrap_or("none")
+                    ),
+                    input: Some(message.to_string()),
+                });
+            }
+        }
+
+        let Some(last) = quote.last else {
+            return Err(ArbitrageError::ParseError {
+                message: format!("Book update for {} before its first ticker", pair),
+                input: None,
+            });
+        };
+
+        Ok(Price {
+            pair,
+            bid: quote.bid,
+            ask: quote.ask,
+            last,
+            volume_24h: quote.volume,
+            timestamp: timestamp(&data["ts"]).unwrap_or(received_at),
+            // books5 sequence ids skip between snapshots, so they can't be gap-checked
+            sequence: None,
+            received_at,
+        })
+    }
+
+    fn is_control(&self, message: &str) -> bool {
+        is_control(message)
+    }
+}
+
+/// Parser for the private `orders` channel
+///
+/// One push can carry several orders; each becomes an `OrderResult`.
+#[derive(Debug, Clone, Default)]
+pub struct OkxOrderParser;
+
+impl OkxOrderParser {
+    /// Create a new OKX order parser
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl MessageParser for OkxOrderParser {
+    type Output = Vec<OrderResult>;
+
+    fn parse(&self, message: &str) -> Result<Self::Output> {
+        let value = json(message)?;
+        check_event(&value)?;
+
+        if value["arg"]["channel"].as_str() != Some("orders") {
+            return Err(ArbitrageError::ParseError {
+                message: "Not an orders message".to_string(),
+                input: Some(message.to_string()),
+            });
+        }
+        let orders: Vec<OkxOrder> =
+            serde_json::from_value(value["data"].clone()).map_err(|e| ArbitrageError::ParseError {
+                message: format!("Invalid order update: {}", e),
+                input: Some(message.to_string()),
+            })?;
+        Ok(orders.into_iter().map(OrderResult::from).collect())
+    }
+
+    fn is_control(&self, message: &str) -> bool {
+        is_control(message)
+    }
+
+    /// `{"event":"login","code":"0"}` on success, `{"event":"error",...}` otherwise
+    fn check_login(&self, reply: &str) -> Result<()> {
+        let value = json(reply)?;
+        if value["event"].as_str() == Some("login") && value["code"].as_str() == Some("0") {
+            return Ok(());
+        }
+        Err(ArbitrageError::AuthenticationError {
+            exchange: ExchangeId::OKX,
+            reason: format!(
+                "WebSocket login rejected: {} {}",
+                value["code"].as_str().unwrap_or_default(),
+                value["msg"].as_str().unwrap_or(reply)
+            ),
+        })
+    }
+}
+
+/// Heartbeat replies, subscription acks and connection notices
+fn is_control(message: &str) -> bool {
+    message == PONG
+        || ["\"event\":\"subscribe\"", "\"event\":\"channel-conn-count\"", "\"event\":\"notice\""]
+            .iter()
+            .any(|marker| message.contains(marker))
+}
+
+fn json(message: &str) -> Result<Value> {
+    serde_json::from_str(message).map_err(|e| ArbitrageError::ParseError {
+        message: format!("Invalid JSON: {}", e),
+        input: Some(message.to_string()),
+    })
+}
+
+/// Rejected requests: {"event":"error","code":"60018","msg":"..."}
+fn check_event(value: &Value) -> Result<()> {
+    if value["event"].as_str() == Some("error") {
+        return Err(ArbitrageError::ExchangeError {
+            exchange: ExchangeId::OKX,
+            message: format!(
+                "OKX WebSocket error: {}",
+                value["msg"].as_str().unwrap_or("Unknown error")
+            ),
+            code: value["code"].as_str().and_then(|c| c.parse().ok()),
+        });
+    }
+    Ok(())
+}
+
+fn decimal(value: &Value, field: &str, message: &str) -> Result<Decimal> {
+    value
+        .as_str()
+        .and_then(|s| Decimal::from_str_exact(s).ok())
+        .ok_or_else(|| ArbitrageError::ParseError {
+            message: format!("Invalid {}", field),
+            input: Some(message.to_string()),
+        })
+}
+
+/// `ts` is Unix milliseconds as a string
+fn timestamp(value: &Value) -> Option<DateTime<Utc>> {
+    value
+        .as_str()
+        .and_then(|s| s.parse::<i64>().ok())
+        .and_then(DateTime::from_timestamp_millis)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exchanges::OrderStatus;
+
+    const TICKER: &str = r#"{"arg":{"channel":"tickers","instId":"SOL-USDC"},"data":[{"instType":"SPOT","instId":"SOL-USDC","last":"150.12","lastSz":"0.5","askPx":"150.13","askSz":"20","bidPx":"150.11","bidSz":"15","open24h":"148","high24h":"152","low24h":"147","volCcy24h":"1500000","vol24h":"10000.5","sodUtc0":"149","sodUtc8":"149","ts":"1700000000123"}]}"#;
+
+    #[test]
+    fn test_parse_ticker() {
+        let price = OkxParser::new().parse(TICKER).unwrap();
+        assert_eq!(price.pair, "SOL/USDC");
+        assert_eq!(price.bid, Decimal::new(15011, 2));
+        assert_eq!(price.ask, Decimal::new(15013, 2));
+        assert_eq!(price.last, Decimal::new(15012, 2));
+        assert_eq!(price.volume_24h, Decimal::new(100005, 1));
+        assert_eq!(price.timestamp.timestamp_millis(), 1_700_000_000_123);
+    }
+
+    #[test]
+    fn test_books5_moves_touch_between_tickers() {
+        let parser = OkxParser::new();
+        let book = r#"{"arg":{"channel":"books5","instId":"SOL-USDC"},"data":[{
+            "asks":[["150.14","3","0","1"],["150.2","5","0","2"]],
+            "bids":[["150.1","4","0","1"],["150.0","9","0","3"]],
+            "instId":"SOL-USDC","ts":"1700000000500","seqId":123456}]}"#;
+
+        // No last trade yet
+        assert!(parser.parse(book).is_err());
+
+        parser.parse(TICKER).unwrap();
+        let price = parser.parse(book).unwrap();
+        assert_eq!(price.bid, Decimal::new(1501, 1));
+        assert_eq!(price.ask, Decimal::new(15014, 2));
+        assert_eq!(price.last, Decimal::new(15012, 2));
+        assert_eq!(price.sequence, None);
+    }
+
+    #[test]
+    fn test_control_and_error_messages() {
+        let parser = OkxParser::new();
+        assert!(parser.is_control("pong"));
+        assert!(parser.is_control(
+            r#"{"event":"subscribe","arg":{"channel":"tickers","instId":"SOL-USDC"},"connId":"a4d3ae55"}"#
+        ));
+        assert!(!parser.is_control(TICKER));
+
+        let rejected = r#"{"event":"error","code":"60018","msg":"Wrong URL or channel:tickers,instId:FOO-BAR doesn't exist","connId":"a4d3ae55"}"#;
+        assert!(!parser.is_control(rejected));
+        assert!(matches!(
+            parser.parse(rejected),
+            Err(ArbitrageError::ExchangeError { code: Some(60018), .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_order_updates() {
+        let push = r#"{"arg":{"channel":"orders","instType":"SPOT","uid":"77982378738415879"},"data":[
+            {"instId":"SOL-USDC","ordId":"1","state":"partially_filled","accFillSz":"1","avgPx":"150","fee":"-0.001","feeCcy":"SOL","uTime":"1700000000000"},
+            {"instId":"SOL-USDC","ordId":"2","state":"canceled","accFillSz":"0","avgPx":"","fee":"0","feeCcy":"USDC","uTime":"1700000000000"}]}"#;
+        let orders = OkxOrderParser::new().parse(push).unwrap();
+        assert_eq!(orders.len(), 2);
+        assert_eq!(orders[0].status, OrderStatus::PartiallyFilled);
+        assert_eq!(orders[1].status, OrderStatus::Cancelled);
+    }
+
+    #[test]
+    fn test_check_login() {
+        let parser = OkxOrderParser::new();
+        assert!(parser.check_login(r#"{"event":"login","code":"0","msg":"","connId":"a4d3ae55"}"#).is_ok());
+        assert!(matches!(
+            parser.check_login(r#"{"event":"error","code":"60009","msg":"Login failed.","connId":"a4d3ae55"}"#),
+            Err(ArbitrageError::AuthenticationError { .. })
+        ));
+    }
+}
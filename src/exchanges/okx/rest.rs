@@ -0,0 +1,316 @@
+//! OKX REST API Client
+//!
+//! Private v5 endpoints for balances and spot orders, signed with `OkxAuth`.
+//!
+//! Based on: https://www.okx.com/docs-v5/en/#order-book-trading-trade-post-place-order
+
+use super::auth::OkxAuth;
+use super::types::{OkxAccount, OkxOrder, OkxOrderAck, OkxOrderRequest, OkxResponse};
+use crate::error::{ArbitrageError, Result};
+use crate::exchanges::symbols::SymbolFormat;
+use crate::exchanges::{Balance, ExchangeId, Order, OrderResult, OrderSide, OrderStatus, OrderType, split_pair};
+use chrono::Utc;
+use reqwest::{Client, Method};
+use rust_decimal::Decimal;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+
+/// Error codes meaning the key, signature, passphrase or timestamp is wrong
+const AUTH_ERRORS: [&str; 8] = [
+    "50100", "50101", "50102", "50103", "50104", "50105", "50111", "50113",
+];
+
+/// "Too many requests"
+const RATE_LIMIT_ERROR: &str = "50011";
+
+/// Insufficient balance for the order
+const INSUFFICIENT_BALANCE_ERROR: &str = "51008";
+
+/// OKX rate limits are per 2-second window
+const RATE_LIMIT_BACKOFF_MS: u64 = 2000;
+
+/// OKX REST API client
+pub struct OkxRestClient {
+    client: Client,
+    auth: OkxAuth,
+    base_url: String,
+    /// Send `x-simulated-trading: 1` so orders go to the demo account
+    demo: bool,
+}
+
+impl OkxRestClient {
+    /// Create a new OKX REST API client
+    ///
+    /// # Arguments
+    /// * `api_key` - API key
+    /// * `api_secret` - API secret
+    /// * `passphrase` - Passphrase set when the key was created
+    /// * `demo` - Trade on the demo account instead of production
+    pub fn new(api_key: String, api_secret: String, passphrase: String, demo: bool) -> Self {
+        Self {
+            client: Client::new(),
+            auth: OkxAuth::new(api_key, api_secret, passphrase),
+            base_url: crate::constants::api::OKX_PRODUCTION.to_string(),
+            demo,
+        }
+    }
+
+    /// Point the client at a different REST base URL (e.g. a local test server)
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    /// Use an exchange-corrected clock when signing requests
+    pub fn with_clock(mut self, clock: crate::clock::ClockSync) -> Self {
+        self.auth = self.auth.with_clock(clock);
+        self
+    }
+
+    /// Get available and frozen balances for several assets in one request
+    ///
+    /// Assets without a balance are omitted from the result.
+    #[tracing::instrument(name = "get_balances", skip(self))]
+    pub async fn get_balances(&self, assets: &[&str]) -> Result<HashMap<String, Balance>> {
+        let path = format!(
+            "{}?ccy={}",
+            crate::constants::api::OKX_BALANCE_PATH,
+            assets.join(",")
+        );
+        let accounts: Vec<OkxAccount> = self.request(Method::GET, &path, None, "balance").await?;
+
+        Ok(accounts
+            .iter()
+            .flat_map(|account| &account.details)
+            .filter(|balance| assets.contains(&balance.ccy.as_str()))
+            .map(|balance| (balance.ccy.clone(), Balance::from(balance)))
+            .collect())
+    }
+
+    /// Get available balance for one asset (zero if the account holds none)
+    #[tracing::instrument(name = "get_balance", skip(self), fields(asset = %asset))]
+    pub async fn get_balance(&self, asset: &str) -> Result<Decimal> {
+        let balances = self.get_balances(&[asset]).await?;
+        Ok(balances.get(asset).map(|b| b.available).unwrap_or_default())
+    }
+
+    /// Place a spot market or limit order, sized in the base asset
+    ///
+    /// The placement response only carries the order id, so the order is looked
+    /// up once for its fills; if that lookup fails it is reported `Pending`.
+    #[tracing::instrument(name = "place_order", skip(self, order), fields(
+        pair = %order.pair,
+        side = ?order.side,
+        quantity = %order.quantity
+    ))]
+    pub async fn place_order(&self, order: Order) -> Result<OrderResult> {
+        let (base, _) = split_pair(&order.pair).ok_or_else(|| ArbitrageError::ExchangeError {
+            exchange: ExchangeId::OKX,
+            message: format!("Invalid pair: {}", order.pair),
+            code: None,
+        })?;
+        let (ord_type, px) = match order.order_type {
+            OrderType::Market => ("market", None),
+            OrderType::Limit { price } => ("limit", Some(price.normalize().to_string())),
+        };
+        let request = OkxOrderRequest {
+            inst_id: SymbolFormat::Dashed.to_venue(&order.pair),
+            td_mode: "cash".to_string(),
+            side: match order.side {
+                OrderSide::Buy => "buy",
+                OrderSide::Sell => "sell",
+            }
+            .to_string(),
+            ord_type: ord_type.to_string(),
+            sz: order.quantity.normalize().to_string(),
+            px,
+            tgt_ccy: (order.order_type == OrderType::Market).then(|| "base_ccy".to_string()),
+        };
+        let body = serde_json::to_string(&request).map_err(|e| ArbitrageError::ParseError {
+            message: format!("Failed to serialize order: {}", e),
+            input: None,
+        })?;
+
+        let acks: Vec<OkxOrderAck> = self
+            .request(
+                Method::POST,
+                crate::constants::api::OKX_ORDER_PATH,
+                Some(body),
+                "order",
+            )
+            .await?;
+        let ack = acks.into_iter().next().ok_or_else(|| ArbitrageError::ExchangeError {
+            exchange: ExchangeId::OKX,
+            message: "Order response missing ordId".to_string(),
+            code: None,
+        })?;
+        if ack.s_code != "0" {
+            return Err(classify(&ack.s_code, &ack.s_msg, Some(base)));
+        }
+
+        match self.get_order(&order.pair, &ack.ord_id).await {
+            Ok(result) => Ok(result),
+            Err(e) => {
+                tracing::warn!(order_id = %ack.ord_id, error = %e, "Order placed but lookup failed");
+                Ok(OrderResult {
+                    order_id: ack.ord_id,
+                    status: OrderStatus::Pending,
+                    filled_quantity: Decimal::ZERO,
+                    average_price: None,
+                    fee: Decimal::ZERO,
+                    fee_asset: base.to_string(),
+                    timestamp: Utc::now(),
+                })
+            }
+        }
+    }
+
+    /// Look up an order by id
+    #[tracing::instrument(name = "get_order", skip(self), fields(order_id = %order_id))]
+    pub async fn get_order(&self, pair: &str, order_id: &str) -> Result<OrderResult> {
+        let path = format!(
+            "{}?instId={}&ordId={}",
+            crate::constants::api::OKX_ORDER_PATH,
+            SymbolFormat::Dashed.to_venue(pair),
+            order_id
+        );
+        let orders: Vec<OkxOrder> = self.request(Method::GET, &path, None, "order").await?;
+        orders
+            .into_iter()
+            .next()
+            .map(OrderResult::from)
+            .ok_or_else(|| ArbitrageError::ExchangeError {
+                exchange: ExchangeId::OKX,
+                message: format!("Order not found: {}", order_id),
+                code: None,
+            })
+    }
+
+    /// List open orders for a pair (e.g. "SOL/USDC")
+    #[tracing::instrument(name = "get_open_orders", skip(self), fields(pair = %pair))]
+    pub async fn get_open_orders(&self, pair: &str) -> Result<Vec<OrderResult>> {
+        let path = format!(
+            "{}?instType=SPOT&instId={}",
+            crate::constants::api::OKX_PENDING_ORDERS_PATH,
+            SymbolFormat::Dashed.to_venue(pair)
+        );
+        let orders: Vec<OkxOrder> = self.request(Method::GET, &path, None, "orders").await?;
+        Ok(orders.into_iter().map(OrderResult::from).collect())
+    }
+
+    /// Signed request, unwrapping OKX's `{code, msg, data}` envelope
+    ///
+    /// `path` includes the query string, which is part of the signature.
+    async fn request<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<String>,
+        what: &str,
+    ) -> Result<Vec<T>> {
+        let body = body.unwrap_or_default();
+        let mut request = self
+            .client
+            .request(method.clone(), format!("{}{}", self.base_url, path))
+            .header("Content-Type", "application/json");
+        for (name, value) in self.auth.headers(method.as_str(), path, &body) {
+            request = request.header(name, value);
+        }
+        if self.demo {
+            request = request.header("x-simulated-trading", "1");
+        }
+        if !body.is_empty() {
+            request = request.body(body);
+        }
+
+        let response = request.send().await.map_err(|e| ArbitrageError::ExchangeError {
+            exchange: ExchangeId::OKX,
+            message: format!("HTTP request failed: {}", e),
+            code: None,
+        })?;
+
+        let status = response.status();
+        let response_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unable to read response".to_string());
+
+        // Errors come as {"code":"50113","msg":"Invalid Sign","data":[]}, with a 4xx or a 200
+        let envelope: OkxResponse<T> = match serde_json::from_str(&response_text) {
+            Ok(envelope) => envelope,
+            Err(_) if !status.is_success() => {
+                return Err(ArbitrageError::ExchangeError {
+                    exchange: ExchangeId::OKX,
+                    message: format!("API error ({}): {}", status, response_text),
+                    code: Some(status.as_u16() as i32),
+                });
+            }
+            Err(e) => {
+                return Err(ArbitrageError::ExchangeError {
+                    exchange: ExchangeId::OKX,
+                    message: format!("Failed to parse {} response: {}", what, e),
+                    code: None,
+                });
+            }
+        };
+
+        // A rejected order has code "1" and its reason in data[0].sCode, which the caller checks
+        if envelope.code != "0" && (envelope.code != "1" || envelope.data.is_empty()) {
+            return Err(classify(&envelope.code, &envelope.msg, None));
+        }
+        Ok(envelope.data)
+    }
+}
+
+/// Map an OKX error code to our error type
+///
+/// `asset` names the asset short of balance, when known.
+fn classify(code: &str, msg: &str, asset: Option<&str>) -> ArbitrageError {
+    if AUTH_ERRORS.contains(&code) {
+        ArbitrageError::AuthenticationError {
+            exchange: ExchangeId::OKX,
+            reason: format!("{} ({})", msg, code),
+        }
+    } else if code == RATE_LIMIT_ERROR {
+        ArbitrageError::RateLimitExceeded {
+            exchange: ExchangeId::OKX,
+            retry_after: RATE_LIMIT_BACKOFF_MS,
+        }
+    } else if code == INSUFFICIENT_BALANCE_ERROR
+        && let Some(asset) = asset
+    {
+        ArbitrageError::InsufficientBalance {
+            exchange: ExchangeId::OKX,
+            asset: asset.to_string(),
+            required: "unknown".to_string(),
+            available: "unknown".to_string(),
+        }
+    } else {
+        ArbitrageError::ExchangeError {
+            exchange: ExchangeId::OKX,
+            message: format!("{} ({})", msg, code),
+            code: code.parse().ok(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_errors() {
+        assert!(matches!(
+            classify("50113", "Invalid Sign", None),
+            ArbitrageError::AuthenticationError { .. }
+        ));
+        assert_eq!(
+            classify("50011", "Too Many Requests", None).retry_after(),
+            Some(std::time::Duration::from_millis(RATE_LIMIT_BACKOFF_MS))
+        );
+        assert!(matches!(
+            classify("51000", "Parameter sz error", None),
+            ArbitrageError::ExchangeError { code: Some(51000), .. }
+        ));
+    }
+}
@@ -0,0 +1,182 @@
+//! OKX-specific REST and private channel types
+
+use crate::exchanges::symbols::SymbolFormat;
+use crate::exchanges::{Balance, OrderResult, OrderStatus};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Envelope of every REST response: `code` is `"0"` on success
+#[derive(Debug, Deserialize)]
+pub struct OkxResponse<T> {
+    pub code: String,
+    #[serde(default)]
+    pub msg: String,
+    #[serde(default = "Vec::new")]
+    pub data: Vec<T>,
+}
+
+/// One account in the `account/balance` result
+#[derive(Debug, Deserialize)]
+pub struct OkxAccount {
+    #[serde(default)]
+    pub details: Vec<OkxBalance>,
+}
+
+/// One currency in a trading account
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OkxBalance {
+    pub ccy: String,
+    #[serde(deserialize_with = "decimal_or_zero")]
+    pub avail_bal: Decimal,
+    /// Reserved by open orders
+    #[serde(default, deserialize_with = "decimal_or_zero")]
+    pub frozen_bal: Decimal,
+}
+
+impl From<&OkxBalance> for Balance {
+    fn from(balance: &OkxBalance) -> Self {
+        Balance {
+            available: balance.avail_bal,
+            hold: balance.frozen_bal,
+        }
+    }
+}
+
+/// `trade/order` request body
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OkxOrderRequest {
+    pub inst_id: String,
+    /// `cash` for spot trading without margin
+    pub td_mode: String,
+    pub side: String,
+    pub ord_type: String,
+    pub sz: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub px: Option<String>,
+    /// Size market buys in the base currency (default is quote)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tgt_ccy: Option<String>,
+}
+
+/// Per-order result of `trade/order`; `s_code` is `"0"` when accepted
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OkxOrderAck {
+    pub ord_id: String,
+    pub s_code: String,
+    #[serde(default)]
+    pub s_msg: String,
+}
+
+/// An order as returned by REST lookups and pushed on the `orders` channel
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OkxOrder {
+    /// Instrument, e.g. `SOL-USDC`
+    pub inst_id: String,
+    pub ord_id: String,
+    /// `live`, `partially_filled`, `filled`, `canceled` or `mmp_canceled`
+    pub state: String,
+    #[serde(deserialize_with = "decimal_or_zero")]
+    pub acc_fill_sz: Decimal,
+    /// Empty until the first fill
+    #[serde(default, deserialize_with = "decimal_or_zero")]
+    pub avg_px: Decimal,
+    /// Negative when charged, positive for rebates
+    #[serde(default, deserialize_with = "decimal_or_zero")]
+    pub fee: Decimal,
+    #[serde(default)]
+    pub fee_ccy: String,
+    /// Last update, Unix milliseconds
+    #[serde(default)]
+    pub u_time: String,
+}
+
+impl OkxOrder {
+    /// Our pair for `inst_id` (`SOL-USDC` -> `SOL/USDC`)
+    pub fn pair(&self) -> String {
+        SymbolFormat::Dashed
+            .to_pair(&self.inst_id)
+            .unwrap_or_else(|| self.inst_id.clone())
+    }
+}
+
+impl From<OkxOrder> for OrderResult {
+    fn from(order: OkxOrder) -> Self {
+        let status = match order.state.as_str() {
+            "filled" => OrderStatus::Filled,
+            "partially_filled" => OrderStatus::PartiallyFilled,
+            "live" => OrderStatus::Pending,
+            "canceled" | "mmp_canceled" => OrderStatus::Cancelled,
+            _ => OrderStatus::Failed,
+        };
+        let timestamp = order
+            .u_time
+            .parse::<i64>()
+            .ok()
+            .and_then(DateTime::from_timestamp_millis)
+            .unwrap_or_else(Utc::now);
+
+        OrderResult {
+            order_id: order.ord_id,
+            status,
+            filled_quantity: order.acc_fill_sz,
+            average_price: (!order.avg_px.is_zero()).then_some(order.avg_px),
+            // OKX reports charges as negative amounts
+            fee: -order.fee,
+            fee_asset: order.fee_ccy,
+            timestamp,
+        }
+    }
+}
+
+/// OKX sends `""` for amounts that don't apply yet (e.g. `avgPx` before a fill)
+fn decimal_or_zero<'de, D>(deserializer: D) -> Result<Decimal, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    if s.is_empty() {
+        return Ok(Decimal::ZERO);
+    }
+    Decimal::from_str_exact(&s)
+        .or_else(|_| Decimal::from_scientific(&s))
+        .map_err(serde::de::Error::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_order_conversion() {
+        let order: OkxOrder = serde_json::from_str(
+            r#"{"instId":"SOL-USDC","ordId":"680800019749904384","clOrdId":"","state":"filled",
+                "side":"buy","ordType":"market","sz":"2","accFillSz":"2","avgPx":"150.5",
+                "fee":"-0.002","feeCcy":"SOL","uTime":"1700000000250","cTime":"1700000000000"}"#,
+        )
+        .unwrap();
+        assert_eq!(order.pair(), "SOL/USDC");
+
+        let result = OrderResult::from(order);
+        assert_eq!(result.status, OrderStatus::Filled);
+        assert_eq!(result.average_price, Some(Decimal::new(1505, 1)));
+        assert_eq!(result.fee, Decimal::new(2, 3));
+        assert_eq!(result.fee_asset, "SOL");
+        assert_eq!(result.timestamp.timestamp_millis(), 1_700_000_000_250);
+    }
+
+    #[test]
+    fn test_unfilled_order_has_no_price() {
+        let order: OkxOrder = serde_json::from_str(
+            r#"{"instId":"BTC-USDT","ordId":"1","state":"live","accFillSz":"0","avgPx":"","fee":"0","feeCcy":"USDT","uTime":""}"#,
+        )
+        .unwrap();
+        let result = OrderResult::from(order);
+        assert_eq!(result.status, OrderStatus::Pending);
+        assert_eq!(result.average_price, None);
+    }
+}
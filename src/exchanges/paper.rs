@@ -0,0 +1,340 @@
+//! Paper trading: live market data, simulated fills
+
+use super::{Exchange, ExchangeId, Order, OrderResult, OrderSide, OrderStatus, OrderType, Price, split_pair};
+use crate::clock::ClockSync;
+use crate::error::{ArbitrageError, Result};
+use crate::state::SequencePolicy;
+use crate::websocket::ConnectionState;
+use async_trait::async_trait;
+use chrono::Utc;
+use parking_lot::RwLock;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::{broadcast, watch};
+
+/// Starting state of a paper account
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PaperConfig {
+    /// Fee per fill as a fraction of notional, charged in the quote asset
+    pub taker_fee: Decimal,
+    /// Fees for exchanges that don't charge `taker_fee`
+    pub exchange_fees: HashMap<ExchangeId, Decimal>,
+    /// Starting balance per asset; unlisted assets start at zero
+    pub balances: HashMap<String, Decimal>,
+}
+
+impl PaperConfig {
+    /// Charge `fee` (e.g. 0.001) on every fill
+    pub fn with_taker_fee(mut self, fee: Decimal) -> Self {
+        self.taker_fee = fee;
+        self
+    }
+
+    /// Charge `fee` on fills on `exchange` instead
+    pub fn with_exchange_fee(mut self, exchange: ExchangeId, fee: Decimal) -> Self {
+        self.exchange_fees.insert(exchange, fee);
+        self
+    }
+
+    /// Start with `amount` of `asset`
+    pub fn with_balance(mut self, asset: impl Into<String>, amount: Decimal) -> Self {
+        self.balances.insert(asset.into(), amount);
+        self
+    }
+}
+
+/// Simulated balances and orders of one paper account
+///
+/// Clones share state, so several clients of one exchange (e.g. one trading
+/// and one refreshing balances) see the same fills.
+#[derive(Debug, Clone)]
+pub struct PaperAccount {
+    taker_fee: Decimal,
+    balances: Arc<RwLock<HashMap<String, Decimal>>>,
+    orders: Arc<RwLock<HashMap<String, OrderResult>>>,
+}
+
+impl PaperAccount {
+    /// Account on `exchange`, starting from `config`
+    pub fn new(exchange: ExchangeId, config: &PaperConfig) -> Self {
+        Self {
+            taker_fee: config.exchange_fees.get(&exchange).copied().unwrap_or(config.taker_fee),
+            balances: Arc::new(RwLock::new(config.balances.clone())),
+            orders: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+}
+
+/// Wraps an exchange, taking its prices but filling orders in memory
+///
+/// # Business Logic
+///
+/// Orders never reach the wrapped exchange. A market order fills in full at the
+/// live ask (buy) or bid (sell); a limit order fills the same way if it is
+/// marketable and is rejected otherwise, since there is no book to rest on.
+/// The taker fee is charged in the quote asset.
+///
+/// Balances start from `PaperConfig` and move with every fill. An order that
+/// would overdraw an asset fails with `InsufficientBalance`, as it would live.
+///
+/// Everything else (connection, subscriptions, prices, `name()`) is the wrapped
+/// exchange's, so a paper exchange can stand in for a live one anywhere.
+///
+/// ```rust,no_run
+/// use arb_bot::exchanges::Exchange;
+/// use arb_bot::exchanges::mock::MockExchange;
+/// use arb_bot::exchanges::paper::{PaperConfig, PaperExchange};
+/// use rust_decimal::Decimal;
+///
+/// let config = PaperConfig::default()
+///     .with_taker_fee(Decimal::new(1, 3))
+///     .with_balance("USDC", Decimal::from(1000));
+/// let paper = PaperExchange::new(MockExchange::new("binance"), config);
+/// assert_eq!(paper.name(), "binance");
+/// ```
+pub struct PaperExchange<E: Exchange> {
+    inner: E,
+    account: PaperAccount,
+}
+
+impl<E: Exchange> PaperExchange<E> {
+    /// Paper trade on `inner`'s prices
+    pub fn new(inner: E, config: PaperConfig) -> Self {
+        let account = PaperAccount::new(inner.id(), &config);
+        Self::with_account(inner, account)
+    }
+
+    /// Paper trade on `inner`'s prices, filling into an existing `account`
+    pub fn with_account(inner: E, account: PaperAccount) -> Self {
+        Self { inner, account }
+    }
+
+    /// Current simulated balances
+    pub fn balances(&self) -> HashMap<String, Decimal> {
+        self.account.balances.read().clone()
+    }
+
+    /// The wrapped exchange
+    pub fn inner(&self) -> &E {
+        &self.inner
+    }
+
+    fn rejected(&self, message: String) -> ArbitrageError {
+        ArbitrageError::ExchangeError {
+            exchange: self.inner.id(),
+            message,
+            code: None,
+        }
+    }
+
+    /// Fill price for `order` against `price`, if it is marketable
+    fn fill_price(order: &Order, price: &Price) -> Option<Decimal> {
+        let touch = match order.side {
+            OrderSide::Buy => price.ask,
+            OrderSide::Sell => price.bid,
+        };
+        match (&order.order_type, &order.side) {
+            (_, _) if touch.is_zero() => None,
+            (OrderType::Market, _) => Some(touch),
+            (OrderType::Limit { price }, OrderSide::Buy) => (*price >= touch).then_some(touch),
+            (OrderType::Limit { price }, OrderSide::Sell) => (*price <= touch).then_some(touch),
+        }
+    }
+}
+
+#[async_trait]
+#[allow(clippy::result_large_err)]
+impl<E: Exchange> Exchange for PaperExchange<E> {
+    async fn connect(&mut self) -> Result<()> {
+        self.inner.connect().await
+    }
+
+    async fn subscribe_ticker(&mut self, pair: &str) -> Result<()> {
+        self.inner.subscribe_ticker(pair).await
+    }
+
+    async fn get_latest_price(&self, pair: &str) -> Result<Price> {
+        self.inner.get_latest_price(pair).await
+    }
+
+    async fn place_order(&mut self, order: Order) -> Result<OrderResult> {
+        let Some((base, quote)) = split_pair(&order.pair) else {
+            return Err(self.rejected(format!("Invalid pair: {}", order.pair)));
+        };
+        let price = self.inner.get_latest_price(&order.pair).await?;
+        let fill = Self::fill_price(&order, &price)
+            .ok_or_else(|| self.rejected("Limit order not marketable".to_string()))?;
+
+        let notional = order.quantity * fill;
+        let fee = notional * self.account.taker_fee;
+        let (spend, spent, receive, received) = match order.side {
+            OrderSide::Buy => (quote, notional + fee, base, order.quantity),
+            OrderSide::Sell => (base, order.quantity, quote, notional - fee),
+        };
+
+        let result = {
+            let mut balances = self.account.balances.write();
+            let available = balances.get(spend).copied().unwrap_or_default();
+            if available < spent {
+                return Err(ArbitrageError::InsufficientBalance {
+                    exchange: self.inner.id(),
+                    asset: spend.to_string(),
+                    required: spent.to_string(),
+                    available: available.to_string(),
+                });
+            }
+            *balances.entry(spend.to_string()).or_default() -= spent;
+            *balances.entry(receive.to_string()).or_default() += received;
+
+            let mut orders = self.account.orders.write();
+            let result = OrderResult {
+                order_id: format!("paper_{}", orders.len() + 1),
+                status: OrderStatus::Filled,
+                filled_quantity: order.quantity,
+                average_price: Some(fill),
+                fee,
+                fee_asset: quote.to_string(),
+                timestamp: Utc::now(),
+            };
+            orders.insert(result.order_id.clone(), result.clone());
+            result
+        };
+        Ok(result)
+    }
+
+    async fn get_balance(&self, asset: &str) -> Result<Decimal> {
+        Ok(self.account.balances.read().get(asset).copied().unwrap_or_default())
+    }
+
+    async fn get_order(&self, _pair: &str, order_id: &str) -> Result<OrderResult> {
+        self.account
+            .orders
+            .read()
+            .get(order_id)
+            .cloned()
+            .ok_or_else(|| self.rejected(format!("Unknown order: {}", order_id)))
+    }
+
+    /// Paper orders fill immediately, so none are ever open
+    async fn get_open_orders(&self, _pair: &str) -> Result<Vec<OrderResult>> {
+        Ok(Vec::new())
+    }
+
+    fn id(&self) -> ExchangeId {
+        self.inner.id()
+    }
+
+    fn is_connected(&self) -> bool {
+        self.inner.is_connected()
+    }
+
+    fn connection_state(&self) -> ConnectionState {
+        self.inner.connection_state()
+    }
+
+    fn watch_connection_state(&self) -> Option<watch::Receiver<ConnectionState>> {
+        self.inner.watch_connection_state()
+    }
+
+    fn clock(&self) -> Option<ClockSync> {
+        self.inner.clock()
+    }
+
+    fn price_updates(&self) -> Option<broadcast::Receiver<Price>> {
+        self.inner.price_updates()
+    }
+
+    fn sequence_policy(&self) -> Option<SequencePolicy> {
+        self.inner.sequence_policy()
+    }
+
+    async fn disconnect(&mut self) -> Result<()> {
+        self.inner.disconnect().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exchanges::mock::MockExchange;
+
+    fn price(bid: i64, ask: i64) -> Price {
+        Price {
+            pair: "SOL/USDC".to_string(),
+            bid: Decimal::from(bid),
+            ask: Decimal::from(ask),
+            last: Decimal::from(bid),
+            volume_24h: Decimal::ZERO,
+            timestamp: Utc::now(),
+            received_at: Utc::now(),
+            sequence: None,
+        }
+    }
+
+    async fn paper(config: PaperConfig) -> PaperExchange<MockExchange> {
+        let mock = MockExchange::new("binance");
+        mock.set_price("SOL/USDC", price(99, 100));
+        let mut paper = PaperExchange::new(mock.clone(), config);
+        paper.connect().await.unwrap();
+        paper
+    }
+
+    #[tokio::test]
+    async fn fills_at_touch_and_moves_balances() {
+        let config = PaperConfig::default()
+            .with_taker_fee(Decimal::new(1, 2))
+            .with_balance("USDC", Decimal::from(1000));
+        let mut paper = paper(config).await;
+
+        let bought = paper.place_order(Order::market_buy("SOL/USDC", Decimal::from(2))).await.unwrap();
+        assert_eq!(bought.average_price, Some(Decimal::from(100)));
+        assert_eq!(bought.fee, Decimal::from(2));
+        assert_eq!(paper.get_balance("USDC").await.unwrap(), Decimal::from(798));
+        assert_eq!(paper.get_balance("SOL").await.unwrap(), Decimal::from(2));
+
+        let sold = paper.place_order(Order::market_sell("SOL/USDC", Decimal::ONE)).await.unwrap();
+        assert_eq!(sold.average_price, Some(Decimal::from(99)));
+        assert_eq!(paper.get_balance("USDC").await.unwrap(), Decimal::new(89601, 2));
+        let found = paper.get_order("SOL/USDC", &sold.order_id).await.unwrap();
+        assert_eq!(found.filled_quantity, Decimal::ONE);
+        assert_eq!(paper.inner().orders_placed(), 0);
+    }
+
+    #[tokio::test]
+    async fn rejects_overdraft_and_resting_limits() {
+        let mut paper = paper(PaperConfig::default().with_balance("USDC", Decimal::from(150))).await;
+
+        let err = paper
+            .place_order(Order::market_buy("SOL/USDC", Decimal::from(2)))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ArbitrageError::InsufficientBalance { ref asset, .. } if asset == "USDC"));
+
+        let resting = Order {
+            order_type: OrderType::Limit {
+                price: Decimal::from(95),
+            },
+            ..Order::market_buy("SOL/USDC", Decimal::ONE)
+        };
+        assert!(paper.place_order(resting).await.is_err());
+        assert_eq!(paper.balances()["USDC"], Decimal::from(150));
+    }
+
+    #[tokio::test]
+    async fn clients_of_one_account_share_fills() {
+        let config = PaperConfig::default()
+            .with_taker_fee(Decimal::new(1, 2))
+            .with_exchange_fee(ExchangeId::BINANCE, Decimal::ZERO)
+            .with_balance("USDC", Decimal::from(1000));
+        let mut trading = paper(config.clone()).await;
+        let refreshing = PaperExchange::with_account(
+            MockExchange::new("binance"),
+            trading.account.clone(),
+        );
+
+        trading.place_order(Order::market_buy("SOL/USDC", Decimal::ONE)).await.unwrap();
+        assert_eq!(refreshing.get_balance("USDC").await.unwrap(), Decimal::from(900));
+        assert_eq!(refreshing.get_balance("SOL").await.unwrap(), Decimal::ONE);
+    }
+}
@@ -0,0 +1,84 @@
+//! Converting our `BASE/QUOTE` pairs to and from exchange symbols
+
+/// Quote assets recognised when splitting a joined symbol like `BTCUSDT`
+///
+/// Checked longest first, so `FDUSD` wins over `USD`.
+const QUOTE_ASSETS: [&str; 14] = [
+    "FDUSD", "USDT", "USDC", "BUSD", "TUSD", "USD", "EUR", "GBP", "TRY", "BRL", "BTC", "ETH",
+    "BNB", "DAI",
+];
+
+/// How an exchange writes a pair
+///
+/// # Business Logic
+///
+/// Config, prices and balances use `BASE/QUOTE` (`SOL/USDC`). Each exchange
+/// client converts at its edges with one of these formats, so a pair reads the
+/// same everywhere above the exchange layer. Exchanges that rename assets too
+/// (Kraken's `XBT`) layer their own table on top.
+///
+/// ```rust
+/// use arb_bot::exchanges::symbols::SymbolFormat;
+///
+/// assert_eq!(SymbolFormat::Dashed.to_venue("SOL/USDC"), "SOL-USDC");
+/// assert_eq!(SymbolFormat::Dashed.to_pair("SOL-USDC").as_deref(), Some("SOL/USDC"));
+/// assert_eq!(SymbolFormat::Joined.to_pair("BTCUSDT").as_deref(), Some("BTC/USDT"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolFormat {
+    /// `SOL-USDC` (Coinbase, OKX)
+    Dashed,
+    /// `SOLUSDC` (Binance, Bybit); split back using known quote assets
+    Joined,
+}
+
+impl SymbolFormat {
+    /// Exchange symbol for a pair (`SOL/USDC` -> `SOL-USDC` or `SOLUSDC`)
+    pub fn to_venue(self, pair: &str) -> String {
+        match self {
+            SymbolFormat::Dashed => pair.replace('/', "-").to_uppercase(),
+            SymbolFormat::Joined => pair.replace('/', "").to_uppercase(),
+        }
+    }
+
+    /// Pair for an exchange symbol, or `None` if it can't be split
+    pub fn to_pair(self, symbol: &str) -> Option<String> {
+        let symbol = symbol.to_uppercase();
+        let (base, quote) = match self {
+            SymbolFormat::Dashed => symbol.split_once('-')?,
+            SymbolFormat::Joined => {
+                let mut quotes = QUOTE_ASSETS;
+                quotes.sort_by_key(|quote| std::cmp::Reverse(quote.len()));
+                let quote = quotes
+                    .into_iter()
+                    .find(|quote| symbol.len() > quote.len() && symbol.ends_with(quote))?;
+                symbol.split_at(symbol.len() - quote.len())
+            }
+        };
+        (!base.is_empty() && !quote.is_empty()).then(|| format!("{}/{}", base, quote))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dashed_round_trips() {
+        assert_eq!(SymbolFormat::Dashed.to_venue("btc/usd"), "BTC-USD");
+        assert_eq!(SymbolFormat::Dashed.to_pair("BTC-USD").as_deref(), Some("BTC/USD"));
+        assert_eq!(SymbolFormat::Dashed.to_pair("BTCUSD"), None);
+        assert_eq!(SymbolFormat::Dashed.to_pair("-USD"), None);
+    }
+
+    #[test]
+    fn joined_splits_on_longest_known_quote() {
+        let pair = |symbol| SymbolFormat::Joined.to_pair(symbol);
+        assert_eq!(pair("SOLUSDC").as_deref(), Some("SOL/USDC"));
+        assert_eq!(pair("BTCFDUSD").as_deref(), Some("BTC/FDUSD"));
+        assert_eq!(pair("ETHBTC").as_deref(), Some("ETH/BTC"));
+        assert_eq!(pair("usdcusdt").as_deref(), Some("USDC/USDT"));
+        assert_eq!(pair("USDT"), None);
+        assert_eq!(pair("SOLXYZ"), None);
+    }
+}
@@ -1,5 +1,6 @@
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
 
 /// Represents current market price data from an exchange.
 ///
@@ -27,6 +28,8 @@ use rust_decimal::Decimal;
 ///     last: Decimal::from(100),
 ///     volume_24h: Decimal::from(1000000),
 ///     timestamp: Utc::now(),
+///     sequence: None,
+///     received_at: Utc::now(),
 /// };
 ///
 /// let spread_pct = price.spread_percentage(); // ~1%
@@ -42,7 +45,14 @@ pub struct Price {
     pub ask: Decimal,
     pub last: Decimal,
     pub volume_24h: Decimal,
+    /// Event time reported by the exchange (falls back to `received_at` if absent)
     pub timestamp: DateTime<Utc>,
+    /// Local time the message was received and parsed
+    pub received_at: DateTime<Utc>,
+    /// Exchange-assigned sequence number, if the feed provides one
+    ///
+    /// Used by `state::SequenceTracker` to detect gaps, duplicates and out-of-order updates.
+    pub sequence: Option<u64>,
 }
 
 impl Price {
@@ -60,6 +70,13 @@ impl Price {
         self.ask - self.bid
     }
 
+    /// Feed delay: time between the exchange event and local receipt.
+    ///
+    /// Can be negative when the local clock is behind the exchange clock.
+    pub fn feed_latency(&self) -> chrono::Duration {
+        self.received_at - self.timestamp
+    }
+
     /// Calculate spread as percentage of mid price.
     ///
     /// Used by arbitrage detection - typically need >0.2% to be profitable after fees.
@@ -73,13 +90,42 @@ impl Price {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+/// Balance of one asset on an exchange
+///
+/// `hold` is reserved by open orders or pending withdrawals (Coinbase `hold`,
+/// Binance `locked`) and can't be traded until released.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Balance {
+    pub available: Decimal,
+    pub hold: Decimal,
+}
+
+impl Balance {
+    /// Available plus on-hold amount
+    pub fn total(&self) -> Decimal {
+        self.available + self.hold
+    }
+}
+
+/// Split a `BASE/QUOTE` pair into its assets
+///
+/// ```
+/// use arb_bot::exchanges::split_pair;
+///
+/// assert_eq!(split_pair("SOL/USDC"), Some(("SOL", "USDC")));
+/// assert_eq!(split_pair("SOLUSDC"), None);
+/// ```
+pub fn split_pair(pair: &str) -> Option<(&str, &str)> {
+    pair.split_once('/')
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum OrderSide {
     Buy,
     Sell,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum OrderType {
     Market,
     Limit { price: Decimal },
@@ -130,7 +176,7 @@ impl Order {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum OrderStatus {
     Pending,
     Filled,
@@ -178,6 +224,8 @@ mod tests {
             last: Decimal::from(101),
             volume_24h: Decimal::from(1000000),
             timestamp: Utc::now(),
+            sequence: None,
+            received_at: Utc::now(),
         };
 
         assert_eq!(price.mid_price(), Decimal::from(101));
@@ -192,6 +240,8 @@ mod tests {
             last: Decimal::from(101),
             volume_24h: Decimal::from(1000000),
             timestamp: Utc::now(),
+            sequence: None,
+            received_at: Utc::now(),
         };
 
         assert_eq!(price.spread(), Decimal::from(2));
@@ -206,6 +256,8 @@ mod tests {
             last: Decimal::from(101),
             volume_24h: Decimal::from(1000000),
             timestamp: Utc::now(),
+            sequence: None,
+            received_at: Utc::now(),
         };
 
         let spread_pct = price.spread_percentage();
@@ -222,6 +274,8 @@ mod tests {
             last: Decimal::ZERO,
             volume_24h: Decimal::ZERO,
             timestamp: Utc::now(),
+            sequence: None,
+            received_at: Utc::now(),
         };
 
         assert_eq!(price.spread_percentage(), Decimal::ZERO);
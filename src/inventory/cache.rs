@@ -0,0 +1,251 @@
+//! Cached exchange balances for instant pre-trade checks
+
+use crate::error::{ArbitrageError, Result};
+use crate::exchanges::{Balance, Exchange, ExchangeId, OrderResult, OrderSide, split_pair};
+use crate::logger::{debug, warn};
+use parking_lot::RwLock;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Default)]
+struct CacheState {
+    balances: HashMap<String, Balance>,
+    refreshed_at: Option<Instant>,
+}
+
+/// Balances for one exchange, refreshed on a schedule and adjusted by fills
+///
+/// # Business Logic
+///
+/// `get_balance()` is a network round trip (rate limited on Coinbase), too slow to
+/// run before every order. The cache answers from memory instead:
+/// - **Refresh**: `refresh()` (or the task from `spawn()`) replaces all balances
+///   with the exchange's view, including on-hold amounts
+/// - **Fills**: `apply_fill()` adjusts available balances immediately, so the cache
+///   stays accurate between refreshes
+/// - **Staleness**: balances older than `max_age` are rejected by
+///   `check_available()`, so a failing refresh can't leave trading on stale numbers
+///
+/// Clones share state.
+///
+/// # Example
+///
+/// ```rust
+/// use arb_bot::exchanges::{Balance, ExchangeId};
+/// use arb_bot::inventory::BalanceCache;
+/// use rust_decimal::Decimal;
+/// use std::time::Duration;
+///
+/// let cache = BalanceCache::new(ExchangeId::COINBASE, Duration::from_secs(30));
+/// assert!(cache.is_stale()); // never refreshed
+///
+/// cache.set_balance("USDC", Balance { available: Decimal::from(500), hold: Decimal::from(100) });
+/// assert!(cache.check_available("USDC", Decimal::from(400)).is_ok());
+/// assert!(cache.check_available("USDC", Decimal::from(550)).is_err()); // hold isn't spendable
+/// ```
+#[derive(Debug, Clone)]
+pub struct BalanceCache {
+    exchange: ExchangeId,
+    max_age: Duration,
+    state: Arc<RwLock<CacheState>>,
+}
+
+impl BalanceCache {
+    /// Create an empty cache for `exchange`; balances older than `max_age` are stale
+    pub fn new(exchange: ExchangeId, max_age: Duration) -> Self {
+        Self {
+            exchange,
+            max_age,
+            state: Arc::new(RwLock::new(CacheState::default())),
+        }
+    }
+
+    /// Exchange this cache belongs to
+    pub fn exchange(&self) -> ExchangeId {
+        self.exchange
+    }
+
+    /// Replace cached balances with the exchange's current view
+    pub async fn refresh<E: Exchange + ?Sized>(&self, exchange: &E, assets: &[&str]) -> Result<()> {
+        let balances = exchange.get_balances(assets).await?;
+        debug!(exchange = %self.exchange, assets = balances.len(), "Balance cache refreshed");
+
+        let mut state = self.state.write();
+        state.balances = balances;
+        state.refreshed_at = Some(Instant::now());
+        Ok(())
+    }
+
+    /// Spawn a background task calling `refresh()` every `interval`
+    ///
+    /// Failed refreshes are logged and the previous balances kept (they turn stale
+    /// after `max_age`).
+    pub fn spawn<E>(
+        self,
+        exchange: Arc<tokio::sync::RwLock<E>>,
+        assets: Vec<String>,
+        interval: Duration,
+    ) -> tokio::task::JoinHandle<()>
+    where
+        E: Exchange + ?Sized + 'static,
+    {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            let assets: Vec<&str> = assets.iter().map(String::as_str).collect();
+            loop {
+                ticker.tick().await;
+                let exchange = exchange.read().await;
+                if let Err(e) = self.refresh(&*exchange, &assets).await {
+                    warn!(exchange = %self.exchange, error = %e, "Balance refresh failed");
+                }
+            }
+        })
+    }
+
+    /// Set one balance directly and mark the cache fresh
+    pub fn set_balance(&self, asset: &str, balance: Balance) {
+        let mut state = self.state.write();
+        state.balances.insert(asset.to_string(), balance);
+        state.refreshed_at = Some(Instant::now());
+    }
+
+    /// Cached balance for an asset
+    pub fn get(&self, asset: &str) -> Option<Balance> {
+        self.state.read().balances.get(asset).copied()
+    }
+
+    /// Cached available amount (zero if unknown)
+    pub fn available(&self, asset: &str) -> Decimal {
+        self.get(asset).map(|b| b.available).unwrap_or_default()
+    }
+
+    /// Time since the last refresh (None if never refreshed)
+    pub fn age(&self) -> Option<Duration> {
+        self.state.read().refreshed_at.map(|t| t.elapsed())
+    }
+
+    /// True if never refreshed or last refreshed more than `max_age` ago
+    pub fn is_stale(&self) -> bool {
+        self.age().is_none_or(|age| age > self.max_age)
+    }
+
+    /// Apply a fill's deltas to available balances
+    pub fn apply_fill(&self, pair: &str, side: &OrderSide, result: &OrderResult) {
+        let Some((base, quote)) = split_pair(pair) else {
+            return;
+        };
+        let notional = result.filled_quantity * result.average_price.unwrap_or_default();
+        let (base_delta, quote_delta) = match side {
+            OrderSide::Buy => (result.filled_quantity, -notional),
+            OrderSide::Sell => (-result.filled_quantity, notional),
+        };
+
+        let mut state = self.state.write();
+        state.balances.entry(base.to_string()).or_default().available += base_delta;
+        state.balances.entry(quote.to_string()).or_default().available += quote_delta;
+        if !result.fee.is_zero() {
+            state
+                .balances
+                .entry(result.fee_asset.clone())
+                .or_default()
+                .available -= result.fee;
+        }
+    }
+
+    /// Fail unless the cache is fresh and at least `required` of `asset` is available
+    pub fn check_available(&self, asset: &str, required: Decimal) -> Result<()> {
+        if self.is_stale() {
+            return Err(ArbitrageError::ExchangeError {
+                exchange: self.exchange,
+                message: format!(
+                    "Balance cache stale (age {:?}, max {:?})",
+                    self.age(),
+                    self.max_age
+                ),
+                code: None,
+            });
+        }
+
+        let available = self.available(asset);
+        if available < required {
+            return Err(ArbitrageError::InsufficientBalance {
+                exchange: self.exchange,
+                asset: asset.to_string(),
+                required: required.to_string(),
+                available: available.to_string(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exchanges::OrderStatus;
+    use chrono::Utc;
+
+    #[test]
+    fn test_apply_fill_updates_available_only() {
+        let cache = BalanceCache::new(ExchangeId::BINANCE, Duration::from_secs(30));
+        cache.set_balance(
+            "USDC",
+            Balance {
+                available: Decimal::from(1000),
+                hold: Decimal::from(50),
+            },
+        );
+
+        cache.apply_fill(
+            "SOL/USDC",
+            &OrderSide::Buy,
+            &OrderResult {
+                order_id: "1".to_string(),
+                status: OrderStatus::Filled,
+                filled_quantity: Decimal::from(2),
+                average_price: Some(Decimal::from(100)),
+                fee: Decimal::ONE,
+                fee_asset: "USDC".to_string(),
+                timestamp: Utc::now(),
+            },
+        );
+
+        assert_eq!(
+            cache.get("USDC"),
+            Some(Balance {
+                available: Decimal::from(799),
+                hold: Decimal::from(50)
+            })
+        );
+        assert_eq!(cache.available("SOL"), Decimal::from(2));
+    }
+
+    #[test]
+    fn test_stale_cache_rejects_checks() {
+        let cache = BalanceCache::new(ExchangeId::BINANCE, Duration::from_millis(20));
+        assert!(cache.check_available("USDC", Decimal::ZERO).is_err());
+
+        cache.set_balance(
+            "USDC",
+            Balance {
+                available: Decimal::from(10),
+                hold: Decimal::ZERO,
+            },
+        );
+        assert!(cache.check_available("USDC", Decimal::from(10)).is_ok());
+        assert!(matches!(
+            cache.check_available("USDC", Decimal::from(11)),
+            Err(ArbitrageError::InsufficientBalance { .. })
+        ));
+
+        std::thread::sleep(Duration::from_millis(40));
+        assert!(cache.is_stale());
+        assert!(matches!(
+            cache.check_available("USDC", Decimal::from(1)),
+            Err(ArbitrageError::ExchangeError { .. })
+        ));
+    }
+}
@@ -0,0 +1,149 @@
+//! Per-venue inventory ledger
+
+use crate::error::Result;
+use crate::exchanges::{Exchange, OrderResult, OrderSide, split_pair};
+use crate::logger::debug;
+use crate::state::ExchangeId;
+use parking_lot::RwLock;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Asset holdings per (exchange, asset)
+///
+/// # Business Logic
+///
+/// Seeded from `Exchange::get_balance()` at startup, then kept current by applying
+/// each fill locally instead of re-querying the exchange:
+/// - **Buy**: base += filled, quote -= filled * price
+/// - **Sell**: base -= filled, quote += filled * price
+/// - **Fee**: deducted from whichever asset it was charged in
+///
+/// Clones share state.
+///
+/// # Example
+///
+/// ```rust
+/// use arb_bot::inventory::InventoryLedger;
+/// use arb_bot::state::ExchangeId;
+/// use rust_decimal::Decimal;
+///
+/// let ledger = InventoryLedger::new();
+/// ledger.set_balance(ExchangeId::BINANCE, "SOL", Decimal::from(10));
+/// ledger.set_balance(ExchangeId::COINBASE, "SOL", Decimal::from(30));
+/// assert_eq!(ledger.total("SOL"), Decimal::from(40));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct InventoryLedger {
+    balances: Arc<RwLock<HashMap<(ExchangeId, String), Decimal>>>,
+}
+
+impl InventoryLedger {
+    /// Create an empty ledger
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load balances for `assets` from an exchange, replacing ledger values
+    pub async fn seed(&self, exchange_id: ExchangeId, exchange: &dyn Exchange, assets: &[&str]) -> Result<()> {
+        for asset in assets {
+            let amount = exchange.get_balance(asset).await?;
+            debug!(exchange = %exchange_id, asset = %asset, amount = %amount, "Seeded inventory");
+            self.set_balance(exchange_id, asset, amount);
+        }
+        Ok(())
+    }
+
+    /// Set a balance directly
+    pub fn set_balance(&self, exchange: ExchangeId, asset: &str, amount: Decimal) {
+        self.balances.write().insert((exchange, asset.to_string()), amount);
+    }
+
+    /// Balance of an asset on one exchange (zero if unknown)
+    pub fn balance(&self, exchange: ExchangeId, asset: &str) -> Decimal {
+        self.balances
+            .read()
+            .get(&(exchange, asset.to_string()))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Balance of an asset across all exchanges
+    pub fn total(&self, asset: &str) -> Decimal {
+        self.balances
+            .read()
+            .iter()
+            .filter(|((_, a), _)| a == asset)
+            .map(|(_, amount)| *amount)
+            .sum()
+    }
+
+    /// Balances of an asset per exchange
+    pub fn by_exchange(&self, asset: &str) -> HashMap<ExchangeId, Decimal> {
+        self.balances
+            .read()
+            .iter()
+            .filter(|((_, a), _)| a == asset)
+            .map(|((exchange, _), amount)| (*exchange, *amount))
+            .collect()
+    }
+
+    /// Copy of every balance
+    pub fn snapshot(&self) -> HashMap<(ExchangeId, String), Decimal> {
+        self.balances.read().clone()
+    }
+
+    /// Apply a fill to base, quote and fee balances
+    pub fn apply_fill(&self, exchange: ExchangeId, pair: &str, side: &OrderSide, result: &OrderResult) {
+        let Some((base, quote)) = split_pair(pair) else {
+            return;
+        };
+        let notional = result.filled_quantity * result.average_price.unwrap_or_default();
+        let (base_delta, quote_delta) = match side {
+            OrderSide::Buy => (result.filled_quantity, -notional),
+            OrderSide::Sell => (-result.filled_quantity, notional),
+        };
+
+        let mut balances = self.balances.write();
+        *balances.entry((exchange, base.to_string())).or_default() += base_delta;
+        *balances.entry((exchange, quote.to_string())).or_default() += quote_delta;
+        if !result.fee.is_zero() {
+            *balances.entry((exchange, result.fee_asset.clone())).or_default() -= result.fee;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exchanges::OrderStatus;
+    use chrono::Utc;
+
+    fn fill(quantity: i64, price: i64, fee: Decimal, fee_asset: &str) -> OrderResult {
+        OrderResult {
+            order_id: "1".to_string(),
+            status: OrderStatus::Filled,
+            filled_quantity: Decimal::from(quantity),
+            average_price: Some(Decimal::from(price)),
+            fee,
+            fee_asset: fee_asset.to_string(),
+            timestamp: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_apply_fill_moves_base_quote_and_fee() {
+        let ledger = InventoryLedger::new();
+        let ex = ExchangeId::BINANCE;
+        ledger.set_balance(ex, "SOL", Decimal::from(5));
+        ledger.set_balance(ex, "USDC", Decimal::from(2000));
+
+        ledger.apply_fill(ex, "SOL/USDC", &OrderSide::Buy, &fill(10, 100, Decimal::ONE, "USDC"));
+        assert_eq!(ledger.balance(ex, "SOL"), Decimal::from(15));
+        assert_eq!(ledger.balance(ex, "USDC"), Decimal::from(999));
+
+        ledger.apply_fill(ex, "SOL/USDC", &OrderSide::Sell, &fill(5, 110, Decimal::new(1, 2), "SOL"));
+        assert_eq!(ledger.balance(ex, "SOL"), Decimal::new(999, 2));
+        assert_eq!(ledger.balance(ex, "USDC"), Decimal::from(1549));
+    }
+}
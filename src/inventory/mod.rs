@@ -0,0 +1,13 @@
+//! Inventory Management Module
+//!
+//! Tracks asset balances per venue and proposes rebalancing when arbitrage
+//! drains inventory on one side. `BalanceCache` keeps each exchange's reported
+//! balances in memory for pre-trade checks.
+
+pub mod cache;
+pub mod ledger;
+pub mod planner;
+
+pub use cache::BalanceCache;
+pub use ledger::InventoryLedger;
+pub use planner::{RebalanceAction, RebalanceConfig, RebalancePlanner};
@@ -0,0 +1,247 @@
+//! Cross-venue rebalancing planner
+
+use super::ledger::InventoryLedger;
+use crate::exchanges::split_pair;
+use crate::logger::info;
+use crate::state::ExchangeId;
+use rust_decimal::Decimal;
+use std::fmt;
+
+/// Planner thresholds
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RebalanceConfig {
+    /// Share of an asset's total on one venue that counts as skewed (e.g. 0.7 = 70%)
+    pub skew_threshold: Decimal,
+    /// Smallest transfer worth proposing, in units of the asset
+    pub min_transfer: Decimal,
+}
+
+impl Default for RebalanceConfig {
+    /// - Skew threshold: 70%
+    /// - Min transfer: 0 (any amount)
+    fn default() -> Self {
+        Self {
+            skew_threshold: Decimal::new(7, 1),
+            min_transfer: Decimal::ZERO,
+        }
+    }
+}
+
+/// A proposed rebalancing step
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RebalanceAction {
+    /// Prefer arbitrage trades on `pair` in this direction, which move inventory back
+    BiasDirection {
+        pair: String,
+        buy_on: ExchangeId,
+        sell_on: ExchangeId,
+    },
+    /// Move `amount` of `asset` between venues (output only - not executed)
+    Transfer {
+        asset: String,
+        from: ExchangeId,
+        to: ExchangeId,
+        amount: Decimal,
+    },
+}
+
+impl fmt::Display for RebalanceAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RebalanceAction::BiasDirection { pair, buy_on, sell_on } => {
+                write!(f, "prefer buying {} on {} and selling on {}", pair, buy_on, sell_on)
+            }
+            RebalanceAction::Transfer {
+                asset,
+                from,
+                to,
+                amount,
+            } => write!(f, "transfer {} {} from {} to {}", amount, asset, from, to),
+        }
+    }
+}
+
+/// Detects inventory skew across venues and proposes rebalancing
+///
+/// # Business Logic
+///
+/// Arbitrage that keeps firing the same way drains quote on the buy venue and base
+/// on the sell venue until one leg can't trade. For each asset, the planner finds
+/// the richest and poorest venue; if the richest holds more than `skew_threshold`
+/// of the total it proposes:
+///
+/// - **BiasDirection** (per pair): sell base where base is overweight and buy
+///   where it is underweight, so normal trading pulls inventory back. Falls back to
+///   the quote asset's skew (buy where quote is overweight) if base is balanced.
+/// - **Transfer** (per asset): move enough from richest to poorest to bring either
+///   to the average, if at least `min_transfer`.
+///
+/// Transfers are only proposed and logged; nothing is moved.
+#[derive(Debug, Clone, Default)]
+pub struct RebalancePlanner {
+    config: RebalanceConfig,
+}
+
+/// Richest/poorest venue for an asset when skewed
+struct Skew {
+    rich: ExchangeId,
+    poor: ExchangeId,
+    transfer: Decimal,
+}
+
+impl RebalancePlanner {
+    /// Create a planner with the given thresholds
+    pub fn new(config: RebalanceConfig) -> Self {
+        Self { config }
+    }
+
+    /// Propose actions for the given pairs based on current inventory
+    pub fn plan(&self, ledger: &InventoryLedger, pairs: &[&str]) -> Vec<RebalanceAction> {
+        let mut actions = Vec::new();
+        let mut assets: Vec<&str> = Vec::new();
+
+        for pair in pairs {
+            let Some((base, quote)) = split_pair(pair) else {
+                continue;
+            };
+
+            let direction = match (self.skew(ledger, base), self.skew(ledger, quote)) {
+                (Some(base_skew), _) => Some((base_skew.poor, base_skew.rich)),
+                (None, Some(quote_skew)) => Some((quote_skew.rich, quote_skew.poor)),
+                (None, None) => None,
+            };
+            if let Some((buy_on, sell_on)) = direction {
+                actions.push(RebalanceAction::BiasDirection {
+                    pair: pair.to_string(),
+                    buy_on,
+                    sell_on,
+                });
+            }
+
+            for asset in [base, quote] {
+                if !assets.contains(&asset) {
+                    assets.push(asset);
+                }
+            }
+        }
+
+        for asset in assets {
+            if let Some(skew) = self.skew(ledger, asset)
+                && skew.transfer > Decimal::ZERO
+                && skew.transfer >= self.config.min_transfer
+            {
+                actions.push(RebalanceAction::Transfer {
+                    asset: asset.to_string(),
+                    from: skew.rich,
+                    to: skew.poor,
+                    amount: skew.transfer,
+                });
+            }
+        }
+
+        for action in &actions {
+            info!(action = %action, "Rebalance proposed");
+        }
+
+        actions
+    }
+
+    fn skew(&self, ledger: &InventoryLedger, asset: &str) -> Option<Skew> {
+        let mut balances: Vec<(ExchangeId, Decimal)> = ledger.by_exchange(asset).into_iter().collect();
+        if balances.len() < 2 {
+            return None;
+        }
+        balances.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+
+        let total: Decimal = balances.iter().map(|(_, amount)| *amount).sum();
+        if total <= Decimal::ZERO {
+            return None;
+        }
+
+        let (poor, poor_amount) = balances[0];
+        let (rich, rich_amount) = balances[balances.len() - 1];
+        if rich_amount / total <= self.config.skew_threshold {
+            return None;
+        }
+
+        let average = total / Decimal::from(balances.len());
+        Some(Skew {
+            rich,
+            poor,
+            transfer: (rich_amount - average).min(average - poor_amount),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_balanced_inventory_needs_nothing() {
+        let ledger = InventoryLedger::new();
+        ledger.set_balance(ExchangeId::BINANCE, "SOL", Decimal::from(10));
+        ledger.set_balance(ExchangeId::COINBASE, "SOL", Decimal::from(12));
+        ledger.set_balance(ExchangeId::BINANCE, "USDC", Decimal::from(1000));
+        ledger.set_balance(ExchangeId::COINBASE, "USDC", Decimal::from(900));
+
+        assert!(RebalancePlanner::default().plan(&ledger, &["SOL/USDC"]).is_empty());
+    }
+
+    #[test]
+    fn test_skewed_base_biases_and_proposes_transfer() {
+        let ledger = InventoryLedger::new();
+        // Repeated buy-on-Binance / sell-on-Coinbase drained Coinbase's SOL
+        ledger.set_balance(ExchangeId::BINANCE, "SOL", Decimal::from(18));
+        ledger.set_balance(ExchangeId::COINBASE, "SOL", Decimal::from(2));
+        ledger.set_balance(ExchangeId::BINANCE, "USDC", Decimal::from(200));
+        ledger.set_balance(ExchangeId::COINBASE, "USDC", Decimal::from(1800));
+
+        let actions = RebalancePlanner::default().plan(&ledger, &["SOL/USDC"]);
+        assert_eq!(
+            actions,
+            vec![
+                RebalanceAction::BiasDirection {
+                    pair: "SOL/USDC".to_string(),
+                    buy_on: ExchangeId::COINBASE,
+                    sell_on: ExchangeId::BINANCE,
+                },
+                RebalanceAction::Transfer {
+                    asset: "SOL".to_string(),
+                    from: ExchangeId::BINANCE,
+                    to: ExchangeId::COINBASE,
+                    amount: Decimal::from(8),
+                },
+                RebalanceAction::Transfer {
+                    asset: "USDC".to_string(),
+                    from: ExchangeId::COINBASE,
+                    to: ExchangeId::BINANCE,
+                    amount: Decimal::from(800),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_quote_skew_biases_when_base_balanced() {
+        let ledger = InventoryLedger::new();
+        ledger.set_balance(ExchangeId::BINANCE, "SOL", Decimal::from(10));
+        ledger.set_balance(ExchangeId::COINBASE, "SOL", Decimal::from(10));
+        ledger.set_balance(ExchangeId::BINANCE, "USDC", Decimal::from(100));
+        ledger.set_balance(ExchangeId::COINBASE, "USDC", Decimal::from(900));
+
+        let planner = RebalancePlanner::new(RebalanceConfig {
+            min_transfer: Decimal::from(1000),
+            ..Default::default()
+        });
+        // Transfer below min_transfer is not proposed
+        assert_eq!(
+            planner.plan(&ledger, &["SOL/USDC"]),
+            vec![RebalanceAction::BiasDirection {
+                pair: "SOL/USDC".to_string(),
+                buy_on: ExchangeId::COINBASE,
+                sell_on: ExchangeId::BINANCE,
+            }]
+        );
+    }
+}
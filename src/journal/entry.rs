@@ -0,0 +1,169 @@
+//! Journal record types
+
+use crate::exchanges::{Order, OrderResult, OrderSide, OrderStatus, OrderType};
+use chrono::{DateTime, Utc};
+use rand::RngCore;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Generate a correlation ID linking an opportunity to its orders and fills
+pub fn new_correlation_id() -> String {
+    let mut bytes = [0u8; 8];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    format!("{}-{}", Utc::now().format("%Y%m%dT%H%M%S"), hex::encode(bytes))
+}
+
+/// Something worth persisting
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum JournalEvent {
+    /// A detected arbitrage opportunity
+    Opportunity {
+        pair: String,
+        buy_exchange: String,
+        sell_exchange: String,
+        buy_price: Decimal,
+        sell_price: Decimal,
+        /// Spread as a fraction of the buy price
+        spread: Decimal,
+    },
+    /// An order about to be sent
+    OrderRequest {
+        exchange: String,
+        pair: String,
+        side: OrderSide,
+        order_type: OrderType,
+        quantity: Decimal,
+    },
+    /// The exchange's response to an order
+    OrderResult {
+        exchange: String,
+        order_id: String,
+        status: OrderStatus,
+        filled_quantity: Decimal,
+        average_price: Option<Decimal>,
+        fee: Decimal,
+        fee_asset: String,
+        exchange_time: DateTime<Utc>,
+    },
+    /// An order the exchange rejected or that failed in transit
+    OrderError { exchange: String, message: String },
+    /// Executed quantity
+    Fill {
+        exchange: String,
+        order_id: String,
+        pair: String,
+        side: OrderSide,
+        quantity: Decimal,
+        price: Decimal,
+        fee: Decimal,
+        fee_asset: String,
+    },
+    /// An operator checked the attempt's unanswered order requests on the
+    /// exchange; reconciliation no longer flags them
+    Resolution { note: String },
+}
+
+impl JournalEvent {
+    /// Event kind as written in the `kind` field
+    pub fn kind(&self) -> &'static str {
+        match self {
+            JournalEvent::Opportunity { .. } => "opportunity",
+            JournalEvent::OrderRequest { .. } => "order_request",
+            JournalEvent::OrderResult { .. } => "order_result",
+            JournalEvent::OrderError { .. } => "order_error",
+            JournalEvent::Fill { .. } => "fill",
+            JournalEvent::Resolution { .. } => "resolution",
+        }
+    }
+
+    /// Exchange the event concerns (None for opportunities and resolutions,
+    /// which span an attempt's exchanges)
+    pub fn exchange(&self) -> Option<&str> {
+        match self {
+            JournalEvent::Opportunity { .. } | JournalEvent::Resolution { .. } => None,
+            JournalEvent::OrderRequest { exchange, .. }
+            | JournalEvent::OrderResult { exchange, .. }
+            | JournalEvent::OrderError { exchange, .. }
+            | JournalEvent::Fill { exchange, .. } => Some(exchange),
+        }
+    }
+
+    /// Order request event
+    pub fn order_request(exchange: &str, order: &Order) -> Self {
+        JournalEvent::OrderRequest {
+            exchange: exchange.to_string(),
+            pair: order.pair.clone(),
+            side: order.side.clone(),
+            order_type: order.order_type.clone(),
+            quantity: order.quantity,
+        }
+    }
+
+    /// Order result event
+    pub fn order_result(exchange: &str, result: &OrderResult) -> Self {
+        JournalEvent::OrderResult {
+            exchange: exchange.to_string(),
+            order_id: result.order_id.clone(),
+            status: result.status.clone(),
+            filled_quantity: result.filled_quantity,
+            average_price: result.average_price,
+            fee: result.fee,
+            fee_asset: result.fee_asset.clone(),
+            exchange_time: result.timestamp,
+        }
+    }
+
+    /// Fill event, or None if nothing was filled at a known price
+    pub fn fill(exchange: &str, order: &Order, result: &OrderResult) -> Option<Self> {
+        let price = result.average_price?;
+        if result.filled_quantity.is_zero() {
+            return None;
+        }
+        Some(JournalEvent::Fill {
+            exchange: exchange.to_string(),
+            order_id: result.order_id.clone(),
+            pair: order.pair.clone(),
+            side: order.side.clone(),
+            quantity: result.filled_quantity,
+            price,
+            fee: result.fee,
+            fee_asset: result.fee_asset.clone(),
+        })
+    }
+}
+
+/// One line in the journal
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    /// Local time the event was recorded
+    pub recorded_at: DateTime<Utc>,
+    /// Links the events of one arbitrage attempt
+    pub correlation_id: String,
+    #[serde(flatten)]
+    pub event: JournalEvent,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entry_round_trips_as_json_line() {
+        let entry = JournalEntry {
+            recorded_at: Utc::now(),
+            correlation_id: new_correlation_id(),
+            event: JournalEvent::order_request("binance", &Order::market_buy("SOL/USDC", Decimal::new(15, 1))),
+        };
+
+        let line = serde_json::to_string(&entry).unwrap();
+        assert!(line.contains(r#""kind":"order_request""#));
+        assert!(line.contains(r#""quantity":"1.5""#));
+        assert_eq!(serde_json::from_str::<JournalEntry>(&line).unwrap(), entry);
+    }
+
+    #[test]
+    fn test_correlation_ids_are_unique() {
+        assert_ne!(new_correlation_id(), new_correlation_id());
+    }
+}
@@ -0,0 +1,12 @@
+//! Trade Journal Module
+//!
+//! Durable, append-only record of opportunities, order requests, results and
+//! fills, linked by correlation IDs and queryable for reports.
+
+pub mod entry;
+pub mod query;
+pub mod writer;
+
+pub use entry::{JournalEntry, JournalEvent, new_correlation_id};
+pub use query::JournalQuery;
+pub use writer::{Journal, read_entries};
@@ -0,0 +1,76 @@
+//! Journal filters for reports
+
+use super::entry::JournalEntry;
+use chrono::{DateTime, Utc};
+
+/// Filter for reading journal entries; an empty query matches everything
+///
+/// ```rust
+/// use arb_bot::journal::JournalQuery;
+/// use chrono::{Duration, Utc};
+///
+/// // Today's fills on Binance
+/// let query = JournalQuery::new()
+///     .kind("fill")
+///     .exchange("binance")
+///     .since(Utc::now() - Duration::days(1));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct JournalQuery {
+    correlation_id: Option<String>,
+    kinds: Vec<String>,
+    exchange: Option<String>,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+}
+
+impl JournalQuery {
+    /// Match everything
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only entries of one arbitrage attempt
+    pub fn correlation_id(mut self, id: impl Into<String>) -> Self {
+        self.correlation_id = Some(id.into());
+        self
+    }
+
+    /// Only entries of this kind (repeatable: any listed kind matches)
+    pub fn kind(mut self, kind: impl Into<String>) -> Self {
+        self.kinds.push(kind.into());
+        self
+    }
+
+    /// Only entries concerning this exchange
+    pub fn exchange(mut self, exchange: impl Into<String>) -> Self {
+        self.exchange = Some(exchange.into());
+        self
+    }
+
+    /// Only entries recorded at or after `time`
+    pub fn since(mut self, time: DateTime<Utc>) -> Self {
+        self.since = Some(time);
+        self
+    }
+
+    /// Only entries recorded before `time`
+    pub fn until(mut self, time: DateTime<Utc>) -> Self {
+        self.until = Some(time);
+        self
+    }
+
+    /// True if `entry` passes every filter
+    pub fn matches(&self, entry: &JournalEntry) -> bool {
+        self.correlation_id
+            .as_ref()
+            .is_none_or(|id| *id == entry.correlation_id)
+            && (self.kinds.is_empty() || self.kinds.iter().any(|k| k == entry.event.kind()))
+            && self
+                .exchange
+                .as_ref()
+                .is_none_or(|ex| entry.event.exchange() == Some(ex.as_str()))
+            && self.since.is_none_or(|t| entry.recorded_at >= t)
+            && self.until.is_none_or(|t| entry.recorded_at < t)
+    }
+}
@@ -0,0 +1,191 @@
+//! Append-only JSON Lines journal
+
+use super::entry::{JournalEntry, JournalEvent};
+use super::query::JournalQuery;
+use crate::error::{ArbitrageError, Result};
+use crate::exchanges::{Order, OrderResult};
+use crate::logger::{error, warn};
+use chrono::Utc;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use tokio::sync::{mpsc, oneshot};
+
+enum Command {
+    Append(Box<JournalEntry>),
+    Flush(oneshot::Sender<()>),
+}
+
+/// Durable record of opportunities, orders and fills
+///
+/// # Business Logic
+///
+/// Entries are appended to a JSON Lines file, one entry per line, never rewritten.
+/// `record()` only queues the entry on a channel - a background task does the file
+/// I/O - so the trading path never waits on disk. `flush()` waits until everything
+/// queued so far is written and synced, for shutdown or before querying.
+///
+/// A line that fails to parse (e.g. cut short by a crash mid-write) is skipped
+/// with a warning when reading.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use arb_bot::journal::{Journal, JournalEvent, JournalQuery, new_correlation_id};
+///
+/// # async fn run() -> arb_bot::error::Result<()> {
+/// let journal = Journal::open("data/journal.jsonl")?;
+/// let id = new_correlation_id();
+/// journal.record(&id, JournalEvent::OrderError {
+///     exchange: "binance".to_string(),
+///     message: "rejected".to_string(),
+/// });
+///
+/// journal.flush().await?;
+/// let entries = journal.query(&JournalQuery::new().correlation_id(&id))?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Journal {
+    path: PathBuf,
+    tx: mpsc::UnboundedSender<Command>,
+}
+
+impl Journal {
+    /// Open (or create) a journal file and start its writer task
+    ///
+    /// Must be called from within a Tokio runtime.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        if let Some(parent) = path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = std::fs::OpenOptions::new().create(true).append(true).open(&path)?;
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        let writer_path = path.clone();
+        tokio::task::spawn_blocking(move || write_loop(file, rx, &writer_path));
+
+        Ok(Self { path, tx })
+    }
+
+    /// Journal file location
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Queue an event for writing (never blocks)
+    pub fn record(&self, correlation_id: &str, event: JournalEvent) {
+        let entry = JournalEntry {
+            recorded_at: Utc::now(),
+            correlation_id: correlation_id.to_string(),
+            event,
+        };
+        if self.tx.send(Command::Append(Box::new(entry))).is_err() {
+            error!(path = %self.path.display(), "Journal writer stopped, entry lost");
+        }
+    }
+
+    /// Record an order's outcome: its result and fill, or the error
+    pub fn record_order_outcome(
+        &self,
+        correlation_id: &str,
+        exchange: &str,
+        order: &Order,
+        outcome: &Result<OrderResult>,
+    ) {
+        match outcome {
+            Ok(result) => {
+                self.record(correlation_id, JournalEvent::order_result(exchange, result));
+                if let Some(fill) = JournalEvent::fill(exchange, order, result) {
+                    self.record(correlation_id, fill);
+                }
+            }
+            Err(e) => self.record(
+                correlation_id,
+                JournalEvent::OrderError {
+                    exchange: exchange.to_string(),
+                    message: e.to_string(),
+                },
+            ),
+        }
+    }
+
+    /// Wait until all previously recorded entries are on disk
+    pub async fn flush(&self) -> Result<()> {
+        let (done_tx, done_rx) = oneshot::channel();
+        self.tx
+            .send(Command::Flush(done_tx))
+            .map_err(|_| writer_stopped(&self.path))?;
+        done_rx.await.map_err(|_| writer_stopped(&self.path))
+    }
+
+    /// Read entries matching `query` (only what has been written so far)
+    pub fn query(&self, query: &JournalQuery) -> Result<Vec<JournalEntry>> {
+        read_entries(&self.path, query)
+    }
+}
+
+fn writer_stopped(path: &Path) -> ArbitrageError {
+    ArbitrageError::Io(std::io::Error::other(format!(
+        "journal writer for {} stopped",
+        path.display()
+    )))
+}
+
+fn write_loop(file: std::fs::File, mut rx: mpsc::UnboundedReceiver<Command>, path: &Path) {
+    let mut writer = std::io::BufWriter::new(file);
+    while let Some(command) = rx.blocking_recv() {
+        match command {
+            Command::Append(entry) => {
+                let result = serde_json::to_writer(&mut writer, &entry)
+                    .map_err(std::io::Error::from)
+                    .and_then(|_| writer.write_all(b"\n"));
+                if let Err(e) = result {
+                    error!(path = %path.display(), error = %e, "Failed to write journal entry");
+                }
+                // Flush when the queue drains so bursts share one write
+                if rx.is_empty()
+                    && let Err(e) = writer.flush()
+                {
+                    error!(path = %path.display(), error = %e, "Failed to flush journal");
+                }
+            }
+            Command::Flush(done) => {
+                if let Err(e) = writer.flush().and_then(|_| writer.get_ref().sync_data()) {
+                    error!(path = %path.display(), error = %e, "Failed to sync journal");
+                }
+                let _ = done.send(());
+            }
+        }
+    }
+    let _ = writer.flush();
+}
+
+/// Read entries matching `query` from a journal file
+///
+/// A missing file reads as empty.
+pub fn read_entries(path: impl AsRef<Path>, query: &JournalQuery) -> Result<Vec<JournalEntry>> {
+    let path = path.as_ref();
+    let file = match std::fs::File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut entries = Vec::new();
+    for (index, line) in BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<JournalEntry>(&line) {
+            Ok(entry) if query.matches(&entry) => entries.push(entry),
+            Ok(_) => {}
+            Err(e) => warn!(path = %path.display(), line = index + 1, error = %e, "Skipping unreadable journal line"),
+        }
+    }
+    Ok(entries)
+}
@@ -3,12 +3,20 @@
 //! This library provides the core functionality for the arbitrage bot,
 //! including exchange integrations, price monitoring, and trading logic.
 
+pub mod cli;
+pub mod clock;
 pub mod config;
 pub mod constants;
 pub mod error;
 pub mod exchanges;
+pub mod inventory;
+pub mod journal;
 pub mod logger;
+pub mod pnl;
+pub mod risk;
 pub mod state;
+pub mod strategy;
+pub mod trading;
 pub mod websocket;
 
 // Stub modules removed - not part of public API
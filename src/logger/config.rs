@@ -19,8 +19,21 @@ pub enum LogFormat {
     Compact,
 }
 
+impl std::str::FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "json" => Ok(LogFormat::Json),
+            "pretty" => Ok(LogFormat::Pretty),
+            "compact" => Ok(LogFormat::Compact),
+            _ => Err("must be one of json, pretty, compact".to_string()),
+        }
+    }
+}
+
 /// Logger configuration with parse pattern
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct LoggerConfig {
     level: String,
     format: LogFormat,
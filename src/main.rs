@@ -1,15 +1,19 @@
-use arb_bot::logger::{info, LoggerConfig, LogFormat};
+use arb_bot::cli::{self, Cli};
+use clap::Parser;
 
-fn main() -> color_eyre::Result<()> {
+#[tokio::main]
+async fn main() -> color_eyre::Result<()> {
     color_eyre::install()?;
-    
-    // Initialize logger
-    LoggerConfig::new()
-        .with_level("info")
-        .with_format(LogFormat::Pretty)
+    // Exchange credentials may live in a .env file
+    let _ = dotenvy::dotenv();
+
+    let cli = Cli::parse();
+
+    cli.logger_config()
         .init()
         .map_err(|e| color_eyre::eyre::eyre!("Failed to initialize logger: {}", e))?;
-    
-    info!("Hello, world!");
-    Ok(())
+
+    cli::execute(cli)
+        .await
+        .map_err(|e| color_eyre::eyre::eyre!("{}", e))
 }
@@ -0,0 +1,8 @@
+//! PnL Accounting Module
+//!
+//! Average-cost positions per exchange and pair, realized PnL on closes, and
+//! unrealized PnL marked to `PriceState` mids.
+
+pub mod tracker;
+
+pub use tracker::{PnlSnapshot, PnlTracker, Position, PositionPnl};
@@ -0,0 +1,401 @@
+//! Average-cost PnL accounting
+
+use crate::exchanges::{OrderResult, OrderSide, split_pair};
+use crate::logger::{info, warn};
+use crate::state::{ExchangeId, PriceState};
+use parking_lot::RwLock;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Net position in one pair on one exchange
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Position {
+    /// Signed base-asset quantity (negative = net sold on this venue)
+    pub quantity: Decimal,
+    /// Average entry price of the open quantity (quote per base)
+    pub avg_cost: Decimal,
+}
+
+impl Position {
+    /// Apply a signed trade at `price`, returning the realized PnL of any closed part
+    fn apply(&mut self, quantity: Decimal, price: Decimal) -> Decimal {
+        if quantity.is_zero() {
+            return Decimal::ZERO;
+        }
+
+        let same_direction = self.quantity.is_zero()
+            || self.quantity.is_sign_positive() == quantity.is_sign_positive();
+        if same_direction {
+            let new_quantity = self.quantity + quantity;
+            self.avg_cost = (self.quantity.abs() * self.avg_cost + quantity.abs() * price)
+                / new_quantity.abs();
+            self.quantity = new_quantity;
+            return Decimal::ZERO;
+        }
+
+        let closed = quantity.abs().min(self.quantity.abs());
+        let direction = if self.quantity.is_sign_positive() {
+            Decimal::ONE
+        } else {
+            Decimal::NEGATIVE_ONE
+        };
+        let realized = closed * (price - self.avg_cost) * direction;
+
+        self.quantity += quantity;
+        if self.quantity.is_zero() {
+            self.avg_cost = Decimal::ZERO;
+        } else if self.quantity.is_sign_positive() == quantity.is_sign_positive() {
+            // Flipped through flat - the remainder opened at this price
+            self.avg_cost = price;
+        }
+
+        realized
+    }
+}
+
+/// PnL of one open position
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PositionPnl {
+    pub exchange: ExchangeId,
+    pub pair: String,
+    pub position: Position,
+    /// Mid price used for marking (None if no price is available)
+    pub mark: Option<Decimal>,
+    /// `(mark - avg_cost) * quantity`, zero when unmarked
+    pub unrealized: Decimal,
+}
+
+/// Point-in-time PnL summary (quote currency)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PnlSnapshot {
+    /// Trading PnL from closed quantity, before fees
+    pub realized: Decimal,
+    /// Fees paid, converted to quote currency
+    pub fees: Decimal,
+    /// Mark-to-mid PnL of open positions
+    pub unrealized: Decimal,
+    /// Open positions
+    pub positions: Vec<PositionPnl>,
+    /// Fees that couldn't be converted, by asset
+    pub unconverted_fees: HashMap<String, Decimal>,
+}
+
+impl PnlSnapshot {
+    /// `realized - fees + unrealized`
+    pub fn net(&self) -> Decimal {
+        self.realized - self.fees + self.unrealized
+    }
+}
+
+#[derive(Debug, Default)]
+struct Ledger {
+    positions: HashMap<(ExchangeId, String), Position>,
+    realized: Decimal,
+    fees: Decimal,
+    unconverted_fees: HashMap<String, Decimal>,
+}
+
+/// Tracks realized and unrealized PnL from order fills
+///
+/// # Business Logic
+///
+/// Each fill updates an average-cost position per (exchange, pair). Trades that
+/// reduce a position realize `(fill price - avg cost) * closed quantity`; the open
+/// remainder is marked to the `PriceState` mid for unrealized PnL.
+///
+/// **Fees** are booked as an expense in the pair's quote currency:
+/// - Quote-asset fees are taken as is
+/// - Base-asset fees are valued at the fill price, and also reduce the position
+///   since that base never arrived (buy) or left in addition to the sale (sell)
+/// - Other assets (e.g. BNB) are converted at the `<FEE>/<QUOTE>` mid on the same
+///   exchange; if no price is available they are kept in `unconverted_fees`
+///
+/// Totals assume all pairs share a quote currency.
+///
+/// # Example
+///
+/// ```rust
+/// use arb_bot::exchanges::{OrderResult, OrderSide, OrderStatus};
+/// use arb_bot::pnl::PnlTracker;
+/// use arb_bot::state::{ExchangeId, PriceState};
+/// use chrono::Utc;
+/// use rust_decimal::Decimal;
+/// use std::time::Duration;
+///
+/// let tracker = PnlTracker::new(PriceState::new(Duration::from_secs(5)));
+/// let fill = |price: i64| OrderResult {
+///     order_id: "1".to_string(),
+///     status: OrderStatus::Filled,
+///     filled_quantity: Decimal::from(10),
+///     average_price: Some(Decimal::from(price)),
+///     fee: Decimal::ZERO,
+///     fee_asset: "USDC".to_string(),
+///     timestamp: Utc::now(),
+/// };
+///
+/// tracker.record_fill(ExchangeId::BINANCE, "SOL/USDC", &OrderSide::Buy, &fill(100));
+/// tracker.record_fill(ExchangeId::BINANCE, "SOL/USDC", &OrderSide::Sell, &fill(102));
+/// assert_eq!(tracker.snapshot().realized, Decimal::from(20));
+/// ```
+#[derive(Clone)]
+pub struct PnlTracker {
+    prices: PriceState,
+    ledger: Arc<RwLock<Ledger>>,
+}
+
+impl PnlTracker {
+    /// Create a tracker marking positions against `prices`
+    pub fn new(prices: PriceState) -> Self {
+        Self {
+            prices,
+            ledger: Arc::new(RwLock::new(Ledger::default())),
+        }
+    }
+
+    /// Apply a fill and return its realized PnL net of fees
+    ///
+    /// Returns `None` (and records nothing) if the result has no fill price.
+    pub fn record_fill(
+        &self,
+        exchange: ExchangeId,
+        pair: &str,
+        side: &OrderSide,
+        result: &OrderResult,
+    ) -> Option<Decimal> {
+        let price = result.average_price?;
+        if result.filled_quantity.is_zero() {
+            return None;
+        }
+
+        let (base, quote) = split_pair(pair).unwrap_or((pair, ""));
+        let mut quantity = match side {
+            OrderSide::Buy => result.filled_quantity,
+            OrderSide::Sell => -result.filled_quantity,
+        };
+
+        let fee_quote = if result.fee.is_zero() || result.fee_asset == quote {
+            Some(result.fee)
+        } else if result.fee_asset == base {
+            quantity -= result.fee;
+            Some(result.fee * price)
+        } else {
+            self.convert(exchange, &result.fee_asset, quote, result.fee)
+        };
+
+        let mut ledger = self.ledger.write();
+        let realized = ledger
+            .positions
+            .entry((exchange, pair.to_string()))
+            .or_default()
+            .apply(quantity, price);
+        ledger.realized += realized;
+
+        match fee_quote {
+            Some(fee) => {
+                ledger.fees += fee;
+                Some(realized - fee)
+            }
+            None => {
+                warn!(
+                    exchange = %exchange,
+                    fee = %result.fee,
+                    fee_asset = %result.fee_asset,
+                    "No price to convert fee, keeping it unconverted"
+                );
+                *ledger
+                    .unconverted_fees
+                    .entry(result.fee_asset.clone())
+                    .or_default() += result.fee;
+                Some(realized)
+            }
+        }
+    }
+
+    /// Convert `amount` of `asset` to `quote` using the same exchange's mid price
+    fn convert(&self, exchange: ExchangeId, asset: &str, quote: &str, amount: Decimal) -> Option<Decimal> {
+        self.prices
+            .get_price(exchange, &format!("{}/{}", asset, quote))
+            .map(|data| amount * data.price.mid_price())
+    }
+
+    /// Current position (flat if never traded)
+    pub fn position(&self, exchange: ExchangeId, pair: &str) -> Position {
+        self.ledger
+            .read()
+            .positions
+            .get(&(exchange, pair.to_string()))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Realized PnL, fees and open positions marked to current mids
+    pub fn snapshot(&self) -> PnlSnapshot {
+        let ledger = self.ledger.read();
+
+        let mut positions: Vec<PositionPnl> = ledger
+            .positions
+            .iter()
+            .filter(|(_, position)| !position.quantity.is_zero())
+            .map(|((exchange, pair), position)| {
+                let mark = self
+                    .prices
+                    .get_price(*exchange, pair)
+                    .map(|data| data.price.mid_price());
+                let unrealized = mark
+                    .map(|mid| (mid - position.avg_cost) * position.quantity)
+                    .unwrap_or_default();
+                PositionPnl {
+                    exchange: *exchange,
+                    pair: pair.clone(),
+                    position: *position,
+                    mark,
+                    unrealized,
+                }
+            })
+            .collect();
+        positions.sort_by(|a, b| (a.exchange, &a.pair).cmp(&(b.exchange, &b.pair)));
+
+        PnlSnapshot {
+            realized: ledger.realized,
+            fees: ledger.fees,
+            unrealized: positions.iter().map(|p| p.unrealized).sum(),
+            positions,
+            unconverted_fees: ledger.unconverted_fees.clone(),
+        }
+    }
+
+    /// Spawn a background task that logs a PnL summary every `interval`
+    pub fn spawn(self, interval: Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                let snapshot = self.snapshot();
+                info!(
+                    realized = %snapshot.realized,
+                    fees = %snapshot.fees,
+                    unrealized = %snapshot.unrealized,
+                    net = %snapshot.net(),
+                    open_positions = snapshot.positions.len(),
+                    "PnL summary"
+                );
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exchanges::{OrderStatus, Price};
+    use chrono::Utc;
+
+    fn fill(quantity: i64, price: i64, fee: Decimal, fee_asset: &str) -> OrderResult {
+        OrderResult {
+            order_id: "1".to_string(),
+            status: OrderStatus::Filled,
+            filled_quantity: Decimal::from(quantity),
+            average_price: Some(Decimal::from(price)),
+            fee,
+            fee_asset: fee_asset.to_string(),
+            timestamp: Utc::now(),
+        }
+    }
+
+    fn price(pair: &str, bid: i64, ask: i64) -> Price {
+        Price {
+            pair: pair.to_string(),
+            bid: Decimal::from(bid),
+            ask: Decimal::from(ask),
+            last: Decimal::from(bid),
+            volume_24h: Decimal::ZERO,
+            timestamp: Utc::now(),
+            sequence: None,
+            received_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_average_cost_and_partial_close() {
+        let tracker = PnlTracker::new(PriceState::new(Duration::from_secs(5)));
+        let ex = ExchangeId::BINANCE;
+
+        tracker.record_fill(ex, "SOL/USDC", &OrderSide::Buy, &fill(10, 100, Decimal::ZERO, "USDC"));
+        tracker.record_fill(ex, "SOL/USDC", &OrderSide::Buy, &fill(10, 110, Decimal::ZERO, "USDC"));
+        assert_eq!(tracker.position(ex, "SOL/USDC").avg_cost, Decimal::from(105));
+
+        let realized =
+            tracker.record_fill(ex, "SOL/USDC", &OrderSide::Sell, &fill(5, 115, Decimal::ZERO, "USDC"));
+        assert_eq!(realized, Some(Decimal::from(50)));
+        let position = tracker.position(ex, "SOL/USDC");
+        assert_eq!(position.quantity, Decimal::from(15));
+        assert_eq!(position.avg_cost, Decimal::from(105));
+    }
+
+    #[test]
+    fn test_short_position_and_flip() {
+        let tracker = PnlTracker::new(PriceState::new(Duration::from_secs(5)));
+        let ex = ExchangeId::COINBASE;
+
+        tracker.record_fill(ex, "SOL/USDC", &OrderSide::Sell, &fill(10, 100, Decimal::ZERO, "USDC"));
+        // Buy back 15 at 90: closes 10 short for +100, opens 5 long at 90
+        let realized =
+            tracker.record_fill(ex, "SOL/USDC", &OrderSide::Buy, &fill(15, 90, Decimal::ZERO, "USDC"));
+        assert_eq!(realized, Some(Decimal::from(100)));
+        assert_eq!(
+            tracker.position(ex, "SOL/USDC"),
+            Position {
+                quantity: Decimal::from(5),
+                avg_cost: Decimal::from(90)
+            }
+        );
+    }
+
+    #[test]
+    fn test_unrealized_marked_to_mid() {
+        let state = PriceState::new(Duration::from_secs(5));
+        let tracker = PnlTracker::new(state.clone());
+        tracker.record_fill(
+            ExchangeId::BINANCE,
+            "SOL/USDC",
+            &OrderSide::Buy,
+            &fill(10, 100, Decimal::ZERO, "USDC"),
+        );
+
+        // No price yet - unmarked
+        let snapshot = tracker.snapshot();
+        assert_eq!(snapshot.positions[0].mark, None);
+        assert_eq!(snapshot.unrealized, Decimal::ZERO);
+
+        state.update_price(ExchangeId::BINANCE, "SOL/USDC", price("SOL/USDC", 104, 106), 1);
+        let snapshot = tracker.snapshot();
+        assert_eq!(snapshot.positions[0].mark, Some(Decimal::from(105)));
+        assert_eq!(snapshot.unrealized, Decimal::from(50));
+        assert_eq!(snapshot.net(), Decimal::from(50));
+    }
+
+    #[test]
+    fn test_fee_conversion() {
+        let state = PriceState::new(Duration::from_secs(5));
+        let tracker = PnlTracker::new(state.clone());
+        let ex = ExchangeId::BINANCE;
+
+        // Quote fee
+        tracker.record_fill(ex, "SOL/USDC", &OrderSide::Buy, &fill(10, 100, Decimal::ONE, "USDC"));
+        // Base fee: valued at fill price and reduces the position
+        tracker.record_fill(ex, "SOL/USDC", &OrderSide::Buy, &fill(10, 100, Decimal::new(1, 1), "SOL"));
+        assert_eq!(tracker.position(ex, "SOL/USDC").quantity, Decimal::new(199, 1));
+        assert_eq!(tracker.snapshot().fees, Decimal::from(11));
+
+        // Third-asset fee without a price stays unconverted
+        tracker.record_fill(ex, "SOL/USDC", &OrderSide::Buy, &fill(1, 100, Decimal::ONE, "BNB"));
+        assert_eq!(tracker.snapshot().unconverted_fees["BNB"], Decimal::ONE);
+
+        // ...and is converted once BNB/USDC is known
+        state.update_price(ex, "BNB/USDC", price("BNB/USDC", 299, 301), 1);
+        tracker.record_fill(ex, "SOL/USDC", &OrderSide::Buy, &fill(1, 100, Decimal::ONE, "BNB"));
+        assert_eq!(tracker.snapshot().fees, Decimal::from(311));
+    }
+}
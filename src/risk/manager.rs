@@ -0,0 +1,304 @@
+//! Pre-trade risk checks
+
+use crate::config::RiskConfig;
+use crate::error::{ArbitrageError, Result};
+use crate::exchanges::{ExchangeId, Order, OrderSide, OrderType, split_pair};
+use crate::logger::warn;
+use chrono::{NaiveDate, Utc};
+use parking_lot::RwLock;
+use rust_decimal::Decimal;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Window for the orders-per-minute limit
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Default)]
+struct RiskState {
+    /// Net base-asset position per (exchange, asset)
+    positions: HashMap<(ExchangeId, String), Decimal>,
+    /// Times of recently accepted orders
+    recent_orders: VecDeque<Instant>,
+    /// Realized PnL for `pnl_date` (UTC)
+    daily_pnl: Decimal,
+    pnl_date: Option<NaiveDate>,
+}
+
+/// Enforces `RiskConfig` limits before every order
+///
+/// # Business Logic
+///
+/// `check_order()` runs, in order:
+/// 1. **Daily loss**: realized PnL for the UTC day must be above `-max_daily_loss`
+/// 2. **Notional**: `quantity * price` must not exceed `max_order_notional`
+/// 3. **Position**: the net base-asset position on that exchange after the order
+///    must stay within `max_position[asset]` (either direction)
+/// 4. **Rate**: fewer than `max_orders_per_minute` accepted in the last 60 seconds
+///
+/// An order that passes counts towards the rate limit immediately. Positions only
+/// move when fills are reported via `record_fill()`, and realized PnL via
+/// `record_realized_pnl()`.
+///
+/// Clones share state, including limits replaced by `set_config()`.
+///
+/// # Example
+///
+/// ```rust
+/// use arb_bot::config::RiskConfig;
+/// use arb_bot::exchanges::{ExchangeId, Order};
+/// use arb_bot::risk::RiskManager;
+/// use rust_decimal::Decimal;
+///
+/// let risk = RiskManager::new(RiskConfig::default().with_max_order_notional(Decimal::from(500)));
+///
+/// let order = Order::market_buy("SOL/USDC", Decimal::from(10));
+/// assert!(risk.check_order(ExchangeId::BINANCE, &order, Decimal::from(40)).is_ok());   // 400 notional
+/// assert!(risk.check_order(ExchangeId::BINANCE, &order, Decimal::from(60)).is_err());  // 600 notional
+/// ```
+#[derive(Debug, Clone)]
+pub struct RiskManager {
+    config: Arc<RwLock<Arc<RiskConfig>>>,
+    state: Arc<RwLock<RiskState>>,
+}
+
+impl RiskManager {
+    /// Create a risk manager with flat positions and no PnL
+    pub fn new(config: RiskConfig) -> Self {
+        Self {
+            config: Arc::new(RwLock::new(Arc::new(config))),
+            state: Arc::new(RwLock::new(RiskState::default())),
+        }
+    }
+
+    /// Limits being enforced
+    pub fn config(&self) -> Arc<RiskConfig> {
+        self.config.read().clone()
+    }
+
+    /// Enforce new limits from the next check on
+    ///
+    /// Positions, the rate window and today's PnL are kept, so a tightened
+    /// limit applies to exposure already taken.
+    pub fn set_config(&self, config: RiskConfig) {
+        *self.config.write() = Arc::new(config);
+    }
+
+    /// Check an order against every limit
+    ///
+    /// `reference_price` values market orders (e.g. the ask for a buy); limit
+    /// orders use their own price.
+    pub fn check_order(&self, exchange: ExchangeId, order: &Order, reference_price: Decimal) -> Result<()> {
+        let config = self.config();
+        let mut state = self.state.write();
+        roll_day(&mut state);
+
+        if let Some(max_loss) = config.max_daily_loss()
+            && state.daily_pnl <= -max_loss
+        {
+            return violation(
+                "max_daily_loss",
+                format!("realized PnL today {} reached limit -{}", state.daily_pnl, max_loss),
+            );
+        }
+
+        let price = match &order.order_type {
+            OrderType::Limit { price } => *price,
+            OrderType::Market => reference_price,
+        };
+        let notional = order.quantity * price;
+        if let Some(max_notional) = config.max_order_notional()
+            && notional > max_notional
+        {
+            return violation(
+                "max_order_notional",
+                format!("order notional {} exceeds {}", notional, max_notional),
+            );
+        }
+
+        let asset = base_asset(&order.pair);
+        if let Some(max_position) = config.max_position(asset) {
+            let current = state
+                .positions
+                .get(&(exchange, asset.to_string()))
+                .copied()
+                .unwrap_or_default();
+            let projected = current + signed_quantity(&order.side, order.quantity);
+            if projected.abs() > max_position {
+                return violation(
+                    "max_position",
+                    format!(
+                        "{} position on {} would be {} (limit {})",
+                        asset, exchange, projected, max_position
+                    ),
+                );
+            }
+        }
+
+        let now = Instant::now();
+        while state
+            .recent_orders
+            .front()
+            .is_some_and(|t| now.duration_since(*t) >= RATE_WINDOW)
+        {
+            state.recent_orders.pop_front();
+        }
+        if let Some(max_rate) = config.max_orders_per_minute()
+            && state.recent_orders.len() >= max_rate as usize
+        {
+            return violation(
+                "max_orders_per_minute",
+                format!("{} orders in the last minute", state.recent_orders.len()),
+            );
+        }
+        state.recent_orders.push_back(now);
+
+        Ok(())
+    }
+
+    /// Apply a fill to the net position on `exchange`
+    pub fn record_fill(&self, exchange: ExchangeId, pair: &str, side: &OrderSide, quantity: Decimal) {
+        let key = (exchange, base_asset(pair).to_string());
+        *self.state.write().positions.entry(key).or_default() += signed_quantity(side, quantity);
+    }
+
+    /// Set the net position directly (e.g. from a balance snapshot)
+    pub fn set_position(&self, exchange: ExchangeId, asset: &str, quantity: Decimal) {
+        self.state
+            .write()
+            .positions
+            .insert((exchange, asset.to_string()), quantity);
+    }
+
+    /// Net base-asset position on an exchange (zero if never traded)
+    pub fn position(&self, exchange: ExchangeId, asset: &str) -> Decimal {
+        self.state
+            .read()
+            .positions
+            .get(&(exchange, asset.to_string()))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Add realized PnL (negative for a loss) to today's total
+    pub fn record_realized_pnl(&self, pnl: Decimal) {
+        let mut state = self.state.write();
+        roll_day(&mut state);
+        state.daily_pnl += pnl;
+    }
+
+    /// Realized PnL for the current UTC day
+    pub fn daily_realized_pnl(&self) -> Decimal {
+        let mut state = self.state.write();
+        roll_day(&mut state);
+        state.daily_pnl
+    }
+}
+
+/// Reset the daily PnL when the UTC date changes
+fn roll_day(state: &mut RiskState) {
+    let today = Utc::now().date_naive();
+    if state.pnl_date != Some(today) {
+        state.pnl_date = Some(today);
+        state.daily_pnl = Decimal::ZERO;
+    }
+}
+
+/// Base asset of a pair (the whole symbol if it has no `/`)
+fn base_asset(pair: &str) -> &str {
+    split_pair(pair).map(|(base, _)| base).unwrap_or(pair)
+}
+
+fn signed_quantity(side: &OrderSide, quantity: Decimal) -> Decimal {
+    match side {
+        OrderSide::Buy => quantity,
+        OrderSide::Sell => -quantity,
+    }
+}
+
+fn violation(limit: &str, message: String) -> Result<()> {
+    warn!(limit = %limit, reason = %message, "Order rejected by risk check");
+    Err(ArbitrageError::RiskLimitExceeded {
+        limit: limit.to_string(),
+        message,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_limit(result: Result<()>, expected: &str) {
+        match result {
+            Err(ArbitrageError::RiskLimitExceeded { limit, .. }) => assert_eq!(limit, expected),
+            other => panic!("expected {} violation, got {:?}", expected, other),
+        }
+    }
+
+    #[test]
+    fn test_notional_limit_uses_limit_price() {
+        let risk = RiskManager::new(RiskConfig::default().with_max_order_notional(Decimal::from(1000)));
+        let order = Order {
+            order_type: OrderType::Limit {
+                price: Decimal::from(200),
+            },
+            ..Order::market_buy("SOL/USDC", Decimal::from(10))
+        };
+        // Reference price is ignored for limit orders
+        assert_limit(risk.check_order(ExchangeId::BINANCE, &order, Decimal::from(1)), "max_order_notional");
+    }
+
+    #[test]
+    fn test_position_limit_per_exchange() {
+        let risk = RiskManager::new(RiskConfig::default().with_max_position("SOL", Decimal::from(15)));
+        let buy = Order::market_buy("SOL/USDC", Decimal::from(10));
+
+        assert!(risk.check_order(ExchangeId::BINANCE, &buy, Decimal::from(100)).is_ok());
+        risk.record_fill(ExchangeId::BINANCE, "SOL/USDC", &OrderSide::Buy, Decimal::from(10));
+
+        assert_limit(risk.check_order(ExchangeId::BINANCE, &buy, Decimal::from(100)), "max_position");
+        // Other exchange is tracked separately
+        assert!(risk.check_order(ExchangeId::COINBASE, &buy, Decimal::from(100)).is_ok());
+        // Reducing the position is allowed
+        let sell = Order::market_sell("SOL/USDC", Decimal::from(20));
+        assert!(risk.check_order(ExchangeId::BINANCE, &sell, Decimal::from(100)).is_ok());
+    }
+
+    #[test]
+    fn test_orders_per_minute() {
+        let risk = RiskManager::new(RiskConfig::default().with_max_orders_per_minute(2));
+        let order = Order::market_buy("SOL/USDC", Decimal::ONE);
+
+        assert!(risk.check_order(ExchangeId::BINANCE, &order, Decimal::from(100)).is_ok());
+        assert!(risk.check_order(ExchangeId::COINBASE, &order, Decimal::from(100)).is_ok());
+        assert_limit(
+            risk.check_order(ExchangeId::BINANCE, &order, Decimal::from(100)),
+            "max_orders_per_minute",
+        );
+    }
+
+    #[test]
+    fn test_daily_loss_limit() {
+        let risk = RiskManager::new(RiskConfig::default().with_max_daily_loss(Decimal::from(50)));
+        let order = Order::market_buy("SOL/USDC", Decimal::ONE);
+
+        risk.record_realized_pnl(Decimal::from(-30));
+        assert!(risk.check_order(ExchangeId::BINANCE, &order, Decimal::from(100)).is_ok());
+
+        risk.record_realized_pnl(Decimal::from(-20));
+        assert_eq!(risk.daily_realized_pnl(), Decimal::from(-50));
+        assert_limit(risk.check_order(ExchangeId::BINANCE, &order, Decimal::from(100)), "max_daily_loss");
+    }
+
+    #[test]
+    fn test_set_config_applies_to_clones_and_keeps_positions() {
+        let risk = RiskManager::new(RiskConfig::default());
+        let clone = risk.clone();
+        let buy = Order::market_buy("SOL/USDC", Decimal::from(10));
+        risk.record_fill(ExchangeId::BINANCE, "SOL/USDC", &OrderSide::Buy, Decimal::from(10));
+
+        clone.set_config(RiskConfig::default().with_max_position("SOL", Decimal::from(15)));
+        assert_eq!(risk.config().max_position("SOL"), Some(Decimal::from(15)));
+        assert_limit(risk.check_order(ExchangeId::BINANCE, &buy, Decimal::from(100)), "max_position");
+    }
+}
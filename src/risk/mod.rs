@@ -0,0 +1,8 @@
+//! Risk Management Module
+//!
+//! Pre-trade limits on order size, positions, order rate and daily loss.
+//! Limits are configured in the `[risk]` TOML section (see `config::RiskConfig`).
+
+pub mod manager;
+
+pub use manager::RiskManager;
@@ -0,0 +1,158 @@
+//! Feed Latency Tracking
+//!
+//! Measures the delay between an exchange event (`Price::timestamp`) and local
+//! receipt (`Price::received_at`), and reports rolling percentiles per exchange.
+
+use super::types::ExchangeId;
+use crate::exchanges::Price;
+use parking_lot::RwLock;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+
+/// Default number of samples kept per exchange
+const DEFAULT_WINDOW: usize = 1000;
+
+/// Percentile summary of recent feed delays for one exchange (milliseconds)
+///
+/// Values can be negative when the local clock runs behind the exchange clock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LatencySnapshot {
+    /// Number of samples in the window
+    pub count: usize,
+    pub p50_ms: i64,
+    pub p90_ms: i64,
+    pub p99_ms: i64,
+    pub max_ms: i64,
+}
+
+/// Thread-safe rolling-window latency tracker keyed by exchange
+///
+/// # Example
+///
+/// ```rust
+/// use arb_bot::state::{ExchangeId, LatencyTracker};
+///
+/// let tracker = LatencyTracker::new(100);
+/// for ms in [10, 20, 30, 40, 50] {
+///     tracker.record_ms(ExchangeId::BINANCE, ms);
+/// }
+///
+/// let snapshot = tracker.snapshot(ExchangeId::BINANCE).unwrap();
+/// assert_eq!(snapshot.p50_ms, 30);
+/// assert_eq!(snapshot.max_ms, 50);
+/// ```
+#[derive(Clone)]
+pub struct LatencyTracker {
+    samples: Arc<RwLock<HashMap<ExchangeId, VecDeque<i64>>>>,
+    window: usize,
+}
+
+impl LatencyTracker {
+    /// Create a tracker keeping the last `window` samples per exchange
+    pub fn new(window: usize) -> Self {
+        Self {
+            samples: Arc::new(RwLock::new(HashMap::new())),
+            window: window.max(1),
+        }
+    }
+
+    /// Record the feed delay of a price update
+    pub fn record(&self, exchange: ExchangeId, price: &Price) {
+        self.record_ms(exchange, price.feed_latency().num_milliseconds());
+    }
+
+    /// Record a feed delay in milliseconds
+    pub fn record_ms(&self, exchange: ExchangeId, latency_ms: i64) {
+        let mut samples = self.samples.write();
+        let window = samples.entry(exchange).or_default();
+        if window.len() == self.window {
+            window.pop_front();
+        }
+        window.push_back(latency_ms);
+    }
+
+    /// Percentile summary for an exchange, or `None` if no samples yet
+    pub fn snapshot(&self, exchange: ExchangeId) -> Option<LatencySnapshot> {
+        let samples = self.samples.read();
+        let window = samples.get(&exchange)?;
+        if window.is_empty() {
+            return None;
+        }
+
+        let mut sorted: Vec<i64> = window.iter().copied().collect();
+        sorted.sort_unstable();
+
+        Some(LatencySnapshot {
+            count: sorted.len(),
+            p50_ms: percentile(&sorted, 50),
+            p90_ms: percentile(&sorted, 90),
+            p99_ms: percentile(&sorted, 99),
+            max_ms: sorted[sorted.len() - 1],
+        })
+    }
+
+    /// Percentile summaries for every exchange with samples
+    pub fn snapshots(&self) -> HashMap<ExchangeId, LatencySnapshot> {
+        let exchanges: Vec<ExchangeId> = self.samples.read().keys().copied().collect();
+        exchanges
+            .into_iter()
+            .filter_map(|ex| self.snapshot(ex).map(|s| (ex, s)))
+            .collect()
+    }
+
+    /// Drop all samples
+    pub fn clear(&self) {
+        self.samples.write().clear();
+    }
+}
+
+impl Default for LatencyTracker {
+    fn default() -> Self {
+        Self::new(DEFAULT_WINDOW)
+    }
+}
+
+/// Nearest-rank percentile of a sorted, non-empty slice
+fn percentile(sorted: &[i64], pct: usize) -> i64 {
+    let rank = (pct * sorted.len()).div_ceil(100).max(1);
+    sorted[rank.min(sorted.len()) - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentiles() {
+        let tracker = LatencyTracker::new(1000);
+        for ms in 1..=100 {
+            tracker.record_ms(ExchangeId::COINBASE, ms);
+        }
+
+        let snapshot = tracker.snapshot(ExchangeId::COINBASE).unwrap();
+        assert_eq!(snapshot.count, 100);
+        assert_eq!(snapshot.p50_ms, 50);
+        assert_eq!(snapshot.p90_ms, 90);
+        assert_eq!(snapshot.p99_ms, 99);
+        assert_eq!(snapshot.max_ms, 100);
+    }
+
+    #[test]
+    fn test_window_evicts_oldest() {
+        let tracker = LatencyTracker::new(3);
+        for ms in [1000, 1, 2, 3] {
+            tracker.record_ms(ExchangeId::BINANCE, ms);
+        }
+
+        let snapshot = tracker.snapshot(ExchangeId::BINANCE).unwrap();
+        assert_eq!(snapshot.count, 3);
+        assert_eq!(snapshot.max_ms, 3);
+    }
+
+    #[test]
+    fn test_no_samples() {
+        let tracker = LatencyTracker::default();
+        assert!(tracker.snapshot(ExchangeId::BINANCE).is_none());
+        assert!(tracker.snapshots().is_empty());
+    }
+}
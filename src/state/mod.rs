@@ -3,9 +3,15 @@
 //! Provides thread-safe shared state for storing and accessing latest prices
 //! from multiple exchanges, with staleness detection and spread calculation.
 
+pub mod latency;
 pub mod price;
+pub mod sequence;
+pub mod staleness;
 pub mod types;
 
+pub use latency::{LatencySnapshot, LatencyTracker};
 pub use price::PriceState;
-pub use types::{ExchangeId, PriceData};
+pub use sequence::{SequenceAction, SequenceCheck, SequencePolicy, SequenceStats, SequenceTracker};
+pub use staleness::{StalenessAlert, StalenessMonitor};
+pub use types::{ExchangeId, PriceData, TimeBasis};
 
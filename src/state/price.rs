@@ -3,13 +3,17 @@
 //! Thread-safe shared state for storing latest prices from multiple exchanges.
 //! Provides staleness detection and spread calculation between exchanges.
 
-use super::types::{ExchangeId, PriceData};
+use super::latency::LatencyTracker;
+use super::sequence::{SequenceAction, SequencePolicy, SequenceTracker};
+use super::types::{ExchangeId, PriceData, TimeBasis};
 use crate::exchanges::Price;
+use crate::websocket::ConnectionState;
 use parking_lot::RwLock;
 use rust_decimal::Decimal;
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::watch;
 
 /// Thread-safe price state manager for tracking prices across exchanges
 ///
@@ -24,7 +28,17 @@ use std::time::Duration;
 ///
 /// **Max Time Difference**: When comparing prices between exchanges, prices captured
 /// more than `max_age / 2` apart are rejected. This ensures we only compare prices
-/// from similar time windows, avoiding false arbitrage opportunities.
+/// from similar time windows, avoiding false arbitrage opportunities. With
+/// `TimeBasis::Exchange` the exchange event times are compared instead of local
+/// receive times, so cross-venue comparisons are genuinely simultaneous.
+///
+/// **Sequence Tracking**: `ingest()` runs exchange sequence numbers through a
+/// `SequenceTracker` so duplicate or out-of-order updates never overwrite newer prices.
+///
+/// **Venue Liveness**: Exchanges registered with `watch_connection()` are excluded
+/// from spread calculations whenever their feed is not `ConnectionState::Live`.
+/// A cached price can look fresh for a moment after its connection drops, so the
+/// connection state is checked in addition to staleness.
 ///
 /// # Example
 ///
@@ -45,8 +59,10 @@ use std::time::Duration;
 ///     last: Decimal::from(100),
 ///     volume_24h: Decimal::ZERO,
 ///     timestamp: Utc::now(),
+///     sequence: None,
+///     received_at: Utc::now(),
 /// };
-/// state.update_price(ExchangeId::Binance, "SOL/USDC", binance_price, 1);
+/// state.update_price(ExchangeId::BINANCE, "SOL/USDC", binance_price, 1);
 ///
 /// // Coinbase WebSocket updates price
 /// let coinbase_price = Price {
@@ -56,11 +72,13 @@ use std::time::Duration;
 ///     last: Decimal::from(102),
 ///     volume_24h: Decimal::ZERO,
 ///     timestamp: Utc::now(),
+///     sequence: None,
+///     received_at: Utc::now(),
 /// };
-/// state.update_price(ExchangeId::Coinbase, "SOL/USDC", coinbase_price, 1);
+/// state.update_price(ExchangeId::COINBASE, "SOL/USDC", coinbase_price, 1);
 ///
 /// // Arbitrage detector reads spread
-/// let spread = state.get_spread(ExchangeId::Binance, ExchangeId::Coinbase, "SOL/USDC");
+/// let spread = state.get_spread(ExchangeId::BINANCE, ExchangeId::COINBASE, "SOL/USDC");
 /// assert!(spread.is_some());
 /// ```
 #[derive(Clone)]
@@ -69,6 +87,14 @@ pub struct PriceState {
     prices: Arc<RwLock<HashMap<(ExchangeId, String), PriceData>>>,
     /// Maximum age before a price is considered stale
     max_age: Duration,
+    /// Per-(exchange, pair) sequence gap detection
+    sequence_tracker: SequenceTracker,
+    /// Per-exchange feed delay percentiles
+    latency_tracker: LatencyTracker,
+    /// Clock used for the max time difference check
+    time_basis: TimeBasis,
+    /// Connection state per exchange (unregistered exchanges are assumed live)
+    connections: Arc<RwLock<HashMap<ExchangeId, watch::Receiver<ConnectionState>>>>,
 }
 
 impl PriceState {
@@ -81,9 +107,77 @@ impl PriceState {
         Self {
             prices: Arc::new(RwLock::new(HashMap::new())),
             max_age,
+            sequence_tracker: SequenceTracker::default(),
+            latency_tracker: LatencyTracker::default(),
+            time_basis: TimeBasis::default(),
+            connections: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
+    /// Set the clock used by the max time difference check in `get_spread()`
+    pub fn with_time_basis(mut self, time_basis: TimeBasis) -> Self {
+        self.time_basis = time_basis;
+        self
+    }
+
+    /// Returns the feed latency tracker
+    pub fn latency_tracker(&self) -> &LatencyTracker {
+        &self.latency_tracker
+    }
+
+    /// Track an exchange's connection state for spread calculations
+    ///
+    /// Typically fed from `Exchange::watch_connection_state()`. Replaces any
+    /// previously registered receiver for the exchange.
+    pub fn watch_connection(&self, exchange: ExchangeId, state: watch::Receiver<ConnectionState>) {
+        self.connections.write().insert(exchange, state);
+    }
+
+    /// Returns true if the exchange's feed is live, or if its state isn't tracked
+    pub fn is_live(&self, exchange: ExchangeId) -> bool {
+        self.connections
+            .read()
+            .get(&exchange)
+            .is_none_or(|state| state.borrow().is_live())
+    }
+
+    /// Set the policy applied to sequence anomalies by `ingest()`
+    pub fn with_sequence_policy(mut self, policy: SequencePolicy) -> Self {
+        self.sequence_tracker = SequenceTracker::new(policy);
+        self
+    }
+
+    /// Returns the sequence tracker (for gap statistics and resets after resync)
+    pub fn sequence_tracker(&self) -> &SequenceTracker {
+        &self.sequence_tracker
+    }
+
+    /// Stores a price received from an exchange feed, honoring its sequence number
+    ///
+    /// Prices without a sequence number are always applied. Otherwise the
+    /// `SequencePolicy` decides: dropped updates leave the state untouched, and
+    /// `SequenceAction::Resync` tells the caller to resubscribe for this pair.
+    ///
+    /// The feed delay of every ingested price is recorded in the latency tracker.
+    pub fn ingest(&self, exchange: ExchangeId, price: Price) -> SequenceAction {
+        self.latency_tracker.record(exchange, &price);
+
+        let pair = price.pair.clone();
+        let (action, sequence) = match price.sequence {
+            Some(sequence) => (
+                self.sequence_tracker.observe(exchange, &pair, sequence),
+                sequence,
+            ),
+            None => (SequenceAction::Apply, 0),
+        };
+
+        if action != SequenceAction::Drop {
+            self.update_price(exchange, &pair, price, sequence);
+        }
+
+        action
+    }
+
     /// Updates the price for a given exchange and trading pair
     ///
     /// This is called by WebSocket managers when new price data arrives.
@@ -105,14 +199,30 @@ impl PriceState {
     /// Calculates the absolute spread between two exchanges for a trading pair
     ///
     /// Returns `None` if:
+    /// - Either exchange is tracked and not `Live`
     /// - Either price is missing
     /// - Either price is stale (> max_age)
     /// - Prices were captured too far apart (> max_age / 2)
     ///
     /// Spread = |mid_price2 - mid_price1|
     pub fn get_spread(&self, ex1: ExchangeId, ex2: ExchangeId, pair: &str) -> Option<Decimal> {
-        let price1 = self.get_price(ex1, pair)?;
-        let price2 = self.get_price(ex2, pair)?;
+        self.get_spread_between((ex1, pair), (ex2, pair))
+    }
+
+    /// Like `get_spread()`, for a market each exchange lists under its own symbol
+    ///
+    /// E.g. `(Binance, "BTC/USDT")` against `(Coinbase, "BTC/USD")`.
+    pub fn get_spread_between(
+        &self,
+        (ex1, pair1): (ExchangeId, &str),
+        (ex2, pair2): (ExchangeId, &str),
+    ) -> Option<Decimal> {
+        if !self.is_live(ex1) || !self.is_live(ex2) {
+            return None;
+        }
+
+        let price1 = self.get_price(ex1, pair1)?;
+        let price2 = self.get_price(ex2, pair2)?;
 
         // Check staleness - reject if either price is too old
         if price1.is_stale(self.max_age) || price2.is_stale(self.max_age) {
@@ -121,10 +231,18 @@ impl PriceState {
 
         // Check max time difference - reject if prices captured too far apart
         // This ensures we compare prices from similar time windows
-        let time_diff = if price1.timestamp > price2.timestamp {
-            price1.timestamp.duration_since(price2.timestamp)
-        } else {
-            price2.timestamp.duration_since(price1.timestamp)
+        let time_diff = match self.time_basis {
+            TimeBasis::Receive => {
+                if price1.timestamp > price2.timestamp {
+                    price1.timestamp.duration_since(price2.timestamp)
+                } else {
+                    price2.timestamp.duration_since(price1.timestamp)
+                }
+            }
+            TimeBasis::Exchange => (price1.price.timestamp - price2.price.timestamp)
+                .abs()
+                .to_std()
+                .unwrap_or(Duration::MAX),
         };
 
         // Max time difference: half of max_age (e.g., 2.5s if max_age is 5s)
@@ -224,11 +342,13 @@ mod tests {
             last: Decimal::from(100),
             volume_24h: Decimal::from(1000000),
             timestamp: Utc::now(),
+            sequence: None,
+            received_at: Utc::now(),
         };
 
-        state.update_price(ExchangeId::Binance, "SOL/USDC", price.clone(), 1);
+        state.update_price(ExchangeId::BINANCE, "SOL/USDC", price.clone(), 1);
 
-        let retrieved = state.get_price(ExchangeId::Binance, "SOL/USDC");
+        let retrieved = state.get_price(ExchangeId::BINANCE, "SOL/USDC");
         assert!(retrieved.is_some());
         let price_data = retrieved.unwrap();
         assert_eq!(price_data.price.pair, "SOL/USDC");
@@ -247,6 +367,8 @@ mod tests {
             last: Decimal::from(100),
             volume_24h: Decimal::ZERO,
             timestamp: Utc::now(),
+            sequence: None,
+            received_at: Utc::now(),
         };
 
         let coinbase_price = Price {
@@ -256,23 +378,50 @@ mod tests {
             last: Decimal::from(102),
             volume_24h: Decimal::ZERO,
             timestamp: Utc::now(),
+            sequence: None,
+            received_at: Utc::now(),
         };
 
-        state.update_price(ExchangeId::Binance, "SOL/USDC", binance_price, 1);
-        state.update_price(ExchangeId::Coinbase, "SOL/USDC", coinbase_price, 1);
+        state.update_price(ExchangeId::BINANCE, "SOL/USDC", binance_price, 1);
+        state.update_price(ExchangeId::COINBASE, "SOL/USDC", coinbase_price, 1);
 
-        let spread = state.get_spread(ExchangeId::Binance, ExchangeId::Coinbase, "SOL/USDC");
+        let spread = state.get_spread(ExchangeId::BINANCE, ExchangeId::COINBASE, "SOL/USDC");
         assert!(spread.is_some());
         // Binance mid: 100.5, Coinbase mid: 102.5, spread: 2.0
         assert_eq!(spread.unwrap(), Decimal::from(2));
     }
 
+    #[test]
+    fn test_spread_between_symbols() {
+        let state = PriceState::new(Duration::from_secs(5));
+        let price = |pair: &str, bid: i64| Price {
+            pair: pair.to_string(),
+            bid: Decimal::from(bid),
+            ask: Decimal::from(bid + 1),
+            last: Decimal::from(bid),
+            volume_24h: Decimal::ZERO,
+            timestamp: Utc::now(),
+            sequence: None,
+            received_at: Utc::now(),
+        };
+
+        state.update_price(ExchangeId::BINANCE, "BTC/USDT", price("BTC/USDT", 100), 1);
+        state.update_price(ExchangeId::COINBASE, "BTC/USD", price("BTC/USD", 103), 1);
+
+        assert!(state.get_spread(ExchangeId::BINANCE, ExchangeId::COINBASE, "BTC/USDT").is_none());
+        let spread = state.get_spread_between(
+            (ExchangeId::BINANCE, "BTC/USDT"),
+            (ExchangeId::COINBASE, "BTC/USD"),
+        );
+        assert_eq!(spread, Some(Decimal::from(3)));
+    }
+
     #[test]
     fn test_spread_missing_price() {
         let state = PriceState::new(Duration::from_secs(5));
 
         state.update_price(
-            ExchangeId::Binance,
+            ExchangeId::BINANCE,
             "SOL/USDC",
             Price {
                 pair: "SOL/USDC".to_string(),
@@ -281,11 +430,13 @@ mod tests {
                 last: Decimal::from(100),
                 volume_24h: Decimal::ZERO,
                 timestamp: Utc::now(),
+                sequence: None,
+                received_at: Utc::now(),
             },
             1,
         );
 
-        let spread = state.get_spread(ExchangeId::Binance, ExchangeId::Coinbase, "SOL/USDC");
+        let spread = state.get_spread(ExchangeId::BINANCE, ExchangeId::COINBASE, "SOL/USDC");
         assert!(spread.is_none());
     }
 
@@ -294,7 +445,7 @@ mod tests {
         let state = PriceState::new(Duration::from_secs(5));
 
         state.update_price(
-            ExchangeId::Binance,
+            ExchangeId::BINANCE,
             "SOL/USDC",
             Price {
                 pair: "SOL/USDC".to_string(),
@@ -303,6 +454,8 @@ mod tests {
                 last: Decimal::from(100),
                 volume_24h: Decimal::ZERO,
                 timestamp: Utc::now(),
+                sequence: None,
+                received_at: Utc::now(),
             },
             1,
         );
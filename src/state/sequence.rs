@@ -0,0 +1,358 @@
+//! Sequence Gap Detection
+//!
+//! Tracks exchange sequence numbers per (exchange, trading pair) and classifies
+//! each update as in-order, gap, duplicate or out-of-order.
+
+use super::types::ExchangeId;
+use crate::logger::{debug, warn};
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Classification of an incoming sequence number relative to the last one seen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SequenceCheck {
+    /// First sequence seen for this stream (or after a reset)
+    First,
+    /// Exactly `last + 1`
+    InOrder,
+    /// Sequence jumped forward; `missed` messages were never received
+    Gap { expected: u64, received: u64, missed: u64 },
+    /// Same sequence as the last one seen
+    Duplicate { sequence: u64 },
+    /// Sequence lower than the last one seen
+    OutOfOrder { last: u64, received: u64 },
+}
+
+/// What the caller should do with an update after the policy is applied
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SequenceAction {
+    /// Apply the update to state
+    Apply,
+    /// Discard the update (duplicate or stale)
+    Drop,
+    /// Apply the update, then resubscribe / refetch a snapshot for this stream
+    Resync,
+}
+
+/// Policy deciding how sequence anomalies are handled
+///
+/// # Business Logic
+///
+/// - **Duplicates / out-of-order**: an older update must never overwrite a newer
+///   price, so they are dropped by default.
+/// - **Gaps**: for ticker feeds the latest message supersedes the missed ones, so the
+///   default is to apply and just count the gap. Order book feeds need every delta
+///   and should set `resync_on_gap`.
+/// - **Shared sequences**: some feeds number every message of the product, across
+///   channels the bot never subscribes to (e.g. Coinbase's ticker), so on a busy
+///   pair they jump by hundreds and a jump says nothing about lost ticks. Without
+///   `contiguous`, gaps are neither counted nor logged; duplicates and reordering
+///   still are. On contiguous feeds every gap is a warning.
+///
+/// Only a feed ingesting every message it receives can be checked at all:
+/// sampled quotes skip whatever arrived in between.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SequencePolicy {
+    /// Drop duplicate and out-of-order updates
+    pub drop_stale: bool,
+    /// Ask the caller to resync when a gap is detected
+    pub resync_on_gap: bool,
+    /// The sequence numbers only this stream's messages, so a gap means messages were lost
+    pub contiguous: bool,
+}
+
+impl SequencePolicy {
+    /// Map a check result to an action
+    pub fn action(&self, check: SequenceCheck) -> SequenceAction {
+        match check {
+            SequenceCheck::First | SequenceCheck::InOrder => SequenceAction::Apply,
+            SequenceCheck::Gap { .. } if self.resync_on_gap => SequenceAction::Resync,
+            SequenceCheck::Gap { .. } => SequenceAction::Apply,
+            SequenceCheck::Duplicate { .. } | SequenceCheck::OutOfOrder { .. }
+                if self.drop_stale =>
+            {
+                SequenceAction::Drop
+            }
+            SequenceCheck::Duplicate { .. } | SequenceCheck::OutOfOrder { .. } => {
+                SequenceAction::Apply
+            }
+        }
+    }
+}
+
+impl Default for SequencePolicy {
+    fn default() -> Self {
+        Self {
+            drop_stale: true,
+            resync_on_gap: false,
+            contiguous: true,
+        }
+    }
+}
+
+/// Counters of sequence anomalies for one stream
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SequenceStats {
+    /// Number of gaps detected
+    pub gaps: u64,
+    /// Total number of messages missed across all gaps
+    pub missed: u64,
+    /// Number of duplicate sequences received
+    pub duplicates: u64,
+    /// Number of out-of-order sequences received
+    pub out_of_order: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+struct StreamState {
+    last: Option<u64>,
+    stats: SequenceStats,
+}
+
+/// Thread-safe sequence tracker keyed by (exchange, trading pair)
+///
+/// Every exchange uses the tracker's policy unless `set_exchange_policy()` gave
+/// it its own.
+///
+/// # Example
+///
+/// ```rust
+/// use arb_bot::state::{ExchangeId, SequenceAction, SequenceTracker};
+///
+/// let tracker = SequenceTracker::default();
+/// assert_eq!(tracker.observe(ExchangeId::COINBASE, "SOL/USDC", 10), SequenceAction::Apply);
+/// assert_eq!(tracker.observe(ExchangeId::COINBASE, "SOL/USDC", 13), SequenceAction::Apply);
+/// assert_eq!(tracker.observe(ExchangeId::COINBASE, "SOL/USDC", 12), SequenceAction::Drop);
+///
+/// let stats = tracker.stats(ExchangeId::COINBASE, "SOL/USDC");
+/// assert_eq!(stats.gaps, 1);
+/// assert_eq!(stats.missed, 2);
+/// assert_eq!(stats.out_of_order, 1);
+/// ```
+#[derive(Clone, Default)]
+pub struct SequenceTracker {
+    streams: Arc<RwLock<HashMap<(ExchangeId, String), StreamState>>>,
+    policy: SequencePolicy,
+    exchange_policies: Arc<RwLock<HashMap<ExchangeId, SequencePolicy>>>,
+}
+
+impl SequenceTracker {
+    /// Create a tracker with the given policy
+    pub fn new(policy: SequencePolicy) -> Self {
+        Self {
+            streams: Arc::new(RwLock::new(HashMap::new())),
+            policy,
+            exchange_policies: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Returns the default policy
+    pub fn policy(&self) -> SequencePolicy {
+        self.policy
+    }
+
+    /// Use `policy` for `exchange`'s streams instead of the default
+    ///
+    /// Typically fed from `Exchange::sequence_policy()`.
+    pub fn set_exchange_policy(&self, exchange: ExchangeId, policy: SequencePolicy) {
+        self.exchange_policies.write().insert(exchange, policy);
+    }
+
+    /// Returns the policy applied to `exchange`'s streams
+    pub fn exchange_policy(&self, exchange: ExchangeId) -> SequencePolicy {
+        self.exchange_policies
+            .read()
+            .get(&exchange)
+            .copied()
+            .unwrap_or(self.policy)
+    }
+
+    /// Classify a sequence number without applying the policy
+    ///
+    /// Updates the last-seen sequence (only when it moves forward) and the counters.
+    pub fn check(&self, exchange: ExchangeId, pair: &str, sequence: u64) -> SequenceCheck {
+        let contiguous = self.exchange_policy(exchange).contiguous;
+        let mut streams = self.streams.write();
+        let stream = streams.entry((exchange, pair.to_string())).or_default();
+
+        let check = match stream.last {
+            None => SequenceCheck::First,
+            Some(last) if sequence == last.saturating_add(1) => SequenceCheck::InOrder,
+            Some(last) if sequence > last => SequenceCheck::Gap {
+                expected: last + 1,
+                received: sequence,
+                missed: sequence - last - 1,
+            },
+            Some(last) if sequence == last => SequenceCheck::Duplicate { sequence },
+            Some(last) => SequenceCheck::OutOfOrder {
+                last,
+                received: sequence,
+            },
+        };
+
+        match check {
+            SequenceCheck::First | SequenceCheck::InOrder => stream.last = Some(sequence),
+            SequenceCheck::Gap {
+                expected,
+                received,
+                missed,
+            } => {
+                stream.last = Some(sequence);
+                if contiguous {
+                    stream.stats.gaps += 1;
+                    stream.stats.missed += missed;
+                    warn!(
+                        exchange = %exchange,
+                        pair = %pair,
+                        expected = expected,
+                        received = received,
+                        missed = missed,
+                        total_gaps = stream.stats.gaps,
+                        "Sequence gap detected"
+                    );
+                }
+            }
+            SequenceCheck::Duplicate { sequence } => {
+                stream.stats.duplicates += 1;
+                debug!(exchange = %exchange, pair = %pair, sequence = sequence, "Duplicate sequence");
+            }
+            SequenceCheck::OutOfOrder { last, received } => {
+                stream.stats.out_of_order += 1;
+                warn!(
+                    exchange = %exchange,
+                    pair = %pair,
+                    last = last,
+                    received = received,
+                    "Out-of-order sequence"
+                );
+            }
+        }
+
+        check
+    }
+
+    /// Classify a sequence number and return the action dictated by the policy
+    pub fn observe(&self, exchange: ExchangeId, pair: &str, sequence: u64) -> SequenceAction {
+        self.exchange_policy(exchange).action(self.check(exchange, pair, sequence))
+    }
+
+    /// Returns the last in-order sequence seen for a stream
+    pub fn last_sequence(&self, exchange: ExchangeId, pair: &str) -> Option<u64> {
+        self.streams
+            .read()
+            .get(&(exchange, pair.to_string()))
+            .and_then(|s| s.last)
+    }
+
+    /// Returns the anomaly counters for a stream
+    pub fn stats(&self, exchange: ExchangeId, pair: &str) -> SequenceStats {
+        self.streams
+            .read()
+            .get(&(exchange, pair.to_string()))
+            .map(|s| s.stats)
+            .unwrap_or_default()
+    }
+
+    /// Forget the last sequence of a stream (call after a resync/resubscribe)
+    ///
+    /// Counters are kept so gaps remain visible across resyncs.
+    pub fn reset(&self, exchange: ExchangeId, pair: &str) {
+        if let Some(stream) = self.streams.write().get_mut(&(exchange, pair.to_string())) {
+            stream.last = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_in_order_sequence() {
+        let tracker = SequenceTracker::default();
+        assert_eq!(tracker.check(ExchangeId::COINBASE, "SOL/USDC", 1), SequenceCheck::First);
+        assert_eq!(tracker.check(ExchangeId::COINBASE, "SOL/USDC", 2), SequenceCheck::InOrder);
+        assert_eq!(tracker.last_sequence(ExchangeId::COINBASE, "SOL/USDC"), Some(2));
+        assert_eq!(tracker.stats(ExchangeId::COINBASE, "SOL/USDC"), SequenceStats::default());
+    }
+
+    #[test]
+    fn test_gap_detection() {
+        let tracker = SequenceTracker::default();
+        tracker.check(ExchangeId::COINBASE, "SOL/USDC", 1);
+        assert_eq!(
+            tracker.check(ExchangeId::COINBASE, "SOL/USDC", 5),
+            SequenceCheck::Gap {
+                expected: 2,
+                received: 5,
+                missed: 3
+            }
+        );
+        let stats = tracker.stats(ExchangeId::COINBASE, "SOL/USDC");
+        assert_eq!(stats.gaps, 1);
+        assert_eq!(stats.missed, 3);
+    }
+
+    #[test]
+    fn test_duplicate_and_out_of_order_dropped() {
+        let tracker = SequenceTracker::default();
+        tracker.observe(ExchangeId::COINBASE, "SOL/USDC", 10);
+        assert_eq!(tracker.observe(ExchangeId::COINBASE, "SOL/USDC", 10), SequenceAction::Drop);
+        assert_eq!(tracker.observe(ExchangeId::COINBASE, "SOL/USDC", 9), SequenceAction::Drop);
+        // Stale updates do not move the last sequence backwards
+        assert_eq!(tracker.last_sequence(ExchangeId::COINBASE, "SOL/USDC"), Some(10));
+
+        let stats = tracker.stats(ExchangeId::COINBASE, "SOL/USDC");
+        assert_eq!(stats.duplicates, 1);
+        assert_eq!(stats.out_of_order, 1);
+    }
+
+    #[test]
+    fn test_resync_policy() {
+        let tracker = SequenceTracker::new(SequencePolicy {
+            resync_on_gap: true,
+            ..Default::default()
+        });
+        tracker.observe(ExchangeId::COINBASE, "SOL/USDC", 1);
+        assert_eq!(tracker.observe(ExchangeId::COINBASE, "SOL/USDC", 3), SequenceAction::Resync);
+
+        tracker.reset(ExchangeId::COINBASE, "SOL/USDC");
+        assert_eq!(tracker.check(ExchangeId::COINBASE, "SOL/USDC", 100), SequenceCheck::First);
+        assert_eq!(tracker.stats(ExchangeId::COINBASE, "SOL/USDC").gaps, 1);
+    }
+
+    #[test]
+    fn test_shared_sequence_gaps_are_not_counted() {
+        let tracker = SequenceTracker::default();
+        tracker.set_exchange_policy(
+            ExchangeId::COINBASE,
+            SequencePolicy {
+                contiguous: false,
+                ..Default::default()
+            },
+        );
+        tracker.check(ExchangeId::COINBASE, "SOL/USDC", 1);
+        assert!(matches!(
+            tracker.check(ExchangeId::COINBASE, "SOL/USDC", 500),
+            SequenceCheck::Gap { missed: 498, .. }
+        ));
+        assert_eq!(tracker.observe(ExchangeId::COINBASE, "SOL/USDC", 400), SequenceAction::Drop);
+        let stats = tracker.stats(ExchangeId::COINBASE, "SOL/USDC");
+        assert_eq!(stats.gaps, 0);
+        assert_eq!(stats.out_of_order, 1);
+
+        // Other exchanges keep the default
+        tracker.check(ExchangeId::BINANCE, "SOL/USDC", 1);
+        tracker.check(ExchangeId::BINANCE, "SOL/USDC", 3);
+        assert_eq!(tracker.stats(ExchangeId::BINANCE, "SOL/USDC").gaps, 1);
+    }
+
+    #[test]
+    fn test_streams_are_independent() {
+        let tracker = SequenceTracker::default();
+        tracker.check(ExchangeId::COINBASE, "SOL/USDC", 10);
+        assert_eq!(tracker.check(ExchangeId::COINBASE, "BTC/USD", 3), SequenceCheck::First);
+        assert_eq!(tracker.check(ExchangeId::BINANCE, "SOL/USDC", 1), SequenceCheck::First);
+    }
+}
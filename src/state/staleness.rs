@@ -0,0 +1,170 @@
+//! Per-pair staleness alerts
+//!
+//! Periodically scans `PriceState` and raises an alert when a (exchange, pair)
+//! price goes stale, and again when it recovers.
+
+use super::price::PriceState;
+use super::types::ExchangeId;
+use crate::logger::{info, warn};
+use parking_lot::RwLock;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// A change in staleness for one (exchange, pair)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StalenessAlert {
+    /// No update received within the threshold
+    Stale {
+        exchange: ExchangeId,
+        pair: String,
+        age: Duration,
+    },
+    /// Updates resumed after being stale
+    Recovered { exchange: ExchangeId, pair: String },
+}
+
+/// Watches `PriceState` for prices that stop updating
+///
+/// # Business Logic
+///
+/// The WebSocket inactivity timeout catches a whole connection going quiet. This
+/// monitor catches the finer case of a single pair freezing while the connection
+/// still delivers other data. Alerts fire on transitions only, so a frozen pair is
+/// reported once rather than on every scan.
+///
+/// Clones share the stale flags, so a clone kept after `spawn()` reports what
+/// the background task found.
+#[derive(Clone)]
+pub struct StalenessMonitor {
+    state: PriceState,
+    threshold: Duration,
+    stale: Arc<RwLock<HashSet<(ExchangeId, String)>>>,
+}
+
+impl StalenessMonitor {
+    /// Create a monitor alerting when a price is older than `threshold`
+    pub fn new(state: PriceState, threshold: Duration) -> Self {
+        Self {
+            state,
+            threshold,
+            stale: Arc::new(RwLock::new(HashSet::new())),
+        }
+    }
+
+    /// Scan all prices and return alerts for pairs that changed staleness
+    pub fn check(&mut self) -> Vec<StalenessAlert> {
+        let mut alerts = Vec::new();
+        let prices = self.state.get_all_prices();
+        let mut stale = self.stale.write();
+
+        for ((exchange, pair), data) in &prices {
+            let key = (*exchange, pair.clone());
+            if data.is_stale(self.threshold) {
+                if stale.insert(key) {
+                    warn!(
+                        exchange = %exchange,
+                        pair = %pair,
+                        age_ms = data.age().as_millis() as u64,
+                        threshold_ms = self.threshold.as_millis() as u64,
+                        "Price feed stale"
+                    );
+                    alerts.push(StalenessAlert::Stale {
+                        exchange: *exchange,
+                        pair: pair.clone(),
+                        age: data.age(),
+                    });
+                }
+            } else if stale.remove(&key) {
+                info!(exchange = %exchange, pair = %pair, "Price feed recovered");
+                alerts.push(StalenessAlert::Recovered {
+                    exchange: *exchange,
+                    pair: pair.clone(),
+                });
+            }
+        }
+
+        // Prices removed from state (e.g. remove_stale_prices) are no longer tracked
+        stale.retain(|key| prices.contains_key(key));
+
+        alerts
+    }
+
+    /// Returns true if the given pair is currently flagged stale
+    pub fn is_flagged(&self, exchange: ExchangeId, pair: &str) -> bool {
+        self.stale.read().contains(&(exchange, pair.to_string()))
+    }
+
+    /// Spawn a background task that runs `check()` every `interval`
+    pub fn spawn(&self, interval: Duration) -> tokio::task::JoinHandle<()> {
+        let mut monitor = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                monitor.check();
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exchanges::Price;
+    use chrono::Utc;
+    use rust_decimal::Decimal;
+
+    fn price() -> Price {
+        Price {
+            pair: "SOL/USDC".to_string(),
+            bid: Decimal::from(100),
+            ask: Decimal::from(101),
+            last: Decimal::from(100),
+            volume_24h: Decimal::ZERO,
+            timestamp: Utc::now(),
+            received_at: Utc::now(),
+            sequence: None,
+        }
+    }
+
+    #[test]
+    fn test_stale_then_recovered() {
+        let state = PriceState::new(Duration::from_secs(5));
+        let mut monitor = StalenessMonitor::new(state.clone(), Duration::from_millis(50));
+
+        state.update_price(ExchangeId::BINANCE, "SOL/USDC", price(), 1);
+        assert!(monitor.check().is_empty());
+
+        std::thread::sleep(Duration::from_millis(80));
+        let alerts = monitor.check();
+        assert_eq!(alerts.len(), 1);
+        assert!(matches!(alerts[0], StalenessAlert::Stale { exchange: ExchangeId::BINANCE, .. }));
+        assert!(monitor.is_flagged(ExchangeId::BINANCE, "SOL/USDC"));
+
+        // Still stale - no repeated alert
+        assert!(monitor.check().is_empty());
+
+        state.update_price(ExchangeId::BINANCE, "SOL/USDC", price(), 2);
+        assert_eq!(
+            monitor.check(),
+            vec![StalenessAlert::Recovered {
+                exchange: ExchangeId::BINANCE,
+                pair: "SOL/USDC".to_string()
+            }]
+        );
+        assert!(!monitor.is_flagged(ExchangeId::BINANCE, "SOL/USDC"));
+    }
+
+    #[tokio::test]
+    async fn test_spawned_task_shares_flags() {
+        let state = PriceState::new(Duration::from_secs(5));
+        let monitor = StalenessMonitor::new(state.clone(), Duration::from_millis(20));
+        state.update_price(ExchangeId::BINANCE, "SOL/USDC", price(), 1);
+
+        let task = monitor.spawn(Duration::from_millis(10));
+        tokio::time::sleep(Duration::from_millis(80)).await;
+        task.abort();
+        assert!(monitor.is_flagged(ExchangeId::BINANCE, "SOL/USDC"));
+    }
+}
@@ -3,22 +3,16 @@
 use crate::exchanges::Price;
 use std::time::{Duration, Instant};
 
-/// Identifies an exchange for price tracking
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub enum ExchangeId {
-    Binance,
-    Coinbase,
-    // Future exchanges can be added here
-}
-
-impl ExchangeId {
-    /// Returns the exchange name as a string
-    pub fn name(&self) -> &'static str {
-        match self {
-            ExchangeId::Binance => "Binance",
-            ExchangeId::Coinbase => "Coinbase",
-        }
-    }
+pub use crate::exchanges::ExchangeId;
+
+/// Clock used when checking whether two prices are close enough in time to compare
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TimeBasis {
+    /// Local receive time - includes differing network delays per venue
+    #[default]
+    Receive,
+    /// Exchange event time - compares what each venue saw at the same moment
+    Exchange,
 }
 
 /// Stores price data with metadata for staleness detection
@@ -62,28 +56,6 @@ mod tests {
     use std::thread;
     use std::time::Duration as StdDuration;
 
-    #[test]
-    fn test_exchange_id_name() {
-        assert_eq!(ExchangeId::Binance.name(), "Binance");
-        assert_eq!(ExchangeId::Coinbase.name(), "Coinbase");
-    }
-
-    #[test]
-    fn test_exchange_id_hash_eq() {
-        let id1 = ExchangeId::Binance;
-        let id2 = ExchangeId::Binance;
-        let id3 = ExchangeId::Coinbase;
-
-        assert_eq!(id1, id2);
-        assert_ne!(id1, id3);
-
-        // Verify it can be used in HashMap
-        use std::collections::HashMap;
-        let mut map = HashMap::new();
-        map.insert(id1, "value1");
-        assert_eq!(map.get(&id2), Some(&"value1"));
-    }
-
     #[test]
     fn test_price_data_new() {
         let price = Price {
@@ -93,6 +65,8 @@ mod tests {
             last: Decimal::from(100),
             volume_24h: Decimal::ZERO,
             timestamp: Utc::now(),
+            sequence: None,
+            received_at: Utc::now(),
         };
 
         let price_data = PriceData::new(price.clone(), 1);
@@ -110,6 +84,8 @@ mod tests {
             last: Decimal::from(100),
             volume_24h: Decimal::ZERO,
             timestamp: Utc::now(),
+            sequence: None,
+            received_at: Utc::now(),
         };
 
         let price_data = PriceData::new(price, 1);
@@ -131,6 +107,8 @@ mod tests {
             last: Decimal::from(100),
             volume_24h: Decimal::ZERO,
             timestamp: Utc::now(),
+            sequence: None,
+            received_at: Utc::now(),
         };
 
         let price_data = PriceData::new(price, 1);
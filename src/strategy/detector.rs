@@ -0,0 +1,246 @@
+//! Cross-venue spread detection
+
+use crate::config::MarketConfig;
+use crate::exchanges::Price;
+use crate::journal::JournalEvent;
+use crate::state::ExchangeId;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use std::time::Duration;
+
+/// An executable price difference between two venues
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Opportunity {
+    pub pair: String,
+    /// Venue to buy on (at its ask)
+    pub buy_exchange: ExchangeId,
+    /// Venue to sell on (at its bid)
+    pub sell_exchange: ExchangeId,
+    pub buy_price: Decimal,
+    pub sell_price: Decimal,
+    /// `(sell_price - buy_price) / buy_price`
+    pub spread: Decimal,
+}
+
+impl Opportunity {
+    /// Journal record of this opportunity
+    pub fn journal_event(&self) -> JournalEvent {
+        JournalEvent::Opportunity {
+            pair: self.pair.clone(),
+            buy_exchange: self.buy_exchange.to_string(),
+            sell_exchange: self.sell_exchange.to_string(),
+            buy_price: self.buy_price,
+            sell_price: self.sell_price,
+            spread: self.spread,
+        }
+    }
+}
+
+/// Finds spreads above a threshold, at most once per cooldown
+///
+/// # Business Logic
+///
+/// An opportunity must be executable, not just a mid-price difference: the bid on
+/// one venue has to exceed the ask on the other by at least `threshold` (as a
+/// fraction of the ask). Both directions are checked and the wider one wins.
+///
+/// After an opportunity is returned, further ones are suppressed until `cooldown`
+/// has passed. Time is passed in by the caller so recorded data can be replayed.
+///
+/// The detector does not check staleness or venue liveness - callers gate on
+/// `PriceState::get_spread()` first.
+///
+/// # Example
+///
+/// ```rust
+/// use arb_bot::exchanges::Price;
+/// use arb_bot::state::ExchangeId;
+/// use arb_bot::strategy::SpreadDetector;
+/// use chrono::Utc;
+/// use rust_decimal::Decimal;
+/// use std::time::Duration;
+///
+/// let price = |bid: i64, ask: i64| Price {
+///     pair: "SOL/USDC".to_string(),
+///     bid: Decimal::from(bid),
+///     ask: Decimal::from(ask),
+///     last: Decimal::from(bid),
+///     volume_24h: Decimal::ZERO,
+///     timestamp: Utc::now(),
+///     received_at: Utc::now(),
+///     sequence: None,
+/// };
+///
+/// let mut detector = SpreadDetector::new(Decimal::new(1, 2), Duration::from_secs(5));
+/// let binance = price(99, 100);
+/// let coinbase = price(102, 103);
+///
+/// let opportunity = detector
+///     .detect((ExchangeId::BINANCE, &binance), (ExchangeId::COINBASE, &coinbase), Utc::now())
+///     .unwrap();
+/// assert_eq!(opportunity.buy_exchange, ExchangeId::BINANCE);
+/// assert_eq!(opportunity.spread, Decimal::new(2, 2));
+/// ```
+#[derive(Debug, Clone)]
+pub struct SpreadDetector {
+    threshold: Decimal,
+    cooldown: Duration,
+    last_fired: Option<DateTime<Utc>>,
+}
+
+impl SpreadDetector {
+    /// Detector firing on spreads of at least `threshold` (a fraction, e.g. 0.002)
+    pub fn new(threshold: Decimal, cooldown: Duration) -> Self {
+        Self {
+            threshold,
+            cooldown,
+            last_fired: None,
+        }
+    }
+
+    /// Detector using a market's spread threshold and cooldown
+    pub fn from_config(config: &MarketConfig) -> Self {
+        Self::new(
+            config.spread_threshold(),
+            Duration::from_millis(config.cooldown_ms()),
+        )
+    }
+
+    /// Switch to a market's threshold and cooldown
+    ///
+    /// A running cooldown is measured against the new length.
+    pub fn update(&mut self, config: &MarketConfig) {
+        self.threshold = config.spread_threshold();
+        self.cooldown = Duration::from_millis(config.cooldown_ms());
+    }
+
+    /// Best opportunity between two venues, ignoring the cooldown
+    pub fn evaluate(
+        &self,
+        a: (ExchangeId, &Price),
+        b: (ExchangeId, &Price),
+    ) -> Option<Opportunity> {
+        let forward = Self::directional(a, b);
+        let backward = Self::directional(b, a);
+        let best = match (forward, backward) {
+            (Some(f), Some(r)) => Some(if r.spread > f.spread { r } else { f }),
+            (f, r) => f.or(r),
+        }?;
+
+        (best.spread >= self.threshold).then_some(best)
+    }
+
+    /// Like `evaluate()`, but at most once per cooldown (measured from `now`)
+    pub fn detect(
+        &mut self,
+        a: (ExchangeId, &Price),
+        b: (ExchangeId, &Price),
+        now: DateTime<Utc>,
+    ) -> Option<Opportunity> {
+        if let Some(last) = self.last_fired
+            && (now - last).to_std().unwrap_or_default() < self.cooldown
+        {
+            return None;
+        }
+
+        let opportunity = self.evaluate(a, b)?;
+        self.last_fired = Some(now);
+        Some(opportunity)
+    }
+
+    /// Buy on `buy` at its ask, sell on `sell` at its bid
+    fn directional(buy: (ExchangeId, &Price), sell: (ExchangeId, &Price)) -> Option<Opportunity> {
+        let (buy_exchange, buy_price) = buy;
+        let (sell_exchange, sell_price) = sell;
+        if buy_price.ask.is_zero() || sell_price.bid <= buy_price.ask {
+            return None;
+        }
+
+        Some(Opportunity {
+            pair: buy_price.pair.clone(),
+            buy_exchange,
+            sell_exchange,
+            buy_price: buy_price.ask,
+            sell_price: sell_price.bid,
+            spread: (sell_price.bid - buy_price.ask) / buy_price.ask,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn price(bid: i64, ask: i64) -> Price {
+        Price {
+            pair: "SOL/USDC".to_string(),
+            bid: Decimal::from(bid),
+            ask: Decimal::from(ask),
+            last: Decimal::from(bid),
+            volume_24h: Decimal::ZERO,
+            timestamp: Utc::now(),
+            received_at: Utc::now(),
+            sequence: None,
+        }
+    }
+
+    #[test]
+    fn test_mid_difference_is_not_enough() {
+        // Mids differ by 1 but the books overlap
+        let detector = SpreadDetector::new(Decimal::ZERO, Duration::ZERO);
+        let a = price(99, 101);
+        let b = price(100, 102);
+        assert!(detector.evaluate((ExchangeId::BINANCE, &a), (ExchangeId::COINBASE, &b)).is_none());
+    }
+
+    #[test]
+    fn test_picks_direction_and_applies_threshold() {
+        let a = price(105, 106);
+        let b = price(99, 100);
+        let detector = SpreadDetector::new(Decimal::new(5, 2), Duration::ZERO);
+        let opportunity = detector
+            .evaluate((ExchangeId::BINANCE, &a), (ExchangeId::COINBASE, &b))
+            .unwrap();
+        assert_eq!(opportunity.buy_exchange, ExchangeId::COINBASE);
+        assert_eq!(opportunity.sell_price, Decimal::from(105));
+
+        let strict = SpreadDetector::new(Decimal::new(6, 2), Duration::ZERO);
+        assert!(strict.evaluate((ExchangeId::BINANCE, &a), (ExchangeId::COINBASE, &b)).is_none());
+    }
+
+    #[test]
+    fn test_update_swaps_threshold_and_cooldown() {
+        let a = price(99, 100);
+        let b = price(102, 103);
+        let mut detector = SpreadDetector::new(Decimal::ONE, Duration::from_secs(60));
+        assert!(detector.evaluate((ExchangeId::BINANCE, &a), (ExchangeId::COINBASE, &b)).is_none());
+
+        let config = crate::config::TradingConfig::try_from(crate::config::trading::RawTradingConfig {
+            pair: Some("SOL/USDC".to_string()),
+            spread_threshold: Some("0.01".into()),
+            order_size: Some(1.0.into()),
+            cooldown_ms: Some(1000),
+            markets: Vec::new(),
+        })
+        .unwrap();
+        detector.update(&config.markets()[0]);
+        let start = Utc::now();
+        assert!(detector.detect((ExchangeId::BINANCE, &a), (ExchangeId::COINBASE, &b), start).is_some());
+        let later = start + chrono::Duration::seconds(1);
+        assert!(detector.detect((ExchangeId::BINANCE, &a), (ExchangeId::COINBASE, &b), later).is_some());
+    }
+
+    #[test]
+    fn test_cooldown_suppresses_repeats() {
+        let a = price(99, 100);
+        let b = price(102, 103);
+        let mut detector = SpreadDetector::new(Decimal::ZERO, Duration::from_secs(5));
+        let start = Utc::now();
+
+        assert!(detector.detect((ExchangeId::BINANCE, &a), (ExchangeId::COINBASE, &b), start).is_some());
+        let soon = start + chrono::Duration::seconds(2);
+        assert!(detector.detect((ExchangeId::BINANCE, &a), (ExchangeId::COINBASE, &b), soon).is_none());
+        let later = start + chrono::Duration::seconds(5);
+        assert!(detector.detect((ExchangeId::BINANCE, &a), (ExchangeId::COINBASE, &b), later).is_some());
+    }
+}
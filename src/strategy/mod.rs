@@ -0,0 +1,10 @@
+//! Trading Strategy Module
+//!
+//! Detects executable cross-venue spreads and replays recorded prices to
+//! estimate what the strategy would have made.
+
+pub mod backtest;
+pub mod detector;
+
+pub use backtest::{Backtest, BacktestReport, BacktestTrade, Tick, parse_ticks};
+pub use detector::{Opportunity, SpreadDetector};
@@ -0,0 +1,402 @@
+//! Global trading kill switch
+//!
+//! Trips on repeated order failures, authentication errors, balance discrepancies,
+//! a kill-switch file, or an explicit call, and blocks order placement until reset.
+
+use crate::error::{ArbitrageError, Result};
+use crate::exchanges::{ExchangeId, OrderResult};
+use crate::logger::{error, info, warn};
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock;
+use rust_decimal::Decimal;
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Why trading was halted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TripReason {
+    /// Too many orders failed in a row
+    ConsecutiveOrderFailures { count: u32 },
+    /// An exchange rejected our credentials
+    AuthenticationFailure { exchange: ExchangeId, reason: String },
+    /// Exchange balance differs from what we expect to hold
+    BalanceDiscrepancy {
+        exchange: ExchangeId,
+        asset: String,
+        expected: Decimal,
+        actual: Decimal,
+    },
+    /// The kill-switch file exists
+    KillSwitchFile { path: PathBuf },
+    /// Halted explicitly by an operator or another component
+    Manual { reason: String },
+    /// Startup reconciliation has not yet completed cleanly
+    RecoveryPending,
+}
+
+impl fmt::Display for TripReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TripReason::ConsecutiveOrderFailures { count } => {
+                write!(f, "{} consecutive order failures", count)
+            }
+            TripReason::AuthenticationFailure { exchange, reason } => {
+                write!(f, "authentication failure on {}: {}", exchange, reason)
+            }
+            TripReason::BalanceDiscrepancy {
+                exchange,
+                asset,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "balance discrepancy on {} for {}: expected {}, actual {}",
+                exchange, asset, expected, actual
+            ),
+            TripReason::KillSwitchFile { path } => {
+                write!(f, "kill-switch file present at {}", path.display())
+            }
+            TripReason::Manual { reason } => write!(f, "manual halt: {}", reason),
+            TripReason::RecoveryPending => write!(f, "startup reconciliation pending"),
+        }
+    }
+}
+
+/// A recorded trip
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trip {
+    pub reason: TripReason,
+    pub tripped_at: DateTime<Utc>,
+}
+
+/// Conditions that trip the guard
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TradingGuardConfig {
+    /// Consecutive failed orders that trip the guard (0 = disabled)
+    pub max_consecutive_failures: u32,
+    /// Largest tolerated |expected - actual| balance difference
+    pub balance_tolerance: Decimal,
+    /// Trip whenever this file exists (e.g. `touch /var/run/arb_bot.halt`)
+    pub kill_switch_file: Option<PathBuf>,
+}
+
+impl Default for TradingGuardConfig {
+    /// - Max consecutive failures: 3
+    /// - Balance tolerance: 0
+    /// - Kill-switch file: none
+    fn default() -> Self {
+        Self {
+            max_consecutive_failures: 3,
+            balance_tolerance: Decimal::ZERO,
+            kill_switch_file: None,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct GuardState {
+    trip: Option<Trip>,
+    consecutive_failures: u32,
+}
+
+/// Shared trading kill switch
+///
+/// # Business Logic
+///
+/// Once tripped, `check()` fails with `ArbitrageError::TradingHalted` until
+/// `reset()` is called by an operator - the guard never resets itself, since the
+/// conditions that trip it (bad credentials, missing funds) need a human to look.
+///
+/// The guard only gates order placement. Price feeds keep running while it is
+/// tripped so monitoring and spread history are unaffected.
+///
+/// Clones share state, so one guard can be handed to every exchange wrapper.
+///
+/// # Example
+///
+/// ```rust
+/// use arb_bot::trading::{TradingGuard, TradingGuardConfig, TripReason};
+///
+/// let guard = TradingGuard::new(TradingGuardConfig::default());
+/// assert!(guard.check().is_ok());
+///
+/// guard.trip(TripReason::Manual { reason: "maintenance".to_string() });
+/// assert!(guard.check().is_err());
+///
+/// guard.reset();
+/// assert!(guard.check().is_ok());
+/// ```
+#[derive(Debug, Clone)]
+pub struct TradingGuard {
+    config: Arc<TradingGuardConfig>,
+    state: Arc<RwLock<GuardState>>,
+}
+
+impl TradingGuard {
+    /// Create an untripped guard
+    pub fn new(config: TradingGuardConfig) -> Self {
+        Self {
+            config: Arc::new(config),
+            state: Arc::new(RwLock::new(GuardState::default())),
+        }
+    }
+
+    /// Guard configuration
+    pub fn config(&self) -> &TradingGuardConfig {
+        &self.config
+    }
+
+    /// Fail if trading is halted; call before every order
+    ///
+    /// Also trips the guard if the kill-switch file has appeared.
+    pub fn check(&self) -> Result<()> {
+        if let Some(path) = &self.config.kill_switch_file
+            && path.exists()
+            && !self.is_tripped()
+        {
+            self.trip(TripReason::KillSwitchFile { path: path.clone() });
+        }
+
+        match self.trip_info() {
+            Some(trip) => Err(ArbitrageError::TradingHalted {
+                reason: trip.reason.to_string(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Halt trading (the first trip wins; later reasons are only logged)
+    pub fn trip(&self, reason: TripReason) {
+        let mut state = self.state.write();
+        if let Some(existing) = &state.trip {
+            warn!(reason = %reason, existing = %existing.reason, "Trading guard already tripped");
+            return;
+        }
+
+        error!(reason = %reason, "Trading guard tripped, order placement halted");
+        state.trip = Some(Trip {
+            reason,
+            tripped_at: Utc::now(),
+        });
+    }
+
+    /// Resume trading after a trip
+    ///
+    /// If the kill-switch file still exists the next `check()` trips again.
+    pub fn reset(&self) {
+        let mut state = self.state.write();
+        if let Some(trip) = state.trip.take() {
+            info!(reason = %trip.reason, tripped_at = %trip.tripped_at, "Trading guard reset");
+        }
+        state.consecutive_failures = 0;
+    }
+
+    /// Reset only if the active trip is for `reason`, returning whether it was
+    ///
+    /// Lets a component lift its own trip without clearing one raised elsewhere.
+    pub fn clear(&self, reason: &TripReason) -> bool {
+        let mut state = self.state.write();
+        match &state.trip {
+            Some(trip) if &trip.reason == reason => {
+                info!(reason = %reason, "Trading guard cleared");
+                state.trip = None;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// True while trading is halted
+    pub fn is_tripped(&self) -> bool {
+        self.state.read().trip.is_some()
+    }
+
+    /// The active trip, if any
+    pub fn trip_info(&self) -> Option<Trip> {
+        self.state.read().trip.clone()
+    }
+
+    /// Current run of failed orders
+    pub fn consecutive_failures(&self) -> u32 {
+        self.state.read().consecutive_failures
+    }
+
+    /// Feed an order outcome into the failure counters
+    pub fn record_order_result(&self, exchange: ExchangeId, result: &Result<OrderResult>) {
+        match result {
+            Ok(_) => self.state.write().consecutive_failures = 0,
+            Err(e) => self.record_error(exchange, e),
+        }
+    }
+
+    /// Record a failed exchange call
+    ///
+    /// Authentication errors trip immediately; other errors count towards
+    /// `max_consecutive_failures`.
+    pub fn record_error(&self, exchange: ExchangeId, err: &ArbitrageError) {
+        if let ArbitrageError::AuthenticationError { reason, .. } = err {
+            self.trip(TripReason::AuthenticationFailure {
+                exchange,
+                reason: reason.clone(),
+            });
+            return;
+        }
+
+        // A blocked order isn't a new failure
+        if matches!(err, ArbitrageError::TradingHalted { .. }) {
+            return;
+        }
+
+        let count = {
+            let mut state = self.state.write();
+            state.consecutive_failures += 1;
+            state.consecutive_failures
+        };
+        warn!(exchange = %exchange, error = %err, consecutive_failures = count, "Order failed");
+
+        let max = self.config.max_consecutive_failures;
+        if max > 0 && count >= max {
+            self.trip(TripReason::ConsecutiveOrderFailures { count });
+        }
+    }
+
+    /// Compare an exchange balance against the expected amount, tripping on mismatch
+    pub fn check_balance(&self, exchange: ExchangeId, asset: &str, expected: Decimal, actual: Decimal) {
+        if (expected - actual).abs() > self.config.balance_tolerance {
+            self.trip(TripReason::BalanceDiscrepancy {
+                exchange,
+                asset: asset.to_string(),
+                expected,
+                actual,
+            });
+        }
+    }
+}
+
+impl Default for TradingGuard {
+    fn default() -> Self {
+        Self::new(TradingGuardConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn order_error() -> ArbitrageError {
+        ArbitrageError::ExchangeError {
+            exchange: ExchangeId::BINANCE,
+            message: "rejected".to_string(),
+            code: None,
+        }
+    }
+
+    #[test]
+    fn test_trips_after_consecutive_failures() {
+        let guard = TradingGuard::default();
+
+        guard.record_error(ExchangeId::BINANCE, &order_error());
+        guard.record_error(ExchangeId::BINANCE, &order_error());
+        assert!(!guard.is_tripped());
+
+        guard.record_error(ExchangeId::BINANCE, &order_error());
+        assert_eq!(
+            guard.trip_info().unwrap().reason,
+            TripReason::ConsecutiveOrderFailures { count: 3 }
+        );
+        assert!(matches!(guard.check(), Err(ArbitrageError::TradingHalted { .. })));
+    }
+
+    #[test]
+    fn test_success_resets_failure_run() {
+        let guard = TradingGuard::default();
+        guard.record_error(ExchangeId::BINANCE, &order_error());
+        guard.record_error(ExchangeId::BINANCE, &order_error());
+        guard.record_order_result(
+            ExchangeId::BINANCE,
+            &Ok(OrderResult {
+                order_id: "1".to_string(),
+                status: crate::exchanges::OrderStatus::Filled,
+                filled_quantity: Decimal::ONE,
+                average_price: None,
+                fee: Decimal::ZERO,
+                fee_asset: "USDC".to_string(),
+                timestamp: Utc::now(),
+            }),
+        );
+        assert_eq!(guard.consecutive_failures(), 0);
+
+        guard.record_error(ExchangeId::BINANCE, &order_error());
+        assert!(!guard.is_tripped());
+    }
+
+    #[test]
+    fn test_auth_error_trips_immediately() {
+        let guard = TradingGuard::default();
+        guard.record_error(
+            ExchangeId::COINBASE,
+            &ArbitrageError::AuthenticationError {
+                exchange: ExchangeId::COINBASE,
+                reason: "invalid key".to_string(),
+            },
+        );
+        assert!(matches!(
+            guard.trip_info().unwrap().reason,
+            TripReason::AuthenticationFailure { .. }
+        ));
+    }
+
+    #[test]
+    fn test_balance_discrepancy() {
+        let guard = TradingGuard::new(TradingGuardConfig {
+            balance_tolerance: Decimal::new(1, 2),
+            ..Default::default()
+        });
+
+        guard.check_balance(ExchangeId::BINANCE, "SOL", Decimal::from(10), Decimal::new(10_005, 3));
+        assert!(!guard.is_tripped());
+
+        guard.check_balance(ExchangeId::BINANCE, "SOL", Decimal::from(10), Decimal::from(9));
+        assert!(guard.is_tripped());
+    }
+
+    #[test]
+    fn test_first_trip_reason_is_kept() {
+        let guard = TradingGuard::default();
+        guard.trip(TripReason::Manual {
+            reason: "first".to_string(),
+        });
+        guard.trip(TripReason::Manual {
+            reason: "second".to_string(),
+        });
+        assert_eq!(
+            guard.trip_info().unwrap().reason,
+            TripReason::Manual {
+                reason: "first".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_kill_switch_file() {
+        let path = std::env::temp_dir().join(format!("arb_bot_halt_{}", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let guard = TradingGuard::new(TradingGuardConfig {
+            kill_switch_file: Some(path.clone()),
+            ..Default::default()
+        });
+        assert!(guard.check().is_ok());
+
+        std::fs::write(&path, b"").unwrap();
+        assert!(guard.check().is_err());
+
+        // Reset while the file exists re-trips on the next check
+        guard.reset();
+        assert!(guard.check().is_err());
+
+        std::fs::remove_file(&path).unwrap();
+        guard.reset();
+        assert!(guard.check().is_ok());
+    }
+}
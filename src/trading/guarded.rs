@@ -0,0 +1,176 @@
+//! Exchange wrapper enforcing the trading guard
+
+use super::guard::TradingGuard;
+use crate::clock::ClockSync;
+use crate::error::Result;
+use crate::exchanges::{Balance, Exchange, ExchangeId, Order, OrderResult, OrderSide, OrderType, Price};
+use crate::risk::RiskManager;
+use crate::state::SequencePolicy;
+use crate::websocket::ConnectionState;
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use tokio::sync::{broadcast, watch};
+
+/// Wraps an `Exchange` so every `place_order` goes through a `TradingGuard`
+///
+/// # Business Logic
+///
+/// - `place_order` fails with `TradingHalted` while the guard is tripped, without
+///   reaching the exchange
+/// - With `with_risk_manager()`, orders are then checked against risk limits
+///   (market orders valued at the current ask/bid) and fills update positions
+/// - Order outcomes feed the guard's failure counters
+/// - Authentication errors from any call trip the guard
+/// - Connection and price methods pass straight through, so market data keeps
+///   flowing while trading is halted
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use arb_bot::config::BinanceConfig;
+/// use arb_bot::exchanges::binance::BinanceExchange;
+/// use arb_bot::trading::{GuardedExchange, TradingGuard};
+///
+/// # fn run(config: BinanceConfig) -> arb_bot::error::Result<()> {
+/// let guard = TradingGuard::default();
+/// let binance = GuardedExchange::new(BinanceExchange::new(config)?, guard.clone());
+/// # Ok(())
+/// # }
+/// ```
+pub struct GuardedExchange<E: Exchange> {
+    inner: E,
+    guard: TradingGuard,
+    risk: Option<RiskManager>,
+}
+
+impl<E: Exchange> GuardedExchange<E> {
+    /// Wrap an exchange with a (possibly shared) guard
+    pub fn new(inner: E, guard: TradingGuard) -> Self {
+        Self {
+            inner,
+            guard,
+            risk: None,
+        }
+    }
+
+    /// Check every order against a (possibly shared) risk manager
+    pub fn with_risk_manager(mut self, risk: RiskManager) -> Self {
+        self.risk = Some(risk);
+        self
+    }
+
+    /// The guard this exchange reports to
+    pub fn guard(&self) -> &TradingGuard {
+        &self.guard
+    }
+
+    /// The wrapped exchange
+    pub fn inner(&self) -> &E {
+        &self.inner
+    }
+
+    /// Unwrap the exchange
+    pub fn into_inner(self) -> E {
+        self.inner
+    }
+}
+
+#[async_trait]
+impl<E: Exchange> Exchange for GuardedExchange<E> {
+    async fn connect(&mut self) -> Result<()> {
+        self.inner.connect().await
+    }
+
+    async fn subscribe_ticker(&mut self, pair: &str) -> Result<()> {
+        self.inner.subscribe_ticker(pair).await
+    }
+
+    async fn get_latest_price(&self, pair: &str) -> Result<Price> {
+        self.inner.get_latest_price(pair).await
+    }
+
+    async fn place_order(&mut self, order: Order) -> Result<OrderResult> {
+        self.guard.check()?;
+
+        if let Some(risk) = &self.risk {
+            let reference_price = match order.order_type {
+                OrderType::Limit { price } => price,
+                OrderType::Market => {
+                    let price = self.inner.get_latest_price(&order.pair).await?;
+                    match order.side {
+                        OrderSide::Buy => price.ask,
+                        OrderSide::Sell => price.bid,
+                    }
+                }
+            };
+            risk.check_order(self.inner.id(), &order, reference_price)?;
+        }
+
+        let (pair, side) = (order.pair.clone(), order.side.clone());
+        let result = self.inner.place_order(order).await;
+        self.guard.record_order_result(self.inner.id(), &result);
+
+        if let (Some(risk), Ok(fill)) = (&self.risk, &result) {
+            risk.record_fill(self.inner.id(), &pair, &side, fill.filled_quantity);
+        }
+
+        result
+    }
+
+    async fn get_balance(&self, asset: &str) -> Result<Decimal> {
+        let result = self.inner.get_balance(asset).await;
+        if let Err(e @ crate::error::ArbitrageError::AuthenticationError { .. }) = &result {
+            self.guard.record_error(self.inner.id(), e);
+        }
+        result
+    }
+
+    async fn get_balances(&self, assets: &[&str]) -> Result<HashMap<String, Balance>> {
+        let result = self.inner.get_balances(assets).await;
+        if let Err(e @ crate::error::ArbitrageError::AuthenticationError { .. }) = &result {
+            self.guard.record_error(self.inner.id(), e);
+        }
+        result
+    }
+
+    async fn get_order(&self, pair: &str, order_id: &str) -> Result<OrderResult> {
+        self.inner.get_order(pair, order_id).await
+    }
+
+    async fn get_open_orders(&self, pair: &str) -> Result<Vec<OrderResult>> {
+        self.inner.get_open_orders(pair).await
+    }
+
+    fn id(&self) -> ExchangeId {
+        self.inner.id()
+    }
+
+    fn is_connected(&self) -> bool {
+        self.inner.is_connected()
+    }
+
+    fn connection_state(&self) -> ConnectionState {
+        self.inner.connection_state()
+    }
+
+    fn watch_connection_state(&self) -> Option<watch::Receiver<ConnectionState>> {
+        self.inner.watch_connection_state()
+    }
+
+    fn clock(&self) -> Option<ClockSync> {
+        self.inner.clock()
+    }
+
+    fn price_updates(&self) -> Option<broadcast::Receiver<Price>> {
+        self.inner.price_updates()
+    }
+
+    fn sequence_policy(&self) -> Option<SequencePolicy> {
+        self.inner.sequence_policy()
+    }
+
+    async fn disconnect(&mut self) -> Result<()> {
+        self.inner.disconnect().await
+    }
+}
@@ -0,0 +1,11 @@
+//! Trading Safety Module
+//!
+//! Global kill switch that halts order placement while leaving market data running.
+
+pub mod guard;
+pub mod guarded;
+pub mod recovery;
+
+pub use guard::{Trip, TradingGuard, TradingGuardConfig, TripReason};
+pub use guarded::GuardedExchange;
+pub use recovery::{Discrepancy, PendingOrder, Recovery, RecoveryReport, unresolved_requests};
@@ -0,0 +1,551 @@
+//! Startup reconciliation
+//!
+//! Rebuilds positions and pending orders from the journal, checks them against
+//! what each exchange reports, and keeps trading halted until the two agree.
+
+use crate::error::Result;
+use crate::exchanges::{Exchange, OrderResult, OrderSide, OrderStatus};
+use crate::journal::{Journal, JournalEntry, JournalEvent, JournalQuery};
+use crate::logger::{info, warn};
+use crate::pnl::PnlTracker;
+use crate::risk::RiskManager;
+use crate::state::ExchangeId;
+use crate::trading::{TradingGuard, TripReason};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// A difference between the journal and an exchange that needs a human
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Discrepancy {
+    /// An order was sent but the journal never saw its result (crashed mid-request)
+    UnresolvedRequest {
+        exchange: ExchangeId,
+        correlation_id: String,
+        pair: String,
+    },
+    /// The exchange has a working order the journal knows nothing about
+    UnknownOpenOrder { exchange: ExchangeId, order_id: String },
+    /// The exchange reports less filled than the journal recorded
+    FillShortfall {
+        exchange: ExchangeId,
+        order_id: String,
+        journaled: Decimal,
+        actual: Decimal,
+    },
+}
+
+impl fmt::Display for Discrepancy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Discrepancy::UnresolvedRequest {
+                exchange,
+                correlation_id,
+                pair,
+            } => write!(
+                f,
+                "{} order on {} ({}) has no recorded outcome",
+                pair, exchange, correlation_id
+            ),
+            Discrepancy::UnknownOpenOrder { exchange, order_id } => {
+                write!(f, "unknown open order {} on {}", order_id, exchange)
+            }
+            Discrepancy::FillShortfall {
+                exchange,
+                order_id,
+                journaled,
+                actual,
+            } => write!(
+                f,
+                "order {} on {} filled {} but journal recorded {}",
+                order_id, exchange, actual, journaled
+            ),
+        }
+    }
+}
+
+/// An order still working on an exchange after reconciliation
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingOrder {
+    pub exchange: ExchangeId,
+    pub pair: String,
+    pub order_id: String,
+    pub correlation_id: String,
+    pub status: OrderStatus,
+    pub filled_quantity: Decimal,
+}
+
+/// Outcome of a reconciliation run
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RecoveryReport {
+    /// Fills applied to positions (journaled plus corrections)
+    pub fills_replayed: usize,
+    /// Fills the exchange reported that the journal had missed; written back to the journal
+    pub corrections: usize,
+    /// Orders still working
+    pub pending: Vec<PendingOrder>,
+    /// Differences that keep trading halted
+    pub discrepancies: Vec<Discrepancy>,
+}
+
+impl RecoveryReport {
+    /// True if nothing needs a human
+    pub fn is_clean(&self) -> bool {
+        self.discrepancies.is_empty()
+    }
+}
+
+/// What the journal believes about one order
+#[derive(Debug, Clone)]
+struct JournaledOrder {
+    correlation_id: String,
+    pair: String,
+    side: OrderSide,
+    status: OrderStatus,
+    filled: Decimal,
+    fees: Decimal,
+}
+
+/// A fill to apply to positions
+#[derive(Debug, Clone)]
+struct ReplayFill {
+    exchange: ExchangeId,
+    pair: String,
+    side: OrderSide,
+    result: OrderResult,
+    /// Journal time, or the exchange's order time for corrections
+    filled_at: DateTime<Utc>,
+}
+
+/// Journal contents folded into per-order state
+#[derive(Debug, Default)]
+struct JournalState {
+    orders: HashMap<(ExchangeId, String), JournaledOrder>,
+    /// Every (exchange, pair) an order was requested on
+    pairs: HashSet<(ExchangeId, String)>,
+    fills: Vec<ReplayFill>,
+    unresolved: Vec<Discrepancy>,
+}
+
+impl JournalState {
+    fn from_entries(entries: &[JournalEntry]) -> Self {
+        let mut state = JournalState::default();
+        // Requests awaiting a result, per (correlation ID, exchange), oldest first
+        let mut requests: HashMap<(String, ExchangeId), Vec<(String, OrderSide)>> = HashMap::new();
+
+        for entry in entries {
+            let cid = entry.correlation_id.clone();
+            match &entry.event {
+                JournalEvent::Opportunity { .. } => {}
+                JournalEvent::Resolution { .. } => {
+                    requests.retain(|(id, _), _| *id != cid);
+                }
+                JournalEvent::OrderRequest {
+                    exchange,
+                    pair,
+                    side,
+                    ..
+                } => {
+                    let Some(exchange) = journaled_exchange(exchange) else {
+                        continue;
+                    };
+                    state.pairs.insert((exchange, pair.clone()));
+                    requests
+                        .entry((cid, exchange))
+                        .or_default()
+                        .push((pair.clone(), side.clone()));
+                }
+                JournalEvent::OrderResult {
+                    exchange,
+                    order_id,
+                    status,
+                    ..
+                } => {
+                    let Some(exchange) = journaled_exchange(exchange) else {
+                        continue;
+                    };
+                    let key = (exchange, order_id.clone());
+                    if let Some(order) = state.orders.get_mut(&key) {
+                        // A later result (e.g. a correction) for a known order
+                        order.status = status.clone();
+                        continue;
+                    }
+                    let request = requests
+                        .get_mut(&(cid.clone(), exchange))
+                        .filter(|queue| !queue.is_empty())
+                        .map(|queue| queue.remove(0));
+                    match request {
+                        Some((pair, side)) => {
+                            state.orders.insert(
+                                key,
+                                JournaledOrder {
+                                    correlation_id: cid,
+                                    pair,
+                                    side,
+                                    status: status.clone(),
+                                    filled: Decimal::ZERO,
+                                    fees: Decimal::ZERO,
+                                },
+                            );
+                        }
+                        None => warn!(
+                            exchange = %exchange,
+                            order_id = %order_id,
+                            "Journaled order result has no matching request"
+                        ),
+                    }
+                }
+                JournalEvent::OrderError { exchange, .. } => {
+                    let Some(exchange) = journaled_exchange(exchange) else {
+                        continue;
+                    };
+                    if let Some(queue) = requests.get_mut(&(cid, exchange))
+                        && !queue.is_empty()
+                    {
+                        queue.remove(0);
+                    }
+                }
+                JournalEvent::Fill {
+                    exchange,
+                    order_id,
+                    pair,
+                    side,
+                    quantity,
+                    price,
+                    fee,
+                    fee_asset,
+                } => {
+                    let Some(exchange) = journaled_exchange(exchange) else {
+                        continue;
+                    };
+                    if let Some(order) = state.orders.get_mut(&(exchange, order_id.clone())) {
+                        order.filled += quantity;
+                        order.fees += fee;
+                    }
+                    state.fills.push(ReplayFill {
+                        exchange,
+                        pair: pair.clone(),
+                        side: side.clone(),
+                        result: OrderResult {
+                            order_id: order_id.clone(),
+                            status: OrderStatus::Filled,
+                            filled_quantity: *quantity,
+                            average_price: Some(*price),
+                            fee: *fee,
+                            fee_asset: fee_asset.clone(),
+                            timestamp: entry.recorded_at,
+                        },
+                        filled_at: entry.recorded_at,
+                    });
+                }
+            }
+        }
+
+        for ((correlation_id, exchange), queue) in requests {
+            for (pair, _) in queue {
+                state.unresolved.push(Discrepancy::UnresolvedRequest {
+                    exchange,
+                    correlation_id: correlation_id.clone(),
+                    pair,
+                });
+            }
+        }
+
+        state
+    }
+}
+
+/// The id of an exchange named in the journal, or None (with a warning) if the
+/// name is not a valid exchange name
+fn journaled_exchange(name: &str) -> Option<ExchangeId> {
+    let id = ExchangeId::from_name(name);
+    if id.is_none() {
+        warn!(exchange = %name, "Journal entry names an invalid exchange, skipped");
+    }
+    id
+}
+
+/// Order requests in `entries` with neither a recorded outcome nor a resolution
+pub fn unresolved_requests(entries: &[JournalEntry]) -> Vec<Discrepancy> {
+    JournalState::from_entries(entries).unresolved
+}
+
+/// Startup reconciliation between the journal and the exchanges
+///
+/// # Business Logic
+///
+/// Creating a `Recovery` trips the guard with `TripReason::RecoveryPending`, so no
+/// order can be placed until `run()` finishes cleanly. `run()` then:
+///
+/// 1. Folds the journal into per-order state (pair, side, status, filled quantity)
+/// 2. Looks up every order the journal last saw as pending or partially filled.
+///    Extra quantity the exchange reports is written back to the journal as a
+///    correction (result plus a fill for the difference, at the order's average price)
+/// 3. Lists open orders for every journaled pair and flags any the journal never saw
+/// 4. Replays all fills into the PnL tracker and risk manager, restoring positions
+///    (realized PnL from fills recorded today also counts towards the daily loss limit)
+///
+/// If every exchange query succeeds and nothing is flagged the guard is cleared.
+/// Requests with no recorded outcome, unknown open orders and fills the exchange
+/// does not confirm are reported as discrepancies and leave the guard tripped for
+/// an operator to `reset()`. A failed exchange query returns the error and also
+/// leaves trading halted, so `run()` can simply be retried.
+///
+/// A request is no longer flagged once the journal holds a
+/// `JournalEvent::Resolution` for its correlation ID (`arb-bot recover --resolve`),
+/// written after the operator has checked the exchange.
+///
+/// # Example
+///
+/// ```no_run
+/// use arb_bot::journal::Journal;
+/// use arb_bot::state::ExchangeId;
+/// use arb_bot::trading::{Recovery, TradingGuard, TradingGuardConfig};
+///
+/// # async fn example(binance: &dyn arb_bot::exchanges::Exchange) -> arb_bot::error::Result<()> {
+/// let guard = TradingGuard::new(TradingGuardConfig::default());
+/// let journal = Journal::open("data/journal.jsonl")?;
+///
+/// let recovery = Recovery::new(journal, guard.clone());
+/// let report = recovery
+///     .run(
+///         &[(ExchangeId::BINANCE, binance)],
+///         &[(ExchangeId::BINANCE, "SOL/USDC")],
+///     )
+///     .await?;
+/// assert_eq!(report.is_clean(), !guard.is_tripped());
+/// # Ok(())
+/// # }
+/// ```
+pub struct Recovery {
+    journal: Journal,
+    guard: TradingGuard,
+    pnl: Option<PnlTracker>,
+    risk: Option<RiskManager>,
+}
+
+impl Recovery {
+    /// Halt trading until reconciliation succeeds
+    pub fn new(journal: Journal, guard: TradingGuard) -> Self {
+        guard.trip(TripReason::RecoveryPending);
+        Self {
+            journal,
+            guard,
+            pnl: None,
+            risk: None,
+        }
+    }
+
+    /// Restore positions and realized PnL into `pnl`
+    pub fn with_pnl_tracker(mut self, pnl: PnlTracker) -> Self {
+        self.pnl = Some(pnl);
+        self
+    }
+
+    /// Restore net positions into `risk`
+    pub fn with_risk_manager(mut self, risk: RiskManager) -> Self {
+        self.risk = Some(risk);
+        self
+    }
+
+    /// Reconcile the journal against `exchanges`
+    ///
+    /// `pairs` - each with the exchange listing it - are checked for open
+    /// orders in addition to any pair the journal has traded on that exchange.
+    pub async fn run(
+        &self,
+        exchanges: &[(ExchangeId, &dyn Exchange)],
+        pairs: &[(ExchangeId, &str)],
+    ) -> Result<RecoveryReport> {
+        self.journal.flush().await?;
+        let entries = self.journal.query(&JournalQuery::new())?;
+        let mut state = JournalState::from_entries(&entries);
+        let mut report = RecoveryReport {
+            discrepancies: std::mem::take(&mut state.unresolved),
+            ..Default::default()
+        };
+
+        for (id, exchange) in exchanges {
+            let pairs: Vec<&str> = pairs
+                .iter()
+                .filter(|(ex, _)| ex == id)
+                .map(|(_, pair)| *pair)
+                .collect();
+            self.reconcile_exchange(*id, *exchange, &pairs, &mut state, &mut report)
+                .await?;
+        }
+        self.journal.flush().await?;
+
+        // Corrections were appended last; stable, so journal order breaks ties
+        state.fills.sort_by_key(|fill| fill.filled_at);
+        self.replay(exchanges, &state.fills, &mut report);
+
+        for discrepancy in &report.discrepancies {
+            warn!(discrepancy = %discrepancy, "Reconciliation discrepancy");
+        }
+        info!(
+            fills = report.fills_replayed,
+            corrections = report.corrections,
+            pending = report.pending.len(),
+            discrepancies = report.discrepancies.len(),
+            "Startup reconciliation finished"
+        );
+
+        if report.is_clean() {
+            self.guard.clear(&TripReason::RecoveryPending);
+        }
+        Ok(report)
+    }
+
+    async fn reconcile_exchange(
+        &self,
+        id: ExchangeId,
+        exchange: &dyn Exchange,
+        pairs: &[&str],
+        state: &mut JournalState,
+        report: &mut RecoveryReport,
+    ) -> Result<()> {
+        let mut open_ids = HashSet::new();
+        for (key, order) in state.orders.iter_mut().filter(|(key, _)| key.0 == id) {
+            if !matches!(order.status, OrderStatus::Pending | OrderStatus::PartiallyFilled) {
+                continue;
+            }
+
+            let actual = exchange.get_order(&order.pair, &key.1).await?;
+            if actual.filled_quantity > order.filled {
+                let fill = self.record_correction(id, order, &actual);
+                report.corrections += 1;
+                state.fills.push(fill);
+            } else if actual.filled_quantity < order.filled {
+                report.discrepancies.push(Discrepancy::FillShortfall {
+                    exchange: id,
+                    order_id: key.1.clone(),
+                    journaled: order.filled,
+                    actual: actual.filled_quantity,
+                });
+            }
+            order.status = actual.status.clone();
+
+            if !actual.is_complete() {
+                open_ids.insert(key.1.clone());
+                report.pending.push(PendingOrder {
+                    exchange: id,
+                    pair: order.pair.clone(),
+                    order_id: key.1.clone(),
+                    correlation_id: order.correlation_id.clone(),
+                    status: actual.status,
+                    filled_quantity: actual.filled_quantity,
+                });
+            }
+        }
+
+        let mut check_pairs: Vec<String> = pairs.iter().map(|p| p.to_string()).collect();
+        for (ex, pair) in &state.pairs {
+            if *ex == id && !check_pairs.contains(pair) {
+                check_pairs.push(pair.clone());
+            }
+        }
+
+        for pair in &check_pairs {
+            for open in exchange.get_open_orders(pair).await? {
+                if !open_ids.contains(&open.order_id)
+                    && !state.orders.contains_key(&(id, open.order_id.clone()))
+                {
+                    report.discrepancies.push(Discrepancy::UnknownOpenOrder {
+                        exchange: id,
+                        order_id: open.order_id,
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Journal the quantity the exchange filled beyond what was recorded
+    fn record_correction(
+        &self,
+        exchange: ExchangeId,
+        order: &mut JournaledOrder,
+        actual: &OrderResult,
+    ) -> ReplayFill {
+        let quantity = actual.filled_quantity - order.filled;
+        let fee = (actual.fee - order.fees).max(Decimal::ZERO);
+        let price = actual.average_price.unwrap_or_default();
+        warn!(
+            exchange = %exchange,
+            order_id = %actual.order_id,
+            journaled = %order.filled,
+            actual = %actual.filled_quantity,
+            "Journal missed fills, recording correction"
+        );
+
+        self.journal.record(
+            &order.correlation_id,
+            JournalEvent::order_result(exchange.as_str(), actual),
+        );
+        self.journal.record(
+            &order.correlation_id,
+            JournalEvent::Fill {
+                exchange: exchange.as_str().to_string(),
+                order_id: actual.order_id.clone(),
+                pair: order.pair.clone(),
+                side: order.side.clone(),
+                quantity,
+                price,
+                fee,
+                fee_asset: actual.fee_asset.clone(),
+            },
+        );
+        order.filled = actual.filled_quantity;
+        order.fees += fee;
+
+        ReplayFill {
+            exchange,
+            pair: order.pair.clone(),
+            side: order.side.clone(),
+            result: OrderResult {
+                filled_quantity: quantity,
+                average_price: Some(price),
+                fee,
+                ..actual.clone()
+            },
+            filled_at: actual.timestamp,
+        }
+    }
+
+    /// Apply fills to the PnL tracker and risk manager in time order
+    fn replay(
+        &self,
+        exchanges: &[(ExchangeId, &dyn Exchange)],
+        fills: &[ReplayFill],
+        report: &mut RecoveryReport,
+    ) {
+        let today = Utc::now().date_naive();
+        for fill in fills {
+            if !exchanges.iter().any(|(id, _)| *id == fill.exchange) {
+                continue;
+            }
+
+            let realized = self
+                .pnl
+                .as_ref()
+                .and_then(|pnl| pnl.record_fill(fill.exchange, &fill.pair, &fill.side, &fill.result));
+            if let Some(risk) = &self.risk {
+                risk.record_fill(
+                    fill.exchange,
+                    &fill.pair,
+                    &fill.side,
+                    fill.result.filled_quantity,
+                );
+                if let Some(pnl) = realized
+                    && fill.filled_at.date_naive() == today
+                {
+                    risk.record_realized_pnl(pnl);
+                }
+            }
+            report.fills_replayed += 1;
+        }
+    }
+}
@@ -0,0 +1,173 @@
+//! Circuit breaker for repeated connection failures
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Circuit breaker state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Failures below threshold - connect normally
+    Closed,
+    /// Too many recent failures - hold off until the cool-off elapses
+    Open,
+    /// Cool-off elapsed - allow one trial connection
+    HalfOpen,
+}
+
+/// Opens after `failure_threshold` failures within `window`, half-opens after `cool_off`
+///
+/// # Business Logic
+///
+/// Backoff alone keeps retrying a venue that is down or flapping, and the retry
+/// counter is reset by every brief successful connect. The breaker counts failures
+/// in a sliding time window instead, so a connection that keeps dropping right after
+/// connecting still trips it.
+///
+/// - **Closed → Open**: `failure_threshold` failures within `window`
+/// - **Open → HalfOpen**: `cool_off` has elapsed since opening
+/// - **HalfOpen → Closed**: the trial connection succeeds
+/// - **HalfOpen → Open**: the trial connection fails (cool-off restarts)
+///
+/// # Example
+///
+/// ```rust
+/// use arb_bot::websocket::{CircuitBreaker, CircuitState};
+/// use std::time::Duration;
+///
+/// let mut breaker = CircuitBreaker::new(2, Duration::from_secs(60), Duration::from_secs(30));
+/// breaker.record_failure();
+/// assert_eq!(breaker.state(), CircuitState::Closed);
+/// breaker.record_failure();
+/// assert_eq!(breaker.state(), CircuitState::Open);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CircuitBreaker {
+    /// Failures within `window` that open the circuit
+    pub failure_threshold: usize,
+    /// Sliding window for counting failures
+    pub window: Duration,
+    /// How long the circuit stays open before a trial connection
+    pub cool_off: Duration,
+    failures: VecDeque<Instant>,
+    opened_at: Option<Instant>,
+}
+
+impl CircuitBreaker {
+    /// Create a closed circuit breaker
+    pub fn new(failure_threshold: usize, window: Duration, cool_off: Duration) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            window,
+            cool_off,
+            failures: VecDeque::new(),
+            opened_at: None,
+        }
+    }
+
+    /// Current state (Open turns into HalfOpen once the cool-off has elapsed)
+    pub fn state(&self) -> CircuitState {
+        match self.opened_at {
+            None => CircuitState::Closed,
+            Some(opened_at) if opened_at.elapsed() >= self.cool_off => CircuitState::HalfOpen,
+            Some(_) => CircuitState::Open,
+        }
+    }
+
+    /// Time left until a trial connection is allowed (None unless Open)
+    pub fn remaining_cool_off(&self) -> Option<Duration> {
+        let opened_at = self.opened_at?;
+        self.cool_off.checked_sub(opened_at.elapsed()).filter(|d| !d.is_zero())
+    }
+
+    /// Record a failed connection attempt
+    pub fn record_failure(&mut self) {
+        let now = Instant::now();
+
+        if self.state() == CircuitState::HalfOpen {
+            // Trial failed - back to open for another cool-off
+            self.opened_at = Some(now);
+            return;
+        }
+
+        self.failures.push_back(now);
+        while self
+            .failures
+            .front()
+            .is_some_and(|t| now.duration_since(*t) > self.window)
+        {
+            self.failures.pop_front();
+        }
+
+        if self.opened_at.is_none() && self.failures.len() >= self.failure_threshold {
+            self.opened_at = Some(now);
+        }
+    }
+
+    /// Record a successful connection (closes a half-open circuit)
+    pub fn record_success(&mut self) {
+        if self.state() == CircuitState::HalfOpen {
+            self.opened_at = None;
+            self.failures.clear();
+        }
+    }
+}
+
+impl Default for CircuitBreaker {
+    /// - Failure threshold: 5
+    /// - Window: 60 seconds
+    /// - Cool-off: 120 seconds
+    fn default() -> Self {
+        Self::new(5, Duration::from_secs(60), Duration::from_secs(120))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_opens_after_threshold() {
+        let mut breaker = CircuitBreaker::new(3, Duration::from_secs(60), Duration::from_secs(60));
+        breaker.record_failure();
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert!(breaker.remaining_cool_off().is_none());
+
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(breaker.remaining_cool_off().unwrap() > Duration::from_secs(59));
+    }
+
+    #[test]
+    fn test_failures_outside_window_expire() {
+        let mut breaker =
+            CircuitBreaker::new(2, Duration::from_millis(20), Duration::from_secs(60));
+        breaker.record_failure();
+        std::thread::sleep(Duration::from_millis(40));
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_half_open_trial() {
+        let mut breaker = CircuitBreaker::new(1, Duration::from_secs(60), Duration::from_millis(20));
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        // Success while open doesn't close the circuit
+        breaker.record_success();
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        std::thread::sleep(Duration::from_millis(40));
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        assert!(breaker.remaining_cool_off().is_none());
+
+        // Failed trial reopens
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        std::thread::sleep(Duration::from_millis(40));
+        breaker.record_success();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+}
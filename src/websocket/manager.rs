@@ -3,11 +3,18 @@
 //! Handles connection lifecycle, message parsing, broadcasting, and reconnection logic.
 
 use crate::error::{ArbitrageError, Result};
-use crate::logger::{error, info, warn};
-use crate::websocket::{MessageParser, ReconnectionStrategy};
+use crate::exchanges::ExchangeId;
+use crate::logger::{debug, error, info, warn};
+use crate::websocket::{
+    CircuitState, ConnectionState, MessageParser, ReconnectionStrategy, parse_retry_after,
+};
 use futures_util::{SinkExt, StreamExt};
-use tokio::sync::broadcast;
-use tokio_tungstenite::{connect_async, tungstenite::Message};
+use std::sync::Arc;
+use tokio::sync::{broadcast, watch};
+use tokio_tungstenite::{connect_async, tungstenite, tungstenite::Message};
+
+/// How long to wait for the server to answer a login message
+const LOGIN_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
 
 /// Generic WebSocket manager for exchange price feeds
 ///
@@ -17,14 +24,15 @@ use tokio_tungstenite::{connect_async, tungstenite::Message};
 /// 1. Maintains persistent WebSocket connection to exchange
 /// 2. Receives messages → parses via `MessageParser` → broadcasts to subscribers
 /// 3. Automatically reconnects on failure using `ReconnectionStrategy`
-/// 4. Sends periodic ping messages to keep connection alive
-//TODO Change Ping Pong to Heartbeat to keep connection alive if exchange supports it
+/// 4. Sends periodic ping frames (or the exchange's own heartbeat message) to keep connection alive
+/// 5. Optionally logs in, then replays on-connect messages (e.g. subscribe requests) after every (re)connect
+/// 6. Optionally tears down half-open connections that stop delivering data
 /// # Example Usage
 ///
 /// ```rust,no_run
 /// use arb_bot::websocket::{WebSocketManager, ReconnectionStrategy};
 /// use arb_bot::websocket::MessageParser;
-/// use arb_bot::exchanges::Price;
+/// use arb_bot::exchanges::{ExchangeId, Price};
 /// use arb_bot::error::Result;
 ///
 /// #[derive(Clone)]
@@ -41,7 +49,8 @@ use tokio_tungstenite::{connect_async, tungstenite::Message};
 ///     let parser = MyParser;
 ///     let strategy = ReconnectionStrategy::exponential_backoff();
 ///
-///     let (mut manager, mut receiver) = WebSocketManager::new(url, parser, strategy);
+///     let (mut manager, mut receiver) =
+///         WebSocketManager::new(ExchangeId::BINANCE, url, parser, strategy);
 ///
 ///     // Spawn manager in background
 ///     tokio::spawn(async move {
@@ -56,6 +65,8 @@ use tokio_tungstenite::{connect_async, tungstenite::Message};
 /// }
 /// ```
 pub struct WebSocketManager<P: MessageParser> {
+    /// Exchange the connection belongs to, named in rate limit errors
+    exchange: ExchangeId,
     /// WebSocket URL to connect to
     url: String,
     /// Parser for converting messages to common types
@@ -66,6 +77,16 @@ pub struct WebSocketManager<P: MessageParser> {
     message_tx: broadcast::Sender<P::Output>,
     /// Interval for sending ping messages (default: 30 seconds)
     health_check_interval: std::time::Duration,
+    /// Text heartbeat sent instead of a ping frame, for exchanges that expect one
+    heartbeat_message: Option<String>,
+    /// Builds a fresh login message on each connect (signed logins expire)
+    login: Option<Arc<dyn Fn() -> String + Send + Sync>>,
+    /// Messages sent right after each successful connection (subscription handshake)
+    on_connect_messages: Vec<String>,
+    /// Reconnect if no data frame arrives within this duration (None = disabled)
+    inactivity_timeout: Option<std::time::Duration>,
+    /// Publishes connection lifecycle transitions
+    state_tx: watch::Sender<ConnectionState>,
 }
 
 impl<P: MessageParser> WebSocketManager<P> {
@@ -79,6 +100,7 @@ impl<P: MessageParser> WebSocketManager<P> {
     ///
     /// Multiple receivers can be created by calling `receiver.resubscribe()`.
     pub fn new(
+        exchange: ExchangeId,
         url: String,
         parser: P,
         reconnect_strategy: ReconnectionStrategy,
@@ -86,43 +108,183 @@ impl<P: MessageParser> WebSocketManager<P> {
         let (message_tx, message_rx) = broadcast::channel(100);
 
         let manager = Self {
+            exchange,
             url,
             parser,
             reconnect_strategy,
             message_tx,
             health_check_interval: std::time::Duration::from_secs(30),
+            heartbeat_message: None,
+            login: None,
+            on_connect_messages: Vec::new(),
+            inactivity_timeout: None,
+            state_tx: watch::Sender::new(ConnectionState::Disconnected),
         };
 
         (manager, message_rx)
     }
 
-    /// Run the WebSocket manager (blocks until connection closes or error)
+    /// Set messages to send after every successful (re)connect
+    ///
+    /// Exchanges like Coinbase subscribe by sending a message rather than via the URL.
+    /// Since a new socket starts with no subscriptions, these messages are replayed
+    /// on each reconnect so the feed resumes without caller involvement.
+    pub fn with_on_connect_messages(mut self, messages: Vec<String>) -> Self {
+        self.on_connect_messages = messages;
+        self
+    }
+
+    /// Send `message` as text every `interval` instead of a ping frame
+    ///
+    /// Some exchanges (OKX, Bybit) ignore protocol pings and close the socket
+    /// unless the client sends its own heartbeat. The reply is data as far as
+    /// the inactivity watchdog is concerned; the parser should report it via
+    /// `is_control()` so it isn't logged as a parse failure.
+    pub fn with_heartbeat(mut self, message: impl Into<String>, interval: std::time::Duration) -> Self {
+        self.heartbeat_message = Some(message.into());
+        self.health_check_interval = interval;
+        self
+    }
+
+    /// Log in before sending the on-connect messages
+    ///
+    /// `login` is called on every (re)connect, since signed logins carry a
+    /// timestamp and expire. The server's first reply is passed to
+    /// `MessageParser::check_login()`; subscriptions are only sent once it
+    /// accepts, because private channels reject subscribers that aren't
+    /// logged in yet.
+    pub fn with_login<F>(mut self, login: F) -> Self
+    where
+        F: Fn() -> String + Send + Sync + 'static,
+    {
+        self.login = Some(Arc::new(login));
+        self
+    }
+
+    /// Reconnect when no data frame arrives for `timeout`
+    ///
+    /// A half-open TCP connection never errors, it just goes quiet. Control frames
+    /// (ping/pong) don't count as data, since a server can keep answering pings
+    /// after its feed has stalled.
+    pub fn with_inactivity_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.inactivity_timeout = Some(timeout);
+        self
+    }
+
+    /// Publish state transitions on an existing watch channel
+    ///
+    /// Lets an owner keep a channel that outlives the manager (e.g. across
+    /// `connect()`/`disconnect()` cycles) so its receivers stay valid.
+    pub fn with_state_sender(mut self, state_tx: watch::Sender<ConnectionState>) -> Self {
+        self.state_tx = state_tx;
+        self
+    }
+
+    /// Subscribe to connection state transitions
+    pub fn state(&self) -> watch::Receiver<ConnectionState> {
+        self.state_tx.subscribe()
+    }
+
+    /// Publish a new connection state (no-op if unchanged)
+    fn set_state(&self, state: ConnectionState) {
+        self.state_tx.send_if_modified(|current| {
+            if *current == state {
+                return false;
+            }
+            debug!(url = %self.url, from = %current, to = %state, "Connection state changed");
+            *current = state;
+            true
+        });
+    }
+
+    /// Convert a failed handshake into an error, keeping any `Retry-After` hint
+    ///
+    /// HTTP 429 becomes `RateLimitExceeded`, waiting the strategy's backoff delay
+    /// if the server gave no `Retry-After`; other rejected handshakes carrying
+    /// `Retry-After` (typically 503) keep the hint on the `NetworkError`.
+    fn handshake_error(&self, e: tungstenite::Error) -> ArbitrageError {
+        let (status, retry_after) = match &e {
+            tungstenite::Error::Http(response) => (
+                Some(response.status()),
+                response
+                    .headers()
+                    .get("retry-after")
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(parse_retry_after),
+            ),
+            _ => (None, None),
+        };
+
+        if status.is_some_and(|s| s.as_u16() == 429) {
+            let retry_after = retry_after.unwrap_or_else(|| self.reconnect_strategy.backoff_delay());
+            return ArbitrageError::RateLimitExceeded {
+                exchange: self.exchange,
+                retry_after: retry_after.as_millis() as u64,
+            };
+        }
+
+        ArbitrageError::NetworkError {
+            message: format!("Failed to connect to {}: {}", self.url, e),
+            retry_after: retry_after.map(|d| d.as_millis() as u64),
+        }
+    }
+
+    /// Run the WebSocket manager (blocks until retries are exhausted)
     ///
     /// # Behavior
     ///
     /// 1. Attempts to connect to WebSocket URL
-    /// 2. On success: runs message loop (receive, parse, broadcast)
-    /// 3. On failure: uses `ReconnectionStrategy` to retry with exponential backoff
-    /// 4. Returns when connection closes normally or retries exhausted
+    /// 2. On success: sends on-connect messages, then runs message loop (receive, parse, broadcast)
+    /// 3. On failure or server close: uses `ReconnectionStrategy` to retry with exponential backoff,
+    ///    waiting longer if the server sent `Retry-After` or the circuit breaker is open
+    /// 4. Returns the last error once retries are exhausted
+    ///
+    /// Each step is published as a `ConnectionState` (see `state()`).
+    ///
+    /// A price feed has no natural end, so a server-side close is treated as a
+    /// disconnect and reconnected just like an error.
     #[tracing::instrument(name = "websocket_manager_run", skip(self), fields(url = %self.url))]
     pub async fn run(&mut self) -> Result<()> {
         loop {
-            match self.connect_and_run().await {
+            let err = match self.connect_and_run().await {
                 Ok(_) => {
-                    // Connection closed normally
-                    return Ok(());
-                }
-                Err(e) => {
-                    // Connection failed
-                    if !self.reconnect_strategy.should_retry() {
-                        return Err(e);
+                    // Server closed connection
+                    warn!(url = %self.url, "WebSocket closed by server");
+                    ArbitrageError::WebSocketError {
+                        endpoint: self.url.clone(),
+                        reconnect_possible: true,
                     }
-
-                    let delay = self.reconnect_strategy.next_delay();
-                    tokio::time::sleep(delay).await;
-                    // Loop continues to retry
                 }
+                Err(e) => e,
+            };
+
+            if !self.reconnect_strategy.should_retry() {
+                error!(url = %self.url, error = %err, "Reconnection attempts exhausted");
+                self.set_state(ConnectionState::Failed {
+                    reason: err.to_string(),
+                });
+                return Err(err);
+            }
+
+            let delay = self.reconnect_strategy.on_failure(err.retry_after());
+            if self.reconnect_strategy.circuit_state() == CircuitState::Open {
+                warn!(
+                    url = %self.url,
+                    cool_off_ms = delay.as_millis() as u64,
+                    "Circuit breaker open, pausing reconnects"
+                );
             }
+            self.set_state(ConnectionState::Reconnecting {
+                attempt: self.reconnect_strategy.current_retry,
+            });
+            warn!(
+                url = %self.url,
+                error = %err,
+                attempt = self.reconnect_strategy.current_retry,
+                delay_ms = delay.as_millis() as u64,
+                "Reconnecting to WebSocket"
+            );
+            tokio::time::sleep(delay).await;
         }
     }
 
@@ -130,26 +292,73 @@ impl<P: MessageParser> WebSocketManager<P> {
     #[tracing::instrument(name = "websocket_connect_and_run", skip(self), fields(url = %self.url))]
     async fn connect_and_run(&mut self) -> Result<()> {
         info!(url = %self.url, "Attempting to connect to WebSocket");
+        self.set_state(ConnectionState::Connecting);
         // Connect to WebSocket
         let (ws_stream, response) = connect_async(&self.url).await.map_err(|e| {
             error!(url = %self.url, error = %e, "Connection failed");
-            ArbitrageError::NetworkError {
-                message: format!("Failed to connect to {}: {}", self.url, e),
-                retry_after: None,
-            }
+            self.handshake_error(e)
         })?;
 
         info!(status = %response.status(), "Connected to WebSocket");
+        // Not live until the first message parses - the subscription may still be rejected
+        self.set_state(ConnectionState::Subscribing);
 
         // Split into read and write halves since we need to send and receive messages concurrently
         let (mut write, mut read) = ws_stream.split();
 
+        if let Some(login) = &self.login {
+            debug!("Sending login message");
+            write
+                .send(Message::Text(login()))
+                .await
+                .map_err(|e| ArbitrageError::NetworkError {
+                    message: format!("Failed to send login message: {}", e),
+                    retry_after: None,
+                })?;
+
+            let reply = tokio::time::timeout(LOGIN_TIMEOUT, async {
+                loop {
+                    match read.next().await {
+                        Some(Ok(Message::Text(text))) => return Ok(text),
+                        Some(Ok(_)) => continue,
+                        Some(Err(e)) => return Err(format!("WebSocket error: {}", e)),
+                        None => return Err("connection closed".to_string()),
+                    }
+                }
+            })
+            .await
+            .map_err(|_| format!("no reply within {}s", LOGIN_TIMEOUT.as_secs()))
+            .and_then(|reply| reply)
+            .map_err(|reason| ArbitrageError::NetworkError {
+                message: format!("Login failed: {}", reason),
+                retry_after: None,
+            })?;
+            self.parser.check_login(&reply)?;
+            debug!("Logged in");
+        }
+
+        // Replay subscription handshake - a fresh socket has no subscriptions
+        for message in &self.on_connect_messages {
+            debug!(message = %message, "Sending on-connect message");
+            write
+                .send(Message::Text(message.clone()))
+                .await
+                .map_err(|e| ArbitrageError::NetworkError {
+                    message: format!("Failed to send on-connect message: {}", e),
+                    retry_after: None,
+                })?;
+        }
+
         // Reset retry counter on successful connection
         self.reconnect_strategy.reset();
 
         // Set up ping interval for health checks
         let mut ping_interval = tokio::time::interval(self.health_check_interval);
 
+        // Inactivity watchdog - deadline pushed forward on every data frame
+        let watchdog = tokio::time::sleep(self.inactivity_timeout.unwrap_or(std::time::Duration::MAX));
+        tokio::pin!(watchdog);
+
         // Main message loop
         loop {
             tokio::select! {
@@ -158,9 +367,19 @@ impl<P: MessageParser> WebSocketManager<P> {
                 message_result = read.next() => {
                     match message_result {
                         Some(Ok(Message::Text(text))) => {
+                            if let Some(timeout) = self.inactivity_timeout {
+                                watchdog.as_mut().reset(tokio::time::Instant::now() + timeout);
+                            }
+
+                            // Heartbeats keep the watchdog happy but carry no data
+                            if self.parser.is_control(&text) {
+                                continue;
+                            }
+
                             // Parse message using the parser
                             match self.parser.parse(&text) {
                                 Ok(parsed) => {
+                                    self.set_state(ConnectionState::Live);
                                     // Broadcast to all subscribers
                                     // Ignore error if no subscribers
                                     let _ = self.message_tx.send(parsed);
@@ -201,9 +420,22 @@ impl<P: MessageParser> WebSocketManager<P> {
                         }
                     }
                 }
+                // No data for too long - treat as a dead connection
+                _ = &mut watchdog, if self.inactivity_timeout.is_some() => {
+                    let timeout = self.inactivity_timeout.unwrap_or_default();
+                    warn!(url = %self.url, timeout_secs = timeout.as_secs(), "No data received, forcing reconnect");
+                    return Err(ArbitrageError::NetworkError {
+                        message: format!("No data received for {}s", timeout.as_secs()),
+                        retry_after: None,
+                    });
+                }
                 // Send periodic ping to keep connection alive
                 _ = ping_interval.tick() => {
-                    if let Err(e) = write.send(Message::Ping(vec![])).await {
+                    let ping = match &self.heartbeat_message {
+                        Some(message) => Message::Text(message.clone()),
+                        None => Message::Ping(vec![]),
+                    };
+                    if let Err(e) = write.send(ping).await {
                         return Err(ArbitrageError::NetworkError {
                             message: format!("Failed to send ping: {}", e),
                             retry_after: None,
@@ -239,6 +471,8 @@ mod tests {
                 last: Decimal::ZERO,
                 volume_24h: Decimal::ZERO,
                 timestamp: Utc::now(),
+                sequence: None,
+                received_at: Utc::now(),
             })
         }
     }
@@ -249,7 +483,7 @@ mod tests {
         let parser = TestParser;
         let strategy = ReconnectionStrategy::exponential_backoff();
 
-        let (manager, receiver) = WebSocketManager::new(url.clone(), parser, strategy);
+        let (manager, receiver) = WebSocketManager::new(ExchangeId::BINANCE, url.clone(), parser, strategy);
 
         // Verify manager was created successfully
         // (Can't easily test internals, but if new() returned, it succeeded)
@@ -265,7 +499,7 @@ mod tests {
         let strategy = ReconnectionStrategy::exponential_backoff();
 
         let (_manager, mut receiver1) =
-            WebSocketManager::new(url.clone(), parser.clone(), strategy.clone());
+            WebSocketManager::new(ExchangeId::BINANCE, url.clone(), parser.clone(), strategy.clone());
 
         // Create second receiver (broadcast allows multiple)
         let mut receiver2 = receiver1.resubscribe();
@@ -274,4 +508,25 @@ mod tests {
         assert!(receiver1.try_recv().is_err()); // No messages yet
         assert!(receiver2.try_recv().is_err()); // No messages yet
     }
+
+    #[test]
+    fn test_rate_limited_handshake_without_retry_after_backs_off() {
+        let strategy = ReconnectionStrategy::new(
+            Some(5),
+            std::time::Duration::from_secs(2),
+            std::time::Duration::from_secs(60),
+        );
+        let url = "wss://example.invalid".to_string();
+        let (manager, _receiver) =
+            WebSocketManager::new(ExchangeId::COINBASE, url, TestParser, strategy);
+        let response = tungstenite::http::Response::builder().status(429).body(None).unwrap();
+
+        match manager.handshake_error(tungstenite::Error::Http(response)) {
+            ArbitrageError::RateLimitExceeded { exchange, retry_after } => {
+                assert_eq!(exchange, ExchangeId::COINBASE);
+                assert_eq!(retry_after, 2000);
+            }
+            other => panic!("expected RateLimitExceeded, got {other:?}"),
+        }
+    }
 }
@@ -2,11 +2,15 @@
 //!
 //! Provides generic WebSocket manager with reconnection logic and message parsing.
 
+pub mod circuit_breaker;
 pub mod manager;
 pub mod parser;
 pub mod reconnect;
+pub mod state;
 
+pub use circuit_breaker::{CircuitBreaker, CircuitState};
 pub use manager::WebSocketManager;
 pub use parser::MessageParser;
-pub use reconnect::ReconnectionStrategy;
+pub use reconnect::{ReconnectionStrategy, parse_retry_after};
+pub use state::ConnectionState;
 
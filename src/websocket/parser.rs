@@ -36,6 +36,20 @@ pub trait MessageParser: Send + Sync + Clone {
     ///
     /// Returns `ParseError` if the message format is invalid or missing required fields
     fn parse(&self, message: &str) -> Result<Self::Output>;
+
+    /// Whether `message` is protocol chatter (heartbeats, acks) to skip without parsing
+    ///
+    /// Defaults to `false`, so every message is parsed and failures are logged.
+    fn is_control(&self, _message: &str) -> bool {
+        false
+    }
+
+    /// Check the server's reply to the login message sent by `WebSocketManager::with_login()`
+    ///
+    /// Defaults to accepting any reply. An error aborts the connection attempt.
+    fn check_login(&self, _reply: &str) -> Result<()> {
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -62,6 +76,8 @@ mod tests {
                 last: Decimal::ZERO,
                 volume_24h: Decimal::ZERO,
                 timestamp: Utc::now(),
+                sequence: None,
+                received_at: Utc::now(),
             })
         }
     }
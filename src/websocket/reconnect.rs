@@ -1,5 +1,7 @@
 //! Reconnection strategy with exponential backoff
 
+use super::circuit_breaker::{CircuitBreaker, CircuitState};
+use rand::Rng;
 use std::time::Duration;
 
 /// Strategy for reconnecting WebSocket connections with exponential backoff
@@ -17,6 +19,13 @@ use std::time::Duration;
 /// - Attempt 4: Wait 8 seconds
 /// - ... up to max_delay cap
 ///
+/// **Jitter**: With `with_jitter()`, delays use decorrelated jitter
+/// (`random(initial_delay, 3 * previous_delay)`, capped) so connections that failed
+/// together don't retry in lockstep.
+///
+/// **Circuit Breaker**: With `with_circuit_breaker()`, a burst of failures opens the
+/// circuit and `on_failure()` holds off for the cool-off before a trial connection.
+///
 /// # Example
 ///
 /// ```rust,no_run
@@ -32,6 +41,12 @@ use std::time::Duration;
 ///     Duration::from_secs(1),     // Start with 1 second
 ///     Duration::from_secs(60),    // Cap at 60 seconds
 /// );
+///
+/// // Jittered, with a circuit breaker (what the exchange feeds use)
+/// use arb_bot::websocket::CircuitBreaker;
+/// let resilient = ReconnectionStrategy::exponential_backoff()
+///     .with_jitter()
+///     .with_circuit_breaker(CircuitBreaker::default());
 /// ```
 #[derive(Debug, Clone)]
 pub struct ReconnectionStrategy {
@@ -45,6 +60,12 @@ pub struct ReconnectionStrategy {
     pub max_delay: Duration,
     /// Multiplier for exponential backoff (typically 2.0)
     pub multiplier: f64,
+    /// Use decorrelated jitter instead of deterministic exponential delays
+    pub jitter: bool,
+    /// Optional breaker that pauses retries after a burst of failures
+    pub circuit_breaker: Option<CircuitBreaker>,
+    /// Previous jittered delay (decorrelated jitter grows from it)
+    last_delay: Duration,
 }
 
 impl ReconnectionStrategy {
@@ -66,9 +87,24 @@ impl ReconnectionStrategy {
             initial_delay,
             max_delay,
             multiplier: 2.0,
+            jitter: false,
+            circuit_breaker: None,
+            last_delay: initial_delay,
         }
     }
 
+    /// Randomize delays with decorrelated jitter
+    pub fn with_jitter(mut self) -> Self {
+        self.jitter = true;
+        self
+    }
+
+    /// Pause retries with a circuit breaker after repeated failures
+    pub fn with_circuit_breaker(mut self, breaker: CircuitBreaker) -> Self {
+        self.circuit_breaker = Some(breaker);
+        self
+    }
+
     /// Create a default exponential backoff strategy
     ///
     /// - Max retries: 10
@@ -99,6 +135,20 @@ impl ReconnectionStrategy {
     ///
     /// Increments `current_retry` counter.
     pub fn next_delay(&mut self) -> Duration {
+        if self.jitter {
+            return self.next_jittered_delay();
+        }
+
+        let delay = self.backoff_delay();
+        self.current_retry += 1;
+        delay
+    }
+
+    /// Exponential backoff delay for the current attempt, without jitter
+    ///
+    /// Unlike `next_delay()` this has no side effects, e.g. to pick a wait for a
+    /// rate-limited handshake that came without `Retry-After`.
+    pub fn backoff_delay(&self) -> Duration {
         // Calculate exponential: multiplier ^ current_retry
         // Cap the exponent to prevent overflow (max ~30 for 2.0 multiplier)
         let exponent = self.current_retry.min(30) as i32;
@@ -107,8 +157,6 @@ impl ReconnectionStrategy {
         // Calculate delay, but ensure it doesn't overflow
         let delay_secs = self.initial_delay.as_secs_f64() * multiplier_power;
 
-        self.current_retry += 1;
-
         // Cap at max_delay to prevent overflow
         let max_delay_secs = self.max_delay.as_secs_f64();
         let capped_secs = delay_secs.min(max_delay_secs);
@@ -117,12 +165,83 @@ impl ReconnectionStrategy {
         Duration::from_secs_f64(capped_secs.min(u64::MAX as f64))
     }
 
+    /// Decorrelated jitter: `min(max_delay, random(initial_delay, 3 * last_delay))`
+    fn next_jittered_delay(&mut self) -> Duration {
+        self.current_retry += 1;
+
+        let low = self.initial_delay.as_secs_f64();
+        let high = (self.last_delay.as_secs_f64() * 3.0).max(low);
+        let secs = if high > low {
+            rand::thread_rng().gen_range(low..high)
+        } else {
+            low
+        };
+
+        let delay = Duration::from_secs_f64(secs.min(self.max_delay.as_secs_f64()));
+        self.last_delay = delay;
+        delay
+    }
+
+    /// Record a failed attempt and return how long to wait before the next one
+    ///
+    /// The wait is the longest of:
+    /// - the backoff delay from `next_delay()`
+    /// - `retry_after`, a server hint such as HTTP 429 `Retry-After`
+    /// - the remaining cool-off if the circuit breaker is open
+    pub fn on_failure(&mut self, retry_after: Option<Duration>) -> Duration {
+        let mut delay = self.next_delay();
+
+        if let Some(hint) = retry_after {
+            delay = delay.max(hint);
+        }
+
+        if let Some(breaker) = self.circuit_breaker.as_mut() {
+            breaker.record_failure();
+            if let Some(cool_off) = breaker.remaining_cool_off() {
+                delay = delay.max(cool_off);
+            }
+        }
+
+        delay
+    }
+
+    /// Circuit breaker state (`Closed` when no breaker is configured)
+    pub fn circuit_state(&self) -> CircuitState {
+        self.circuit_breaker
+            .as_ref()
+            .map(|b| b.state())
+            .unwrap_or(CircuitState::Closed)
+    }
+
     /// Reset the retry counter (called after successful connection)
+    ///
+    /// Also closes a half-open circuit breaker.
     pub fn reset(&mut self) {
         self.current_retry = 0;
+        self.last_delay = self.initial_delay;
+        if let Some(breaker) = self.circuit_breaker.as_mut() {
+            breaker.record_success();
+        }
     }
 }
 
+/// Parse a `Retry-After` header value: delay in seconds, or an HTTP date
+///
+/// Dates in the past yield a zero delay. Returns `None` for unparseable values.
+pub fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        (date.with_timezone(&chrono::Utc) - chrono::Utc::now())
+            .to_std()
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -214,5 +333,66 @@ mod tests {
         let delay = strategy.next_delay();
         assert_eq!(delay.as_secs(), 1);
     }
+
+    #[test]
+    fn test_jittered_delays_are_bounded_and_vary() {
+        let mut strategy = ReconnectionStrategy::new(
+            None,
+            Duration::from_millis(100),
+            Duration::from_secs(5),
+        )
+        .with_jitter();
+
+        let delays: Vec<Duration> = (0..50).map(|_| strategy.next_delay()).collect();
+        assert!(delays.iter().all(|d| *d >= Duration::from_millis(100)));
+        assert!(delays.iter().all(|d| *d <= Duration::from_secs(5)));
+        assert!(delays.windows(2).any(|w| w[0] != w[1]));
+        assert_eq!(strategy.current_retry, 50);
+    }
+
+    #[test]
+    fn test_on_failure_honors_retry_after() {
+        let mut strategy = ReconnectionStrategy::new(
+            Some(5),
+            Duration::from_secs(1),
+            Duration::from_secs(60),
+        );
+
+        assert_eq!(strategy.on_failure(Some(Duration::from_secs(30))), Duration::from_secs(30));
+        // Hint shorter than backoff doesn't shorten the wait
+        assert_eq!(strategy.on_failure(Some(Duration::from_millis(10))), Duration::from_secs(2));
+    }
+
+    #[test]
+    fn test_on_failure_waits_out_open_circuit() {
+        let mut strategy = ReconnectionStrategy::new(
+            None,
+            Duration::from_millis(1),
+            Duration::from_millis(10),
+        )
+        .with_circuit_breaker(CircuitBreaker::new(
+            2,
+            Duration::from_secs(60),
+            Duration::from_secs(30),
+        ));
+
+        assert!(strategy.on_failure(None) < Duration::from_secs(1));
+        assert_eq!(strategy.circuit_state(), CircuitState::Closed);
+
+        let delay = strategy.on_failure(None);
+        assert_eq!(strategy.circuit_state(), CircuitState::Open);
+        assert!(delay > Duration::from_secs(29));
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        assert_eq!(parse_retry_after("120"), Some(Duration::from_secs(120)));
+        assert_eq!(parse_retry_after(" 5 "), Some(Duration::from_secs(5)));
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("soon"), None);
+    }
 }
 
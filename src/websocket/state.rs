@@ -0,0 +1,74 @@
+//! Connection state machine for WebSocket feeds
+
+use std::fmt;
+
+/// Lifecycle state of an exchange feed connection
+///
+/// # Transitions
+///
+/// ```text
+/// Disconnected -> Connecting -> Subscribing -> Live
+///                     ^              |          |
+///                     |              v          v
+///                     +------ Reconnecting{attempt} -> Failed{reason}
+/// ```
+///
+/// Only `Live` means prices are flowing and safe to trade on.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum ConnectionState {
+    /// Not connected and not trying to connect
+    #[default]
+    Disconnected,
+    /// Opening the WebSocket connection
+    Connecting,
+    /// Connected, waiting for the first parsed message after subscribing
+    Subscribing,
+    /// Receiving parsed messages
+    Live,
+    /// Connection lost, waiting before retry `attempt`
+    Reconnecting { attempt: u32 },
+    /// Retries exhausted; manager has stopped
+    Failed { reason: String },
+}
+
+impl ConnectionState {
+    /// True only when the feed is delivering data
+    pub fn is_live(&self) -> bool {
+        matches!(self, ConnectionState::Live)
+    }
+}
+
+impl fmt::Display for ConnectionState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConnectionState::Disconnected => write!(f, "disconnected"),
+            ConnectionState::Connecting => write!(f, "connecting"),
+            ConnectionState::Subscribing => write!(f, "subscribing"),
+            ConnectionState::Live => write!(f, "live"),
+            ConnectionState::Reconnecting { attempt } => write!(f, "reconnecting (attempt {})", attempt),
+            ConnectionState::Failed { reason } => write!(f, "failed: {}", reason),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_live_is_live() {
+        assert!(ConnectionState::Live.is_live());
+        assert!(!ConnectionState::Subscribing.is_live());
+        assert!(!ConnectionState::Reconnecting { attempt: 1 }.is_live());
+        assert!(!ConnectionState::default().is_live());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(ConnectionState::Reconnecting { attempt: 3 }.to_string(), "reconnecting (attempt 3)");
+        assert_eq!(
+            ConnectionState::Failed { reason: "boom".to_string() }.to_string(),
+            "failed: boom"
+        );
+    }
+}
@@ -0,0 +1,288 @@
+//! Tests for the Bybit exchange: signed REST and the price feed against local stand-ins
+//!
+//! Tests marked with `#[ignore]` require live connection to Bybit WebSocket.
+//! Run them with: `cargo test --test bybit -- --ignored`
+
+use arb_bot::config::BybitConfig;
+use arb_bot::error::ArbitrageError;
+use arb_bot::exchanges::bybit::{BybitAuth, BybitExchange};
+use arb_bot::exchanges::{Exchange, Order, OrderStatus};
+use futures_util::{SinkExt, StreamExt};
+use rust_decimal::Decimal;
+use std::str::FromStr;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio_tungstenite::tungstenite::Message;
+
+mod common;
+use common::HttpStub;
+use common::http_stub::RecordedRequest;
+
+fn exchange(api_key: &str) -> BybitExchange {
+    BybitExchange::new(BybitConfig {
+        api_key: api_key.to_string(),
+        api_secret: "test-secret".to_string(),
+        testnet: false,
+    })
+    .unwrap()
+}
+
+fn client(stub: &HttpStub) -> BybitExchange {
+    exchange("test-key").with_rest_url(stub.base_url.clone())
+}
+
+/// Requests the test made, without the exchange's background clock sync
+fn trading_requests(stub: &HttpStub) -> Vec<RecordedRequest> {
+    stub.requests
+        .lock()
+        .iter()
+        .filter(|r| !r.path.starts_with("/v5/market/time"))
+        .cloned()
+        .collect()
+}
+
+#[tokio::test]
+async fn test_get_balances() {
+    let balances = r#"{"retCode":0,"retMsg":"OK","result":{"list":[{"accountType":"UNIFIED","coin":[
+        {"coin":"SOL","walletBalance":"42.5","locked":"2.5","equity":"42.5"},
+        {"coin":"USDC","walletBalance":"2500","locked":"","equity":"2500"}]}]},"retExtInfo":{},"time":1700000000000}"#;
+    let stub = HttpStub::start(vec![("/v5/account/wallet-balance", 200, balances.to_string())]).await;
+
+    let balances = client(&stub).get_balances(&["SOL", "USDC", "BTC"]).await.unwrap();
+    assert_eq!(balances.len(), 2);
+    assert_eq!(balances["SOL"].available, Decimal::from(40));
+    assert_eq!(balances["SOL"].hold, Decimal::from_str("2.5").unwrap());
+    assert_eq!(balances["USDC"].available, Decimal::from(2500));
+}
+
+#[tokio::test]
+async fn test_requests_are_signed() {
+    let stub = HttpStub::start(vec![(
+        "/v5/account/wallet-balance",
+        200,
+        r#"{"retCode":0,"retMsg":"OK","result":{"list":[]}}"#.to_string(),
+    )])
+    .await;
+
+    let balance = client(&stub).get_balance("BTC").await.unwrap();
+    assert_eq!(balance, Decimal::ZERO);
+
+    let requests = trading_requests(&stub);
+    let request = &requests[0];
+    assert_eq!(request.method, "GET");
+    assert_eq!(request.path, "/v5/account/wallet-balance?accountType=UNIFIED&coin=BTC");
+    assert_eq!(request.header("x-bapi-api-key"), Some("test-key"));
+    assert_eq!(request.header("x-bapi-recv-window"), Some("5000"));
+
+    let timestamp: i64 = request.header("x-bapi-timestamp").unwrap().parse().unwrap();
+    let auth = BybitAuth::new("test-key".to_string(), "test-secret".to_string());
+    assert_eq!(
+        request.header("x-bapi-sign"),
+        Some(auth.sign(timestamp, "accountType=UNIFIED&coin=BTC").as_str())
+    );
+}
+
+#[tokio::test]
+async fn test_signing_uses_synced_exchange_clock() {
+    // Bybit's clock is 2 minutes ahead of ours
+    let server_ms = chrono::Utc::now().timestamp_millis() + 120_000;
+    let stub = HttpStub::start(vec![
+        (
+            "/v5/market/time",
+            200,
+            format!(r#"{{"retCode":0,"retMsg":"OK","result":{{}},"time":{}}}"#, server_ms),
+        ),
+        (
+            "/v5/account/wallet-balance",
+            200,
+            r#"{"retCode":0,"retMsg":"OK","result":{"list":[]}}"#.to_string(),
+        ),
+    ])
+    .await;
+
+    let exchange = client(&stub);
+    let clock = exchange.clock().expect("credentials start a clock");
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while clock.offset().is_none() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("clock synced");
+    assert!((clock.offset_ms() - 120_000).abs() < 1000);
+    assert!(clock.is_alarmed());
+
+    exchange.get_balance("BTC").await.unwrap();
+    let requests = trading_requests(&stub);
+    let timestamp: i64 = requests[0].header("x-bapi-timestamp").unwrap().parse().unwrap();
+    assert!((timestamp - chrono::Utc::now().timestamp_millis() - 120_000).abs() < 1000);
+
+    // Without credentials nothing is signed, so no clock is kept
+    let public = BybitExchange::new(BybitConfig {
+        api_key: String::new(),
+        api_secret: String::new(),
+        testnet: false,
+    })
+    .unwrap();
+    assert!(public.clock().is_none());
+}
+
+#[tokio::test]
+async fn test_place_order_looks_up_fills() {
+    let created = r#"{"retCode":0,"retMsg":"OK","result":{"orderId":"1321003749386327552","orderLinkId":""}}"#;
+    let realtime = r#"{"retCode":0,"retMsg":"OK","result":{"list":[{"orderId":"1321003749386327552",
+        "symbol":"SOLUSDC","side":"Buy","orderStatus":"Filled","cumExecQty":"2","avgPrice":"150",
+        "cumExecFee":"0.002","updatedTime":"1700000000250"}]}}"#;
+    let stub = HttpStub::start(vec![
+        ("/v5/order/create", 200, created.to_string()),
+        ("/v5/order/realtime", 200, realtime.to_string()),
+    ])
+    .await;
+
+    let result = client(&stub)
+        .place_order(Order::market_buy("SOL/USDC", Decimal::from(2)))
+        .await
+        .unwrap();
+    assert_eq!(result.order_id, "1321003749386327552");
+    assert_eq!(result.status, OrderStatus::Filled);
+    assert_eq!(result.average_price, Some(Decimal::from(150)));
+    assert_eq!(result.fee_asset, "SOL");
+
+    let requests = trading_requests(&stub);
+    assert_eq!(requests[0].method, "POST");
+    let body: serde_json::Value = serde_json::from_str(&requests[0].body).unwrap();
+    assert_eq!(body["category"], "spot");
+    assert_eq!(body["symbol"], "SOLUSDC");
+    assert_eq!(body["side"], "Buy");
+    assert_eq!(body["orderType"], "Market");
+    assert_eq!(body["qty"], "2");
+    assert_eq!(body["marketUnit"], "baseCoin");
+
+    let timestamp: i64 = requests[0].header("x-bapi-timestamp").unwrap().parse().unwrap();
+    let auth = BybitAuth::new("test-key".to_string(), "test-secret".to_string());
+    assert_eq!(
+        requests[0].header("x-bapi-sign"),
+        Some(auth.sign(timestamp, &requests[0].body).as_str())
+    );
+    assert_eq!(
+        requests[1].path,
+        "/v5/order/realtime?category=spot&orderId=1321003749386327552"
+    );
+}
+
+#[tokio::test]
+async fn test_closed_order_found_in_history() {
+    let empty = r#"{"retCode":0,"retMsg":"OK","result":{"list":[]}}"#;
+    let history = r#"{"retCode":0,"retMsg":"OK","result":{"list":[{"orderId":"42","symbol":"BTCUSDT",
+        "side":"Sell","orderStatus":"Cancelled","cumExecQty":"0","avgPrice":"","cumExecFee":"0","updatedTime":""}]}}"#;
+    let stub = HttpStub::start(vec![
+        ("/v5/order/realtime", 200, empty.to_string()),
+        ("/v5/order/history", 200, history.to_string()),
+    ])
+    .await;
+
+    let order = client(&stub).get_order("BTC/USDT", "42").await.unwrap();
+    assert_eq!(order.status, OrderStatus::Cancelled);
+    assert_eq!(order.average_price, None);
+    assert_eq!(order.fee_asset, "USDT");
+}
+
+#[tokio::test]
+async fn test_error_codes_map_to_error_types() {
+    let stub = HttpStub::start(vec![
+        (
+            "/v5/account/wallet-balance",
+            200,
+            r#"{"retCode":10003,"retMsg":"API key is invalid.","result":{}}"#.to_string(),
+        ),
+        (
+            "/v5/order/realtime",
+            200,
+            r#"{"retCode":10006,"retMsg":"Too many visits!","result":{}}"#.to_string(),
+        ),
+        (
+            "/v5/order/create",
+            200,
+            r#"{"retCode":170131,"retMsg":"Insufficient balance.","result":{}}"#.to_string(),
+        ),
+    ])
+    .await;
+    let mut client = client(&stub);
+
+    assert!(matches!(
+        client.get_balance("BTC").await,
+        Err(ArbitrageError::AuthenticationError { .. })
+    ));
+    assert!(matches!(
+        client.get_open_orders("BTC/USDT").await,
+        Err(ArbitrageError::RateLimitExceeded { .. })
+    ));
+    let err = client
+        .place_order(Order::market_buy("SOL/USDC", Decimal::from(2)))
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, ArbitrageError::InsufficientBalance { ref asset, .. } if asset == "USDC")
+    );
+}
+
+#[tokio::test]
+async fn test_price_feed_against_local_server() {
+    // Stand-in for the public spot stream: acks the subscription, answers pings, pushes a quote
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("ws://{}", listener.local_addr().unwrap());
+    let server = tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+        let mut received = Vec::new();
+        while let Some(Ok(message)) = ws.next().await {
+            let Message::Text(text) = message else { continue };
+            let request: serde_json::Value = serde_json::from_str(&text).unwrap();
+            let op = request["op"].as_str().unwrap().to_string();
+            let ack = serde_json::json!({"success": true, "ret_msg": "", "conn_id": "c1", "op": op});
+            ws.send(Message::Text(ack.to_string())).await.unwrap();
+            if op == "subscribe" {
+                for push in [
+                    r#"{"topic":"tickers.SOLUSDC","ts":1700000000123,"type":"snapshot","data":{"symbol":"SOLUSDC","lastPrice":"150.12","volume24h":"10000.5"}}"#,
+                    r#"{"topic":"orderbook.1.SOLUSDC","type":"snapshot","ts":1700000000500,"data":{"s":"SOLUSDC","b":[["150.1","4"]],"a":[["150.14","3"]],"u":1,"seq":2}}"#,
+                ] {
+                    ws.send(Message::Text(push.to_string())).await.unwrap();
+                }
+            }
+            received.push(op);
+        }
+        received
+    });
+
+    let mut exchange = exchange("").with_ws_url(url);
+    exchange.subscribe_ticker("SOL/USDC").await.unwrap();
+
+    let price = exchange.get_latest_price("SOL/USDC").await.unwrap();
+    assert_eq!(price.bid, Decimal::from_str("150.1").unwrap());
+    assert_eq!(price.ask, Decimal::from_str("150.14").unwrap());
+    assert_eq!(price.last, Decimal::from_str("150.12").unwrap());
+    assert!(exchange.is_connected());
+
+    // Dropping the connection ends the server loop
+    exchange.disconnect().await.unwrap();
+    let received = tokio::time::timeout(Duration::from_secs(5), server)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(received[0], "subscribe");
+    assert!(received.contains(&"ping".to_string()));
+}
+
+#[tokio::test]
+#[ignore] // Ignored by default - requires live connection
+async fn test_bybit_live_ticker() {
+    let mut exchange = exchange("");
+
+    exchange.subscribe_ticker("BTC/USDT").await.unwrap();
+    let price = exchange.get_latest_price("BTC/USDT").await.unwrap();
+    assert!(price.bid > Decimal::ZERO);
+    assert!(price.ask >= price.bid);
+    assert!(exchange.is_connected());
+
+    exchange.disconnect().await.unwrap();
+}
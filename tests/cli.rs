@@ -0,0 +1,142 @@
+//! Integration tests for the command-line interface
+
+use arb_bot::cli::{Cli, Command, ConfigCommand, SideArg, account, check};
+use arb_bot::config::{AppConfig, ConfigLoader};
+use arb_bot::error::ArbitrageError;
+use arb_bot::exchanges::ExchangeId;
+use arb_bot::logger::LogFormat;
+use clap::Parser;
+use rust_decimal::Decimal;
+use std::io::Write;
+
+#[test]
+fn test_parses_subcommands() {
+    let cli = Cli::try_parse_from(["arb-bot", "--config", "prod.toml", "run", "--dry-run"]).unwrap();
+    assert_eq!(cli.config.to_str(), Some("prod.toml"));
+    assert!(matches!(cli.command, Command::Run(ref args) if args.dry_run));
+    let Command::Run(args) = cli.command else {
+        panic!("expected run");
+    };
+    assert_eq!(args.journal_path().to_str(), Some("data/journal.jsonl"));
+
+    let cli = Cli::try_parse_from([
+        "arb-bot", "run", "--paper", "--paper-balance", "usdc=1000", "--paper-balance", "SOL=2.5",
+    ])
+    .unwrap();
+    let Command::Run(args) = cli.command else {
+        panic!("expected run");
+    };
+    assert!(args.paper);
+    assert_eq!(
+        args.paper_balances,
+        [("USDC".to_string(), Decimal::from(1000)), ("SOL".to_string(), Decimal::new(25, 1))]
+    );
+    assert_eq!(args.journal_path().to_str(), Some("data/paper-journal.jsonl"));
+
+    let cli = Cli::try_parse_from(["arb-bot", "order", "coinbase", "sell", "1.5", "--yes"]).unwrap();
+    let Command::Order(args) = cli.command else {
+        panic!("expected order");
+    };
+    assert_eq!(args.exchange, ExchangeId::COINBASE);
+    assert_eq!(args.side, SideArg::Sell);
+    assert_eq!(args.quantity, Decimal::new(15, 1));
+    assert!(args.yes);
+
+    let cli = Cli::try_parse_from(["arb-bot", "config", "check", "-c", "other.toml", "-p", "prod"]).unwrap();
+    assert!(matches!(
+        cli.command,
+        Command::Config {
+            command: ConfigCommand::Check
+        }
+    ));
+    assert_eq!(cli.config.to_str(), Some("other.toml"));
+    assert_eq!(cli.loader().profile_path(), Some("other.prod.toml".into()));
+}
+
+#[test]
+fn test_rejects_bad_arguments() {
+    assert!(Cli::try_parse_from(["arb-bot", "order", "bin ance", "buy", "1"]).is_err());
+    assert!(Cli::try_parse_from(["arb-bot", "order", "binance", "buy", "lots"]).is_err());
+    assert!(Cli::try_parse_from(["arb-bot"]).is_err());
+    // Paper balances need --paper and a valid amount
+    assert!(Cli::try_parse_from(["arb-bot", "run", "--paper-balance", "USDC=1"]).is_err());
+    assert!(Cli::try_parse_from(["arb-bot", "run", "--paper", "--paper-balance", "USDC"]).is_err());
+    assert!(Cli::try_parse_from(["arb-bot", "run", "--paper", "--paper-balance", "USDC=x"]).is_err());
+}
+
+#[test]
+fn test_example_config_is_valid() {
+    let config = AppConfig::parse(include_str!("../config.example.toml")).unwrap();
+    assert_eq!(config.trading().pair(), "SOL/USDC");
+    assert_eq!(config.exchanges().enabled().len(), 2);
+    assert!(config.risk().max_order_notional().is_some());
+}
+
+#[test]
+fn test_config_check_reports_invalid_file() {
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    writeln!(
+        file,
+        "[trading]\npair = \"SOL/USDC\"\nspread_threshold = 2.0\norder_size = 1.0\ncooldown_ms = 5000"
+    )
+    .unwrap();
+    let err = check::run(&ConfigLoader::new(file.path())).unwrap_err();
+    assert!(err.to_string().to_lowercase().contains("spread"));
+
+    assert!(check::run(&ConfigLoader::new("does/not/exist.toml")).is_err());
+}
+
+#[test]
+fn test_log_flags_override_config() {
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    writeln!(
+        file,
+        "[trading]\npair = \"SOL/USDC\"\nspread_threshold = 0.002\norder_size = 1.0\ncooldown_ms = 5000\n\n\
+         [logging]\nlevel = \"warn\"\nformat = \"compact\""
+    )
+    .unwrap();
+    let path = file.path().to_str().unwrap();
+
+    let cli = Cli::try_parse_from(["arb-bot", "-c", path, "monitor"]).unwrap();
+    let logging = cli.logger_config();
+    assert_eq!(logging.level(), "warn");
+    assert_eq!(logging.format(), LogFormat::Compact);
+
+    let cli = Cli::try_parse_from(["arb-bot", "-c", path, "--log-format", "json", "monitor"]).unwrap();
+    let logging = cli.logger_config();
+    assert_eq!(logging.level(), "warn");
+    assert_eq!(logging.format(), LogFormat::Json);
+}
+
+#[tokio::test]
+async fn test_manual_order_honours_kill_switch_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let kill_switch = dir.path().join("halt");
+    std::fs::write(&kill_switch, "").unwrap();
+    let config = AppConfig::parse(
+        "[trading]\npair = \"SOL/USDC\"\nspread_threshold = 0.002\norder_size = 1.0\ncooldown_ms = 5000",
+    )
+    .unwrap();
+
+    let journal = dir.path().join("journal.jsonl");
+    let (journal, kill_switch) = (journal.to_str().unwrap(), kill_switch.to_str().unwrap());
+    let cli = Cli::try_parse_from([
+        "arb-bot",
+        "order",
+        "binance",
+        "buy",
+        "1",
+        "--yes",
+        "--journal",
+        journal,
+        "--kill-switch-file",
+        kill_switch,
+    ])
+    .unwrap();
+    let Command::Order(args) = cli.command else {
+        panic!("expected order");
+    };
+
+    let err = account::order(&config, &args).await.unwrap_err();
+    assert!(matches!(err, ArbitrageError::TradingHalted { .. }));
+}
@@ -0,0 +1,93 @@
+//! Integration tests for exchange clock synchronization
+//!
+//! Server time endpoints are served by a local stub, so no network access is needed.
+
+mod common;
+
+use arb_bot::clock::{
+    BinanceTimeSource, BybitTimeSource, ClockSync, ClockSyncConfig, CoinbaseTimeSource,
+    OkxTimeSource, ServerTimeSource,
+};
+use chrono::Utc;
+use common::HttpStub;
+
+#[tokio::test]
+async fn test_binance_server_time_offset() {
+    // Binance server clock is 2 minutes ahead of ours
+    let server_ms = Utc::now().timestamp_millis() + 120_000;
+    let stub = HttpStub::start(vec![(
+        "/api/v3/time",
+        200,
+        format!(r#"{{"serverTime":{}}}"#, server_ms),
+    )])
+    .await;
+
+    let clock = ClockSync::new("binance", ClockSyncConfig::default());
+    let source = BinanceTimeSource::with_base_url(&stub.base_url);
+    let offset = clock.sync_once(&source).await.unwrap();
+
+    assert!((offset.offset_ms - 120_000).abs() < 1000);
+    assert!(clock.is_alarmed());
+}
+
+#[tokio::test]
+async fn test_coinbase_server_time_offset() {
+    let server_ms = Utc::now().timestamp_millis() - 30_000;
+    let stub = HttpStub::start(vec![(
+        "/api/v3/brokerage/time",
+        200,
+        format!(
+            r#"{{"iso":"2025-10-30T12:00:00Z","epochSeconds":"{}","epochMillis":"{}"}}"#,
+            server_ms / 1000,
+            server_ms
+        ),
+    )])
+    .await;
+
+    let clock = ClockSync::new("coinbase", ClockSyncConfig::default());
+    let source = CoinbaseTimeSource::with_base_url(&stub.base_url);
+    clock.sync_once(&source).await.unwrap();
+
+    assert!((clock.offset_ms() + 30_000).abs() < 1000);
+    assert_eq!(stub.requests.lock().len(), ClockSyncConfig::default().samples);
+}
+
+#[tokio::test]
+async fn test_okx_and_bybit_server_time() {
+    let server_ms = Utc::now().timestamp_millis() + 5_000;
+    let stub = HttpStub::start(vec![
+        (
+            "/api/v5/public/time",
+            200,
+            format!(r#"{{"code":"0","msg":"","data":[{{"ts":"{}"}}]}}"#, server_ms),
+        ),
+        (
+            "/v5/market/time",
+            200,
+            format!(
+                r#"{{"retCode":0,"retMsg":"OK","result":{{"timeSecond":"{}","timeNano":"{}"}},"time":{}}}"#,
+                server_ms / 1000,
+                server_ms * 1_000_000,
+                server_ms
+            ),
+        ),
+    ])
+    .await;
+
+    let okx = OkxTimeSource::with_base_url(&stub.base_url);
+    assert_eq!(okx.server_time().await.unwrap().timestamp_millis(), server_ms);
+    let bybit = BybitTimeSource::with_base_url(&stub.base_url);
+    assert_eq!(bybit.server_time().await.unwrap().timestamp_millis(), server_ms);
+}
+
+#[tokio::test]
+async fn test_failed_sync_keeps_local_clock() {
+    let stub = HttpStub::start(vec![("/api/v3/time", 500, "{}".to_string())]).await;
+
+    let clock = ClockSync::new("binance", ClockSyncConfig::default());
+    let source = BinanceTimeSource::with_base_url(&stub.base_url);
+
+    assert!(clock.sync_once(&source).await.is_err());
+    assert!(clock.offset().is_none());
+    assert_eq!(clock.offset_ms(), 0);
+}
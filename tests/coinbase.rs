@@ -17,6 +17,7 @@ use tokio::time::timeout;
 
 /// Helper to create a test Coinbase config
 /// For WebSocket ticker streams, no API keys are needed
+#[allow(dead_code)] // Kept for when the sandbox WebSocket is usable again
 fn create_sandbox_config() -> CoinbaseConfig {
     // WebSocket ticker streams don't require authentication
     let api_key = std::env::var("COINBASE_SANDBOX_API_KEY").unwrap_or_else(|_| String::new());
@@ -171,3 +172,14 @@ async fn test_coinbase_error_handling() {
     let error_msg = result.unwrap_err().to_string();
     assert!(error_msg.contains("No price data") || error_msg.contains("INVALID"));
 }
+
+#[tokio::test]
+async fn test_coinbase_subscribe_message() {
+    // Subscription message is replayed by the WebSocketManager on every (re)connect
+    let message = CoinbaseExchange::subscribe_message("SOL/USDC").unwrap();
+    let json: serde_json::Value = serde_json::from_str(&message).unwrap();
+
+    assert_eq!(json["type"], "subscribe");
+    assert_eq!(json["product_ids"][0], "SOL-USDC");
+    assert_eq!(json["channels"][0], "ticker");
+}
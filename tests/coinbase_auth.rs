@@ -141,7 +141,7 @@ fn test_jwt_contains_required_claims() {
     let nbf = payload["nbf"].as_i64().expect("nbf should be a number");
     let duration = exp - nbf;
     assert!(
-        duration >= 115 && duration <= 125,
+        (115..=125).contains(&duration),
         "JWT should expire in ~2 minutes (120 seconds), got {} seconds",
         duration
     );
@@ -385,7 +385,6 @@ async fn test_jwt_authentication_with_readonly_api() {
         println!("✅ JWT Authentication SUCCESS!");
         println!("   Status: {}", status);
         println!("   Response preview: {}", &response_text[..response_text.len().min(200)]);
-        assert!(true, "JWT authentication verified - credentials are valid and authorized");
     } else if status == 401 || status == 403 {
         panic!(
             "❌ JWT Authentication FAILED!\n   Status: {}\n   Response: {}\n   This means:\n   - JWT format might be wrong\n   - API key might be invalid\n   - Private key might not match API key\n   - Credentials might not be authorized",
@@ -19,6 +19,9 @@ use arb_bot::exchanges::{Order, OrderSide, OrderStatus, OrderType};
 use rust_decimal::Decimal;
 use std::str::FromStr;
 
+mod common;
+use common::{HttpStub, TEST_EC_PRIVATE_KEY};
+
 /// Load environment variables from .env file
 fn load_env() {
     let _ = dotenvy::dotenv();
@@ -612,3 +615,77 @@ async fn test_place_order_invalid_order_type() {
     }
 }
 
+
+#[tokio::test]
+async fn test_get_balances_reads_available_and_hold() {
+    let accounts = r#"{"accounts":[
+        {"uuid":"1","name":"USDC Wallet","currency":"USDC",
+         "available_balance":{"value":"950.25","currency":"USDC"},
+         "hold":{"value":"49.75","currency":"USDC"}},
+        {"uuid":"2","name":"SOL Wallet","currency":"SOL",
+         "available_balance":{"value":"3.5","currency":"SOL"}},
+        {"uuid":"3","name":"BTC Wallet","currency":"BTC",
+         "available_balance":{"value":"1","currency":"BTC"}}
+    ]}"#;
+    let stub = HttpStub::start(vec![("/api/v3/brokerage/accounts", 200, accounts.to_string())]).await;
+
+    let client = CoinbaseRestClient::new(
+        "organizations/org/apiKeys/key".to_string(),
+        TEST_EC_PRIVATE_KEY.to_string(),
+        false,
+    )
+    .unwrap()
+    .with_base_url(stub.base_url.clone());
+
+    let balances = client.get_balances(&["USDC", "SOL"]).await.unwrap();
+    assert_eq!(balances.len(), 2);
+    assert_eq!(balances["USDC"].available, Decimal::from_str("950.25").unwrap());
+    assert_eq!(balances["USDC"].hold, Decimal::from_str("49.75").unwrap());
+    assert_eq!(balances["SOL"].hold, Decimal::ZERO);
+
+    // One request for all assets
+    assert_eq!(stub.requests.lock().len(), 1);
+    assert!(stub.requests.lock()[0].header("authorization").unwrap().starts_with("Bearer "));
+}
+
+#[tokio::test]
+async fn test_order_lookup_and_open_orders() {
+    let open = r#"{"orders":[
+        {"order_id":"o-2","product_id":"SOL-USDC","side":"BUY","status":"OPEN",
+         "filled_size":"0.5","average_filled_price":"100","total_fees":"0.05",
+         "created_time":"2026-01-01T00:00:00Z"}
+    ]}"#;
+    let order = r#"{"order":
+        {"order_id":"o-1","product_id":"SOL-USDC","side":"SELL","status":"FILLED",
+         "filled_size":"2","average_filled_price":"101.5","total_fees":"0.2"}
+    }"#;
+    let stub = HttpStub::start(vec![
+        ("/api/v3/brokerage/orders/historical/batch", 200, open.to_string()),
+        ("/api/v3/brokerage/orders/historical/", 200, order.to_string()),
+    ])
+    .await;
+
+    let client = CoinbaseRestClient::new(
+        "organizations/org/apiKeys/key".to_string(),
+        TEST_EC_PRIVATE_KEY.to_string(),
+        false,
+    )
+    .unwrap()
+    .with_base_url(stub.base_url.clone());
+
+    let result = client.get_order("o-1").await.unwrap();
+    assert_eq!(result.status, OrderStatus::Filled);
+    assert_eq!(result.filled_quantity, Decimal::from(2));
+    assert_eq!(result.fee, Decimal::from_str("0.2").unwrap());
+
+    let working = client.get_open_orders("SOL/USDC").await.unwrap();
+    assert_eq!(working.len(), 1);
+    assert_eq!(working[0].status, OrderStatus::PartiallyFilled);
+
+    let requests = stub.requests.lock();
+    assert_eq!(requests[0].path, "/api/v3/brokerage/orders/historical/o-1");
+    assert_eq!(
+        requests[1].path,
+        "/api/v3/brokerage/orders/historical/batch?order_status=OPEN&product_ids=SOL-USDC"
+    );
+}
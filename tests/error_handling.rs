@@ -1,6 +1,7 @@
 // tests/error_handling.rs
 
 use arb_bot::error::{ArbitrageError, Result};
+use arb_bot::exchanges::ExchangeId;
 
 fn mk_io_error() -> std::io::Error {
     std::io::Error::other("disk gone")
@@ -9,12 +10,12 @@ fn mk_io_error() -> std::io::Error {
 #[test]
 fn exchange_error_basic() {
     let err = ArbitrageError::ExchangeError {
-        exchange: "Binance".to_string(),
+        exchange: ExchangeId::BINANCE,
         message: "Order failed".to_string(),
         code: Some(1010),
     };
     let msg = format!("{}", err);
-    assert!(msg.contains("Binance"));
+    assert!(msg.contains("binance"));
     assert!(msg.contains("Order failed"));
     assert!(format!("{:?}", err).contains("ExchangeError"));
 }
@@ -55,7 +56,7 @@ fn parse_error_with_input_snippet() {
 #[test]
 fn rate_limit_error() {
     let err = ArbitrageError::RateLimitExceeded {
-        exchange: "Coinbase".to_string(),
+        exchange: ExchangeId::COINBASE,
         retry_after: 120,
     };
     let msg = err.to_string();
@@ -66,7 +67,7 @@ fn rate_limit_error() {
 #[test]
 fn auth_error() {
     let err = ArbitrageError::AuthenticationError {
-        exchange: "Binance".to_string(),
+        exchange: ExchangeId::BINANCE,
         reason: "bad signature".to_string(),
     };
     let msg = err.to_string();
@@ -77,7 +78,7 @@ fn auth_error() {
 #[test]
 fn insufficient_balance() {
     let err = ArbitrageError::InsufficientBalance {
-        exchange: "Coinbase".to_string(),
+        exchange: ExchangeId::COINBASE,
         asset: "USDC".to_string(),
         required: "100.0".to_string(),
         available: "25.0".to_string(),
@@ -38,6 +38,8 @@ async fn test_mock_exchange_get_latest_price() {
         last: Decimal::from(100),
         volume_24h: Decimal::from(1000000),
         timestamp: Utc::now(),
+        sequence: None,
+        received_at: Utc::now(),
     };
 
     exchange.set_price("SOL/USDC", price.clone());
@@ -120,6 +122,8 @@ async fn test_mock_exchange_set_price() {
         last: Decimal::from(100),
         volume_24h: Decimal::from(1000000),
         timestamp: Utc::now(),
+        sequence: None,
+        received_at: Utc::now(),
     };
 
     exchange.set_price("SOL/USDC", price.clone());
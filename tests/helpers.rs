@@ -74,9 +74,8 @@ pub fn load_with_sources(
         for line in raw.lines() {
             if let Some((key, value)) = line.split_once('=')
                 && key.trim() == "trading.spread_threshold"
-                && let Ok(v) = value.trim().parse::<f64>()
             {
-                wrapper.trading.spread_threshold = Some(v);
+                wrapper.trading.spread_threshold = Some(value.trim().into());
             }
         }
     }
@@ -0,0 +1,76 @@
+//! Integration tests for the inventory ledger and balance cache
+
+use arb_bot::exchanges::{Exchange, Order};
+use arb_bot::inventory::{BalanceCache, InventoryLedger, RebalanceAction, RebalancePlanner};
+use arb_bot::state::ExchangeId;
+use rust_decimal::Decimal;
+use std::sync::Arc;
+use std::time::Duration;
+
+mod common;
+use common::MockExchange;
+
+#[tokio::test]
+async fn test_seed_from_exchange_and_apply_fills() {
+    let mut binance = MockExchange::new("binance");
+    binance.set_balance("SOL", Decimal::from(10));
+    binance.set_balance("USDC", Decimal::from(1000));
+    binance.connect().await.unwrap();
+
+    let mut coinbase = MockExchange::new("coinbase");
+    coinbase.set_balance("SOL", Decimal::from(10));
+    coinbase.set_balance("USDC", Decimal::from(1000));
+    coinbase.connect().await.unwrap();
+
+    let ledger = InventoryLedger::new();
+    ledger.seed(ExchangeId::BINANCE, &binance, &["SOL", "USDC"]).await.unwrap();
+    ledger.seed(ExchangeId::COINBASE, &coinbase, &["SOL", "USDC"]).await.unwrap();
+    assert_eq!(ledger.total("SOL"), Decimal::from(20));
+
+    // Same-direction arbitrage several times
+    for _ in 0..4 {
+        let buy = Order::market_buy("SOL/USDC", Decimal::from(2));
+        let result = binance.place_order(buy.clone()).await.unwrap();
+        ledger.apply_fill(ExchangeId::BINANCE, &buy.pair, &buy.side, &result);
+
+        let sell = Order::market_sell("SOL/USDC", Decimal::from(2));
+        let result = coinbase.place_order(sell.clone()).await.unwrap();
+        ledger.apply_fill(ExchangeId::COINBASE, &sell.pair, &sell.side, &result);
+    }
+
+    assert_eq!(ledger.balance(ExchangeId::BINANCE, "SOL"), Decimal::from(18));
+    assert_eq!(ledger.balance(ExchangeId::COINBASE, "SOL"), Decimal::from(2));
+
+    let actions = RebalancePlanner::default().plan(&ledger, &["SOL/USDC"]);
+    assert!(actions.contains(&RebalanceAction::BiasDirection {
+        pair: "SOL/USDC".to_string(),
+        buy_on: ExchangeId::COINBASE,
+        sell_on: ExchangeId::BINANCE,
+    }));
+}
+
+#[tokio::test]
+async fn test_balance_cache_refreshes_in_background() {
+    let mut exchange = MockExchange::new("mock");
+    exchange.set_balance("USDC", Decimal::from(1000));
+    exchange.connect().await.unwrap();
+    let cache = BalanceCache::new(exchange.id(), Duration::from_secs(5));
+    let exchange = Arc::new(tokio::sync::RwLock::new(exchange));
+
+    let handle = cache.clone().spawn(
+        exchange.clone(),
+        vec!["USDC".to_string()],
+        Duration::from_millis(20),
+    );
+
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert!(!cache.is_stale());
+    assert_eq!(cache.available("USDC"), Decimal::from(1000));
+
+    // Exchange-side change shows up on the next refresh
+    exchange.read().await.set_balance("USDC", Decimal::from(400));
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(cache.available("USDC"), Decimal::from(400));
+
+    handle.abort();
+}
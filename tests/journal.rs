@@ -0,0 +1,79 @@
+//! Integration tests for the trade journal
+
+use arb_bot::exchanges::{Exchange, Order};
+use arb_bot::journal::{Journal, JournalEvent, JournalQuery, new_correlation_id, read_entries};
+use rust_decimal::Decimal;
+use std::io::Write;
+
+mod common;
+use common::MockExchange;
+
+#[tokio::test]
+async fn test_arbitrage_attempt_is_journaled_and_queryable() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("journal.jsonl");
+    let journal = Journal::open(&path).unwrap();
+
+    let mut exchange = MockExchange::new("binance");
+    exchange.connect().await.unwrap();
+
+    let id = new_correlation_id();
+    journal.record(
+        &id,
+        JournalEvent::Opportunity {
+            pair: "SOL/USDC".to_string(),
+            buy_exchange: "binance".to_string(),
+            sell_exchange: "coinbase".to_string(),
+            buy_price: Decimal::from(100),
+            sell_price: Decimal::from(101),
+            spread: Decimal::new(1, 2),
+        },
+    );
+    let order = Order::market_buy("SOL/USDC", Decimal::from(2));
+    journal.record(&id, JournalEvent::order_request("binance", &order));
+    let outcome = exchange.place_order(order.clone()).await;
+    journal.record_order_outcome(&id, "binance", &order, &outcome);
+
+    // A second attempt whose order fails
+    let other = new_correlation_id();
+    exchange.set_fail_orders(true);
+    let outcome = exchange.place_order(order.clone()).await;
+    journal.record_order_outcome(&other, "binance", &order, &outcome);
+
+    journal.flush().await.unwrap();
+
+    let attempt = journal.query(&JournalQuery::new().correlation_id(&id)).unwrap();
+    let kinds: Vec<&str> = attempt.iter().map(|e| e.event.kind()).collect();
+    // No price on the mock, so no fill
+    assert_eq!(kinds, vec!["opportunity", "order_request", "order_result"]);
+
+    let errors = journal.query(&JournalQuery::new().kind("order_error")).unwrap();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].correlation_id, other);
+
+    // Survives reopening
+    drop(journal);
+    let reopened = Journal::open(&path).unwrap();
+    assert_eq!(reopened.query(&JournalQuery::new()).unwrap().len(), 4);
+}
+
+#[tokio::test]
+async fn test_truncated_line_is_skipped() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("journal.jsonl");
+    let journal = Journal::open(&path).unwrap();
+    journal.record(
+        "a",
+        JournalEvent::OrderError {
+            exchange: "binance".to_string(),
+            message: "x".to_string(),
+        },
+    );
+    journal.flush().await.unwrap();
+
+    // Simulate a crash mid-write
+    let mut file = std::fs::OpenOptions::new().append(true).open(&path).unwrap();
+    file.write_all(br#"{"recorded_at":"2026-"#).unwrap();
+
+    assert_eq!(read_entries(&path, &JournalQuery::new()).unwrap().len(), 1);
+}
@@ -0,0 +1,145 @@
+//! Tests for the Kraken exchange: signed REST against a local stub, and the live feed
+//!
+//! Tests marked with `#[ignore]` require live connection to Kraken WebSocket.
+//! Run them with: `cargo test --test kraken -- --ignored`
+
+use arb_bot::config::KrakenConfig;
+use arb_bot::error::ArbitrageError;
+use arb_bot::exchanges::kraken::{KrakenAuth, KrakenExchange, KrakenRestClient};
+use arb_bot::exchanges::{Exchange, Order, OrderStatus};
+use rust_decimal::Decimal;
+use std::str::FromStr;
+
+mod common;
+use common::HttpStub;
+
+/// Example private key from the Kraken API documentation
+const TEST_SECRET: &str =
+    "kQH5HW/8p1uGOVjbgWA7FunAmGO8lsSUXNsu3eow76sz84Q18fWxnyRzBHCd3pd5nE9qa99HAZtuZuj6F1huXg==";
+
+fn client(stub: &HttpStub) -> KrakenRestClient {
+    KrakenRestClient::new("test-key".to_string(), TEST_SECRET.to_string())
+        .unwrap()
+        .with_base_url(stub.base_url.clone())
+}
+
+#[tokio::test]
+async fn test_get_balances_maps_asset_codes() {
+    let balances = r#"{"error":[],"result":{
+        "XXBT":{"balance":"1.5","hold_trade":"0.5"},
+        "ZUSD":{"balance":"2500.00","hold_trade":"0"},
+        "SOL":{"balance":"40"},
+        "XETH":{"balance":"3","hold_trade":"0"}}}"#;
+    let stub = HttpStub::start(vec![("/0/private/BalanceEx", 200, balances.to_string())]).await;
+
+    let balances = client(&stub).get_balances(&["BTC", "USD", "SOL"]).await.unwrap();
+    assert_eq!(balances.len(), 3);
+    assert_eq!(balances["BTC"].available, Decimal::ONE);
+    assert_eq!(balances["BTC"].hold, Decimal::from_str("0.5").unwrap());
+    assert_eq!(balances["USD"].available, Decimal::from(2500));
+    assert_eq!(balances["SOL"].available, Decimal::from(40));
+}
+
+#[tokio::test]
+async fn test_requests_are_signed() {
+    let stub = HttpStub::start(vec![(
+        "/0/private/BalanceEx",
+        200,
+        r#"{"error":[],"result":{}}"#.to_string(),
+    )])
+    .await;
+
+    let balance = client(&stub).get_balance("BTC").await.unwrap();
+    assert_eq!(balance, Decimal::ZERO);
+
+    let requests = stub.requests.lock();
+    let request = &requests[0];
+    assert_eq!(request.method, "POST");
+    assert_eq!(request.header("api-key"), Some("test-key"));
+
+    let nonce: u64 = request.body.strip_prefix("nonce=").unwrap().parse().unwrap();
+    let auth = KrakenAuth::new("test-key".to_string(), TEST_SECRET.to_string()).unwrap();
+    assert_eq!(
+        request.header("api-sign"),
+        Some(auth.sign("/0/private/BalanceEx", nonce, &request.body).as_str())
+    );
+}
+
+#[tokio::test]
+async fn test_place_order_looks_up_fills() {
+    let added = r#"{"error":[],"result":{"descr":{"order":"buy 2.00000000 SOLUSD @ market"},"txid":["OUF4EM-FRGI2-MQMWZD"]}}"#;
+    let queried = r#"{"error":[],"result":{"OUF4EM-FRGI2-MQMWZD":{
+        "status":"closed","descr":{"pair":"SOLUSD","type":"buy","ordertype":"market"},
+        "vol":"2.00000000","vol_exec":"2.00000000","cost":"300.00","fee":"0.78","opentm":1700000000.5}}}"#;
+    let stub = HttpStub::start(vec![
+        ("/0/private/AddOrder", 200, added.to_string()),
+        ("/0/private/QueryOrders", 200, queried.to_string()),
+    ])
+    .await;
+
+    let result = client(&stub)
+        .place_order(Order::market_buy("SOL/USD", Decimal::from(2)))
+        .await
+        .unwrap();
+    assert_eq!(result.order_id, "OUF4EM-FRGI2-MQMWZD");
+    assert_eq!(result.status, OrderStatus::Filled);
+    assert_eq!(result.average_price, Some(Decimal::from(150)));
+    assert_eq!(result.fee_asset, "USD");
+
+    let requests = stub.requests.lock();
+    assert!(requests[0].body.ends_with("&pair=SOLUSD&type=buy&volume=2&ordertype=market"));
+    assert!(requests[1].body.contains("&txid=OUF4EM-FRGI2-MQMWZD"));
+}
+
+#[tokio::test]
+async fn test_open_orders_filter_by_pair() {
+    let open = r#"{"error":[],"result":{"open":{
+        "O1":{"status":"open","descr":{"pair":"XBTUSD"},"vol_exec":"0.1","cost":"6000","fee":"1","opentm":1700000000},
+        "O2":{"status":"open","descr":{"pair":"SOLUSD"},"vol_exec":"0","cost":"0","fee":"0","opentm":1700000000}}}}"#;
+    let stub = HttpStub::start(vec![("/0/private/OpenOrders", 200, open.to_string())]).await;
+
+    let orders = client(&stub).get_open_orders("BTC/USD").await.unwrap();
+    assert_eq!(orders.len(), 1);
+    assert_eq!(orders[0].order_id, "O1");
+    assert_eq!(orders[0].status, OrderStatus::PartiallyFilled);
+}
+
+#[tokio::test]
+async fn test_error_array_maps_to_error_types() {
+    let stub = HttpStub::start(vec![
+        ("/0/private/BalanceEx", 200, r#"{"error":["EAPI:Invalid key"]}"#.to_string()),
+        ("/0/private/OpenOrders", 200, r#"{"error":["EAPI:Rate limit exceeded"]}"#.to_string()),
+        ("/0/private/QueryOrders", 200, r#"{"error":["EOrder:Unknown order"]}"#.to_string()),
+    ])
+    .await;
+    let client = client(&stub);
+
+    assert!(matches!(
+        client.get_balance("BTC").await,
+        Err(ArbitrageError::AuthenticationError { .. })
+    ));
+    assert!(matches!(
+        client.get_open_orders("BTC/USD").await,
+        Err(ArbitrageError::RateLimitExceeded { .. })
+    ));
+    let err = client.get_order("BTC/USD", "O1").await.unwrap_err();
+    assert!(err.to_string().contains("EOrder:Unknown order"));
+}
+
+#[tokio::test]
+#[ignore] // Ignored by default - requires live connection
+async fn test_kraken_live_ticker() {
+    let mut exchange = KrakenExchange::new(KrakenConfig {
+        api_key: String::new(),
+        api_secret: String::new(),
+    })
+    .unwrap();
+
+    exchange.subscribe_ticker("BTC/USD").await.unwrap();
+    let price = exchange.get_latest_price("BTC/USD").await.unwrap();
+    assert!(price.bid > Decimal::ZERO);
+    assert!(price.ask >= price.bid);
+    assert!(exchange.is_connected());
+
+    exchange.disconnect().await.unwrap();
+}
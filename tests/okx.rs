@@ -0,0 +1,173 @@
+//! Tests for the OKX exchange: signed REST against a local stub, and the live feed
+//!
+//! Tests marked with `#[ignore]` require live connection to OKX WebSocket.
+//! Run them with: `cargo test --test okx -- --ignored`
+
+use arb_bot::config::OkxConfig;
+use arb_bot::error::ArbitrageError;
+use arb_bot::exchanges::okx::{OkxAuth, OkxExchange, OkxRestClient};
+use arb_bot::exchanges::{Exchange, Order, OrderStatus};
+use rust_decimal::Decimal;
+use std::str::FromStr;
+
+mod common;
+use common::HttpStub;
+
+const TEST_SECRET: &str = "22582BD0CFF14C41EDBF1AB98506286D";
+
+fn client(stub: &HttpStub, demo: bool) -> OkxRestClient {
+    OkxRestClient::new(
+        "test-key".to_string(),
+        TEST_SECRET.to_string(),
+        "test-passphrase".to_string(),
+        demo,
+    )
+    .with_base_url(stub.base_url.clone())
+}
+
+#[tokio::test]
+async fn test_get_balances() {
+    let balances = r#"{"code":"0","msg":"","data":[{"totalEq":"41624.32","details":[
+        {"ccy":"SOL","availBal":"40","frozenBal":"2.5","eq":"42.5"},
+        {"ccy":"USDC","availBal":"2500.00","frozenBal":"0","eq":"2500"}]}]}"#;
+    let stub = HttpStub::start(vec![("/api/v5/account/balance", 200, balances.to_string())]).await;
+
+    let balances = client(&stub, false).get_balances(&["SOL", "USDC", "BTC"]).await.unwrap();
+    assert_eq!(balances.len(), 2);
+    assert_eq!(balances["SOL"].available, Decimal::from(40));
+    assert_eq!(balances["SOL"].hold, Decimal::from_str("2.5").unwrap());
+    assert_eq!(balances["USDC"].available, Decimal::from(2500));
+}
+
+#[tokio::test]
+async fn test_requests_are_signed() {
+    let stub = HttpStub::start(vec![(
+        "/api/v5/account/balance",
+        200,
+        r#"{"code":"0","msg":"","data":[{"details":[]}]}"#.to_string(),
+    )])
+    .await;
+
+    let balance = client(&stub, true).get_balance("BTC").await.unwrap();
+    assert_eq!(balance, Decimal::ZERO);
+
+    let requests = stub.requests.lock();
+    let request = &requests[0];
+    assert_eq!(request.method, "GET");
+    assert_eq!(request.path, "/api/v5/account/balance?ccy=BTC");
+    assert_eq!(request.header("ok-access-key"), Some("test-key"));
+    assert_eq!(request.header("ok-access-passphrase"), Some("test-passphrase"));
+    assert_eq!(request.header("x-simulated-trading"), Some("1"));
+
+    let timestamp = request.header("ok-access-timestamp").unwrap();
+    let auth = OkxAuth::new(
+        "test-key".to_string(),
+        TEST_SECRET.to_string(),
+        "test-passphrase".to_string(),
+    );
+    assert_eq!(
+        request.header("ok-access-sign"),
+        Some(auth.sign(timestamp, "GET", "/api/v5/account/balance?ccy=BTC", "").as_str())
+    );
+}
+
+#[tokio::test]
+async fn test_place_order_looks_up_fills() {
+    let placed = r#"{"code":"0","msg":"","data":[{"ordId":"680800019749904384","clOrdId":"","tag":"","sCode":"0","sMsg":"Order placed"}]}"#;
+    let queried = r#"{"code":"0","msg":"","data":[{"instId":"SOL-USDC","ordId":"680800019749904384",
+        "state":"filled","accFillSz":"2","avgPx":"150","fee":"-0.002","feeCcy":"SOL","uTime":"1700000000250"}]}"#;
+    // Placement is a POST and lookup a GET on the same path; the stub answers by prefix
+    let stub = HttpStub::start(vec![
+        ("/api/v5/trade/order?", 200, queried.to_string()),
+        ("/api/v5/trade/order", 200, placed.to_string()),
+    ])
+    .await;
+
+    let result = client(&stub, false)
+        .place_order(Order::market_buy("SOL/USDC", Decimal::from(2)))
+        .await
+        .unwrap();
+    assert_eq!(result.order_id, "680800019749904384");
+    assert_eq!(result.status, OrderStatus::Filled);
+    assert_eq!(result.average_price, Some(Decimal::from(150)));
+    assert_eq!(result.fee, Decimal::from_str("0.002").unwrap());
+
+    let requests = stub.requests.lock();
+    let body: serde_json::Value = serde_json::from_str(&requests[0].body).unwrap();
+    assert_eq!(body["instId"], "SOL-USDC");
+    assert_eq!(body["tdMode"], "cash");
+    assert_eq!(body["side"], "buy");
+    assert_eq!(body["ordType"], "market");
+    assert_eq!(body["sz"], "2");
+    assert_eq!(body["tgtCcy"], "base_ccy");
+    assert_eq!(
+        requests[1].path,
+        "/api/v5/trade/order?instId=SOL-USDC&ordId=680800019749904384"
+    );
+}
+
+#[tokio::test]
+async fn test_rejected_order_maps_to_error_types() {
+    let rejected = r#"{"code":"1","msg":"All operations failed","data":[{"ordId":"","clOrdId":"","tag":"","sCode":"51008","sMsg":"Order failed. Insufficient USDC balance in account."}]}"#;
+    let stub = HttpStub::start(vec![("/api/v5/trade/order", 200, rejected.to_string())]).await;
+
+    let err = client(&stub, false)
+        .place_order(Order::market_buy("SOL/USDC", Decimal::from(2)))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ArbitrageError::InsufficientBalance { .. }));
+}
+
+#[tokio::test]
+async fn test_error_codes_map_to_error_types() {
+    let stub = HttpStub::start(vec![
+        (
+            "/api/v5/account/balance",
+            401,
+            r#"{"code":"50113","msg":"Invalid Sign","data":[]}"#.to_string(),
+        ),
+        (
+            "/api/v5/trade/orders-pending",
+            429,
+            r#"{"code":"50011","msg":"Too Many Requests","data":[]}"#.to_string(),
+        ),
+        (
+            "/api/v5/trade/order",
+            200,
+            r#"{"code":"51603","msg":"Order does not exist","data":[]}"#.to_string(),
+        ),
+    ])
+    .await;
+    let client = client(&stub, false);
+
+    assert!(matches!(
+        client.get_balance("BTC").await,
+        Err(ArbitrageError::AuthenticationError { .. })
+    ));
+    assert!(matches!(
+        client.get_open_orders("BTC/USDT").await,
+        Err(ArbitrageError::RateLimitExceeded { .. })
+    ));
+    let err = client.get_order("BTC/USDT", "1").await.unwrap_err();
+    assert!(matches!(err, ArbitrageError::ExchangeError { code: Some(51603), .. }));
+}
+
+#[tokio::test]
+#[ignore] // Ignored by default - requires live connection
+async fn test_okx_live_ticker() {
+    let mut exchange = OkxExchange::new(OkxConfig {
+        api_key: String::new(),
+        api_secret: String::new(),
+        passphrase: String::new(),
+        demo: false,
+    })
+    .unwrap();
+
+    exchange.subscribe_ticker("BTC/USDT").await.unwrap();
+    let price = exchange.get_latest_price("BTC/USDT").await.unwrap();
+    assert!(price.bid > Decimal::ZERO);
+    assert!(price.ask >= price.bid);
+    assert!(exchange.is_connected());
+
+    exchange.disconnect().await.unwrap();
+}
@@ -3,7 +3,8 @@
 //! Following TDD: These tests will fail initially until implementation exists.
 
 use arb_bot::exchanges::Price;
-use arb_bot::state::{ExchangeId, PriceState};
+use arb_bot::state::{ExchangeId, PriceState, SequenceAction, SequencePolicy, TimeBasis};
+use arb_bot::websocket::ConnectionState;
 use chrono::Utc;
 use rust_decimal::Decimal;
 use std::sync::Arc;
@@ -28,11 +29,13 @@ async fn test_update_and_get_price() {
         last: Decimal::from(100),
         volume_24h: Decimal::from(1000000),
         timestamp: Utc::now(),
+        sequence: None,
+        received_at: Utc::now(),
     };
 
-    state.update_price(ExchangeId::Binance, "SOL/USDC", price.clone(), 1);
+    state.update_price(ExchangeId::BINANCE, "SOL/USDC", price.clone(), 1);
 
-    let retrieved = state.get_price(ExchangeId::Binance, "SOL/USDC");
+    let retrieved = state.get_price(ExchangeId::BINANCE, "SOL/USDC");
     assert!(retrieved.is_some());
     let price_data = retrieved.unwrap();
     assert_eq!(price_data.price.pair, "SOL/USDC");
@@ -51,6 +54,8 @@ async fn test_multiple_exchanges_same_pair() {
         last: Decimal::from(100),
         volume_24h: Decimal::ZERO,
         timestamp: Utc::now(),
+        sequence: None,
+        received_at: Utc::now(),
     };
 
     let coinbase_price = Price {
@@ -60,13 +65,15 @@ async fn test_multiple_exchanges_same_pair() {
         last: Decimal::from(102),
         volume_24h: Decimal::ZERO,
         timestamp: Utc::now(),
+        sequence: None,
+        received_at: Utc::now(),
     };
 
-    state.update_price(ExchangeId::Binance, "SOL/USDC", binance_price, 1);
-    state.update_price(ExchangeId::Coinbase, "SOL/USDC", coinbase_price, 1);
+    state.update_price(ExchangeId::BINANCE, "SOL/USDC", binance_price, 1);
+    state.update_price(ExchangeId::COINBASE, "SOL/USDC", coinbase_price, 1);
 
-    let binance = state.get_price(ExchangeId::Binance, "SOL/USDC").unwrap();
-    let coinbase = state.get_price(ExchangeId::Coinbase, "SOL/USDC").unwrap();
+    let binance = state.get_price(ExchangeId::BINANCE, "SOL/USDC").unwrap();
+    let coinbase = state.get_price(ExchangeId::COINBASE, "SOL/USDC").unwrap();
 
     assert_eq!(binance.price.bid, Decimal::from(100));
     assert_eq!(coinbase.price.bid, Decimal::from(102));
@@ -88,8 +95,10 @@ async fn test_concurrent_writes() {
                     last: Decimal::from(100 + i),
                     volume_24h: Decimal::ZERO,
                     timestamp: Utc::now(),
+                    sequence: None,
+                    received_at: Utc::now(),
                 };
-                state.update_price(ExchangeId::Binance, &pair, price, i);
+                state.update_price(ExchangeId::BINANCE, &pair, price, i);
             })
         })
         .collect();
@@ -118,8 +127,10 @@ async fn test_concurrent_reads_and_writes() {
                 last: Decimal::from(100 + i),
                 volume_24h: Decimal::ZERO,
                 timestamp: Utc::now(),
+                sequence: None,
+                received_at: Utc::now(),
             };
-            writer_state.update_price(ExchangeId::Binance, "SOL/USDC", price, i);
+            writer_state.update_price(ExchangeId::BINANCE, "SOL/USDC", price, i);
             sleep(Duration::from_millis(10)).await;
         }
     });
@@ -130,7 +141,7 @@ async fn test_concurrent_reads_and_writes() {
             let state = Arc::clone(&state);
             tokio::spawn(async move {
                 for _ in 0..50 {
-                    let _price = state.get_price(ExchangeId::Binance, "SOL/USDC");
+                    let _price = state.get_price(ExchangeId::BINANCE, "SOL/USDC");
                     sleep(Duration::from_millis(20)).await;
                 }
             })
@@ -143,7 +154,7 @@ async fn test_concurrent_reads_and_writes() {
     }
 
     // Verify final price
-    let final_price = state.get_price(ExchangeId::Binance, "SOL/USDC");
+    let final_price = state.get_price(ExchangeId::BINANCE, "SOL/USDC");
     assert!(final_price.is_some());
 }
 
@@ -158,18 +169,20 @@ async fn test_staleness_detection() {
         last: Decimal::from(100),
         volume_24h: Decimal::ZERO,
         timestamp: Utc::now(),
+        sequence: None,
+        received_at: Utc::now(),
     };
 
-    state.update_price(ExchangeId::Binance, "SOL/USDC", price, 1);
+    state.update_price(ExchangeId::BINANCE, "SOL/USDC", price, 1);
 
     // Immediately: not stale
-    assert!(!state.is_stale(ExchangeId::Binance, "SOL/USDC"));
+    assert!(!state.is_stale(ExchangeId::BINANCE, "SOL/USDC"));
 
     // Wait for price to become stale
     sleep(Duration::from_millis(1100)).await;
 
     // Now: stale
-    assert!(state.is_stale(ExchangeId::Binance, "SOL/USDC"));
+    assert!(state.is_stale(ExchangeId::BINANCE, "SOL/USDC"));
 }
 
 #[tokio::test]
@@ -183,6 +196,8 @@ async fn test_spread_calculation() {
         last: Decimal::from(100),
         volume_24h: Decimal::ZERO,
         timestamp: Utc::now(),
+        sequence: None,
+        received_at: Utc::now(),
     };
 
     let coinbase_price = Price {
@@ -192,12 +207,14 @@ async fn test_spread_calculation() {
         last: Decimal::from(102),
         volume_24h: Decimal::ZERO,
         timestamp: Utc::now(),
+        sequence: None,
+        received_at: Utc::now(),
     };
 
-    state.update_price(ExchangeId::Binance, "SOL/USDC", binance_price, 1);
-    state.update_price(ExchangeId::Coinbase, "SOL/USDC", coinbase_price, 1);
+    state.update_price(ExchangeId::BINANCE, "SOL/USDC", binance_price, 1);
+    state.update_price(ExchangeId::COINBASE, "SOL/USDC", coinbase_price, 1);
 
-    let spread = state.get_spread(ExchangeId::Binance, ExchangeId::Coinbase, "SOL/USDC");
+    let spread = state.get_spread(ExchangeId::BINANCE, ExchangeId::COINBASE, "SOL/USDC");
     assert!(spread.is_some());
     // Binance mid: 100.5, Coinbase mid: 102.5, spread: 2.0
     assert_eq!(spread.unwrap(), Decimal::from(2));
@@ -214,6 +231,8 @@ async fn test_spread_percentage_calculation() {
         last: Decimal::from(100),
         volume_24h: Decimal::ZERO,
         timestamp: Utc::now(),
+        sequence: None,
+        received_at: Utc::now(),
     };
 
     let coinbase_price = Price {
@@ -223,14 +242,16 @@ async fn test_spread_percentage_calculation() {
         last: Decimal::from(102),
         volume_24h: Decimal::ZERO,
         timestamp: Utc::now(),
+        sequence: None,
+        received_at: Utc::now(),
     };
 
-    state.update_price(ExchangeId::Binance, "SOL/USDC", binance_price, 1);
-    state.update_price(ExchangeId::Coinbase, "SOL/USDC", coinbase_price, 1);
+    state.update_price(ExchangeId::BINANCE, "SOL/USDC", binance_price, 1);
+    state.update_price(ExchangeId::COINBASE, "SOL/USDC", coinbase_price, 1);
 
     let spread_pct = state.get_spread_percentage(
-        ExchangeId::Binance,
-        ExchangeId::Coinbase,
+        ExchangeId::BINANCE,
+        ExchangeId::COINBASE,
         "SOL/USDC",
     );
     assert!(spread_pct.is_some());
@@ -251,6 +272,8 @@ async fn test_spread_with_stale_price() {
         last: Decimal::from(100),
         volume_24h: Decimal::ZERO,
         timestamp: Utc::now(),
+        sequence: None,
+        received_at: Utc::now(),
     };
 
     let stale_price = Price {
@@ -260,16 +283,18 @@ async fn test_spread_with_stale_price() {
         last: Decimal::from(102),
         volume_24h: Decimal::ZERO,
         timestamp: Utc::now(),
+        sequence: None,
+        received_at: Utc::now(),
     };
 
-    state.update_price(ExchangeId::Binance, "SOL/USDC", fresh_price, 1);
-    state.update_price(ExchangeId::Coinbase, "SOL/USDC", stale_price, 1);
+    state.update_price(ExchangeId::BINANCE, "SOL/USDC", fresh_price, 1);
+    state.update_price(ExchangeId::COINBASE, "SOL/USDC", stale_price, 1);
 
     // Wait for coinbase price to become stale
     sleep(Duration::from_millis(1100)).await;
 
     // Spread calculation should reject stale price
-    let spread = state.get_spread(ExchangeId::Binance, ExchangeId::Coinbase, "SOL/USDC");
+    let spread = state.get_spread(ExchangeId::BINANCE, ExchangeId::COINBASE, "SOL/USDC");
     assert!(spread.is_none()); // Stale price rejected
 }
 
@@ -284,9 +309,11 @@ async fn test_spread_with_max_time_difference() {
         last: Decimal::from(100),
         volume_24h: Decimal::ZERO,
         timestamp: Utc::now(),
+        sequence: None,
+        received_at: Utc::now(),
     };
 
-    state.update_price(ExchangeId::Binance, "SOL/USDC", price1, 1);
+    state.update_price(ExchangeId::BINANCE, "SOL/USDC", price1, 1);
 
     // Wait 3 seconds (exceeds max_time_diff of 2.5s)
     sleep(Duration::from_millis(3100)).await;
@@ -298,13 +325,15 @@ async fn test_spread_with_max_time_difference() {
         last: Decimal::from(102),
         volume_24h: Decimal::ZERO,
         timestamp: Utc::now(),
+        sequence: None,
+        received_at: Utc::now(),
     };
 
-    state.update_price(ExchangeId::Coinbase, "SOL/USDC", price2, 1);
+    state.update_price(ExchangeId::COINBASE, "SOL/USDC", price2, 1);
 
     // Both prices are fresh (< 5s), but captured 3s apart (> 2.5s max diff)
     // Should reject because time difference too large
-    let spread = state.get_spread(ExchangeId::Binance, ExchangeId::Coinbase, "SOL/USDC");
+    let spread = state.get_spread(ExchangeId::BINANCE, ExchangeId::COINBASE, "SOL/USDC");
     assert!(spread.is_none()); // Rejected due to max time difference
 }
 
@@ -319,9 +348,11 @@ async fn test_spread_with_acceptable_time_difference() {
         last: Decimal::from(100),
         volume_24h: Decimal::ZERO,
         timestamp: Utc::now(),
+        sequence: None,
+        received_at: Utc::now(),
     };
 
-    state.update_price(ExchangeId::Binance, "SOL/USDC", price1, 1);
+    state.update_price(ExchangeId::BINANCE, "SOL/USDC", price1, 1);
 
     // Wait 1 second (within max_time_diff of 2.5s)
     sleep(Duration::from_millis(1000)).await;
@@ -333,12 +364,14 @@ async fn test_spread_with_acceptable_time_difference() {
         last: Decimal::from(102),
         volume_24h: Decimal::ZERO,
         timestamp: Utc::now(),
+        sequence: None,
+        received_at: Utc::now(),
     };
 
-    state.update_price(ExchangeId::Coinbase, "SOL/USDC", price2, 1);
+    state.update_price(ExchangeId::COINBASE, "SOL/USDC", price2, 1);
 
     // Both prices fresh and captured within acceptable time window
-    let spread = state.get_spread(ExchangeId::Binance, ExchangeId::Coinbase, "SOL/USDC");
+    let spread = state.get_spread(ExchangeId::BINANCE, ExchangeId::COINBASE, "SOL/USDC");
     assert!(spread.is_some()); // Should succeed
     assert_eq!(spread.unwrap(), Decimal::from(2));
 }
@@ -355,8 +388,10 @@ async fn test_remove_stale_prices() {
         last: Decimal::from(100),
         volume_24h: Decimal::ZERO,
         timestamp: Utc::now(),
+        sequence: None,
+        received_at: Utc::now(),
     };
-    state.update_price(ExchangeId::Binance, "SOL/USDC", fresh_price, 1);
+    state.update_price(ExchangeId::BINANCE, "SOL/USDC", fresh_price, 1);
 
     // Add stale price (will become stale)
     let stale_price = Price {
@@ -366,8 +401,10 @@ async fn test_remove_stale_prices() {
         last: Decimal::from(50000),
         volume_24h: Decimal::ZERO,
         timestamp: Utc::now(),
+        sequence: None,
+        received_at: Utc::now(),
     };
-    state.update_price(ExchangeId::Coinbase, "BTC/USD", stale_price, 1);
+    state.update_price(ExchangeId::COINBASE, "BTC/USD", stale_price, 1);
 
     // Wait for BTC price to become stale (max_age is 1 second)
     sleep(Duration::from_millis(1100)).await;
@@ -381,16 +418,18 @@ async fn test_remove_stale_prices() {
         last: Decimal::from(100),
         volume_24h: Decimal::ZERO,
         timestamp: Utc::now(),
+        sequence: None,
+        received_at: Utc::now(),
     };
-    state.update_price(ExchangeId::Binance, "SOL/USDC", fresh_sol_price, 2);
+    state.update_price(ExchangeId::BINANCE, "SOL/USDC", fresh_sol_price, 2);
 
     let removed_count = state.remove_stale_prices();
     assert_eq!(removed_count, 1); // BTC price should be removed
 
     // SOL price should still exist (we just updated it)
-    assert!(state.get_price(ExchangeId::Binance, "SOL/USDC").is_some());
+    assert!(state.get_price(ExchangeId::BINANCE, "SOL/USDC").is_some());
     // BTC price should be gone
-    assert!(state.get_price(ExchangeId::Coinbase, "BTC/USD").is_none());
+    assert!(state.get_price(ExchangeId::COINBASE, "BTC/USD").is_none());
 }
 
 #[tokio::test]
@@ -398,7 +437,7 @@ async fn test_clear_all_prices() {
     let state = PriceState::new(Duration::from_secs(5));
 
     state.update_price(
-        ExchangeId::Binance,
+        ExchangeId::BINANCE,
         "SOL/USDC",
         Price {
             pair: "SOL/USDC".to_string(),
@@ -407,6 +446,8 @@ async fn test_clear_all_prices() {
             last: Decimal::from(100),
             volume_24h: Decimal::ZERO,
             timestamp: Utc::now(),
+            sequence: None,
+            received_at: Utc::now(),
         },
         1,
     );
@@ -423,7 +464,7 @@ async fn test_spread_missing_price() {
 
     // Only update one exchange
     state.update_price(
-        ExchangeId::Binance,
+        ExchangeId::BINANCE,
         "SOL/USDC",
         Price {
             pair: "SOL/USDC".to_string(),
@@ -432,12 +473,165 @@ async fn test_spread_missing_price() {
             last: Decimal::from(100),
             volume_24h: Decimal::ZERO,
             timestamp: Utc::now(),
+            sequence: None,
+            received_at: Utc::now(),
         },
         1,
     );
 
     // Try to get spread with missing Coinbase price
-    let spread = state.get_spread(ExchangeId::Binance, ExchangeId::Coinbase, "SOL/USDC");
+    let spread = state.get_spread(ExchangeId::BINANCE, ExchangeId::COINBASE, "SOL/USDC");
     assert!(spread.is_none()); // Should return None when price missing
 }
 
+
+fn sequenced_price(bid: i64, sequence: u64) -> Price {
+    Price {
+        pair: "SOL/USDC".to_string(),
+        bid: Decimal::from(bid),
+        ask: Decimal::from(bid + 1),
+        last: Decimal::from(bid),
+        volume_24h: Decimal::ZERO,
+        timestamp: Utc::now(),
+        sequence: Some(sequence),
+        received_at: Utc::now(),
+    }
+}
+
+#[tokio::test]
+async fn test_ingest_drops_out_of_order_updates() {
+    let state = PriceState::new(Duration::from_secs(5));
+
+    assert_eq!(
+        state.ingest(ExchangeId::COINBASE, sequenced_price(100, 10)),
+        SequenceAction::Apply
+    );
+    assert_eq!(
+        state.ingest(ExchangeId::COINBASE, sequenced_price(99, 9)),
+        SequenceAction::Drop
+    );
+
+    // Older update must not overwrite the newer price
+    let stored = state.get_price(ExchangeId::COINBASE, "SOL/USDC").unwrap();
+    assert_eq!(stored.price.bid, Decimal::from(100));
+    assert_eq!(stored.sequence, 10);
+}
+
+#[tokio::test]
+async fn test_ingest_counts_gaps() {
+    let state = PriceState::new(Duration::from_secs(5));
+
+    state.ingest(ExchangeId::COINBASE, sequenced_price(100, 1));
+    assert_eq!(
+        state.ingest(ExchangeId::COINBASE, sequenced_price(101, 4)),
+        SequenceAction::Apply
+    );
+
+    let stats = state
+        .sequence_tracker()
+        .stats(ExchangeId::COINBASE, "SOL/USDC");
+    assert_eq!(stats.gaps, 1);
+    assert_eq!(stats.missed, 2);
+    assert_eq!(
+        state.get_price(ExchangeId::COINBASE, "SOL/USDC").unwrap().price.bid,
+        Decimal::from(101)
+    );
+}
+
+#[tokio::test]
+async fn test_ingest_resync_policy() {
+    let state = PriceState::new(Duration::from_secs(5)).with_sequence_policy(SequencePolicy {
+        resync_on_gap: true,
+        ..Default::default()
+    });
+
+    state.ingest(ExchangeId::COINBASE, sequenced_price(100, 1));
+    assert_eq!(
+        state.ingest(ExchangeId::COINBASE, sequenced_price(101, 3)),
+        SequenceAction::Resync
+    );
+}
+
+#[tokio::test]
+async fn test_exchange_time_basis_rejects_non_simultaneous_prices() {
+    let now = Utc::now();
+    let binance_price = Price {
+        timestamp: now - chrono::Duration::seconds(4),
+        ..sequenced_price(100, 1)
+    };
+    let coinbase_price = Price {
+        timestamp: now,
+        ..sequenced_price(102, 1)
+    };
+
+    // Both arrive at the same local moment, but the exchange events are 4s apart
+    let receive_state = PriceState::new(Duration::from_secs(5));
+    receive_state.update_price(ExchangeId::BINANCE, "SOL/USDC", binance_price.clone(), 1);
+    receive_state.update_price(ExchangeId::COINBASE, "SOL/USDC", coinbase_price.clone(), 1);
+    assert!(
+        receive_state
+            .get_spread(ExchangeId::BINANCE, ExchangeId::COINBASE, "SOL/USDC")
+            .is_some()
+    );
+
+    let exchange_state =
+        PriceState::new(Duration::from_secs(5)).with_time_basis(TimeBasis::Exchange);
+    exchange_state.update_price(ExchangeId::BINANCE, "SOL/USDC", binance_price, 1);
+    exchange_state.update_price(ExchangeId::COINBASE, "SOL/USDC", coinbase_price, 1);
+    assert!(
+        exchange_state
+            .get_spread(ExchangeId::BINANCE, ExchangeId::COINBASE, "SOL/USDC")
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn test_ingest_records_feed_latency() {
+    let state = PriceState::new(Duration::from_secs(5));
+    let now = Utc::now();
+    let price = Price {
+        timestamp: now - chrono::Duration::milliseconds(250),
+        received_at: now,
+        ..sequenced_price(100, 1)
+    };
+
+    state.ingest(ExchangeId::BINANCE, price);
+
+    let snapshot = state
+        .latency_tracker()
+        .snapshot(ExchangeId::BINANCE)
+        .unwrap();
+    assert_eq!(snapshot.count, 1);
+    assert_eq!(snapshot.p50_ms, 250);
+}
+
+#[tokio::test]
+async fn test_spread_excludes_venues_not_live() {
+    let state = PriceState::new(Duration::from_secs(5));
+    let (coinbase_tx, coinbase_rx) = tokio::sync::watch::channel(ConnectionState::Live);
+    state.watch_connection(ExchangeId::COINBASE, coinbase_rx);
+
+    state.ingest(ExchangeId::BINANCE, sequenced_price(100, 1));
+    state.ingest(ExchangeId::COINBASE, sequenced_price(102, 1));
+    assert!(
+        state
+            .get_spread(ExchangeId::BINANCE, ExchangeId::COINBASE, "SOL/USDC")
+            .is_some()
+    );
+
+    // Cached price is still fresh, but the feed is reconnecting
+    coinbase_tx.send_replace(ConnectionState::Reconnecting { attempt: 1 });
+    assert!(!state.is_live(ExchangeId::COINBASE));
+    assert!(
+        state
+            .get_spread(ExchangeId::BINANCE, ExchangeId::COINBASE, "SOL/USDC")
+            .is_none()
+    );
+
+    coinbase_tx.send_replace(ConnectionState::Live);
+    assert!(
+        state
+            .get_spread(ExchangeId::BINANCE, ExchangeId::COINBASE, "SOL/USDC")
+            .is_some()
+    );
+}
@@ -0,0 +1,246 @@
+//! Integration tests for startup reconciliation
+
+use arb_bot::config::RiskConfig;
+use arb_bot::exchanges::{Exchange, Order, OrderResult, OrderStatus};
+use arb_bot::journal::{Journal, JournalEvent, JournalQuery};
+use arb_bot::pnl::PnlTracker;
+use arb_bot::risk::RiskManager;
+use arb_bot::state::{ExchangeId, PriceState};
+use arb_bot::trading::{
+    Discrepancy, Recovery, TradingGuard, TradingGuardConfig, TripReason, unresolved_requests,
+};
+use chrono::Utc;
+use rust_decimal::Decimal;
+use std::time::Duration;
+
+mod common;
+use common::MockExchange;
+
+fn result(order_id: &str, status: OrderStatus, filled: i64) -> OrderResult {
+    OrderResult {
+        order_id: order_id.to_string(),
+        status,
+        filled_quantity: Decimal::from(filled),
+        average_price: Some(Decimal::from(100)),
+        fee: Decimal::ZERO,
+        fee_asset: "USDC".to_string(),
+        timestamp: Utc::now(),
+    }
+}
+
+/// Journal an order request and its result (plus a fill if anything filled)
+fn journal_order(journal: &Journal, id: &str, order: &Order, result: &OrderResult) {
+    journal.record(id, JournalEvent::order_request("binance", order));
+    journal.record_order_outcome(id, "binance", order, &Ok(result.clone()));
+}
+
+async fn connected_mock() -> MockExchange {
+    let mut exchange = MockExchange::new("binance");
+    exchange.connect().await.unwrap();
+    exchange
+}
+
+#[tokio::test]
+async fn test_clean_journal_restores_positions_and_resumes_trading() {
+    let dir = tempfile::tempdir().unwrap();
+    let journal = Journal::open(dir.path().join("journal.jsonl")).unwrap();
+    let order = Order::market_buy("SOL/USDC", Decimal::from(3));
+    journal_order(&journal, "a", &order, &result("o-1", OrderStatus::Filled, 3));
+    journal.flush().await.unwrap();
+
+    let exchange = connected_mock().await;
+    let guard = TradingGuard::new(TradingGuardConfig::default());
+    let pnl = PnlTracker::new(PriceState::new(Duration::from_secs(5)));
+    let risk = RiskManager::new(RiskConfig::default());
+
+    let recovery = Recovery::new(journal, guard.clone())
+        .with_pnl_tracker(pnl.clone())
+        .with_risk_manager(risk.clone());
+    assert!(guard.check().is_err());
+
+    let report = recovery
+        .run(&[(ExchangeId::BINANCE, &exchange)], &[(ExchangeId::BINANCE, "SOL/USDC")])
+        .await
+        .unwrap();
+
+    assert!(report.is_clean());
+    assert_eq!(report.fills_replayed, 1);
+    assert!(!guard.is_tripped());
+    assert_eq!(pnl.position(ExchangeId::BINANCE, "SOL/USDC").quantity, Decimal::from(3));
+    assert_eq!(risk.position(ExchangeId::BINANCE, "SOL"), Decimal::from(3));
+}
+
+#[tokio::test]
+async fn test_missed_fill_is_corrected_and_journaled() {
+    let dir = tempfile::tempdir().unwrap();
+    let journal = Journal::open(dir.path().join("journal.jsonl")).unwrap();
+    let order = Order::market_buy("SOL/USDC", Decimal::from(2));
+    // Crashed after the order was acknowledged but before it filled
+    journal_order(&journal, "a", &order, &result("o-1", OrderStatus::Pending, 0));
+
+    let exchange = connected_mock().await;
+    exchange.set_order(result("o-1", OrderStatus::Filled, 2));
+    let guard = TradingGuard::new(TradingGuardConfig::default());
+    let pnl = PnlTracker::new(PriceState::new(Duration::from_secs(5)));
+
+    let report = Recovery::new(journal.clone(), guard.clone())
+        .with_pnl_tracker(pnl.clone())
+        .run(&[(ExchangeId::BINANCE, &exchange)], &[])
+        .await
+        .unwrap();
+
+    assert!(report.is_clean());
+    assert_eq!(report.corrections, 1);
+    assert!(report.pending.is_empty());
+    assert!(!guard.is_tripped());
+    assert_eq!(pnl.position(ExchangeId::BINANCE, "SOL/USDC").quantity, Decimal::from(2));
+
+    let fills = journal
+        .query(&JournalQuery::new().correlation_id("a").kind("fill"))
+        .unwrap();
+    assert_eq!(fills.len(), 1);
+}
+
+#[tokio::test]
+async fn test_working_order_is_reported_pending() {
+    let dir = tempfile::tempdir().unwrap();
+    let journal = Journal::open(dir.path().join("journal.jsonl")).unwrap();
+    let order = Order::market_buy("SOL/USDC", Decimal::from(2));
+    journal_order(&journal, "a", &order, &result("o-1", OrderStatus::Pending, 0));
+
+    let exchange = connected_mock().await;
+    let working = result("o-1", OrderStatus::PartiallyFilled, 1);
+    exchange.set_order(working.clone());
+    exchange.add_open_order("SOL/USDC", working);
+    let guard = TradingGuard::new(TradingGuardConfig::default());
+
+    let report = Recovery::new(journal, guard.clone())
+        .run(&[(ExchangeId::BINANCE, &exchange)], &[])
+        .await
+        .unwrap();
+
+    assert!(report.is_clean());
+    assert_eq!(report.pending.len(), 1);
+    assert_eq!(report.pending[0].correlation_id, "a");
+    assert_eq!(report.pending[0].filled_quantity, Decimal::from(1));
+}
+
+#[tokio::test]
+async fn test_discrepancies_keep_trading_halted() {
+    let dir = tempfile::tempdir().unwrap();
+    let journal = Journal::open(dir.path().join("journal.jsonl")).unwrap();
+    // Request sent, then the process died before the response was journaled
+    journal.record(
+        "a",
+        JournalEvent::order_request("binance", &Order::market_sell("SOL/USDC", Decimal::ONE)),
+    );
+
+    let exchange = connected_mock().await;
+    exchange.add_open_order("SOL/USDC", result("stray", OrderStatus::Pending, 0));
+    let guard = TradingGuard::new(TradingGuardConfig::default());
+
+    let report = Recovery::new(journal, guard.clone())
+        .run(&[(ExchangeId::BINANCE, &exchange)], &[])
+        .await
+        .unwrap();
+
+    assert_eq!(report.discrepancies.len(), 2);
+    assert!(report.discrepancies.contains(&Discrepancy::UnknownOpenOrder {
+        exchange: ExchangeId::BINANCE,
+        order_id: "stray".to_string(),
+    }));
+    assert_eq!(guard.trip_info().unwrap().reason, TripReason::RecoveryPending);
+}
+
+#[tokio::test]
+async fn test_failed_lookup_keeps_trading_halted() {
+    let dir = tempfile::tempdir().unwrap();
+    let journal = Journal::open(dir.path().join("journal.jsonl")).unwrap();
+    let order = Order::market_buy("SOL/USDC", Decimal::from(2));
+    journal_order(&journal, "a", &order, &result("o-1", OrderStatus::Pending, 0));
+
+    // The exchange doesn't know the order, so the lookup fails
+    let exchange = connected_mock().await;
+    let guard = TradingGuard::new(TradingGuardConfig::default());
+
+    let recovery = Recovery::new(journal, guard.clone());
+    assert!(recovery.run(&[(ExchangeId::BINANCE, &exchange)], &[]).await.is_err());
+    assert!(guard.is_tripped());
+}
+
+#[test]
+fn test_recovery_does_not_clear_other_trips() {
+    let guard = TradingGuard::new(TradingGuardConfig::default());
+    guard.trip(TripReason::Manual {
+        reason: "maintenance".to_string(),
+    });
+    assert!(!guard.clear(&TripReason::RecoveryPending));
+    assert!(guard.is_tripped());
+}
+
+#[tokio::test]
+async fn test_resolved_request_no_longer_halts_trading() {
+    let dir = tempfile::tempdir().unwrap();
+    let journal = Journal::open(dir.path().join("journal.jsonl")).unwrap();
+    journal.record(
+        "a",
+        JournalEvent::order_request("binance", &Order::market_sell("SOL/USDC", Decimal::ONE)),
+    );
+    journal.flush().await.unwrap();
+    let entries = journal.query(&JournalQuery::new()).unwrap();
+    assert_eq!(unresolved_requests(&entries).len(), 1);
+
+    // The operator checked the exchange and acknowledged the attempt
+    journal.record(
+        "a",
+        JournalEvent::Resolution {
+            note: "no order on exchange".to_string(),
+        },
+    );
+    let exchange = connected_mock().await;
+    let guard = TradingGuard::new(TradingGuardConfig::default());
+
+    let report = Recovery::new(journal, guard.clone())
+        .run(&[(ExchangeId::BINANCE, &exchange)], &[])
+        .await
+        .unwrap();
+
+    assert!(report.is_clean());
+    assert!(!guard.is_tripped());
+}
+
+#[tokio::test]
+async fn test_corrections_replay_in_fill_order() {
+    let dir = tempfile::tempdir().unwrap();
+    let journal = Journal::open(dir.path().join("journal.jsonl")).unwrap();
+    let buy = Order::market_buy("SOL/USDC", Decimal::ONE);
+    journal_order(&journal, "a", &buy, &result("o-1", OrderStatus::Filled, 1));
+    journal_order(&journal, "b", &buy, &result("o-2", OrderStatus::Pending, 0));
+    let filled_at = Utc::now();
+    tokio::time::sleep(Duration::from_millis(5)).await;
+    let mut sell = result("o-3", OrderStatus::Filled, 1);
+    sell.average_price = Some(Decimal::from(110));
+    journal_order(&journal, "c", &Order::market_sell("SOL/USDC", Decimal::ONE), &sell);
+
+    // o-2 filled at 120 before the sell, but the journal never saw it
+    let exchange = connected_mock().await;
+    exchange.set_order(OrderResult {
+        average_price: Some(Decimal::from(120)),
+        timestamp: filled_at,
+        ..result("o-2", OrderStatus::Filled, 1)
+    });
+    let guard = TradingGuard::new(TradingGuardConfig::default());
+    let pnl = PnlTracker::new(PriceState::new(Duration::from_secs(5)));
+
+    let report = Recovery::new(journal, guard.clone())
+        .with_pnl_tracker(pnl.clone())
+        .run(&[(ExchangeId::BINANCE, &exchange)], &[])
+        .await
+        .unwrap();
+
+    assert_eq!(report.corrections, 1);
+    // Sold one of two bought at an average of 110; replaying the correction last
+    // would have realized 10 and left one at 120
+    assert_eq!(pnl.snapshot().realized, Decimal::ZERO);
+    assert_eq!(pnl.position(ExchangeId::BINANCE, "SOL/USDC").avg_cost, Decimal::from(110));
+}
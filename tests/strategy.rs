@@ -0,0 +1,72 @@
+//! Integration tests for spread detection and backtesting
+
+use arb_bot::config::FeesConfig;
+use arb_bot::state::ExchangeId;
+use arb_bot::strategy::{Backtest, SpreadDetector, parse_ticks};
+use rust_decimal::Decimal;
+use std::time::Duration;
+
+const TICKS: &str = "\
+timestamp,exchange,pair,bid,ask
+# books overlap
+2026-01-01T00:00:00Z,binance,SOL/USDC,100.0,100.1
+2026-01-01T00:00:01Z,coinbase,SOL/USDC,100.0,100.2
+# coinbase bid above binance ask
+2026-01-01T00:00:02Z,coinbase,SOL/USDC,101.1,101.2
+# still open, but inside the cooldown
+2026-01-01T00:00:03Z,coinbase,SOL/USDC,101.1,101.2
+# cooldown over
+2026-01-01T00:00:08Z,binance,SOL/USDC,100.0,100.1
+";
+
+#[test]
+fn test_parse_ticks() {
+    let ticks = parse_ticks(TICKS).unwrap();
+    assert_eq!(ticks.len(), 5);
+    assert_eq!(ticks[1].exchange, ExchangeId::COINBASE);
+    assert_eq!(ticks[1].price.ask, Decimal::new(1002, 1));
+
+    let err = parse_ticks("2026-01-01T00:00:00Z,kra ken,SOL/USDC,1,2").unwrap_err();
+    assert!(err.to_string().contains("line 1"));
+}
+
+#[test]
+fn test_backtest_trades_spreads_above_threshold() {
+    let detector = SpreadDetector::new(Decimal::new(5, 3), Duration::from_secs(5));
+    let report = Backtest::new(detector, Decimal::from(2), FeesConfig::flat(Decimal::new(1, 3)))
+        .run(parse_ticks(TICKS).unwrap());
+
+    assert_eq!(report.ticks, 5);
+    assert_eq!(report.trades.len(), 2);
+    let trade = &report.trades[0];
+    assert_eq!(trade.opportunity.buy_exchange, ExchangeId::BINANCE);
+    // 2 * (101.1 - 100.1)
+    assert_eq!(trade.gross, Decimal::from(2));
+    // 2 * (100.1 + 101.1) * 0.001
+    assert_eq!(trade.fees, Decimal::new(4024, 4));
+    assert_eq!(report.net(), report.gross - report.fees);
+}
+
+#[test]
+fn test_backtest_charges_each_venues_fee() {
+    let detector = SpreadDetector::new(Decimal::new(5, 3), Duration::from_secs(5));
+    let fees = FeesConfig::flat(Decimal::new(1, 3)).with_taker(ExchangeId::COINBASE, Decimal::new(6, 3));
+    let report = Backtest::new(detector, Decimal::from(2), fees).run(parse_ticks(TICKS).unwrap());
+
+    // 2 * (100.1 * 0.001 + 101.1 * 0.006)
+    assert_eq!(report.trades[0].fees, Decimal::new(14134, 4));
+}
+
+#[test]
+fn test_backtest_skips_quotes_too_far_apart() {
+    let ticks = parse_ticks(
+        "2026-01-01T00:00:00Z,binance,SOL/USDC,100.0,100.1\n\
+         2026-01-01T00:01:00Z,coinbase,SOL/USDC,105.0,105.1",
+    )
+    .unwrap();
+    let detector = SpreadDetector::new(Decimal::ZERO, Duration::ZERO);
+    let report = Backtest::new(detector, Decimal::ONE, FeesConfig::flat(Decimal::ZERO))
+        .with_max_age(Duration::from_secs(5))
+        .run(ticks);
+    assert!(report.trades.is_empty());
+}
@@ -0,0 +1,117 @@
+//! Integration tests for the trading kill switch and risk checks
+
+use arb_bot::error::ArbitrageError;
+use arb_bot::exchanges::{Exchange, Order, Price};
+use arb_bot::config::RiskConfig;
+use arb_bot::risk::RiskManager;
+use arb_bot::trading::{GuardedExchange, TradingGuard, TradingGuardConfig, TripReason};
+use chrono::Utc;
+use rust_decimal::Decimal;
+
+mod common;
+use common::MockExchange;
+
+fn order() -> Order {
+    Order::market_buy("SOL/USDC", Decimal::from(1))
+}
+
+fn price() -> Price {
+    Price {
+        pair: "SOL/USDC".to_string(),
+        bid: Decimal::from(100),
+        ask: Decimal::from(101),
+        last: Decimal::from(100),
+        volume_24h: Decimal::ZERO,
+        timestamp: Utc::now(),
+        sequence: None,
+        received_at: Utc::now(),
+    }
+}
+
+#[tokio::test]
+async fn test_guard_halts_orders_after_failures_but_not_prices() {
+    let mock = MockExchange::new("mock");
+    mock.set_price("SOL/USDC", price());
+    mock.set_fail_orders(true);
+
+    let guard = TradingGuard::new(TradingGuardConfig {
+        max_consecutive_failures: 2,
+        ..Default::default()
+    });
+    let mut exchange = GuardedExchange::new(mock, guard.clone());
+    exchange.connect().await.unwrap();
+
+    assert!(exchange.place_order(order()).await.is_err());
+    assert!(exchange.place_order(order()).await.is_err());
+    assert_eq!(
+        guard.trip_info().unwrap().reason,
+        TripReason::ConsecutiveOrderFailures { count: 2 }
+    );
+
+    // Blocked orders never reach the exchange
+    exchange.inner().set_fail_orders(false);
+    let err = exchange.place_order(order()).await.unwrap_err();
+    assert!(matches!(err, ArbitrageError::TradingHalted { .. }));
+    assert_eq!(exchange.inner().orders_placed(), 2);
+
+    // Market data is unaffected
+    assert!(exchange.get_latest_price("SOL/USDC").await.is_ok());
+
+    guard.reset();
+    assert!(exchange.place_order(order()).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_shared_guard_halts_every_exchange() {
+    let guard = TradingGuard::default();
+    let mut binance = GuardedExchange::new(MockExchange::new("binance"), guard.clone());
+    let mut coinbase = GuardedExchange::new(MockExchange::new("coinbase"), guard.clone());
+    binance.connect().await.unwrap();
+    coinbase.connect().await.unwrap();
+
+    guard.trip(TripReason::Manual {
+        reason: "operator".to_string(),
+    });
+
+    assert!(binance.place_order(order()).await.is_err());
+    assert!(coinbase.place_order(order()).await.is_err());
+    assert_eq!(coinbase.inner().orders_placed(), 0);
+}
+
+#[tokio::test]
+async fn test_risk_limits_checked_before_orders() {
+    let mock = MockExchange::new("mock");
+    mock.set_price("SOL/USDC", price());
+
+    let risk = RiskManager::new(
+        RiskConfig::default()
+            .with_max_order_notional(Decimal::from(1000))
+            .with_max_position("SOL", Decimal::from(15)),
+    );
+    let mut exchange =
+        GuardedExchange::new(mock, TradingGuard::default()).with_risk_manager(risk.clone());
+    exchange.connect().await.unwrap();
+
+    // 20 * ask 101 = 2020 notional
+    let err = exchange
+        .place_order(Order::market_buy("SOL/USDC", Decimal::from(20)))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ArbitrageError::RiskLimitExceeded { ref limit, .. } if limit == "max_order_notional"));
+    assert_eq!(exchange.inner().orders_placed(), 0);
+
+    exchange
+        .place_order(Order::market_buy("SOL/USDC", Decimal::from(9)))
+        .await
+        .unwrap();
+    assert_eq!(risk.position(exchange.id(), "SOL"), Decimal::from(9));
+
+    let err = exchange
+        .place_order(Order::market_buy("SOL/USDC", Decimal::from(9)))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ArbitrageError::RiskLimitExceeded { ref limit, .. } if limit == "max_position"));
+    // Risk rejections are not exchange failures
+    assert!(!exchange.guard().is_tripped());
+    assert_eq!(exchange.guard().consecutive_failures(), 0);
+}